@@ -21,6 +21,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
+use terminal::{TaskActivity, TaskStatus};
 use ui::{ButtonLike, ContextMenu, PopoverMenu, PopoverMenuHandle, Tooltip, prelude::*};
 use util::truncate_and_trailoff;
 use workspace::{StatusItemView, Workspace, item::ItemHandle};
@@ -123,6 +124,13 @@ impl ActivityIndicator {
             )
             .detach();
 
+            cx.subscribe(&project, |_, _, event, cx| {
+                if let project::Event::TaskActivityChanged = event {
+                    cx.notify();
+                }
+            })
+            .detach();
+
             if let Some(auto_updater) = auto_updater.as_ref() {
                 cx.observe(auto_updater, |_, _, cx| cx.notify()).detach();
             }
@@ -240,6 +248,21 @@ impl ActivityIndicator {
         self.project.read(cx).shell_environment_errors(cx)
     }
 
+    fn background_tasks(&self, activity: TaskActivity, cx: &App) -> Vec<String> {
+        self.project
+            .read(cx)
+            .local_terminal_handles()
+            .iter()
+            .filter_map(|terminal| terminal.upgrade())
+            .filter_map(|terminal| {
+                let task = terminal.read(cx).task()?;
+                (task.status == TaskStatus::Running
+                    && task.background.as_ref()?.activity == activity)
+                    .then(|| task.label.clone())
+            })
+            .collect()
+    }
+
     fn content_to_render(&mut self, cx: &mut Context<Self>) -> Option<Content> {
         // Show if any direnv calls failed
         if let Some((abs_path, error)) = self.pending_environment_errors(cx).next() {
@@ -331,6 +354,29 @@ impl ActivityIndicator {
             }
         }
 
+        // Show background tasks that are in the middle of a cycle
+        let busy_tasks = self.background_tasks(TaskActivity::Busy, cx);
+        if let Some((label, rest)) = busy_tasks.split_first() {
+            let mut message = format!("Running {label}…");
+            if !rest.is_empty() {
+                write!(&mut message, " + {} more", rest.len()).unwrap();
+            }
+            return Some(Content {
+                icon: Some(
+                    Icon::new(IconName::ArrowCircle)
+                        .size(IconSize::Small)
+                        .with_animation(
+                            "arrow-circle",
+                            Animation::new(Duration::from_secs(2)).repeat(),
+                            |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+                        )
+                        .into_any_element(),
+                ),
+                message,
+                on_click: None,
+            });
+        }
+
         // Show any language server installation info.
         let mut downloading = SmallVec::<[_; 3]>::new();
         let mut checking_for_update = SmallVec::<[_; 3]>::new();
@@ -523,6 +569,24 @@ impl ActivityIndicator {
             }
         }
 
+        let idle_tasks = self.background_tasks(TaskActivity::Idle, cx);
+        if let Some((label, rest)) = idle_tasks.split_first() {
+            let mut message = format!("{label} is idle");
+            if !rest.is_empty() {
+                write!(&mut message, " + {} more", rest.len()).unwrap();
+            }
+            return Some(Content {
+                icon: Some(
+                    Icon::new(IconName::Circle)
+                        .size(IconSize::Small)
+                        .color(Color::Success)
+                        .into_any_element(),
+                ),
+                message,
+                on_click: None,
+            });
+        }
+
         None
     }
 
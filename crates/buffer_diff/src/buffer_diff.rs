@@ -32,6 +32,9 @@ pub struct BufferDiffSnapshot {
 struct BufferDiffInner {
     hunks: SumTree<InternalDiffHunk>,
     pending_hunks: SumTree<PendingHunk>,
+    /// The index text written by staging or unstaging lines, until the index is reloaded.
+    /// Partly staged hunks can't be replayed from `pending_hunks`, so later changes build on it.
+    pending_index_text: Option<Rope>,
     base_text: language::BufferSnapshot,
     base_text_exists: bool,
 }
@@ -156,6 +159,7 @@ impl BufferDiffSnapshot {
                 base_text: language::Buffer::build_empty_snapshot(cx),
                 hunks: SumTree::new(buffer),
                 pending_hunks: SumTree::new(buffer),
+                pending_index_text: None,
                 base_text_exists: false,
             },
             secondary_diff: None,
@@ -204,6 +208,7 @@ impl BufferDiffSnapshot {
                     hunks,
                     base_text_exists,
                     pending_hunks: SumTree::new(&buffer),
+                    pending_index_text: None,
                 },
                 secondary_diff: None,
             }
@@ -224,6 +229,7 @@ impl BufferDiffSnapshot {
                     inner: BufferDiffInner {
                         base_text: base_text_snapshot,
                         pending_hunks: SumTree::new(&buffer),
+                        pending_index_text: None,
                         hunks: compute_hunks(base_text_pair, buffer),
                         base_text_exists,
                     },
@@ -332,54 +338,28 @@ impl BufferDiffInner {
             }
         };
 
-        let mut pending_hunks = SumTree::new(buffer);
-        let mut old_pending_hunks = self.pending_hunks.cursor::<DiffHunkSummary>(buffer);
-
         // first, merge new hunks into pending_hunks
-        for DiffHunk {
-            buffer_range,
-            diff_base_byte_range,
-            secondary_status,
-            ..
-        } in hunks.iter().cloned()
-        {
-            let preceding_pending_hunks =
-                old_pending_hunks.slice(&buffer_range.start, Bias::Left, buffer);
-            pending_hunks.append(preceding_pending_hunks, buffer);
-
-            // Skip all overlapping or adjacent old pending hunks
-            while old_pending_hunks.item().is_some_and(|old_hunk| {
-                old_hunk
-                    .buffer_range
-                    .start
-                    .cmp(&buffer_range.end, buffer)
-                    .is_le()
-            }) {
-                old_pending_hunks.next(buffer);
-            }
-
-            if (stage && secondary_status == DiffHunkSecondaryStatus::NoSecondaryHunk)
-                || (!stage && secondary_status == DiffHunkSecondaryStatus::HasSecondaryHunk)
-            {
-                continue;
-            }
-
-            pending_hunks.push(
-                PendingHunk {
-                    buffer_range,
-                    diff_base_byte_range,
+        let new_status = if stage {
+            DiffHunkSecondaryStatus::SecondaryHunkRemovalPending
+        } else {
+            DiffHunkSecondaryStatus::SecondaryHunkAdditionPending
+        };
+        self.insert_pending_hunks(
+            hunks.iter().map(|hunk| {
+                let is_noop = (stage
+                    && hunk.secondary_status == DiffHunkSecondaryStatus::NoSecondaryHunk)
+                    || (!stage
+                        && hunk.secondary_status == DiffHunkSecondaryStatus::HasSecondaryHunk);
+                let pending_hunk = (!is_noop).then(|| PendingHunk {
+                    buffer_range: hunk.buffer_range.clone(),
+                    diff_base_byte_range: hunk.diff_base_byte_range.clone(),
                     buffer_version: buffer.version().clone(),
-                    new_status: if stage {
-                        DiffHunkSecondaryStatus::SecondaryHunkRemovalPending
-                    } else {
-                        DiffHunkSecondaryStatus::SecondaryHunkAdditionPending
-                    },
-                },
-                buffer,
-            );
-        }
-        // append the remainder
-        pending_hunks.append(old_pending_hunks.suffix(buffer), buffer);
+                    new_status,
+                });
+                (hunk.buffer_range.clone(), pending_hunk)
+            }),
+            buffer,
+        );
 
         let mut unstaged_hunk_cursor = unstaged_diff.hunks.cursor::<DiffHunkSummary>(buffer);
         unstaged_hunk_cursor.next(buffer);
@@ -388,7 +368,7 @@ impl BufferDiffInner {
         let mut prev_unstaged_hunk_buffer_end = 0;
         let mut prev_unstaged_hunk_base_text_end = 0;
         let mut edits = Vec::<(Range<usize>, String)>::new();
-        let mut pending_hunks_iter = pending_hunks.iter().cloned().peekable();
+        let mut pending_hunks_iter = self.pending_hunks.iter().cloned().peekable();
         while let Some(PendingHunk {
             buffer_range,
             diff_base_byte_range,
@@ -473,8 +453,6 @@ impl BufferDiffInner {
             edits.push((index_byte_range, replacement_text));
         }
         drop(pending_hunks_iter);
-        drop(old_pending_hunks);
-        self.pending_hunks = pending_hunks;
 
         #[cfg(debug_assertions)] // invariants: non-overlapping and sorted
         {
@@ -496,6 +474,100 @@ impl BufferDiffInner {
         Some(new_index_text)
     }
 
+    /// Replaces the pending hunks that overlap or touch each of the given ranges with the
+    /// given hunk, if any. The ranges must be sorted.
+    fn insert_pending_hunks(
+        &mut self,
+        hunks: impl IntoIterator<Item = (Range<Anchor>, Option<PendingHunk>)>,
+        buffer: &text::BufferSnapshot,
+    ) {
+        let mut pending_hunks = SumTree::new(buffer);
+        let mut old_pending_hunks = self.pending_hunks.cursor::<DiffHunkSummary>(buffer);
+        for (buffer_range, pending_hunk) in hunks {
+            let preceding_pending_hunks =
+                old_pending_hunks.slice(&buffer_range.start, Bias::Left, buffer);
+            pending_hunks.append(preceding_pending_hunks, buffer);
+
+            // Skip all overlapping or adjacent old pending hunks
+            while old_pending_hunks.item().is_some_and(|old_hunk| {
+                old_hunk
+                    .buffer_range
+                    .start
+                    .cmp(&buffer_range.end, buffer)
+                    .is_le()
+            }) {
+                old_pending_hunks.next(buffer);
+            }
+
+            if let Some(pending_hunk) = pending_hunk {
+                pending_hunks.push(pending_hunk, buffer);
+            }
+        }
+        // append the remainder
+        pending_hunks.append(old_pending_hunks.suffix(buffer), buffer);
+        drop(old_pending_hunks);
+        self.pending_hunks = pending_hunks;
+    }
+
+    /// Marks the hunks containing the selected lines as pending, with the staged state they
+    /// have in the new index text.
+    fn insert_pending_lines(
+        &mut self,
+        buffer_rows: &[Range<u32>],
+        base_rows: &[Range<u32>],
+        head_text: &str,
+        new_index_text: &str,
+        buffer_text: &str,
+        buffer: &text::BufferSnapshot,
+    ) {
+        let staged_changes = changed_rows(head_text, new_index_text);
+        let unstaged_changes = changed_rows(new_index_text, buffer_text);
+        let mut pending_hunks = Vec::new();
+        for hunk in self.hunks.iter() {
+            let hunk_buffer_rows = point_range_to_rows(hunk.buffer_range.to_point(buffer));
+            let hunk_base_rows = point_range_to_rows(
+                self.base_text
+                    .offset_to_point(hunk.diff_base_byte_range.start)
+                    ..self
+                        .base_text
+                        .offset_to_point(hunk.diff_base_byte_range.end),
+            );
+            let is_selected = buffer_rows
+                .iter()
+                .any(|rows| rows_intersect(rows, &hunk_buffer_rows))
+                || base_rows
+                    .iter()
+                    .any(|rows| rows_intersect(rows, &hunk_base_rows));
+            if !is_selected {
+                continue;
+            }
+
+            let has_staged_lines = staged_changes
+                .iter()
+                .any(|(head_rows, _)| rows_intersect(head_rows, &hunk_base_rows));
+            let has_unstaged_lines = unstaged_changes
+                .iter()
+                .any(|(_, buffer_rows)| rows_intersect(buffer_rows, &hunk_buffer_rows));
+            let new_status = match (has_staged_lines, has_unstaged_lines) {
+                (_, false) => DiffHunkSecondaryStatus::SecondaryHunkRemovalPending,
+                (false, true) => DiffHunkSecondaryStatus::SecondaryHunkAdditionPending,
+                (true, true) => DiffHunkSecondaryStatus::OverlapsWithSecondaryHunk,
+            };
+            pending_hunks.push(PendingHunk {
+                buffer_range: hunk.buffer_range.clone(),
+                diff_base_byte_range: hunk.diff_base_byte_range.clone(),
+                buffer_version: buffer.version().clone(),
+                new_status,
+            });
+        }
+        self.insert_pending_hunks(
+            pending_hunks
+                .into_iter()
+                .map(|hunk| (hunk.buffer_range.clone(), Some(hunk))),
+            buffer,
+        );
+    }
+
     fn hunks_intersecting_range<'a>(
         &'a self,
         range: Range<Anchor>,
@@ -714,6 +786,176 @@ impl BufferDiffInner {
     }
 }
 
+/// Splits text into lines, keeping their line endings.
+fn text_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Returns the rows that differ between two texts, as pairs of old and new row ranges.
+fn changed_rows(old_text: &str, new_text: &str) -> Vec<(Range<u32>, Range<u32>)> {
+    let mut options = GitOptions::default();
+    options.context_lines(0);
+    let Some(patch) = GitPatch::from_buffers(
+        old_text.as_bytes(),
+        None,
+        new_text.as_bytes(),
+        None,
+        Some(&mut options),
+    )
+    .log_err() else {
+        return Vec::new();
+    };
+
+    (0..patch.num_hunks())
+        .filter_map(|hunk_index| {
+            let (hunk, _) = patch.hunk(hunk_index).log_err()?;
+            // Empty ranges are reported as starting after the preceding line.
+            let start = |start: u32, lines: u32| if lines == 0 { start } else { start - 1 };
+            let old_start = start(hunk.old_start(), hunk.old_lines());
+            let new_start = start(hunk.new_start(), hunk.new_lines());
+            Some((
+                old_start..old_start + hunk.old_lines(),
+                new_start..new_start + hunk.new_lines(),
+            ))
+        })
+        .collect()
+}
+
+/// Maps each new row that is unchanged by `changes` to its old row.
+fn unchanged_rows(changes: &[(Range<u32>, Range<u32>)], new_row_count: u32) -> Vec<Option<u32>> {
+    let mut old_rows = vec![None; new_row_count as usize];
+    let mut old_row = 0;
+    let mut new_row = 0;
+    let boundaries = changes
+        .iter()
+        .cloned()
+        .chain([(u32::MAX..u32::MAX, new_row_count..new_row_count)]);
+    for (old_range, new_range) in boundaries {
+        while new_row < new_range.start.min(new_row_count) {
+            old_rows[new_row as usize] = Some(old_row);
+            old_row += 1;
+            new_row += 1;
+        }
+        old_row = old_range.end;
+        new_row = new_range.end;
+    }
+    old_rows
+}
+
+fn contains_row(ranges: &[Range<u32>], row: u32) -> bool {
+    ranges.iter().any(|range| range.contains(&row))
+}
+
+/// Whether two row ranges overlap. Empty ranges, which insert lines, intersect the ranges
+/// that they touch.
+fn rows_intersect(a: &Range<u32>, b: &Range<u32>) -> bool {
+    if a.is_empty() || b.is_empty() {
+        a.start.max(b.start) <= a.end.min(b.end)
+    } else {
+        a.start < b.end && b.start < a.end
+    }
+}
+
+fn point_range_to_rows(range: Range<Point>) -> Range<u32> {
+    let end_row = if range.end.column > 0 {
+        range.end.row + 1
+    } else {
+        range.end.row
+    };
+    range.start.row..end_row
+}
+
+fn push_line(text: &mut String, line: &str) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(line);
+}
+
+/// Applies the selected changes between the index and the buffer to the index.
+///
+/// Removed lines are selected by their row in HEAD, since that's what the diff displays.
+fn stage_lines(
+    head_text: &str,
+    index_text: &str,
+    buffer_text: &str,
+    head_rows: &[Range<u32>],
+    buffer_rows: &[Range<u32>],
+) -> String {
+    let index_lines = text_lines(index_text);
+    let buffer_lines = text_lines(buffer_text);
+    let head_row_for_index_row = unchanged_rows(
+        &changed_rows(head_text, index_text),
+        index_lines.len() as u32,
+    );
+
+    let mut new_index_text = String::with_capacity(index_text.len());
+    let mut index_row = 0;
+    for (index_range, buffer_range) in changed_rows(index_text, buffer_text) {
+        for line in &index_lines[index_row as usize..index_range.start as usize] {
+            push_line(&mut new_index_text, line);
+        }
+        for row in index_range.clone() {
+            let is_removed = head_row_for_index_row[row as usize]
+                .is_some_and(|head_row| contains_row(head_rows, head_row));
+            if !is_removed {
+                push_line(&mut new_index_text, index_lines[row as usize]);
+            }
+        }
+        for row in buffer_range {
+            if contains_row(buffer_rows, row) {
+                push_line(&mut new_index_text, buffer_lines[row as usize]);
+            }
+        }
+        index_row = index_range.end;
+    }
+    for line in &index_lines[index_row as usize..] {
+        push_line(&mut new_index_text, line);
+    }
+    new_index_text
+}
+
+/// Reverts the selected changes between HEAD and the index in the index.
+fn unstage_lines(
+    head_text: &str,
+    index_text: &str,
+    buffer_text: &str,
+    head_rows: &[Range<u32>],
+    buffer_rows: &[Range<u32>],
+) -> String {
+    let head_lines = text_lines(head_text);
+    let index_lines = text_lines(index_text);
+    let buffer_row_for_index_row = unchanged_rows(
+        &changed_rows(buffer_text, index_text),
+        index_lines.len() as u32,
+    );
+
+    let mut new_index_text = String::with_capacity(index_text.len());
+    let mut index_row = 0;
+    for (head_range, index_range) in changed_rows(head_text, index_text) {
+        for line in &index_lines[index_row as usize..index_range.start as usize] {
+            push_line(&mut new_index_text, line);
+        }
+        for row in head_range {
+            if contains_row(head_rows, row) {
+                push_line(&mut new_index_text, head_lines[row as usize]);
+            }
+        }
+        for row in index_range.clone() {
+            let is_unstaged = buffer_row_for_index_row[row as usize]
+                .is_some_and(|buffer_row| contains_row(buffer_rows, buffer_row));
+            if !is_unstaged {
+                push_line(&mut new_index_text, index_lines[row as usize]);
+            }
+        }
+        index_row = index_range.end;
+    }
+    for line in &index_lines[index_row as usize..] {
+        push_line(&mut new_index_text, line);
+    }
+    new_index_text
+}
+
 fn compute_hunks(
     diff_base: Option<(Arc<String>, Rope)>,
     buffer: text::BufferSnapshot,
@@ -913,6 +1155,7 @@ impl BufferDiff {
     pub fn clear_pending_hunks(&mut self, cx: &mut Context<Self>) {
         if self.secondary_diff.is_some() {
             self.inner.pending_hunks = SumTree::from_summary(DiffHunkSummary::default());
+            self.inner.pending_index_text = None;
             cx.emit(BufferDiffEvent::DiffChanged {
                 changed_range: Some(Anchor::MIN..Anchor::MAX),
             });
@@ -927,6 +1170,33 @@ impl BufferDiff {
         file_exists: bool,
         cx: &mut Context<Self>,
     ) -> Option<Rope> {
+        // Pending hunks can't describe lines that were staged since the index was loaded,
+        // so stage the hunks' lines on top of them instead.
+        if self.inner.pending_index_text.is_some() {
+            let base_text = &self.inner.base_text;
+            let buffer_rows = hunks
+                .iter()
+                .map(|hunk| point_range_to_rows(hunk.buffer_range.to_point(buffer)))
+                .collect::<Vec<_>>();
+            let base_rows = hunks
+                .iter()
+                .map(|hunk| {
+                    point_range_to_rows(
+                        base_text.offset_to_point(hunk.diff_base_byte_range.start)
+                            ..base_text.offset_to_point(hunk.diff_base_byte_range.end),
+                    )
+                })
+                .collect::<Vec<_>>();
+            return self.stage_or_unstage_lines(
+                stage,
+                &buffer_rows,
+                &base_rows,
+                buffer,
+                file_exists,
+                cx,
+            );
+        }
+
         let new_index_text = self.inner.stage_or_unstage_hunks_impl(
             &self.secondary_diff.as_ref()?.read(cx).inner,
             stage,
@@ -947,6 +1217,73 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages or unstages individual lines, rather than whole hunks.
+    ///
+    /// `buffer_rows` selects added lines in the buffer, and `base_rows` selects removed
+    /// lines in the base text. Changes that aren't selected keep their current staged state.
+    /// Returns the new index text, the same way as [`Self::stage_or_unstage_hunks`], and marks
+    /// the hunks containing the lines as pending until the index is reloaded.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        buffer_rows: &[Range<u32>],
+        base_rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        file_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let unstaged_diff = self.secondary_diff.as_ref()?.read(cx).inner.clone();
+        let head_text = self
+            .inner
+            .base_text_exists
+            .then(|| self.inner.base_text.text());
+
+        // Changes that are still waiting to be written to the index are folded in first.
+        let index_text = if let Some(pending_index_text) = &self.inner.pending_index_text {
+            Some(pending_index_text.to_string())
+        } else if self.inner.pending_hunks.is_empty()
+            || !self.inner.base_text_exists
+            || !unstaged_diff.base_text_exists
+        {
+            unstaged_diff
+                .base_text_exists
+                .then(|| unstaged_diff.base_text.text())
+        } else {
+            self.inner
+                .stage_or_unstage_hunks_impl(&unstaged_diff, stage, &[], buffer, file_exists)
+                .map(|rope| rope.to_string())
+        };
+
+        let head = head_text.as_deref().unwrap_or_default();
+        let index = index_text.as_deref().unwrap_or_default();
+        let buffer_text = buffer.text();
+        let new_index_text = if stage {
+            stage_lines(head, index, &buffer_text, base_rows, buffer_rows)
+        } else {
+            unstage_lines(head, index, &buffer_text, base_rows, buffer_rows)
+        };
+        self.inner.insert_pending_lines(
+            buffer_rows,
+            base_rows,
+            head,
+            &new_index_text,
+            &buffer_text,
+            buffer,
+        );
+        self.inner.pending_index_text = Some(Rope::from(new_index_text.as_str()));
+        // Leave files that end up empty out of the index, unless they're tracked.
+        let new_index_text = (!new_index_text.is_empty() || head_text.is_some())
+            .then(|| Rope::from(new_index_text.as_str()));
+
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(
+            new_index_text.clone(),
+        ));
+        cx.emit(BufferDiffEvent::DiffChanged {
+            changed_range: Some(Anchor::MIN..Anchor::MAX),
+        });
+        new_index_text
+    }
+
     pub fn range_to_hunk_range(
         &self,
         range: Range<Anchor>,
@@ -1062,6 +1399,7 @@ impl BufferDiff {
                 }
             }
             state.pending_hunks = SumTree::new(buffer);
+            state.pending_index_text = None;
         }
 
         cx.emit(BufferDiffEvent::DiffChanged {
@@ -1891,6 +2229,132 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_stage_and_unstage_lines(cx: &mut TestAppContext) {
+        let head_text = "
+            one
+            two
+            three
+            four
+        "
+        .unindent();
+        let buffer_text = "
+            one
+            TWO
+            three
+            FOUR
+            five
+        "
+        .unindent();
+
+        let buffer = Buffer::new(0, BufferId::new(1).unwrap(), buffer_text.clone());
+        let unstaged = BufferDiffSnapshot::new_sync(buffer.clone(), head_text.clone(), cx);
+        let uncommitted = BufferDiffSnapshot::new_sync(buffer.clone(), head_text.clone(), cx);
+        let unstaged_diff = cx.new(|cx| {
+            let mut diff = BufferDiff::new(&buffer, cx);
+            diff.set_snapshot(unstaged, &buffer, cx);
+            diff
+        });
+        let uncommitted_diff = cx.new(|cx| {
+            let mut diff = BufferDiff::new(&buffer, cx);
+            diff.set_snapshot(uncommitted, &buffer, cx);
+            diff.set_secondary_diff(unstaged_diff.clone());
+            diff
+        });
+
+        // Stage the removal of "two" and the addition of "TWO", but nothing else.
+        uncommitted_diff.update(cx, |diff, cx| {
+            let new_index_text = diff
+                .stage_or_unstage_lines(true, &[1..2], &[1..2], &buffer, true, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(
+                new_index_text,
+                "
+                one
+                TWO
+                three
+                four
+                "
+                .unindent()
+            );
+        });
+
+        // Only the added lines are staged, so the removed line stays in the index. The lines
+        // staged before are kept, even though the index wasn't reloaded yet.
+        uncommitted_diff.update(cx, |diff, cx| {
+            let new_index_text = diff
+                .stage_or_unstage_lines(true, &[3..5], &[], &buffer, true, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(
+                new_index_text,
+                "
+                one
+                TWO
+                three
+                four
+                FOUR
+                five
+                "
+                .unindent()
+            );
+
+            let statuses = diff
+                .hunks(&buffer, cx)
+                .map(|hunk| hunk.secondary_status)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                statuses,
+                [
+                    DiffHunkSecondaryStatus::SecondaryHunkRemovalPending,
+                    DiffHunkSecondaryStatus::OverlapsWithSecondaryHunk,
+                ]
+            );
+        });
+
+        // Staging the rest of the hunk builds on the staged lines, too.
+        uncommitted_diff.update(cx, |diff, cx| {
+            let hunk = diff.hunks(&buffer, cx).nth(1).unwrap();
+            let new_index_text = diff
+                .stage_or_unstage_hunks(true, &[hunk], &buffer, true, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(new_index_text, buffer_text);
+
+            let statuses = diff
+                .hunks(&buffer, cx)
+                .map(|hunk| hunk.secondary_status)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                statuses,
+                [DiffHunkSecondaryStatus::SecondaryHunkRemovalPending; 2]
+            );
+        });
+    }
+
+    #[test]
+    fn test_unstage_lines() {
+        let head_text = "one\ntwo\nthree\n";
+        let index_text = "one\nTWO\nthree\nfour\n";
+        let buffer_text = "one\nTWO\nthree\nfour\n";
+
+        // Unstage the added "TWO" while keeping the removal of "two" staged.
+        assert_eq!(
+            unstage_lines(head_text, index_text, buffer_text, &[], &[1..2]),
+            "one\nthree\nfour\n"
+        );
+        // Unstage the removal of "two" while keeping the added "TWO" staged.
+        assert_eq!(
+            unstage_lines(head_text, index_text, buffer_text, &[1..2], &[]),
+            "one\ntwo\nTWO\nthree\nfour\n"
+        );
+        assert_eq!(
+            unstage_lines(head_text, index_text, buffer_text, &[1..2], &[1..2, 3..4]),
+            head_text
+        );
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_compare(cx: &mut TestAppContext) {
         let base_text = "
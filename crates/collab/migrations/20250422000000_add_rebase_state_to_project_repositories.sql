@@ -0,0 +1 @@
+ALTER TABLE "project_repositories" ADD COLUMN "rebase_state" VARCHAR;
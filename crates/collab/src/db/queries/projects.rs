@@ -348,10 +348,12 @@ impl Database {
                                         .unwrap(),
                                 )),
 
-                                // Old clients do not use abs path, entry ids or head_commit_details.
+                                // Old clients do not use abs path, entry ids, head_commit_details
+                                // or rebase_state.
                                 abs_path: ActiveValue::set(String::new()),
                                 entry_ids: ActiveValue::set("[]".into()),
                                 head_commit_details: ActiveValue::set(None),
+                                rebase_state: ActiveValue::set(None),
                             }
                         }),
                     )
@@ -497,6 +499,12 @@ impl Database {
                         .as_ref()
                         .map(|details| serde_json::to_string(details).unwrap()),
                 ),
+                rebase_state: ActiveValue::Set(
+                    update
+                        .rebase_state
+                        .as_ref()
+                        .map(|rebase_state| serde_json::to_string(rebase_state).unwrap()),
+                ),
                 current_merge_conflicts: ActiveValue::Set(Some(
                     serde_json::to_string(&update.current_merge_conflicts).unwrap(),
                 )),
@@ -513,6 +521,7 @@ impl Database {
                     project_repository::Column::AbsPath,
                     project_repository::Column::CurrentMergeConflicts,
                     project_repository::Column::HeadCommitDetails,
+                    project_repository::Column::RebaseState,
                 ])
                 .to_owned(),
             )
@@ -943,6 +952,12 @@ impl Database {
                     .transpose()?
                     .unwrap_or_default();
 
+                let rebase_state = db_repository_entry
+                    .rebase_state
+                    .as_ref()
+                    .map(|rebase_state| serde_json::from_str(&rebase_state))
+                    .transpose()?;
+
                 let entry_ids = serde_json::from_str(&db_repository_entry.entry_ids)
                     .context("failed to deserialize repository's entry ids")?;
 
@@ -970,6 +985,7 @@ impl Database {
                         current_merge_conflicts,
                         branch_summary,
                         head_commit_details,
+                        rebase_state,
                         scan_id: db_repository_entry.scan_id as u64,
                         is_last_update: true,
                     });
@@ -762,6 +762,12 @@ impl Database {
                         .transpose()?
                         .unwrap_or_default();
 
+                    let rebase_state = db_repository
+                        .rebase_state
+                        .as_ref()
+                        .map(|rebase_state| serde_json::from_str(&rebase_state))
+                        .transpose()?;
+
                     let entry_ids = serde_json::from_str(&db_repository.entry_ids)
                         .context("failed to deserialize repository's entry ids")?;
 
@@ -786,6 +792,7 @@ impl Database {
                             current_merge_conflicts,
                             branch_summary,
                             head_commit_details,
+                            rebase_state,
                             project_id: project_id.to_proto(),
                             id: db_repository.id as u64,
                             abs_path: db_repository.abs_path,
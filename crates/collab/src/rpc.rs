@@ -416,8 +416,10 @@ impl Server {
             .add_request_handler(forward_read_only_project_request::<proto::GetRemotes>)
             .add_request_handler(forward_read_only_project_request::<proto::GitShow>)
             .add_request_handler(forward_read_only_project_request::<proto::LoadCommitDiff>)
-            .add_request_handler(forward_read_only_project_request::<proto::GitReset>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitReset>)
             .add_request_handler(forward_read_only_project_request::<proto::GitCheckoutFiles>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCherryPick>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRevert>)
             .add_request_handler(forward_mutating_project_request::<proto::SetIndexText>)
             .add_request_handler(forward_mutating_project_request::<proto::ToggleBreakpoint>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BreakpointsForFile>)
@@ -425,6 +427,35 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::GitDiff>)
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::GitChangeBranch>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitStashEntries>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitStashShow>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStash>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashApply>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashPop>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashDrop>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitRebaseTodo>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseStart>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseContinue>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseSkip>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseAbort>)
+            .add_request_handler(forward_read_only_project_request::<proto::LoadConflictTexts>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLog>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitWorktrees>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCreateWorktree>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRemoveWorktree>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitPruneWorktrees>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitTags>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCreateTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitDeleteTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitPushTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRenameBranch>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitDeleteBranch>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitSetUpstream>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitCompareBranch>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitFileHistory>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadTextAtRevision>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitBlameAtRevision>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadCommitSignature>)
             .add_request_handler(forward_mutating_project_request::<proto::CheckForPushedCommits>)
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
             .add_message_handler(update_context)
@@ -288,6 +288,11 @@ pub async fn fetch_latest_adapter_version_from_github(
 pub trait DebugAdapter: 'static + Send + Sync {
     fn name(&self) -> DebugAdapterName;
 
+    /// Whether the debug adapter can attach to a running process, given its process id.
+    fn supports_attach(&self) -> bool {
+        false
+    }
+
     async fn get_binary(
         &self,
         delegate: &dyn DapDelegate,
@@ -418,6 +423,10 @@ impl DebugAdapter for FakeAdapter {
         DebugAdapterName(Self::ADAPTER_NAME.into())
     }
 
+    fn supports_attach(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         _: &dyn DapDelegate,
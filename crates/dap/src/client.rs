@@ -253,6 +253,13 @@ impl DebugAdapterClient {
         transport.on_request::<R, F>(handler);
     }
 
+    /// Replays the responses, events and reverse requests of a recorded debug session.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn replay(&self, recording: &crate::recording::DapRecording) {
+        let transport = self.transport_delegate.transport().as_fake();
+        transport.replay(recording);
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub async fn fake_reverse_request<R: dap_types::requests::Request>(&self, args: R::Arguments) {
         self.send_message(Message::Request(dap_types::messages::Request {
@@ -2,6 +2,7 @@ pub mod adapters;
 pub mod client;
 pub mod debugger_settings;
 pub mod proto_conversions;
+pub mod recording;
 mod registry;
 pub mod transport;
 
@@ -31,6 +31,10 @@ pub struct DebuggerSettings {
     ///
     /// Default: true
     pub format_dap_log_messages: bool,
+    /// Whether to show the values of variables inline in the editor while debugging
+    ///
+    /// Default: true
+    pub inline_values: bool,
 }
 
 impl Default for DebuggerSettings {
@@ -42,6 +46,7 @@ impl Default for DebuggerSettings {
             timeout: 2000,
             log_dap_communications: true,
             format_dap_log_messages: true,
+            inline_values: true,
         }
     }
 }
@@ -0,0 +1,214 @@
+use anyhow::{Context as _, Result};
+#[cfg(any(test, feature = "test-support"))]
+use collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(any(test, feature = "test-support"))]
+use std::collections::VecDeque;
+
+use crate::transport::IoKind;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageDirection {
+    /// A message that Zed sent to the debug adapter.
+    ToAdapter,
+    /// A message that the debug adapter sent to Zed.
+    FromAdapter,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    pub direction: MessageDirection,
+    pub message: Value,
+}
+
+/// The DAP messages exchanged with a debug adapter during a debug session, in the order they were sent.
+///
+/// Recordings can be exported from the DAP log, and replayed by a fake debug adapter in tests
+/// to reproduce the behavior of a real debug adapter without having it installed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DapRecording {
+    /// The name of the debug adapter the messages were exchanged with.
+    pub adapter_name: String,
+    pub messages: Vec<RecordedMessage>,
+}
+
+impl DapRecording {
+    pub fn new(adapter_name: impl Into<String>) -> Self {
+        Self {
+            adapter_name: adapter_name.into(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Records a message as it is passed to an RPC log handler.
+    pub fn record(&mut self, io_kind: IoKind, message: &str) -> Result<()> {
+        let direction = match io_kind {
+            IoKind::StdIn => MessageDirection::ToAdapter,
+            IoKind::StdOut => MessageDirection::FromAdapter,
+            IoKind::StdErr => return Ok(()),
+        };
+        let message = serde_json::from_str(message).context("parsing recorded DAP message")?;
+        self.messages.push(RecordedMessage { direction, message });
+        Ok(())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("parsing DAP recording")
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("serializing DAP recording")
+    }
+
+    /// Pairs every request Zed sent with the debug adapter's response to it, in the order the
+    /// requests were sent, grouped by command.
+    ///
+    /// The events and reverse requests the debug adapter sent after a request, and before Zed's
+    /// next request, are replayed after the response to that request.
+    #[cfg(any(test, feature = "test-support"))]
+    pub(crate) fn replies(&self) -> HashMap<String, VecDeque<RecordedReply>> {
+        let mut requests = Vec::<(String, u64)>::new();
+        let mut replies = HashMap::<u64, RecordedReply>::default();
+        let mut leading_messages = Vec::new();
+
+        for RecordedMessage { direction, message } in &self.messages {
+            let kind = message.get("type").and_then(Value::as_str);
+            let seq = message.get("seq").and_then(Value::as_u64);
+            match (direction, kind) {
+                (MessageDirection::ToAdapter, Some("request")) => {
+                    let (Some(seq), Some(command)) =
+                        (seq, message.get("command").and_then(Value::as_str))
+                    else {
+                        continue;
+                    };
+                    requests.push((command.to_owned(), seq));
+                    replies.insert(
+                        seq,
+                        RecordedReply {
+                            response: None,
+                            follow_ups: std::mem::take(&mut leading_messages),
+                        },
+                    );
+                }
+                (MessageDirection::ToAdapter, _) => {}
+                (MessageDirection::FromAdapter, Some("response")) => {
+                    if let Some(reply) = message
+                        .get("request_seq")
+                        .and_then(Value::as_u64)
+                        .and_then(|request_seq| replies.get_mut(&request_seq))
+                    {
+                        reply.response = Some(message.clone());
+                    }
+                }
+                (MessageDirection::FromAdapter, _) => {
+                    match requests
+                        .last()
+                        .and_then(|(_, last_seq)| replies.get_mut(last_seq))
+                    {
+                        Some(reply) => reply.follow_ups.push(message.clone()),
+                        None => leading_messages.push(message.clone()),
+                    }
+                }
+            }
+        }
+
+        let mut replies_by_command = HashMap::<String, VecDeque<RecordedReply>>::default();
+        for (command, seq) in requests {
+            if let Some(reply) = replies.remove(&seq) {
+                replies_by_command
+                    .entry(command)
+                    .or_default()
+                    .push_back(reply);
+            }
+        }
+        replies_by_command
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RecordedReply {
+    /// The recorded response, if the debug adapter responded before the recording stopped.
+    pub response: Option<Value>,
+    /// The events and reverse requests to send after the response.
+    pub follow_ups: Vec<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_replies_follow_the_requests_they_were_sent_after() {
+        let mut recording = DapRecording::new("test-adapter");
+        for (io_kind, message) in [
+            (
+                IoKind::StdIn,
+                json!({"seq": 1, "type": "request", "command": "initialize"}),
+            ),
+            (
+                IoKind::StdOut,
+                json!({"seq": 1, "type": "response", "request_seq": 1, "command": "initialize", "success": true, "body": {}}),
+            ),
+            (
+                IoKind::StdOut,
+                json!({"seq": 2, "type": "event", "event": "initialized"}),
+            ),
+            (
+                IoKind::StdIn,
+                json!({"seq": 2, "type": "request", "command": "threads"}),
+            ),
+            (
+                IoKind::StdIn,
+                json!({"seq": 3, "type": "request", "command": "threads"}),
+            ),
+            (
+                IoKind::StdOut,
+                json!({"seq": 3, "type": "response", "request_seq": 3, "command": "threads", "success": true, "body": {"threads": []}}),
+            ),
+            (
+                IoKind::StdOut,
+                json!({"seq": 4, "type": "response", "request_seq": 2, "command": "threads", "success": true, "body": {"threads": [{"id": 1, "name": "main"}]}}),
+            ),
+            (IoKind::StdErr, json!("not a DAP message")),
+        ] {
+            recording.record(io_kind, &message.to_string()).unwrap();
+        }
+
+        assert_eq!(7, recording.messages.len());
+        assert_eq!(
+            recording,
+            DapRecording::from_json(&recording.to_json().unwrap()).unwrap()
+        );
+
+        let mut replies = recording.replies();
+        assert_eq!(
+            Some(
+                vec![RecordedReply {
+                    response: Some(
+                        json!({"seq": 1, "type": "response", "request_seq": 1, "command": "initialize", "success": true, "body": {}})
+                    ),
+                    follow_ups: vec![json!({"seq": 2, "type": "event", "event": "initialized"})],
+                }]
+                .into()
+            ),
+            replies.remove("initialize")
+        );
+        assert_eq!(
+            vec![
+                Some(json!({"threads": [{"id": 1, "name": "main"}]})),
+                Some(json!({"threads": []})),
+            ],
+            replies
+                .remove("threads")
+                .unwrap()
+                .into_iter()
+                .map(|reply| reply.response.unwrap().get("body").cloned())
+                .collect::<Vec<_>>()
+        );
+        assert!(replies.is_empty());
+    }
+}
@@ -42,4 +42,15 @@ impl DapRegistry {
     pub fn enumerate_adapters(&self) -> Vec<DebugAdapterName> {
         self.0.read().adapters.keys().cloned().collect()
     }
+
+    /// The debug adapters that can attach to a running process.
+    pub fn enumerate_attach_adapters(&self) -> Vec<DebugAdapterName> {
+        self.0
+            .read()
+            .adapters
+            .iter()
+            .filter(|(_, adapter)| adapter.supports_attach())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
 }
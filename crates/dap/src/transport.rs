@@ -24,6 +24,8 @@ use std::{
 use task::TcpArgumentsTemplate;
 use util::ResultExt as _;
 
+#[cfg(any(test, feature = "test-support"))]
+use crate::recording::{DapRecording, RecordedReply};
 use crate::{adapters::DebugAdapterBinary, debugger_settings::DebuggerSettings};
 
 pub type IoHandler = Box<dyn Send + FnMut(IoKind, &str)>;
@@ -699,9 +701,11 @@ type ResponseHandler = Box<dyn Send + Fn(Response)>;
 #[cfg(any(test, feature = "test-support"))]
 pub struct FakeTransport {
     // for sending fake response back from adapter side
-    request_handlers: Arc<parking_lot::Mutex<HashMap<&'static str, RequestHandler>>>,
+    request_handlers: Arc<parking_lot::Mutex<HashMap<String, RequestHandler>>>,
     // for reverse request responses
     response_handlers: Arc<parking_lot::Mutex<HashMap<&'static str, ResponseHandler>>>,
+    // for events and reverse requests that are sent after the next response
+    queued_messages: Arc<parking_lot::Mutex<Vec<String>>>,
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -711,7 +715,7 @@ impl FakeTransport {
         F: 'static + Send + FnMut(u64, R::Arguments) -> Result<R::Response, ErrorResponse>,
     {
         self.request_handlers.lock().insert(
-            R::COMMAND,
+            R::COMMAND.to_string(),
             Box::new(move |seq, args| {
                 let result = handler(seq, serde_json::from_value(args).unwrap());
                 let response = match result {
@@ -746,10 +750,58 @@ impl FakeTransport {
             .insert(R::COMMAND, Box::new(handler));
     }
 
+    /// Responds to requests the way the debug adapter of the recording did, replaying the
+    /// events and reverse requests it sent after them.
+    ///
+    /// Requests with the same command get the recorded responses in order,
+    /// and the last one once all of them have been replayed.
+    pub fn replay(&self, recording: &DapRecording) {
+        for (command, mut replies) in recording.replies() {
+            let queued_messages = self.queued_messages.clone();
+            self.request_handlers.lock().insert(
+                command.clone(),
+                Box::new(move |seq, _| {
+                    let reply = if replies.len() > 1 {
+                        replies.pop_front()
+                    } else {
+                        replies.front_mut().map(|reply| RecordedReply {
+                            response: reply.response.clone(),
+                            follow_ups: std::mem::take(&mut reply.follow_ups),
+                        })
+                    }
+                    .expect("recorded commands have at least one reply");
+
+                    queued_messages.lock().extend(
+                        reply
+                            .follow_ups
+                            .into_iter()
+                            .map(|message| message.to_string()),
+                    );
+
+                    let mut response = reply
+                        .response
+                        .and_then(|response| serde_json::from_value::<Response>(response).log_err())
+                        .unwrap_or_else(|| Response {
+                            seq: 0,
+                            request_seq: 0,
+                            success: true,
+                            command: command.clone(),
+                            body: None,
+                            message: None,
+                        });
+                    response.seq = seq + 1;
+                    response.request_seq = seq;
+                    response
+                }),
+            );
+        }
+    }
+
     async fn start(cx: AsyncApp) -> Result<(TransportPipe, Self)> {
         let this = Self {
             request_handlers: Arc::new(parking_lot::Mutex::new(HashMap::default())),
             response_handlers: Arc::new(parking_lot::Mutex::new(HashMap::default())),
+            queued_messages: Arc::new(parking_lot::Mutex::new(Vec::new())),
         };
         use dap_types::requests::{Request, RunInTerminal, StartDebugging};
         use serde_json::json;
@@ -759,6 +811,7 @@ impl FakeTransport {
 
         let request_handlers = this.request_handlers.clone();
         let response_handlers = this.response_handlers.clone();
+        let queued_messages = this.queued_messages.clone();
         let stdout_writer = Arc::new(Mutex::new(stdout_writer));
 
         cx.background_executor()
@@ -810,16 +863,22 @@ impl FakeTransport {
                                         let message =
                                             serde_json::to_string(&Message::Response(response))
                                                 .unwrap();
+                                        let queued_messages =
+                                            std::mem::take(&mut *queued_messages.lock());
 
                                         let mut writer = stdout_writer.lock().await;
 
-                                        writer
-                                            .write_all(
-                                                TransportDelegate::build_rpc_message(message)
-                                                    .as_bytes(),
-                                            )
-                                            .await
-                                            .unwrap();
+                                        for message in
+                                            std::iter::once(message).chain(queued_messages)
+                                        {
+                                            writer
+                                                .write_all(
+                                                    TransportDelegate::build_rpc_message(message)
+                                                        .as_bytes(),
+                                                )
+                                                .await
+                                                .unwrap();
+                                        }
                                         writer.flush().await.unwrap();
                                     }
                                 }
@@ -59,6 +59,10 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         DebugAdapterName(Self::ADAPTER_NAME.into())
     }
 
+    fn supports_attach(&self) -> bool {
+        true
+    }
+
     async fn install_binary(
         &self,
         version: AdapterVersion,
@@ -2,6 +2,7 @@ mod codelldb;
 mod gdb;
 mod go;
 mod javascript;
+mod lldb_dap;
 mod php;
 mod python;
 
@@ -21,6 +22,7 @@ use gdb::GdbDebugAdapter;
 use go::GoDebugAdapter;
 use gpui::{App, BorrowAppContext};
 use javascript::JsDebugAdapter;
+use lldb_dap::LldbDapDebugAdapter;
 use php::PhpDebugAdapter;
 use python::PythonDebugAdapter;
 use serde_json::{Value, json};
@@ -34,6 +36,7 @@ pub fn init(cx: &mut App) {
         registry.add_adapter(Arc::from(JsDebugAdapter));
         registry.add_adapter(Arc::from(GoDebugAdapter));
         registry.add_adapter(Arc::from(GdbDebugAdapter));
+        registry.add_adapter(Arc::from(LldbDapDebugAdapter));
     })
 }
 
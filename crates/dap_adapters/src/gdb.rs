@@ -59,6 +59,10 @@ impl DebugAdapter for GdbDebugAdapter {
         DebugAdapterName(Self::ADAPTER_NAME.into())
     }
 
+    fn supports_attach(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &dyn DapDelegate,
@@ -14,6 +14,7 @@ impl GoDebugAdapter {
         let mut args = match &config.request {
             dap::DebugRequest::Attach(attach_config) => {
                 json!({
+                    "mode": "local",
                     "processId": attach_config.process_id,
                 })
             }
@@ -43,6 +44,10 @@ impl DebugAdapter for GoDebugAdapter {
         DebugAdapterName(Self::ADAPTER_NAME.into())
     }
 
+    fn supports_attach(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &dyn DapDelegate,
@@ -55,6 +55,10 @@ impl DebugAdapter for JsDebugAdapter {
         DebugAdapterName(Self::ADAPTER_NAME.into())
     }
 
+    fn supports_attach(&self) -> bool {
+        true
+    }
+
     async fn fetch_latest_adapter_version(
         &self,
         delegate: &dyn DapDelegate,
@@ -0,0 +1,120 @@
+use std::{collections::HashMap, ffi::OsStr};
+
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use dap::StartDebuggingRequestArguments;
+use gpui::AsyncApp;
+use task::{DebugRequest, DebugTaskDefinition};
+
+use crate::*;
+
+/// The debug adapter that ships with LLDB, called `lldb-vscode` before LLVM 18.
+/// Unlike CodeLLDB, it isn't downloaded but has to be installed on the system.
+#[derive(Default)]
+pub(crate) struct LldbDapDebugAdapter;
+
+impl LldbDapDebugAdapter {
+    const ADAPTER_NAME: &'static str = "lldb-dap";
+    const BINARY_NAMES: [&'static str; 2] = ["lldb-dap", "lldb-vscode"];
+
+    fn request_args(&self, config: &DebugTaskDefinition) -> StartDebuggingRequestArguments {
+        let mut args = json!({
+            "request": match config.request {
+                DebugRequest::Launch(_) => "launch",
+                DebugRequest::Attach(_) => "attach",
+            },
+        });
+
+        let map = args.as_object_mut().unwrap();
+        match &config.request {
+            DebugRequest::Attach(attach) => {
+                map.insert("pid".into(), attach.process_id.into());
+            }
+
+            DebugRequest::Launch(launch) => {
+                map.insert("program".into(), launch.program.clone().into());
+
+                if !launch.args.is_empty() {
+                    map.insert("args".into(), launch.args.clone().into());
+                }
+
+                if let Some(stop_on_entry) = config.stop_on_entry {
+                    map.insert("stopOnEntry".into(), stop_on_entry.into());
+                }
+                if let Some(cwd) = launch.cwd.as_ref() {
+                    map.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
+                }
+            }
+        }
+        StartDebuggingRequestArguments {
+            configuration: args,
+            request: config.request.to_dap(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DebugAdapter for LldbDapDebugAdapter {
+    fn name(&self) -> DebugAdapterName {
+        DebugAdapterName(Self::ADAPTER_NAME.into())
+    }
+
+    fn supports_attach(&self) -> bool {
+        true
+    }
+
+    async fn get_binary(
+        &self,
+        delegate: &dyn DapDelegate,
+        config: &DebugTaskDefinition,
+        user_installed_path: Option<std::path::PathBuf>,
+        _: &mut AsyncApp,
+    ) -> Result<DebugAdapterBinary> {
+        let user_setting_path = user_installed_path
+            .filter(|p| p.exists())
+            .and_then(|p| p.to_str().map(|s| s.to_string()));
+
+        let lldb_dap_path = user_setting_path.or_else(|| {
+            Self::BINARY_NAMES.iter().find_map(|binary_name| {
+                delegate
+                    .which(OsStr::new(binary_name))
+                    .and_then(|p| p.to_str().map(|s| s.to_string()))
+            })
+        });
+
+        let Some(lldb_dap_path) = lldb_dap_path else {
+            bail!("Could not find lldb-dap or lldb-vscode in path, is LLDB installed?");
+        };
+
+        Ok(DebugAdapterBinary {
+            command: lldb_dap_path,
+            arguments: Vec::new(),
+            envs: HashMap::default(),
+            cwd: None,
+            connection: None,
+            request_args: self.request_args(config),
+        })
+    }
+
+    async fn install_binary(
+        &self,
+        _version: AdapterVersion,
+        _delegate: &dyn DapDelegate,
+    ) -> Result<()> {
+        Err(anyhow!("lldb-dap is installed along with LLDB"))
+    }
+
+    async fn fetch_latest_adapter_version(&self, _: &dyn DapDelegate) -> Result<AdapterVersion> {
+        Err(anyhow!("lldb-dap is installed along with LLDB"))
+    }
+
+    async fn get_installed_binary(
+        &self,
+        _: &dyn DapDelegate,
+        _: &DebugTaskDefinition,
+        _: Option<std::path::PathBuf>,
+        _: &mut AsyncApp,
+    ) -> Result<DebugAdapterBinary> {
+        Err(anyhow!("lldb-dap is installed along with LLDB"))
+    }
+}
@@ -1,6 +1,7 @@
 use dap::{
     client::SessionId,
     debugger_settings::DebuggerSettings,
+    recording::DapRecording,
     transport::{IoKind, LogKind},
 };
 use editor::{Editor, EditorEvent};
@@ -21,14 +22,18 @@ use settings::Settings as _;
 use std::{
     borrow::Cow,
     collections::{HashMap, VecDeque},
+    path::PathBuf,
     sync::Arc,
 };
-use util::maybe;
+use util::{ResultExt as _, maybe};
 use workspace::{
     ToolbarItemEvent, ToolbarItemView, Workspace,
     item::Item,
     searchable::{Direction, SearchEvent, SearchableItem, SearchableItemHandle},
-    ui::{Button, Clickable, ContextMenu, Label, LabelCommon, PopoverMenu, h_flex},
+    ui::{
+        Button, ButtonCommon, Clickable, ContextMenu, FluentBuilder as _, Label, LabelCommon,
+        PopoverMenu, Toggleable, Tooltip, h_flex,
+    },
 };
 
 struct DapLogView {
@@ -46,6 +51,11 @@ struct LogStore {
     debug_clients: HashMap<SessionId, DebugAdapterState>,
     rpc_tx: UnboundedSender<(SessionId, IoKind, String)>,
     adapter_log_tx: UnboundedSender<(SessionId, IoKind, String)>,
+    /// The recording of the last session that shut down, so that it can still be exported.
+    last_recording: Option<(SessionId, DapRecording)>,
+    /// Whether the next session that starts gets recorded from its first message,
+    /// which replaying needs, as the adapter is initialized and launched then.
+    record_next_session: bool,
 }
 
 struct ProjectState {
@@ -55,6 +65,11 @@ struct ProjectState {
 struct DebugAdapterState {
     log_messages: VecDeque<String>,
     rpc_messages: RpcMessages,
+    adapter_name: String,
+    /// Unlike `rpc_messages`, keeps every message so that the session can be replayed.
+    /// Only set once recording was started from the log view.
+    recording: Option<DapRecording>,
+    is_recording: bool,
 }
 
 struct RpcMessages {
@@ -92,10 +107,13 @@ impl MessageKind {
 }
 
 impl DebugAdapterState {
-    fn new() -> Self {
+    fn new(adapter_name: String) -> Self {
         Self {
             log_messages: VecDeque::new(),
             rpc_messages: RpcMessages::new(),
+            adapter_name,
+            recording: None,
+            is_recording: false,
         }
     }
 }
@@ -136,6 +154,8 @@ impl LogStore {
             adapter_log_tx,
             projects: HashMap::new(),
             debug_clients: HashMap::new(),
+            last_recording: None,
+            record_next_session: false,
         }
     }
 
@@ -208,6 +228,11 @@ impl LogStore {
             IoKind::StdOut | IoKind::StdErr => MessageKind::Receive,
             IoKind::StdIn => MessageKind::Send,
         };
+        if debug_client_state.is_recording {
+            if let Some(recording) = debug_client_state.recording.as_mut() {
+                recording.record(io_kind, &message).log_err();
+            }
+        }
 
         let rpc_messages = &mut debug_client_state.rpc_messages;
         if rpc_messages.last_message_kind != Some(kind) {
@@ -290,14 +315,20 @@ impl LogStore {
         client: Entity<Session>,
         cx: &App,
     ) -> Option<&mut DebugAdapterState> {
-        let client_state = self
-            .debug_clients
-            .entry(client_id)
-            .or_insert_with(DebugAdapterState::new);
+        let session = client.read(cx);
+        let record_next_session = &mut self.record_next_session;
+        let client_state = self.debug_clients.entry(client_id).or_insert_with(|| {
+            let mut state = DebugAdapterState::new(session.adapter_name().to_string());
+            if std::mem::take(record_next_session) {
+                state.recording = Some(DapRecording::new(state.adapter_name.clone()));
+                state.is_recording = true;
+            }
+            state
+        });
 
         let io_tx = self.rpc_tx.clone();
 
-        let client = client.read(cx).adapter_client()?;
+        let client = session.adapter_client()?;
         client.add_log_handler(
             move |io_kind, message| {
                 io_tx
@@ -321,7 +352,46 @@ impl LogStore {
     }
 
     fn remove_debug_client(&mut self, client_id: SessionId, cx: &mut Context<Self>) {
-        self.debug_clients.remove(&client_id);
+        if let Some(recording) = self
+            .debug_clients
+            .remove(&client_id)
+            .and_then(|state| state.recording)
+        {
+            self.last_recording = Some((client_id, recording));
+        }
+        cx.notify();
+    }
+
+    fn toggle_recording(&mut self, client_id: SessionId, cx: &mut Context<Self>) {
+        let Some(state) = self.debug_clients.get_mut(&client_id) else {
+            return;
+        };
+        state.is_recording = !state.is_recording;
+        if state.is_recording {
+            state.recording = Some(DapRecording::new(state.adapter_name.clone()));
+        }
+        cx.notify();
+    }
+
+    fn toggle_record_next_session(&mut self, cx: &mut Context<Self>) {
+        self.record_next_session = !self.record_next_session;
+        cx.notify();
+    }
+
+    fn is_recording(&self, client_id: SessionId) -> bool {
+        self.debug_clients
+            .get(&client_id)
+            .is_some_and(|state| state.is_recording)
+    }
+
+    /// Stops all recordings and drops the ones that were kept for export.
+    fn clear_recordings(&mut self, cx: &mut Context<Self>) {
+        for state in self.debug_clients.values_mut() {
+            state.recording = None;
+            state.is_recording = false;
+        }
+        self.last_recording = None;
+        self.record_next_session = false;
         cx.notify();
     }
 
@@ -329,6 +399,17 @@ impl LogStore {
         Some(&mut self.debug_clients.get_mut(&client_id)?.log_messages)
     }
 
+    fn recording_for_client(&self, client_id: SessionId) -> Option<&DapRecording> {
+        match self.debug_clients.get(&client_id) {
+            Some(state) => state.recording.as_ref(),
+            None => self
+                .last_recording
+                .as_ref()
+                .filter(|(id, _)| *id == client_id)
+                .map(|(_, recording)| recording),
+        }
+    }
+
     fn rpc_messages_for_client(&mut self, client_id: SessionId) -> Option<&mut VecDeque<String>> {
         Some(
             &mut self
@@ -362,6 +443,17 @@ impl Render for DapLogToolbarItemView {
                 log_view.current_view.map(|(client_id, _)| client_id),
             )
         });
+        let (is_running, is_recording, has_recording) = current_client_id
+            .map(|client_id| {
+                let log_store = log_view.read(cx).log_store.read(cx);
+                (
+                    log_store.debug_clients.contains_key(&client_id),
+                    log_store.is_recording(client_id),
+                    log_store.recording_for_client(client_id).is_some(),
+                )
+            })
+            .unwrap_or_default();
+        let record_next_session = log_view.read(cx).log_store.read(cx).record_next_session;
 
         let current_client = current_client_id.and_then(|current_client_id| {
             menu_rows
@@ -442,6 +534,75 @@ impl Render for DapLogToolbarItemView {
         h_flex()
             .size_full()
             .child(dap_menu)
+            .when(is_running, |this| {
+                this.child(
+                    div()
+                        .child(
+                            Button::new(
+                                "toggle_recording_button",
+                                if is_recording {
+                                    "Stop Recording"
+                                } else {
+                                    "Record"
+                                },
+                            )
+                            .tooltip(Tooltip::text(
+                                "Record the messages of this session from now on. \
+                                Replaying needs a recording of the next session instead",
+                            ))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                if let Some(log_view) = this.log_view.as_ref() {
+                                    log_view.update(cx, |log_view, cx| {
+                                        log_view.toggle_recording(cx);
+                                    })
+                                }
+                            })),
+                        )
+                        .ml_2(),
+                )
+            })
+            .child(
+                div()
+                    .child(
+                        Button::new(
+                            "toggle_record_next_session_button",
+                            "Record Next Session",
+                        )
+                        .toggle_state(record_next_session)
+                        .tooltip(Tooltip::text(
+                            "Record every message of the next session that starts, to replay them in tests",
+                        ))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            if let Some(log_view) = this.log_view.as_ref() {
+                                log_view.update(cx, |log_view, cx| {
+                                    log_view.log_store.update(cx, |log_store, cx| {
+                                        log_store.toggle_record_next_session(cx);
+                                    });
+                                })
+                            }
+                        })),
+                    )
+                    .ml_2(),
+            )
+            .when(has_recording, |this| {
+                this.child(
+                    div()
+                        .child(
+                            Button::new("export_recording_button", "Export")
+                                .tooltip(Tooltip::text(
+                                    "Save the recorded messages of this session",
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    if let Some(log_view) = this.log_view.as_ref() {
+                                        log_view.update(cx, |log_view, cx| {
+                                            log_view.export_recording(cx);
+                                        })
+                                    }
+                                })),
+                        )
+                        .ml_2(),
+                )
+            })
             .child(
                 div()
                     .child(
@@ -518,6 +679,13 @@ impl DapLogView {
             }
         });
 
+        // Recordings are unbounded, so they only live as long as the log view.
+        let release_subscription = cx.on_release(|log_view, cx| {
+            log_view
+                .log_store
+                .update(cx, |log_store, cx| log_store.clear_recordings(cx));
+        });
+
         Self {
             editor,
             focus_handle,
@@ -525,7 +693,7 @@ impl DapLogView {
             log_store,
             editor_subscriptions,
             current_view: None,
-            _subscriptions: vec![events_subscriptions],
+            _subscriptions: vec![events_subscriptions, release_subscription],
         }
     }
 
@@ -624,6 +792,45 @@ impl DapLogView {
         cx.focus_self(window);
     }
 
+    fn toggle_recording(&mut self, cx: &mut Context<Self>) {
+        let Some((client_id, _)) = self.current_view else {
+            return;
+        };
+        self.log_store.update(cx, |log_store, cx| {
+            log_store.toggle_recording(client_id, cx);
+        });
+        cx.notify();
+    }
+
+    fn export_recording(&mut self, cx: &mut Context<Self>) {
+        let Some((client_id, _)) = self.current_view else {
+            return;
+        };
+        let Some(recording) = self
+            .log_store
+            .read(cx)
+            .recording_for_client(client_id)
+            .and_then(|recording| recording.to_json().log_err())
+        else {
+            return;
+        };
+
+        let project = self.project.read(cx);
+        let fs = project.fs().clone();
+        let directory = project
+            .visible_worktrees(cx)
+            .find_map(|worktree| Some(worktree.read(cx).as_local()?.abs_path().to_path_buf()))
+            .unwrap_or_else(PathBuf::new);
+        let path = cx.prompt_for_new_path(&directory);
+        cx.spawn(async move |_, _| {
+            let Some(path) = path.await?? else {
+                return anyhow::Ok(());
+            };
+            fs.atomic_write(path, recording).await
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn show_log_messages_for_adapter(
         &mut self,
         client_id: SessionId,
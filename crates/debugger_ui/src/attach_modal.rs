@@ -4,7 +4,7 @@ use gpui::Subscription;
 use gpui::{DismissEvent, Entity, EventEmitter, Focusable, Render};
 use picker::{Picker, PickerDelegate};
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use sysinfo::System;
 use ui::{Context, Tooltip, prelude::*};
 use ui::{ListItem, ListItemSpacing};
@@ -16,6 +16,17 @@ pub(super) struct Candidate {
     pub(super) pid: u32,
     pub(super) name: SharedString,
     pub(super) command: Vec<String>,
+    pub(super) cwd: Option<PathBuf>,
+}
+
+impl Candidate {
+    fn command_line(&self) -> String {
+        if self.command.is_empty() {
+            self.name.to_string()
+        } else {
+            self.command.join(" ")
+        }
+    }
 }
 
 pub(crate) struct AttachModalDelegate {
@@ -70,6 +81,7 @@ impl AttachModal {
                         .iter()
                         .map(|s| s.to_string_lossy().to_string())
                         .collect::<Vec<_>>(),
+                    cwd: process.cwd().map(|cwd| cwd.to_path_buf()),
                 }
             })
             .collect();
@@ -169,10 +181,15 @@ impl PickerDelegate for AttachModalDelegate {
                         StringMatchCandidate::new(
                             id,
                             format!(
-                                "{} {} {}",
+                                "{} {} {}{}",
                                 candidate.command.join(" "),
                                 candidate.pid,
-                                candidate.name
+                                candidate.name,
+                                candidate
+                                    .cwd
+                                    .as_ref()
+                                    .map(|cwd| format!(" {}", cwd.display()))
+                                    .unwrap_or_default()
                             )
                             .as_str(),
                         )
@@ -264,30 +281,21 @@ impl PickerDelegate for AttachModalDelegate {
                         .child(
                             div()
                                 .id(SharedString::from(format!("process-entry-{ix}-command")))
-                                .tooltip(Tooltip::text(
-                                    candidate
-                                        .command
-                                        .clone()
-                                        .into_iter()
-                                        .collect::<Vec<_>>()
-                                        .join(" "),
-                                ))
+                                .tooltip(Tooltip::text(candidate.command_line()))
                                 .child(
-                                    Label::new(format!(
-                                        "{} {}",
-                                        candidate.name,
-                                        candidate
-                                            .command
-                                            .clone()
-                                            .into_iter()
-                                            .skip(1)
-                                            .collect::<Vec<_>>()
-                                            .join(" ")
-                                    ))
-                                    .size(LabelSize::Small)
-                                    .color(Color::Muted),
+                                    Label::new(candidate.command_line())
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .truncate(),
                                 ),
-                        ),
+                        )
+                        .when_some(candidate.cwd.as_ref(), |this, cwd| {
+                            this.child(
+                                Label::new(cwd.to_string_lossy().into_owned())
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                        }),
                 ),
         )
     }
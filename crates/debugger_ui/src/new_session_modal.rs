@@ -4,16 +4,16 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context as _, Result, anyhow};
 use dap::{DapRegistry, DebugRequest};
 use editor::{Editor, EditorElement, EditorStyle};
 use gpui::{
     App, AppContext, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, TextStyle,
     WeakEntity,
 };
-use project::Project;
+use project::{Project, TaskSourceKind};
 use settings::Settings;
-use task::{DebugTaskDefinition, DebugTaskTemplate, LaunchRequest};
+use task::{DebugTaskDefinition, DebugTaskTemplate, LaunchRequest, TaskTemplate};
 use theme::ThemeSettings;
 use ui::{
     ActiveTheme, Button, ButtonCommon, ButtonSize, CheckboxWithLabel, Clickable, Color, Context,
@@ -35,6 +35,8 @@ pub(super) struct NewSessionModal {
     initialize_args: Option<serde_json::Value>,
     debugger: Option<SharedString>,
     last_selected_profile_name: Option<SharedString>,
+    /// The debug.json or launch.json scenario picked from the profiles, with its source.
+    selected_scenario: Option<(TaskSourceKind, TaskTemplate)>,
 }
 
 fn suggested_label(request: &DebugRequest, debugger: &str) -> String {
@@ -84,6 +86,7 @@ impl NewSessionModal {
                 .map(Into::into)
                 .unwrap_or(ToggleState::Unselected),
             last_selected_profile_name: None,
+            selected_scenario: None,
             initialize_args: None,
         }
     }
@@ -103,7 +106,75 @@ impl NewSessionModal {
         })
     }
 
+    /// The task to schedule for the selected scenario instead of starting a session right away:
+    /// the scenario is either compound, or has tasks to run before its session starts.
+    fn scenario_task(&self, cx: &App) -> Option<(TaskSourceKind, TaskTemplate)> {
+        let (task_source_kind, template) = self.selected_scenario.as_ref()?;
+        if template.is_compound() {
+            return Some((task_source_kind.clone(), template.clone()));
+        }
+        if template.depends_on.is_empty() {
+            return None;
+        }
+        let template = DebugTaskTemplate {
+            locator: None,
+            depends_on: template.depends_on.clone(),
+            definition: self.debug_config(cx)?,
+        };
+        Some((task_source_kind.clone(), template.to_zed_format()))
+    }
+
+    fn schedule_scenario_task(
+        &self,
+        task_source_kind: TaskSourceKind,
+        template: TaskTemplate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace = self.workspace.clone();
+        let task_contexts = workspace
+            .update(cx, |workspace, cx| {
+                tasks_ui::task_contexts(workspace, window, cx)
+            })
+            .ok();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let task_context = if let Some(task) = task_contexts {
+                task.await
+                    .active_worktree_context
+                    .map_or(task::TaskContext::default(), |context| context.1)
+            } else {
+                task::TaskContext::default()
+            };
+            let resolved_task = template
+                .resolve_task(&task_source_kind.to_id_base(), &task_context)
+                .with_context(|| {
+                    format!("Failed to resolve debug scenario {:?}", template.label)
+                })?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.schedule_resolved_task(
+                    task_source_kind,
+                    resolved_task,
+                    false,
+                    window,
+                    cx,
+                );
+            })?;
+            this.update(cx, |_, cx| {
+                cx.emit(DismissEvent);
+            })
+            .ok();
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn start_new_session(&self, window: &mut Window, cx: &mut Context<Self>) -> Result<()> {
+        if let Some((task_source_kind, template)) = self.scenario_task(cx) {
+            self.schedule_scenario_task(task_source_kind, template, window, cx);
+            return Ok(());
+        }
+
         let workspace = self.workspace.clone();
         let config = self
             .debug_config(cx)
@@ -132,6 +203,7 @@ impl NewSessionModal {
             let task = project.update(cx, |this, cx| {
                 let template = DebugTaskTemplate {
                     locator: None,
+                    depends_on: Vec::new(),
                     definition: config.clone(),
                 };
                 if let Some(debug_config) = template
@@ -187,6 +259,7 @@ impl NewSessionModal {
         let workspace = self.workspace.clone();
         let weak = cx.weak_entity();
         let debugger = self.debugger.clone();
+        let is_attach = matches!(self.mode, NewSessionMode::Attach(_));
         DropdownMenu::new(
             "dap-adapter-picker",
             debugger
@@ -209,7 +282,14 @@ impl NewSessionModal {
                 };
 
                 let available_adapters = workspace
-                    .update(cx, |_, cx| DapRegistry::global(cx).enumerate_adapters())
+                    .update(cx, |_, cx| {
+                        let registry = DapRegistry::global(cx);
+                        if is_attach {
+                            registry.enumerate_attach_adapters()
+                        } else {
+                            registry.enumerate_adapters()
+                        }
+                    })
                     .ok()
                     .unwrap_or_default();
 
@@ -233,49 +313,67 @@ impl NewSessionModal {
             "debug-config-menu",
             last_profile.unwrap_or_else(|| SELECT_SCENARIO_LABEL.clone()),
             ContextMenu::build(window, cx, move |mut menu, _, cx| {
-                let setter_for_name = |task: DebugTaskDefinition| {
-                    let weak = weak.clone();
-                    move |window: &mut Window, cx: &mut App| {
-                        weak.update(cx, |this, cx| {
-                            this.last_selected_profile_name = Some(SharedString::from(&task.label));
-                            this.debugger = Some(task.adapter.clone().into());
-                            this.initialize_args = task.initialize_args.clone();
-                            match &task.request {
-                                DebugRequest::Launch(launch_config) => {
-                                    this.mode = NewSessionMode::launch(
-                                        Some(launch_config.clone()),
-                                        window,
-                                        cx,
-                                    );
-                                }
-                                DebugRequest::Attach(_) => {
-                                    let Ok(project) = this
-                                        .workspace
-                                        .read_with(cx, |this, _| this.project().clone())
-                                    else {
-                                        return;
-                                    };
-                                    this.mode = NewSessionMode::attach(
-                                        this.debugger.clone(),
-                                        project,
-                                        window,
-                                        cx,
-                                    );
-                                    this.mode.focus_handle(cx).focus(window);
-                                    if let Some((debugger, attach)) =
-                                        this.debugger.as_ref().zip(this.mode.as_attach())
-                                    {
-                                        Self::update_attach_picker(&attach, &debugger, window, cx);
+                let setter_for_name =
+                    |task: DebugTaskDefinition, scenario: (TaskSourceKind, TaskTemplate)| {
+                        let weak = weak.clone();
+                        move |window: &mut Window, cx: &mut App| {
+                            weak.update(cx, |this, cx| {
+                                this.last_selected_profile_name =
+                                    Some(SharedString::from(&task.label));
+                                this.selected_scenario = Some(scenario.clone());
+                                this.debugger = Some(task.adapter.clone().into());
+                                this.initialize_args = task.initialize_args.clone();
+                                match &task.request {
+                                    DebugRequest::Launch(launch_config) => {
+                                        this.mode = NewSessionMode::launch(
+                                            Some(launch_config.clone()),
+                                            window,
+                                            cx,
+                                        );
+                                    }
+                                    DebugRequest::Attach(_) => {
+                                        let Ok(project) = this
+                                            .workspace
+                                            .read_with(cx, |this, _| this.project().clone())
+                                        else {
+                                            return;
+                                        };
+                                        this.mode = NewSessionMode::attach(
+                                            this.debugger.clone(),
+                                            project,
+                                            window,
+                                            cx,
+                                        );
+                                        this.mode.focus_handle(cx).focus(window);
+                                        if let Some((debugger, attach)) =
+                                            this.debugger.as_ref().zip(this.mode.as_attach())
+                                        {
+                                            Self::update_attach_picker(
+                                                &attach, &debugger, window, cx,
+                                            );
+                                        }
                                     }
                                 }
-                            }
+                                cx.notify();
+                            })
+                            .ok();
+                        }
+                    };
+
+                let setter_for_compound = |scenario: (TaskSourceKind, TaskTemplate)| {
+                    let weak = weak.clone();
+                    move |_: &mut Window, cx: &mut App| {
+                        weak.update(cx, |this, cx| {
+                            this.last_selected_profile_name =
+                                Some(SharedString::from(&scenario.1.label));
+                            this.selected_scenario = Some(scenario.clone());
                             cx.notify();
                         })
                         .ok();
                     }
                 };
 
-                let available_adapters: Vec<DebugTaskTemplate> = workspace
+                let scenarios: Vec<(TaskSourceKind, TaskTemplate)> = workspace
                     .update(cx, |this, cx| {
                         this.project()
                             .read(cx)
@@ -284,19 +382,28 @@ impl NewSessionModal {
                             .task_inventory()
                             .iter()
                             .flat_map(|task_inventory| task_inventory.read(cx).list_debug_tasks())
-                            .cloned()
-                            .filter_map(|task| task.try_into().ok())
+                            .map(|(source_kind, task)| (source_kind, task.clone()))
                             .collect()
                     })
                     .ok()
                     .unwrap_or_default();
 
-                for debug_definition in available_adapters {
-                    menu = menu.entry(
-                        debug_definition.definition.label.clone(),
-                        None,
-                        setter_for_name(debug_definition.definition),
-                    );
+                for scenario in scenarios {
+                    if scenario.1.is_compound() {
+                        menu = menu.entry(
+                            scenario.1.label.clone(),
+                            None,
+                            setter_for_compound(scenario),
+                        );
+                    } else if let Ok(debug_definition) =
+                        DebugTaskTemplate::try_from(scenario.1.clone())
+                    {
+                        menu = menu.entry(
+                            debug_definition.definition.label.clone(),
+                            None,
+                            setter_for_name(debug_definition.definition, scenario),
+                        );
+                    }
                 }
                 menu
             }),
@@ -554,6 +661,7 @@ impl Render for NewSessionModal {
                                 .style(ui::ButtonStyle::Subtle)
                                 .toggle_state(matches!(self.mode, NewSessionMode::Launch(_)))
                                 .on_click(cx.listener(|this, _, window, cx| {
+                                    this.selected_scenario = None;
                                     this.mode = NewSessionMode::launch(None, window, cx);
                                     this.mode.focus_handle(cx).focus(window);
                                     cx.notify();
@@ -575,6 +683,16 @@ impl Render for NewSessionModal {
                                     else {
                                         return;
                                     };
+                                    this.selected_scenario = None;
+                                    let supports_attach =
+                                        this.debugger.as_ref().is_some_and(|debugger| {
+                                            DapRegistry::global(cx)
+                                                .adapter(debugger)
+                                                .is_some_and(|adapter| adapter.supports_attach())
+                                        });
+                                    if !supports_attach {
+                                        this.debugger = None;
+                                    }
                                     this.mode = NewSessionMode::attach(
                                         this.debugger.clone(),
                                         project,
@@ -633,7 +751,12 @@ impl Render for NewSessionModal {
                                     .on_click(cx.listener(|this, _, window, cx| {
                                         this.start_new_session(window, cx).log_err();
                                     }))
-                                    .disabled(self.debugger.is_none()),
+                                    .disabled(
+                                        self.debugger.is_none()
+                                            && !self.selected_scenario.as_ref().is_some_and(
+                                                |(_, scenario)| scenario.is_compound(),
+                                            ),
+                                    ),
                             ),
                     ),
             )
@@ -10,8 +10,9 @@ use workspace::{Member, Pane, PaneAxis, Workspace};
 
 use crate::session::running::{
     self, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
-    loaded_source_list::LoadedSourceList, module_list::ModuleList,
-    stack_frame_list::StackFrameList, variable_list::VariableList,
+    disassembly_view::DisassemblyView, exception_view::ExceptionView,
+    loaded_source_list::LoadedSourceList, memory_view::MemoryView, module_list::ModuleList,
+    stack_frame_list::StackFrameList, variable_list::VariableList, watch_list::WatchList,
 };
 
 #[derive(Clone, Hash, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -22,6 +23,10 @@ pub(crate) enum DebuggerPaneItem {
     Frames,
     Modules,
     LoadedSources,
+    Watch,
+    Memory,
+    Disassembly,
+    Exception,
 }
 
 impl DebuggerPaneItem {
@@ -33,6 +38,10 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Frames,
             DebuggerPaneItem::Modules,
             DebuggerPaneItem::LoadedSources,
+            DebuggerPaneItem::Watch,
+            DebuggerPaneItem::Memory,
+            DebuggerPaneItem::Disassembly,
+            DebuggerPaneItem::Exception,
         ];
         VARIANTS
     }
@@ -43,6 +52,21 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources => capabilities
                 .supports_loaded_sources_request
                 .unwrap_or_default(),
+            DebuggerPaneItem::Memory => capabilities
+                .supports_read_memory_request
+                .unwrap_or_default(),
+            DebuggerPaneItem::Disassembly => capabilities
+                .supports_disassemble_request
+                .unwrap_or_default(),
+            DebuggerPaneItem::Exception => {
+                capabilities
+                    .supports_exception_info_request
+                    .unwrap_or_default()
+                    || capabilities
+                        .exception_breakpoint_filters
+                        .as_ref()
+                        .is_some_and(|filters| !filters.is_empty())
+            }
             _ => true,
         }
     }
@@ -55,6 +79,10 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Frames => SharedString::new_static("Frames"),
             DebuggerPaneItem::Modules => SharedString::new_static("Modules"),
             DebuggerPaneItem::LoadedSources => SharedString::new_static("Sources"),
+            DebuggerPaneItem::Watch => SharedString::new_static("Watch"),
+            DebuggerPaneItem::Memory => SharedString::new_static("Memory"),
+            DebuggerPaneItem::Disassembly => SharedString::new_static("Disassembly"),
+            DebuggerPaneItem::Exception => SharedString::new_static("Exception"),
         }
     }
 }
@@ -85,6 +113,7 @@ pub(crate) struct SerializedPane {
 }
 
 const DEBUGGER_PANEL_PREFIX: &str = "debugger_panel_";
+const DEBUGGER_WATCHES_PREFIX: &str = "debugger_watches_";
 
 pub(crate) async fn serialize_pane_layout(
     adapter_name: SharedString,
@@ -159,6 +188,38 @@ pub(crate) async fn get_serialized_pane_layout(
         .and_then(|value| serde_json::from_str::<SerializedPaneLayout>(&value).ok())
 }
 
+/// Watch expressions are kept per project, identified by the paths of its visible worktrees.
+pub(crate) fn watches_key(project: &Project, cx: &App) -> Option<String> {
+    let mut worktree_paths = project
+        .visible_worktrees(cx)
+        .map(|worktree| worktree.read(cx).abs_path().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if worktree_paths.is_empty() {
+        return None;
+    }
+    worktree_paths.sort();
+
+    Some(format!(
+        "{DEBUGGER_WATCHES_PREFIX}-{}",
+        worktree_paths.join(",")
+    ))
+}
+
+pub(crate) async fn serialize_watches(key: String, expressions: Vec<String>) -> anyhow::Result<()> {
+    KEY_VALUE_STORE
+        .write_kvp(key, serde_json::to_string(&expressions)?)
+        .await
+}
+
+pub(crate) fn get_serialized_watches(key: &str) -> Vec<String> {
+    KEY_VALUE_STORE
+        .read_kvp(key)
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str::<Vec<String>>(&value).ok())
+        .unwrap_or_default()
+}
+
 pub(crate) fn deserialize_pane_layout(
     serialized: SerializedPaneLayout,
     workspace: &WeakEntity<Workspace>,
@@ -169,6 +230,10 @@ pub(crate) fn deserialize_pane_layout(
     console: &Entity<Console>,
     breakpoint_list: &Entity<BreakpointList>,
     loaded_sources: &Entity<LoadedSourceList>,
+    watch_list: &Entity<WatchList>,
+    memory_view: &Entity<MemoryView>,
+    disassembly_view: &Entity<DisassemblyView>,
+    exception_view: &Entity<ExceptionView>,
     subscriptions: &mut HashMap<EntityId, Subscription>,
     window: &mut Window,
     cx: &mut Context<RunningState>,
@@ -191,6 +256,10 @@ pub(crate) fn deserialize_pane_layout(
                     console,
                     breakpoint_list,
                     loaded_sources,
+                    watch_list,
+                    memory_view,
+                    disassembly_view,
+                    exception_view,
                     subscriptions,
                     window,
                     cx,
@@ -259,6 +328,34 @@ pub(crate) fn deserialize_pane_layout(
                         None,
                         cx,
                     )),
+                    DebuggerPaneItem::Watch => Box::new(SubView::new(
+                        watch_list.focus_handle(cx),
+                        watch_list.clone().into(),
+                        DebuggerPaneItem::Watch,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Memory => Box::new(SubView::new(
+                        memory_view.focus_handle(cx),
+                        memory_view.clone().into(),
+                        DebuggerPaneItem::Memory,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                        disassembly_view.focus_handle(cx),
+                        disassembly_view.clone().into(),
+                        DebuggerPaneItem::Disassembly,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Exception => Box::new(SubView::new(
+                        exception_view.focus_handle(cx),
+                        exception_view.clone().into(),
+                        DebuggerPaneItem::Exception,
+                        None,
+                        cx,
+                    )),
                     DebuggerPaneItem::Console => Box::new(SubView::new(
                         pane.focus_handle(cx),
                         console.clone().into(),
@@ -1,9 +1,13 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
+pub(crate) mod exception_view;
 pub(crate) mod loaded_source_list;
+pub(crate) mod memory_view;
 pub(crate) mod module_list;
 pub mod stack_frame_list;
 pub mod variable_list;
+pub(crate) mod watch_list;
 
 use std::{any::Any, ops::ControlFlow, sync::Arc, time::Duration};
 
@@ -14,11 +18,14 @@ use breakpoint_list::BreakpointList;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{Capabilities, Thread, client::SessionId, debugger_settings::DebuggerSettings};
+use disassembly_view::DisassemblyView;
+use exception_view::ExceptionView;
 use gpui::{
     Action as _, AnyView, AppContext, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
     NoAction, Pixels, Point, Subscription, Task, WeakEntity,
 };
 use loaded_source_list::LoadedSourceList;
+use memory_view::MemoryView;
 use module_list::ModuleList;
 use project::{
     Project,
@@ -33,7 +40,8 @@ use ui::{
     StatefulInteractiveElement, Styled, Tab, Window, div, h_flex, v_flex,
 };
 use util::ResultExt;
-use variable_list::VariableList;
+use variable_list::{VariableList, VariableListEvent};
+use watch_list::WatchList;
 use workspace::{
     ActivePaneDecorator, DraggedTab, Item, Member, Pane, PaneGroup, Workspace,
     item::TabContentParams, move_item, pane::Event,
@@ -53,6 +61,10 @@ pub struct RunningState {
     module_list: Entity<module_list::ModuleList>,
     _console: Entity<Console>,
     breakpoint_list: Entity<BreakpointList>,
+    watch_list: Entity<WatchList>,
+    memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
+    exception_view: Entity<ExceptionView>,
     panes: PaneGroup,
     pane_close_subscriptions: HashMap<EntityId, Subscription>,
     _schedule_serialize: Option<Task<()>>,
@@ -81,6 +93,9 @@ impl Render for RunningState {
         self.variable_list.update(cx, |this, cx| {
             this.disabled(thread_status != ThreadStatus::Stopped, cx);
         });
+        self.watch_list.update(cx, |this, cx| {
+            this.disabled(thread_status != ThreadStatus::Stopped, cx);
+        });
         v_flex()
             .size_full()
             .key_context("DebugSessionItem")
@@ -377,7 +392,13 @@ impl RunningState {
         let session_id = session.read(cx).session_id();
         let weak_state = cx.weak_entity();
         let stack_frame_list = cx.new(|cx| {
-            StackFrameList::new(workspace.clone(), session.clone(), weak_state, window, cx)
+            StackFrameList::new(
+                workspace.clone(),
+                session.clone(),
+                weak_state.clone(),
+                window,
+                cx,
+            )
         });
 
         let variable_list =
@@ -397,10 +418,42 @@ impl RunningState {
             )
         });
 
-        let breakpoint_list = BreakpointList::new(session.clone(), workspace.clone(), &project, cx);
+        let breakpoint_list =
+            BreakpointList::new(session.clone(), workspace.clone(), &project, window, cx);
+
+        let watches_key = persistence::watches_key(project.read(cx), cx);
+        let watch_list = cx.new(|cx| {
+            WatchList::new(
+                session.clone(),
+                stack_frame_list.clone(),
+                watches_key,
+                window,
+                cx,
+            )
+        });
+
+        let memory_view = cx.new(|cx| MemoryView::new(session.clone(), window, cx));
+
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(session.clone(), weak_state, stack_frame_list.clone(), cx)
+        });
+
+        let exception_view = cx.new(|cx| ExceptionView::new(session.clone(), cx));
 
         let _subscriptions = vec![
             cx.observe(&module_list, |_, _, cx| cx.notify()),
+            cx.subscribe_in(
+                &variable_list,
+                window,
+                |this, _, event, window, cx| match event {
+                    VariableListEvent::ViewMemory(memory_reference) => {
+                        this.memory_view.update(cx, |memory_view, cx| {
+                            memory_view.open(memory_reference.clone(), window, cx);
+                        });
+                        this.activate_pane_item(DebuggerPaneItem::Memory, window, cx);
+                    }
+                },
+            ),
             cx.subscribe_in(&session, window, |this, _, event, window, cx| {
                 match event {
                     SessionEvent::Stopped(thread_id) => {
@@ -418,6 +471,14 @@ impl RunningState {
                         let threads = this.session.update(cx, |this, cx| this.threads(cx));
                         this.select_current_thread(&threads, cx);
                     }
+                    SessionEvent::ExceptionInfo => {
+                        let session = this.session.read(cx);
+                        if session.exception_info().is_some()
+                            && DebuggerPaneItem::Exception.is_supported(session.capabilities())
+                        {
+                            this.activate_pane_item(DebuggerPaneItem::Exception, window, cx);
+                        }
+                    }
                     _ => {}
                 }
                 cx.notify()
@@ -439,6 +500,10 @@ impl RunningState {
                 &console,
                 &breakpoint_list,
                 &loaded_source_list,
+                &watch_list,
+                &memory_view,
+                &disassembly_view,
+                &exception_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -478,6 +543,7 @@ impl RunningState {
                 loaded_source_list,
                 &console,
                 &breakpoint_list,
+                &watch_list,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -500,6 +566,10 @@ impl RunningState {
             module_list,
             _console: console,
             breakpoint_list,
+            watch_list,
+            memory_view,
+            disassembly_view,
+            exception_view,
             loaded_sources_list: loaded_source_list,
             pane_close_subscriptions,
             _schedule_serialize: None,
@@ -551,62 +621,133 @@ impl RunningState {
         );
 
         if let Some(pane) = self.panes.pane_at_pixel_position(position) {
-            let sub_view = match item_kind {
-                DebuggerPaneItem::Console => {
-                    let weak_console = self._console.clone().downgrade();
+            let sub_view = self.create_sub_view(item_kind, pane, cx);
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(sub_view), false, false, None, window, cx);
+            })
+        }
+    }
 
-                    Box::new(SubView::new(
-                        pane.focus_handle(cx),
-                        self._console.clone().into(),
-                        item_kind,
-                        Some(Box::new(move |cx| {
-                            weak_console
-                                .read_with(cx, |console, cx| console.show_indicator(cx))
-                                .unwrap_or_default()
-                        })),
-                        cx,
-                    ))
-                }
-                DebuggerPaneItem::Variables => Box::new(SubView::new(
-                    self.variable_list.focus_handle(cx),
-                    self.variable_list.clone().into(),
-                    item_kind,
-                    None,
-                    cx,
-                )),
-                DebuggerPaneItem::BreakpointList => Box::new(SubView::new(
-                    self.breakpoint_list.focus_handle(cx),
-                    self.breakpoint_list.clone().into(),
-                    item_kind,
-                    None,
-                    cx,
-                )),
-                DebuggerPaneItem::Frames => Box::new(SubView::new(
-                    self.stack_frame_list.focus_handle(cx),
-                    self.stack_frame_list.clone().into(),
-                    item_kind,
-                    None,
-                    cx,
-                )),
-                DebuggerPaneItem::Modules => Box::new(SubView::new(
-                    self.module_list.focus_handle(cx),
-                    self.module_list.clone().into(),
-                    item_kind,
-                    None,
-                    cx,
-                )),
-                DebuggerPaneItem::LoadedSources => Box::new(SubView::new(
-                    self.loaded_sources_list.focus_handle(cx),
-                    self.loaded_sources_list.clone().into(),
-                    item_kind,
-                    None,
-                    cx,
-                )),
-            };
+    /// Activates the item of the given kind, adding it next to the variables if it isn't open yet.
+    pub(crate) fn activate_pane_item(
+        &mut self,
+        item_kind: DebuggerPaneItem,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let find_item = |kind: DebuggerPaneItem, cx: &App| {
+            self.panes.panes().into_iter().find_map(|pane| {
+                pane.read(cx)
+                    .items_of_type::<SubView>()
+                    .position(|view| view.read(cx).view_kind() == kind)
+                    .map(|ix| (pane.clone(), ix))
+            })
+        };
 
+        if let Some((pane, ix)) = find_item(item_kind, cx) {
             pane.update(cx, |pane, cx| {
-                pane.add_item(sub_view, false, false, None, window, cx);
-            })
+                pane.activate_item(ix, true, true, window, cx);
+            });
+            return;
+        }
+
+        let Some(pane) = find_item(DebuggerPaneItem::Variables, cx)
+            .map(|(pane, _)| pane)
+            .or_else(|| self.panes.panes().into_iter().next().cloned())
+        else {
+            return;
+        };
+        let sub_view = self.create_sub_view(item_kind, &pane, cx);
+        pane.update(cx, |pane, cx| {
+            pane.add_item(Box::new(sub_view), true, true, None, window, cx);
+        });
+    }
+
+    fn create_sub_view(
+        &self,
+        item_kind: DebuggerPaneItem,
+        pane: &Entity<Pane>,
+        cx: &mut Context<Self>,
+    ) -> Entity<SubView> {
+        match item_kind {
+            DebuggerPaneItem::Console => {
+                let weak_console = self._console.clone().downgrade();
+
+                SubView::new(
+                    pane.focus_handle(cx),
+                    self._console.clone().into(),
+                    item_kind,
+                    Some(Box::new(move |cx| {
+                        weak_console
+                            .read_with(cx, |console, cx| console.show_indicator(cx))
+                            .unwrap_or_default()
+                    })),
+                    cx,
+                )
+            }
+            DebuggerPaneItem::Variables => SubView::new(
+                self.variable_list.focus_handle(cx),
+                self.variable_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::BreakpointList => SubView::new(
+                self.breakpoint_list.focus_handle(cx),
+                self.breakpoint_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::Frames => SubView::new(
+                self.stack_frame_list.focus_handle(cx),
+                self.stack_frame_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::Modules => SubView::new(
+                self.module_list.focus_handle(cx),
+                self.module_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::LoadedSources => SubView::new(
+                self.loaded_sources_list.focus_handle(cx),
+                self.loaded_sources_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::Watch => SubView::new(
+                self.watch_list.focus_handle(cx),
+                self.watch_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::Memory => SubView::new(
+                self.memory_view.focus_handle(cx),
+                self.memory_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::Disassembly => SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
+            DebuggerPaneItem::Exception => SubView::new(
+                self.exception_view.focus_handle(cx),
+                self.exception_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            ),
         }
     }
 
@@ -737,6 +878,21 @@ impl RunningState {
         &self.variable_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn watch_list(&self) -> &Entity<WatchList> {
+        &self.watch_list
+    }
+
+    #[cfg(test)]
+    pub(crate) fn memory_view(&self) -> &Entity<MemoryView> {
+        &self.memory_view
+    }
+
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
     pub fn capabilities(&self, cx: &App) -> Capabilities {
         self.session().read(cx).capabilities().clone()
     }
@@ -950,6 +1106,7 @@ impl RunningState {
         loaded_source_list: Option<&Entity<LoadedSourceList>>,
         console: &Entity<Console>,
         breakpoints: &Entity<BreakpointList>,
+        watch_list: &Entity<WatchList>,
         subscriptions: &mut HashMap<EntityId, Subscription>,
         window: &mut Window,
         cx: &mut Context<'_, RunningState>,
@@ -1038,6 +1195,21 @@ impl RunningState {
                 );
                 this.activate_item(1, false, false, window, cx);
             }
+
+            this.add_item(
+                Box::new(SubView::new(
+                    watch_list.focus_handle(cx),
+                    watch_list.clone().into(),
+                    DebuggerPaneItem::Watch,
+                    None,
+                    cx,
+                )),
+                false,
+                false,
+                None,
+                window,
+                cx,
+            );
         });
 
         let rightmost_pane = new_debugger_pane(workspace.clone(), project.clone(), window, cx);
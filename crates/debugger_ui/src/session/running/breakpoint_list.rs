@@ -3,26 +3,31 @@ use std::{
     time::Duration,
 };
 
+use collections::HashMap;
 use dap::ExceptionBreakpointsFilter;
 use editor::Editor;
 use gpui::{
-    AppContext, Entity, FocusHandle, Focusable, ListState, MouseButton, Stateful, Task, WeakEntity,
-    list,
+    AppContext, Entity, FocusHandle, Focusable, ListState, MouseButton, Stateful, Subscription,
+    Task, WeakEntity, list,
 };
 use language::Point;
+use menu::Confirm;
 use project::{
     Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
+        breakpoint_store::{
+            BreakpointEditAction, BreakpointStore, DataBreakpoint, FunctionBreakpoint,
+            SourceBreakpoint,
+        },
         session::Session,
     },
     worktree_store::WorktreeStore,
 };
 use ui::{
-    App, Clickable, Color, Context, Div, Icon, IconButton, IconName, Indicator, InteractiveElement,
-    IntoElement, Label, LabelCommon, LabelSize, ListItem, ParentElement, Render, RenderOnce,
-    Scrollbar, ScrollbarState, SharedString, StatefulInteractiveElement, Styled, Window, div,
-    h_flex, px, v_flex,
+    App, Clickable, Color, Context, Div, Divider, FluentBuilder, Icon, IconButton, IconName,
+    Indicator, InteractiveElement, IntoElement, Label, LabelCommon, LabelSize, ListItem,
+    ParentElement, Render, RenderOnce, Scrollbar, ScrollbarState, SharedString,
+    StatefulInteractiveElement, Styled, Window, div, h_flex, px, v_flex,
 };
 use util::{ResultExt, maybe};
 use workspace::Workspace;
@@ -37,7 +42,11 @@ pub(crate) struct BreakpointList {
     session: Entity<Session>,
     hide_scrollbar_task: Option<Task<()>>,
     show_scrollbar: bool,
+    function_breakpoint_editor: Entity<Editor>,
+    /// Condition editors of the exception filters that support conditions, by filter id.
+    exception_condition_editors: HashMap<String, Entity<Editor>>,
     focus_handle: FocusHandle,
+    _subscription: Subscription,
 }
 
 impl Focusable for BreakpointList {
@@ -50,6 +59,7 @@ impl BreakpointList {
         session: Entity<Session>,
         workspace: WeakEntity<Workspace>,
         project: &Entity<Project>,
+        window: &mut Window,
         cx: &mut App,
     ) -> Entity<Self> {
         let project = project.read(cx);
@@ -72,6 +82,12 @@ impl BreakpointList {
                     breakpoint.render(window, cx).into_any_element()
                 },
             );
+            let function_breakpoint_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Add a function breakpoint", cx);
+                editor
+            });
+            let _subscription = cx.observe(&breakpoint_store, |_, _, cx| cx.notify());
             Self {
                 breakpoint_store,
                 worktree_store,
@@ -82,11 +98,78 @@ impl BreakpointList {
                 show_scrollbar: false,
                 workspace,
                 session,
+                function_breakpoint_editor,
+                exception_condition_editors: HashMap::default(),
                 focus_handle: cx.focus_handle(),
+                _subscription,
             }
         })
     }
 
+    fn add_function_breakpoint(
+        &mut self,
+        _: &Confirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name = self.function_breakpoint_editor.update(cx, |editor, cx| {
+            let name = editor.text(cx);
+            editor.clear(window, cx);
+            name
+        });
+        self.breakpoint_store.update(cx, |store, cx| {
+            if !store
+                .function_breakpoints()
+                .iter()
+                .any(|breakpoint| *breakpoint.name == *name.trim())
+            {
+                store.toggle_function_breakpoint(&name, cx);
+            }
+        });
+    }
+
+    fn update_exception_condition_editors(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let session = self.session.read(cx);
+        // Conditions are only sent to adapters that support exception filter options.
+        let supports_exception_filter_options = session
+            .capabilities()
+            .supports_exception_filter_options
+            .unwrap_or_default();
+        let filters = session
+            .exception_breakpoints()
+            .filter(|(filter, _)| {
+                supports_exception_filter_options && filter.supports_condition.unwrap_or_default()
+            })
+            .map(|(filter, _)| {
+                (
+                    filter.filter.clone(),
+                    filter.condition_description.clone(),
+                    session
+                        .exception_breakpoint_condition(&filter.filter)
+                        .map(ToOwned::to_owned),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (id, condition_description, condition) in filters {
+            self.exception_condition_editors
+                .entry(id)
+                .or_insert_with(|| {
+                    cx.new(|cx| {
+                        let mut editor = Editor::single_line(window, cx);
+                        editor.set_placeholder_text(
+                            condition_description.unwrap_or_else(|| "Condition".to_owned()),
+                            cx,
+                        );
+                        if let Some(condition) = condition {
+                            editor.set_text(condition, window, cx);
+                        }
+                        editor
+                    })
+                });
+        }
+    }
+
     fn hide_scrollbar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         const SCROLLBAR_SHOW_INTERVAL: Duration = Duration::from_secs(1);
         self.hide_scrollbar_task = Some(cx.spawn_in(window, async move |panel, cx| {
@@ -143,9 +226,16 @@ impl BreakpointList {
 impl Render for BreakpointList {
     fn render(
         &mut self,
-        _window: &mut ui::Window,
+        window: &mut ui::Window,
         cx: &mut ui::Context<Self>,
     ) -> impl ui::IntoElement {
+        self.update_exception_condition_editors(window, cx);
+        let session_id = self.session.read(cx).session_id();
+        let capabilities = self.session.read(cx).capabilities();
+        let supports_function_breakpoints = capabilities
+            .supports_function_breakpoints
+            .unwrap_or_default();
+        let supports_data_breakpoints = capabilities.supports_data_breakpoints.unwrap_or_default();
         let old_len = self.breakpoints.len();
         let breakpoints = self.breakpoint_store.read(cx).all_breakpoints(cx);
         self.breakpoints.clear();
@@ -203,11 +293,50 @@ impl Render for BreakpointList {
                         id: data.filter.clone(),
                         data: data.clone(),
                         is_enabled: *is_enabled,
+                        condition_editor: self
+                            .exception_condition_editors
+                            .get(&data.filter)
+                            .cloned(),
                     }),
                     weak: weak.clone(),
                 });
-        self.breakpoints
-            .extend(breakpoints.chain(exception_breakpoints));
+        let function_breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .function_breakpoints()
+            .iter()
+            .filter(|_| supports_function_breakpoints)
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::FunctionBreakpoint(FunctionBreakpointItem {
+                    breakpoint: breakpoint.clone(),
+                }),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
+        let data_breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .data_breakpoints()
+            .iter()
+            .filter(|breakpoint| {
+                supports_data_breakpoints
+                    && breakpoint
+                        .session_id
+                        .is_none_or(|breakpoint_session| breakpoint_session == session_id)
+            })
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::DataBreakpoint(DataBreakpointItem {
+                    breakpoint: breakpoint.clone(),
+                }),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
+        self.breakpoints.extend(
+            breakpoints
+                .chain(function_breakpoints)
+                .chain(data_breakpoints)
+                .chain(exception_breakpoints),
+        );
         if self.breakpoints.len() != old_len {
             self.list_state.reset(self.breakpoints.len());
         }
@@ -222,10 +351,19 @@ impl Render for BreakpointList {
                     this.hide_scrollbar(window, cx);
                 }
             }))
+            .on_action(cx.listener(Self::add_function_breakpoint))
             .size_full()
             .m_0p5()
             .child(list(self.list_state.clone()).flex_grow())
             .children(self.render_vertical_scrollbar(cx))
+            .when(supports_function_breakpoints, |this| {
+                this.child(Divider::horizontal()).child(
+                    div()
+                        .px_1()
+                        .py_1()
+                        .child(self.function_breakpoint_editor.clone()),
+                )
+            })
     }
 }
 #[derive(Clone, Debug)]
@@ -406,6 +544,7 @@ struct ExceptionBreakpoint {
     id: String,
     data: ExceptionBreakpointsFilter,
     is_enabled: bool,
+    condition_editor: Option<Entity<Editor>>,
 }
 
 impl ExceptionBreakpoint {
@@ -427,14 +566,18 @@ impl ExceptionBreakpoint {
                     "exception-breakpoint-ui-item-{}-click-handler",
                     self.id
                 )))
-                .on_click(move |_, _, cx| {
-                    list.update(cx, |this, cx| {
-                        this.session.update(cx, |this, cx| {
-                            this.toggle_exception_breakpoint(&id, cx);
-                        });
-                        cx.notify();
-                    })
-                    .ok();
+                .on_click({
+                    let list = list.clone();
+                    let id = id.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.session.update(cx, |this, cx| {
+                                this.toggle_exception_breakpoint(&id, cx);
+                            });
+                            cx.notify();
+                        })
+                        .ok();
+                    }
                 })
                 .cursor_pointer()
                 .child(Indicator::icon(Icon::new(IconName::Flame)).color(color)),
@@ -453,13 +596,186 @@ impl ExceptionBreakpoint {
                         .size(LabelSize::XSmall)
                         .line_height_style(ui::LineHeightStyle::UiLabel)
                         .color(Color::Muted)
+                }))
+                .children(self.condition_editor.map(|editor| {
+                    div()
+                        .pt_1()
+                        .on_action({
+                            let editor = editor.clone();
+                            move |_: &Confirm, _, cx| {
+                                let condition = editor.read(cx).text(cx);
+                                list.update(cx, |this, cx| {
+                                    this.session.update(cx, |this, cx| {
+                                        this.set_exception_breakpoint_condition(
+                                            &id,
+                                            Some(condition),
+                                            cx,
+                                        );
+                                    });
+                                })
+                                .ok();
+                            }
+                        })
+                        .child(editor)
                 })),
         )
     }
 }
+#[derive(Clone, Debug)]
+struct FunctionBreakpointItem {
+    breakpoint: FunctionBreakpoint,
+}
+
+impl FunctionBreakpointItem {
+    fn render(self, list: WeakEntity<BreakpointList>) -> ListItem {
+        let breakpoint = self.breakpoint;
+        let icon_name = if breakpoint.state.is_enabled() {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+        let name = breakpoint.name;
+        ListItem::new(SharedString::from(format!(
+            "function-breakpoint-ui-item-{name}"
+        )))
+        .rounded()
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "function-breakpoint-ui-toggle-{name}"
+                )))
+                .cursor_pointer()
+                .on_click({
+                    let list = list.clone();
+                    let name = name.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.breakpoint_store.update(cx, |store, cx| {
+                                store.invert_function_breakpoint_state(&name, cx);
+                            })
+                        })
+                        .ok();
+                    }
+                })
+                .child(Indicator::icon(Icon::new(icon_name)).color(Color::Debugger)),
+        )
+        .end_hover_slot(
+            IconButton::new(
+                SharedString::from(format!("function-breakpoint-ui-remove-{name}")),
+                IconName::Close,
+            )
+            .on_click({
+                let name = name.clone();
+                move |_, _, cx| {
+                    list.update(cx, |this, cx| {
+                        this.breakpoint_store.update(cx, |store, cx| {
+                            store.toggle_function_breakpoint(&name, cx);
+                        })
+                    })
+                    .ok();
+                }
+            })
+            .icon_size(ui::IconSize::XSmall),
+        )
+        .child(
+            h_flex()
+                .py_1()
+                .gap_1()
+                .child(
+                    Label::new(SharedString::from(name.to_string()))
+                        .size(LabelSize::Small)
+                        .line_height_style(ui::LineHeightStyle::UiLabel),
+                )
+                .child(
+                    Label::new("Function")
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted)
+                        .line_height_style(ui::LineHeightStyle::UiLabel),
+                ),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DataBreakpointItem {
+    breakpoint: DataBreakpoint,
+}
+
+impl DataBreakpointItem {
+    fn render(self, list: WeakEntity<BreakpointList>) -> ListItem {
+        let breakpoint = self.breakpoint;
+        let icon_name = if breakpoint.state.is_enabled() {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+        let id = format!("{}-{}", breakpoint.data_id, breakpoint.access_type.to_int());
+        let access = format!("Data ({})", breakpoint.access_type.label());
+        ListItem::new(SharedString::from(format!("data-breakpoint-ui-item-{id}")))
+            .rounded()
+            .start_slot(
+                div()
+                    .id(SharedString::from(format!(
+                        "data-breakpoint-ui-toggle-{id}"
+                    )))
+                    .cursor_pointer()
+                    .on_click({
+                        let list = list.clone();
+                        let data_id = breakpoint.data_id.clone();
+                        let access_type = breakpoint.access_type;
+                        move |_, _, cx| {
+                            list.update(cx, |this, cx| {
+                                this.breakpoint_store.update(cx, |store, cx| {
+                                    store.invert_data_breakpoint_state(&data_id, access_type, cx);
+                                })
+                            })
+                            .ok();
+                        }
+                    })
+                    .child(Indicator::icon(Icon::new(icon_name)).color(Color::Debugger)),
+            )
+            .end_hover_slot(
+                IconButton::new(
+                    SharedString::from(format!("data-breakpoint-ui-remove-{id}")),
+                    IconName::Close,
+                )
+                .on_click({
+                    let breakpoint = breakpoint.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.breakpoint_store.update(cx, |store, cx| {
+                                store.toggle_data_breakpoint(breakpoint.clone(), cx);
+                            })
+                        })
+                        .ok();
+                    }
+                })
+                .icon_size(ui::IconSize::XSmall),
+            )
+            .child(
+                h_flex()
+                    .py_1()
+                    .gap_1()
+                    .child(
+                        Label::new(SharedString::from(breakpoint.description.to_string()))
+                            .size(LabelSize::Small)
+                            .line_height_style(ui::LineHeightStyle::UiLabel),
+                    )
+                    .child(
+                        Label::new(access)
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted)
+                            .line_height_style(ui::LineHeightStyle::UiLabel),
+                    ),
+            )
+    }
+}
+
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
+    FunctionBreakpoint(FunctionBreakpointItem),
+    DataBreakpoint(DataBreakpointItem),
     ExceptionBreakpoint(ExceptionBreakpoint),
 }
 
@@ -474,6 +790,12 @@ impl RenderOnce for BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.render(self.weak)
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.render(self.weak)
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                data_breakpoint.render(self.weak)
+            }
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 exception_breakpoint.render(self.weak)
             }
@@ -0,0 +1,379 @@
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+use dap::{DisassembledInstruction, SteppingGranularity};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent, ThreadId, ThreadStatus};
+use std::ops::Range;
+use ui::{Divider, Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::ResultExt;
+
+/// How many instructions before the instruction pointer are disassembled.
+const INSTRUCTIONS_BEFORE: i64 = 20;
+/// How many instructions are disassembled in total.
+const INSTRUCTION_COUNT: u64 = 60;
+
+/// Shows the instructions around the instruction pointer of the selected stack frame,
+/// and lets the user step by instruction and set instruction breakpoints.
+pub struct DisassemblyView {
+    session: Entity<Session>,
+    state: WeakEntity<RunningState>,
+    stack_frame_list: Entity<StackFrameList>,
+    instructions: Vec<DisassembledInstruction>,
+    instruction_pointer: Option<String>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    list_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    disassemble_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub fn new(
+        session: Entity<Session>,
+        state: WeakEntity<RunningState>,
+        stack_frame_list: Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => this.disassemble(cx),
+            }),
+            cx.subscribe(&session, |_, _, event, cx| match event {
+                SessionEvent::InstructionBreakpoints => cx.notify(),
+                _ => {}
+            }),
+        ];
+
+        let list_handle = UniformListScrollHandle::default();
+        let mut this = Self {
+            scrollbar_state: ScrollbarState::new(list_handle.clone()),
+            list_handle,
+            session,
+            state,
+            stack_frame_list,
+            instructions: Vec::new(),
+            instruction_pointer: None,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            disassemble_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.disassemble(cx);
+        this
+    }
+
+    fn disassemble(&mut self, cx: &mut Context<Self>) {
+        let Some(instruction_pointer) = self
+            .stack_frame_list
+            .read(cx)
+            .selected_stack_frame()
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference.clone())
+        else {
+            self.instructions.clear();
+            self.instruction_pointer = None;
+            cx.notify();
+            return;
+        };
+
+        let disassemble = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer.clone(),
+                -INSTRUCTIONS_BEFORE,
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        });
+        self.disassemble_task = cx.spawn(async move |this, cx| {
+            let result = disassemble.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(instructions) => {
+                        this.instructions = instructions;
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.instructions.clear();
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                this.instruction_pointer = Some(instruction_pointer);
+                if let Some(ix) = this.instruction_pointer_ix() {
+                    this.list_handle.scroll_to_item(ix, ScrollStrategy::Center);
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+
+    fn instruction_pointer_ix(&self) -> Option<usize> {
+        let instruction_pointer = self.instruction_pointer.as_ref()?;
+        self.instructions
+            .iter()
+            .position(|instruction| same_address(&instruction.address, instruction_pointer))
+    }
+
+    fn toggle_breakpoint(&mut self, address: String, cx: &mut Context<Self>) {
+        let toggle = self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(address, cx)
+        });
+        cx.spawn(async move |this, cx| {
+            if let Err(error) = toggle.await {
+                this.update(cx, |this, cx| {
+                    this.error = Some(error.to_string().into());
+                    cx.notify();
+                })
+                .log_err();
+            }
+        })
+        .detach();
+    }
+
+    fn step(
+        &mut self,
+        step: fn(&mut Session, ThreadId, SteppingGranularity, &mut Context<Session>),
+        cx: &mut Context<Self>,
+    ) {
+        let Some(thread_id) = self
+            .state
+            .read_with(cx, |state, _| state.selected_thread_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        self.session.update(cx, |session, cx| {
+            step(session, thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rows(&self, cx: &App) -> Vec<String> {
+        let breakpoints = self.session.read(cx).instruction_breakpoints();
+        self.instructions
+            .iter()
+            .enumerate()
+            .map(|(ix, instruction)| {
+                let marker = if Some(ix) == self.instruction_pointer_ix() {
+                    "->"
+                } else if breakpoints.contains(&instruction.address) {
+                    "* "
+                } else {
+                    "  "
+                };
+                format!(
+                    "{marker} {} {}",
+                    instruction.address, instruction.instruction
+                )
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn toggle_breakpoint_at(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let address = self.instructions[ix].address.clone();
+        self.toggle_breakpoint(address, cx);
+    }
+
+    fn render_entries(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range.map(|ix| self.render_entry(ix, cx)).collect()
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let instruction = &self.instructions[ix];
+        let is_instruction_pointer = Some(ix) == self.instruction_pointer_ix();
+        let has_breakpoint = self
+            .session
+            .read(cx)
+            .instruction_breakpoints()
+            .contains(&instruction.address);
+        let supports_breakpoints = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_instruction_breakpoints
+            .unwrap_or_default();
+
+        h_flex()
+            .id(("disassembly-instruction", ix))
+            .w_full()
+            .gap_2()
+            .pr_2()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .text_xs()
+            .when(is_instruction_pointer, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .child(
+                div()
+                    .id(("disassembly-gutter", ix))
+                    .flex_none()
+                    .w_4()
+                    .h_full()
+                    .when(supports_breakpoints, |this| {
+                        this.cursor_pointer().on_click(cx.listener({
+                            let address = instruction.address.clone();
+                            move |this, _, _, cx| {
+                                this.toggle_breakpoint(address.clone(), cx);
+                            }
+                        }))
+                    })
+                    .when(has_breakpoint, |this| {
+                        this.child(
+                            Icon::new(IconName::DebugBreakpoint)
+                                .size(IconSize::XSmall)
+                                .color(Color::Debugger),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(instruction.address.clone()),
+            )
+            .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                this.child(
+                    div()
+                        .w_32()
+                        .overflow_x_hidden()
+                        .text_color(cx.theme().colors().text_disabled)
+                        .child(bytes),
+                )
+            })
+            .child(div().flex_1().child(instruction.instruction.clone()))
+            .when_some(instruction.symbol.clone(), |this, symbol| {
+                this.child(
+                    div()
+                        .text_color(cx.theme().colors().text_muted)
+                        .child(format!("<{symbol}>")),
+                )
+            })
+            .into_any_element()
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self
+            .state
+            .read_with(cx, |state, cx| state.thread_status(cx))
+            .ok()
+            .flatten()
+            .is_some_and(|status| status == ThreadStatus::Stopped);
+
+        h_flex()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .child(
+                IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Over Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(Session::step_over, cx))),
+            )
+            .child(
+                IconButton::new("disassembly-step-into", IconName::DebugStepInto)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Into Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(Session::step_in, cx))),
+            )
+            .child(
+                IconButton::new("disassembly-step-out", IconName::DebugStepOut)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Out"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(Session::step_out, cx))),
+            )
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("disassembly-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+/// Debug adapters don't agree on the casing and padding of addresses.
+fn same_address(a: &str, b: &str) -> bool {
+    fn normalize(address: &str) -> String {
+        let address = address.trim().to_lowercase();
+        let digits = address.strip_prefix("0x").unwrap_or(&address);
+        digits.trim_start_matches('0').to_string()
+    }
+    normalize(a) == normalize(b)
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_toolbar(cx))
+            .child(Divider::horizontal())
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+            })
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "disassembly-view",
+                            self.instructions.len(),
+                            |this, range, _window, cx| this.render_entries(range, cx),
+                        )
+                        .track_scroll(self.list_handle.clone())
+                        .size_full(),
+                    )
+                    .child(self.render_vertical_scrollbar(cx)),
+            )
+    }
+}
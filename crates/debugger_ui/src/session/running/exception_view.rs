@@ -0,0 +1,135 @@
+use dap::ExceptionDetails;
+use gpui::{AnyElement, Entity, FocusHandle, Focusable, Subscription};
+use project::debugger::session::{ExceptionInfo, Session};
+use ui::prelude::*;
+
+/// Shows the exception the debuggee stopped on: its type, its message and its stack trace,
+/// followed by the exceptions it wraps.
+pub(crate) struct ExceptionView {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl ExceptionView {
+    pub fn new(session: Entity<Session>, cx: &mut Context<Self>) -> Self {
+        let _subscription = cx.observe(&session, |_, _, cx| cx.notify());
+
+        Self {
+            session,
+            focus_handle: cx.focus_handle(),
+            _subscription,
+        }
+    }
+
+    fn render_exception_info(&self, exception_info: &ExceptionInfo, cx: &App) -> AnyElement {
+        let type_name = exception_info
+            .details
+            .as_ref()
+            .and_then(|details| {
+                details
+                    .full_type_name
+                    .clone()
+                    .or_else(|| details.type_name.clone())
+            })
+            .unwrap_or_else(|| exception_info.exception_id.clone());
+        let message = exception_info
+            .details
+            .as_ref()
+            .and_then(|details| details.message.clone())
+            .or_else(|| exception_info.description.clone());
+
+        v_flex()
+            .gap_2()
+            .child(Self::render_summary(type_name, message))
+            .children(
+                exception_info
+                    .details
+                    .as_ref()
+                    .map(|details| Self::render_details(details, cx)),
+            )
+            .into_any_element()
+    }
+
+    fn render_summary(type_name: String, message: Option<String>) -> impl IntoElement {
+        v_flex()
+            .gap_0p5()
+            .child(
+                Label::new(type_name)
+                    .size(LabelSize::Small)
+                    .color(Color::Error),
+            )
+            .children(message.map(|message| Label::new(message).size(LabelSize::Small)))
+    }
+
+    fn render_details(details: &ExceptionDetails, cx: &App) -> AnyElement {
+        v_flex()
+            .gap_2()
+            .children(details.stack_trace.clone().map(|stack_trace| {
+                div()
+                    .p_1()
+                    .rounded_sm()
+                    .bg(cx.theme().colors().editor_background)
+                    .font_buffer(cx)
+                    .text_ui_xs(cx)
+                    .whitespace_normal()
+                    .child(stack_trace)
+            }))
+            .children(
+                details
+                    .inner_exception
+                    .iter()
+                    .flatten()
+                    .map(|inner_exception| {
+                        let type_name = inner_exception
+                            .full_type_name
+                            .clone()
+                            .or_else(|| inner_exception.type_name.clone())
+                            .unwrap_or_else(|| "Inner exception".to_owned());
+                        v_flex()
+                            .gap_1()
+                            .pl_2()
+                            .border_l_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(
+                                Label::new("Inner exception")
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                            .child(Self::render_summary(
+                                type_name,
+                                inner_exception.message.clone(),
+                            ))
+                            .child(Self::render_details(inner_exception, cx))
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+impl Focusable for ExceptionView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ExceptionView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let exception_info = self.session.read(cx).exception_info().cloned();
+
+        v_flex()
+            .id("exception-view")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_2()
+            .overflow_y_scroll()
+            .map(|this| match exception_info {
+                Some(exception_info) => this.child(self.render_exception_info(&exception_info, cx)),
+                None => this.child(
+                    Label::new("The debuggee didn't stop on an exception")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            })
+    }
+}
@@ -0,0 +1,465 @@
+use editor::Editor;
+use gpui::{
+    AnyElement, ClickEvent, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription,
+    Task, TextStyleRefinement, UniformListScrollHandle, uniform_list,
+};
+use menu::{Cancel, Confirm};
+use project::debugger::session::{MemoryBlock, Session, SessionEvent};
+use std::ops::Range;
+use ui::{Divider, Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::ResultExt;
+
+const BYTES_PER_ROW: usize = 16;
+/// How many bytes are read from the debuggee at once.
+const PAGE_SIZE: u64 = 256;
+
+/// Shows the debuggee's memory around a memory reference as a hex dump, and lets the user edit it byte by byte.
+pub struct MemoryView {
+    session: Entity<Session>,
+    memory_reference: Option<String>,
+    /// The offset of the shown page from the memory reference, in bytes.
+    offset: i64,
+    block: Option<MemoryBlock>,
+    error: Option<SharedString>,
+    address_editor: Entity<Editor>,
+    edited_byte: Option<(usize, Entity<Editor>)>,
+    focus_handle: FocusHandle,
+    list_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    load_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl MemoryView {
+    pub fn new(session: Entity<Session>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let address_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Go to memory reference or address", cx);
+            editor
+        });
+
+        let _subscription = cx.subscribe(&session, |this, _, event, cx| match event {
+            SessionEvent::Stopped(_) | SessionEvent::Memory => this.load(cx),
+            _ => {}
+        });
+
+        let list_handle = UniformListScrollHandle::default();
+        Self {
+            scrollbar_state: ScrollbarState::new(list_handle.clone()),
+            list_handle,
+            session,
+            memory_reference: None,
+            offset: 0,
+            block: None,
+            error: None,
+            address_editor,
+            edited_byte: None,
+            focus_handle: cx.focus_handle(),
+            load_task: Task::ready(()),
+            _subscription,
+        }
+    }
+
+    /// Shows the memory starting at the given memory reference, e.g. the one of a pointer variable.
+    pub(crate) fn open(
+        &mut self,
+        memory_reference: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.address_editor.update(cx, |editor, cx| {
+            editor.set_text(memory_reference.as_str(), window, cx);
+        });
+        self.memory_reference = Some(memory_reference);
+        self.offset = 0;
+        self.edited_byte = None;
+        self.load(cx);
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+
+        let read = self.session.update(cx, |session, cx| {
+            session.read_memory(memory_reference, self.offset, PAGE_SIZE, cx)
+        });
+        self.load_task = cx.spawn(async move |this, cx| {
+            let result = read.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(block) => {
+                        this.block = Some(block);
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.block = None;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+
+    fn go_to_page(&mut self, offset: i64, cx: &mut Context<Self>) {
+        self.offset = offset;
+        self.edited_byte = None;
+        self.load(cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .edited_byte
+            .as_ref()
+            .is_some_and(|(_, editor)| editor.focus_handle(cx).is_focused(window))
+        {
+            self.confirm_byte_edit(window, cx);
+            return;
+        }
+
+        let memory_reference = self.address_editor.read(cx).text(cx).trim().to_string();
+        if !memory_reference.is_empty() {
+            self.open(memory_reference, window, cx);
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.edited_byte.take().is_some() {
+            self.focus_handle.focus(window);
+            cx.notify();
+        }
+    }
+
+    fn edit_byte(&mut self, index: usize, value: u8, window: &mut Window, cx: &mut Context<Self>) {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            let refinement = TextStyleRefinement {
+                font_size: Some(
+                    TextSize::XSmall
+                        .rems(cx)
+                        .to_pixels(window.rem_size())
+                        .into(),
+                ),
+                ..Default::default()
+            };
+            editor.set_text_style_refinement(refinement);
+            editor.set_text(format!("{value:02x}"), window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        editor.focus_handle(cx).focus(window);
+        self.edited_byte = Some((index, editor));
+        cx.notify();
+    }
+
+    fn confirm_byte_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((index, editor)) = self.edited_byte.take() else {
+            return;
+        };
+        self.focus_handle.focus(window);
+        cx.notify();
+
+        let text = editor.read(cx).text(cx);
+        let Some(value) = parse_byte(&text) else {
+            self.error = Some(format!("`{}` is not a hexadecimal byte", text.trim()).into());
+            return;
+        };
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+
+        let write = self.session.update(cx, |session, cx| {
+            session.write_memory(
+                memory_reference,
+                self.offset + index as i64,
+                vec![value],
+                cx,
+            )
+        });
+        cx.spawn(async move |this, cx| {
+            if let Err(error) = write.await {
+                this.update(cx, |this, cx| {
+                    this.error = Some(error.to_string().into());
+                    cx.notify();
+                })
+                .log_err();
+            }
+        })
+        .detach();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rows(&self) -> Vec<String> {
+        (0..self.row_count())
+            .map(|row| {
+                let (hex, ascii) = self.row_bytes(row).into_iter().fold(
+                    (Vec::new(), String::new()),
+                    |(mut hex, mut ascii), byte| {
+                        hex.push(byte.map_or("??".to_string(), |byte| format!("{byte:02x}")));
+                        ascii.push(byte.map_or('.', printable_char));
+                        (hex, ascii)
+                    },
+                );
+                format!("{}  {}  {}", self.row_address(row), hex.join(" "), ascii)
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_byte(
+        &mut self,
+        index: usize,
+        text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.edit_byte(index, 0, window, cx);
+        if let Some((_, editor)) = &self.edited_byte {
+            editor.update(cx, |editor, cx| editor.set_text(text, window, cx));
+        }
+        self.confirm_byte_edit(window, cx);
+    }
+
+    fn row_count(&self) -> usize {
+        self.block.as_ref().map_or(0, |block| {
+            (block.data.len() + block.unreadable_bytes as usize).div_ceil(BYTES_PER_ROW)
+        })
+    }
+
+    /// The bytes of a row, `None` for the ones that could not be read.
+    fn row_bytes(&self, row: usize) -> Vec<Option<u8>> {
+        let Some(block) = self.block.as_ref() else {
+            return Vec::new();
+        };
+        let len = block.data.len() + block.unreadable_bytes as usize;
+        (row * BYTES_PER_ROW..((row + 1) * BYTES_PER_ROW).min(len))
+            .map(|ix| block.data.get(ix).copied())
+            .collect()
+    }
+
+    fn row_address(&self, row: usize) -> String {
+        let row_offset = (row * BYTES_PER_ROW) as u64;
+        match self.block.as_ref() {
+            Some(block) => match parse_address(&block.address) {
+                Some(address) => format!("0x{:016x}", address.wrapping_add(row_offset)),
+                None => format!("{}+0x{row_offset:x}", block.address),
+            },
+            None => String::new(),
+        }
+    }
+
+    fn render_rows(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range.map(|row| self.render_row(row, cx)).collect()
+    }
+
+    fn render_row(&self, row: usize, cx: &mut Context<Self>) -> AnyElement {
+        let bytes = self.row_bytes(row);
+        let can_write = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_write_memory_request
+            .unwrap_or_default();
+
+        let ascii = bytes
+            .iter()
+            .map(|byte| byte.map_or('.', printable_char))
+            .collect::<String>();
+
+        h_flex()
+            .id(("memory-row", row))
+            .w_full()
+            .gap_3()
+            .px_2()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .text_xs()
+            .child(
+                div()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(self.row_address(row)),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(bytes.iter().enumerate().map(|(column, byte)| {
+                        let index = row * BYTES_PER_ROW + column;
+                        if let Some((_, editor)) = self
+                            .edited_byte
+                            .as_ref()
+                            .filter(|(edited, _)| *edited == index)
+                        {
+                            return div().w_6().child(editor.clone()).into_any_element();
+                        }
+
+                        match byte {
+                            Some(value) => {
+                                let value = *value;
+                                div()
+                                    .id(("memory-byte", index))
+                                    .w_4()
+                                    .when(value == 0, |this| {
+                                        this.text_color(cx.theme().colors().text_muted)
+                                    })
+                                    .when(can_write, |this| {
+                                        this.hover(|style| {
+                                            style.bg(cx.theme().colors().ghost_element_hover)
+                                        })
+                                        .on_click(
+                                            cx.listener(
+                                                move |this, click: &ClickEvent, window, cx| {
+                                                    if click.down.click_count < 2 {
+                                                        return;
+                                                    }
+                                                    this.edit_byte(index, value, window, cx);
+                                                },
+                                            ),
+                                        )
+                                    })
+                                    .child(format!("{value:02x}"))
+                                    .into_any_element()
+                            }
+                            None => div()
+                                .w_4()
+                                .text_color(cx.theme().colors().text_disabled)
+                                .child("??")
+                                .into_any_element(),
+                        }
+                    })),
+            )
+            .child(
+                div()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(ascii),
+            )
+            .into_any_element()
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_memory_reference = self.memory_reference.is_some();
+        h_flex()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .child(div().flex_1().child(self.address_editor.clone()))
+            .child(
+                IconButton::new("memory-previous-page", IconName::ChevronUp)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_memory_reference)
+                    .tooltip(Tooltip::text("Previous Page"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.go_to_page(this.offset - PAGE_SIZE as i64, cx);
+                    })),
+            )
+            .child(
+                IconButton::new("memory-next-page", IconName::ChevronDown)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_memory_reference)
+                    .tooltip(Tooltip::text("Next Page"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.go_to_page(this.offset + PAGE_SIZE as i64, cx);
+                    })),
+            )
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("memory-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+/// Parses an address as reported by debug adapters, e.g. `0x7ffee4b8c9a0`.
+fn parse_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    let digits = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))?;
+    u64::from_str_radix(digits, 16).ok()
+}
+
+fn parse_byte(text: &str) -> Option<u8> {
+    let text = text.trim();
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    u8::from_str_radix(digits, 16).ok()
+}
+
+fn printable_char(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+impl Focusable for MemoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("MemoryView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .child(self.render_toolbar(cx))
+            .child(Divider::horizontal())
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+            })
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "memory-view",
+                            self.row_count(),
+                            |this, range, _window, cx| this.render_rows(range, cx),
+                        )
+                        .track_scroll(self.list_handle.clone())
+                        .size_full(),
+                    )
+                    .child(self.render_vertical_scrollbar(cx)),
+            )
+    }
+}
@@ -2,12 +2,15 @@ use super::stack_frame_list::{StackFrameList, StackFrameListEvent};
 use dap::{ScopePresentationHint, StackFrameId, VariablePresentationHintKind, VariableReference};
 use editor::Editor;
 use gpui::{
-    AnyElement, ClickEvent, ClipboardItem, Context, DismissEvent, Entity, FocusHandle, Focusable,
-    Hsla, MouseButton, MouseDownEvent, Point, Stateful, Subscription, TextStyleRefinement,
-    UniformListScrollHandle, actions, anchored, deferred, uniform_list,
+    AnyElement, ClickEvent, ClipboardItem, Context, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful, Subscription,
+    TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred, uniform_list,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
-use project::debugger::session::{Session, SessionEvent};
+use project::debugger::{
+    breakpoint_store::DataBreakpointAccessType,
+    session::{Session, SessionEvent},
+};
 use std::{collections::HashMap, ops::Range, sync::Arc};
 use ui::{ContextMenu, ListItem, Scrollbar, ScrollbarState, prelude::*};
 use util::{debug_panic, maybe};
@@ -128,6 +131,12 @@ impl ListEntry {
     }
 }
 
+#[derive(Debug)]
+pub(crate) enum VariableListEvent {
+    /// The user asked to inspect the memory a variable points to.
+    ViewMemory(String),
+}
+
 pub struct VariableList {
     entries: Vec<ListEntry>,
     entry_states: HashMap<EntryPath, EntryState>,
@@ -503,11 +512,28 @@ impl VariableList {
 
         let variable_value = dap_var.value.clone();
         let variable_name = dap_var.name.clone();
+        let memory_reference = dap_var.memory_reference.clone().filter(|_| {
+            self.session
+                .read(cx)
+                .capabilities()
+                .supports_read_memory_request
+                .unwrap_or_default()
+        });
+        let data_breakpoint_parent = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_data_breakpoints
+            .unwrap_or_default()
+            .then(|| self.entry_states.get(&variable.path))
+            .flatten()
+            .map(|state| state.parent_reference);
         let this = cx.entity().clone();
 
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
-            menu.entry("Copy name", None, move |_, cx| {
-                cx.write_to_clipboard(ClipboardItem::new_string(variable_name.clone()))
+            menu.entry("Copy name", None, {
+                let variable_name = variable_name.clone();
+                move |_, cx| cx.write_to_clipboard(ClipboardItem::new_string(variable_name.clone()))
             })
             .entry("Copy value", None, {
                 let variable_value = variable_value.clone();
@@ -515,13 +541,50 @@ impl VariableList {
                     cx.write_to_clipboard(ClipboardItem::new_string(variable_value.clone()))
                 }
             })
-            .entry("Set value", None, move |window, cx| {
-                this.update(cx, |variable_list, cx| {
-                    let editor = Self::create_variable_editor(&variable_value, window, cx);
-                    variable_list.edited_path = Some((variable.path.clone(), editor));
-
-                    cx.notify();
-                });
+            .entry("Set value", None, {
+                let this = this.clone();
+                move |window, cx| {
+                    this.update(cx, |variable_list, cx| {
+                        let editor = Self::create_variable_editor(&variable_value, window, cx);
+                        variable_list.edited_path = Some((variable.path.clone(), editor));
+
+                        cx.notify();
+                    });
+                }
+            })
+            .when_some(memory_reference, |menu, memory_reference| {
+                let this = this.clone();
+                menu.entry("View memory", None, move |_, cx| {
+                    this.update(cx, |_, cx| {
+                        cx.emit(VariableListEvent::ViewMemory(memory_reference.clone()));
+                    });
+                })
+            })
+            .when_some(data_breakpoint_parent, |menu, parent_reference| {
+                [
+                    ("Break on value change", DataBreakpointAccessType::Write),
+                    ("Break on value read", DataBreakpointAccessType::Read),
+                ]
+                .into_iter()
+                .fold(menu.separator(), |menu, (label, access_type)| {
+                    let this = this.clone();
+                    let variable_name = variable_name.clone();
+                    menu.entry(label, None, move |_, cx| {
+                        this.update(cx, |variable_list, cx| {
+                            variable_list
+                                .session
+                                .update(cx, |session, cx| {
+                                    session.add_data_breakpoint(
+                                        parent_reference,
+                                        variable_name.clone(),
+                                        access_type,
+                                        cx,
+                                    )
+                                })
+                                .detach_and_log_err(cx);
+                        });
+                    })
+                })
             })
         });
 
@@ -918,6 +981,8 @@ impl VariableList {
     }
 }
 
+impl EventEmitter<VariableListEvent> for VariableList {}
+
 impl Focusable for VariableList {
     fn focus_handle(&self, _: &App) -> gpui::FocusHandle {
         self.focus_handle.clone()
@@ -0,0 +1,520 @@
+use super::stack_frame_list::{StackFrameList, StackFrameListEvent};
+use crate::persistence;
+use collections::HashSet;
+use dap::{StackFrameId, VariableReference};
+use editor::Editor;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription, Task,
+    UniformListScrollHandle, uniform_list,
+};
+use menu::Confirm;
+use project::debugger::session::{Session, SessionEvent};
+use std::{ops::Range, sync::Arc};
+use ui::{Divider, ListItem, Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::ResultExt;
+
+/// An expression entered by the user, re-evaluated whenever the debuggee stops or another stack frame gets selected.
+struct Watch {
+    expression: SharedString,
+    /// `None` until the expression was evaluated for the first time.
+    result: Option<Result<dap::EvaluateResponse, SharedString>>,
+}
+
+impl Watch {
+    fn variables_reference(&self) -> VariableReference {
+        match &self.result {
+            Some(Ok(response)) => response.variables_reference,
+            _ => 0,
+        }
+    }
+}
+
+/// The watch expression, followed by the names of the variables leading to the entry.
+type EntryPath = Arc<[SharedString]>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum EntryKind {
+    Watch(usize),
+    Variable(dap::Variable),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct WatchEntry {
+    kind: EntryKind,
+    path: EntryPath,
+    variables_reference: VariableReference,
+}
+
+impl WatchEntry {
+    fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    fn item_id(&self) -> ElementId {
+        let id = self
+            .path
+            .iter()
+            .map(|name| name.as_ref())
+            .collect::<Vec<_>>()
+            .join("-");
+        SharedString::from(format!("watch-{id}")).into()
+    }
+}
+
+pub struct WatchList {
+    session: Entity<Session>,
+    watches: Vec<Watch>,
+    entries: Vec<WatchEntry>,
+    expanded_paths: HashSet<EntryPath>,
+    selected_stack_frame_id: Option<StackFrameId>,
+    expression_editor: Entity<Editor>,
+    serialization_key: Option<String>,
+    focus_handle: FocusHandle,
+    list_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    disabled: bool,
+    evaluate_task: Task<()>,
+    serialize_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl WatchList {
+    pub fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        serialization_key: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let expression_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Add a watch expression", cx);
+            editor.set_use_autoclose(false);
+            editor
+        });
+
+        let watches = serialization_key
+            .as_deref()
+            .map(persistence::get_serialized_watches)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|expression| Watch {
+                expression: expression.into(),
+                result: None,
+            })
+            .collect();
+
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, Self::handle_stack_frame_list_events),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                // Rebuilding the entries fetches the children of nested expanded entries,
+                // even when the watch list is not visible.
+                SessionEvent::Variables => {
+                    this.build_entries(cx);
+                    cx.notify();
+                }
+                _ => {}
+            }),
+        ];
+
+        let list_handle = UniformListScrollHandle::default();
+        let mut this = Self {
+            scrollbar_state: ScrollbarState::new(list_handle.clone()),
+            list_handle,
+            selected_stack_frame_id: stack_frame_list.read(cx).selected_stack_frame_id(),
+            session,
+            watches,
+            entries: Vec::new(),
+            expanded_paths: HashSet::default(),
+            expression_editor,
+            serialization_key,
+            focus_handle: cx.focus_handle(),
+            disabled: false,
+            evaluate_task: Task::ready(()),
+            serialize_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.evaluate_watches(cx);
+        this
+    }
+
+    pub(super) fn disabled(&mut self, disabled: bool, cx: &mut Context<Self>) {
+        let old_disabled = std::mem::take(&mut self.disabled);
+        self.disabled = disabled;
+        if old_disabled != disabled {
+            cx.notify();
+        }
+    }
+
+    fn handle_stack_frame_list_events(
+        &mut self,
+        _: Entity<StackFrameList>,
+        event: &StackFrameListEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
+                self.selected_stack_frame_id = Some(*stack_frame_id);
+                self.evaluate_watches(cx);
+            }
+        }
+    }
+
+    pub(crate) fn add_watch(&mut self, expression: String, cx: &mut Context<Self>) {
+        let expression = expression.trim();
+        if expression.is_empty()
+            || self
+                .watches
+                .iter()
+                .any(|watch| watch.expression.as_ref() == expression)
+        {
+            return;
+        }
+
+        self.watches.push(Watch {
+            expression: SharedString::from(expression.to_string()),
+            result: None,
+        });
+        self.serialize(cx);
+        self.evaluate_watches(cx);
+        cx.notify();
+    }
+
+    fn remove_watch(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix >= self.watches.len() {
+            return;
+        }
+
+        let removed = self.watches.remove(ix);
+        self.expanded_paths
+            .retain(|path| path.first() != Some(&removed.expression));
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn confirm_expression(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let expression = self.expression_editor.update(cx, |editor, cx| {
+            let expression = editor.text(cx);
+            editor.clear(window, cx);
+            expression
+        });
+        self.add_watch(expression, cx);
+    }
+
+    fn evaluate_watches(&mut self, cx: &mut Context<Self>) {
+        let Some(stack_frame_id) = self.selected_stack_frame_id else {
+            return;
+        };
+
+        let evaluations = self
+            .watches
+            .iter()
+            .map(|watch| {
+                let expression = watch.expression.clone();
+                let evaluation = self.session.update(cx, |session, cx| {
+                    session.evaluate_watch(expression.to_string(), Some(stack_frame_id), cx)
+                });
+                async move { (expression, evaluation.await) }
+            })
+            .collect::<Vec<_>>();
+
+        self.evaluate_task = cx.spawn(async move |this, cx| {
+            let results = futures::future::join_all(evaluations).await;
+            this.update(cx, |this, cx| {
+                for (expression, result) in results {
+                    if let Some(watch) = this
+                        .watches
+                        .iter_mut()
+                        .find(|watch| watch.expression == expression)
+                    {
+                        watch.result = Some(result.map_err(|error| error.to_string().into()));
+                    }
+                }
+                this.build_entries(cx);
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.serialization_key.clone() else {
+            return;
+        };
+
+        let expressions = self
+            .watches
+            .iter()
+            .map(|watch| watch.expression.to_string())
+            .collect();
+        self.serialize_task = cx.background_spawn(async move {
+            persistence::serialize_watches(key, expressions)
+                .await
+                .log_err();
+        });
+    }
+
+    fn build_entries(&mut self, cx: &mut Context<Self>) {
+        let mut entries = Vec::new();
+        for (ix, watch) in self.watches.iter().enumerate() {
+            let path = EntryPath::from([watch.expression.clone()]);
+            let variables_reference = watch.variables_reference();
+            entries.push(WatchEntry {
+                kind: EntryKind::Watch(ix),
+                path: path.clone(),
+                variables_reference,
+            });
+            self.push_children(path, variables_reference, &mut entries, cx);
+        }
+        self.entries = entries;
+    }
+
+    fn push_children(
+        &self,
+        path: EntryPath,
+        variables_reference: VariableReference,
+        entries: &mut Vec<WatchEntry>,
+        cx: &mut Context<Self>,
+    ) {
+        if variables_reference == 0 || !self.expanded_paths.contains(&path) {
+            return;
+        }
+
+        let children = self
+            .session
+            .update(cx, |session, cx| session.variables(variables_reference, cx));
+        for child in children {
+            let child_path = path
+                .iter()
+                .cloned()
+                .chain(std::iter::once(SharedString::from(child.name.clone())))
+                .collect::<EntryPath>();
+            let child_reference = child.variables_reference;
+            entries.push(WatchEntry {
+                kind: EntryKind::Variable(child),
+                path: child_path.clone(),
+                variables_reference: child_reference,
+            });
+            self.push_children(child_path, child_reference, entries, cx);
+        }
+    }
+
+    fn toggle_entry(&mut self, path: &EntryPath, cx: &mut Context<Self>) {
+        if !self.expanded_paths.remove(path) {
+            if let Some(entry) = self.entries.iter().find(|entry| &entry.path == path) {
+                let variables_reference = entry.variables_reference;
+                self.session.update(cx, |session, cx| {
+                    session.variables(variables_reference, cx);
+                });
+            }
+            self.expanded_paths.insert(path.clone());
+        }
+        cx.notify();
+    }
+
+    #[track_caller]
+    #[cfg(test)]
+    pub(crate) fn assert_visual_entries(&mut self, expected: Vec<&str>, cx: &mut Context<Self>) {
+        const INDENT: &'static str = "    ";
+
+        self.build_entries(cx);
+
+        let visual_entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let toggle = if entry.variables_reference == 0 {
+                    " "
+                } else if self.expanded_paths.contains(&entry.path) {
+                    "v"
+                } else {
+                    ">"
+                };
+                let (name, value) = match &entry.kind {
+                    EntryKind::Watch(ix) => {
+                        let watch = &self.watches[*ix];
+                        let value = match &watch.result {
+                            Some(Ok(response)) => response.result.clone(),
+                            Some(Err(_)) => "<error>".to_string(),
+                            None => "<pending>".to_string(),
+                        };
+                        (watch.expression.to_string(), value)
+                    }
+                    EntryKind::Variable(variable) => {
+                        (variable.name.clone(), variable.value.clone())
+                    }
+                };
+                format!("{}{toggle} {name} = {value}", INDENT.repeat(entry.depth()))
+            })
+            .collect::<Vec<_>>();
+
+        pretty_assertions::assert_eq!(expected, visual_entries);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn toggle_path(&mut self, path: &[&str], cx: &mut Context<Self>) {
+        let path = path
+            .iter()
+            .map(|name| SharedString::from(name.to_string()))
+            .collect::<EntryPath>();
+        self.toggle_entry(&path, cx);
+    }
+
+    fn render_entries(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        range
+            .filter_map(|ix| {
+                let entry = self.entries.get(ix)?;
+                Some(self.render_entry(entry, cx))
+            })
+            .collect()
+    }
+
+    fn render_entry(&self, entry: &WatchEntry, cx: &mut Context<Self>) -> AnyElement {
+        let (name, value, is_error) = match &entry.kind {
+            EntryKind::Watch(ix) => {
+                let watch = &self.watches[*ix];
+                match &watch.result {
+                    Some(Ok(response)) => (
+                        watch.expression.clone(),
+                        Some(SharedString::from(response.result.clone())),
+                        false,
+                    ),
+                    Some(Err(error)) => (watch.expression.clone(), Some(error.clone()), true),
+                    None => (watch.expression.clone(), None, false),
+                }
+            }
+            EntryKind::Variable(variable) => (
+                SharedString::from(variable.name.clone()),
+                Some(SharedString::from(variable.value.clone())),
+                false,
+            ),
+        };
+
+        let value_color = if self.disabled {
+            Color::Disabled
+        } else if is_error {
+            Color::Error
+        } else {
+            Color::Muted
+        };
+
+        ListItem::new(entry.item_id())
+            .disabled(self.disabled)
+            .selectable(false)
+            .indent_level(entry.depth() + 1)
+            .indent_step_size(px(20.))
+            .always_show_disclosure_icon(true)
+            .when(entry.variables_reference > 0, |list_item| {
+                let path = entry.path.clone();
+                list_item
+                    .toggle(self.expanded_paths.contains(&entry.path))
+                    .on_toggle(cx.listener(move |this, _, _, cx| this.toggle_entry(&path, cx)))
+            })
+            .when_some(
+                match entry.kind {
+                    EntryKind::Watch(ix) => Some(ix),
+                    EntryKind::Variable(_) => None,
+                },
+                |list_item, ix| {
+                    list_item.end_hover_slot(
+                        IconButton::new(("remove-watch", ix), IconName::Close)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Remove Watch"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.remove_watch(ix, cx);
+                            })),
+                    )
+                },
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .text_ui_sm(cx)
+                    .w_full()
+                    .child(Label::new(name).color(if self.disabled {
+                        Color::Disabled
+                    } else {
+                        Color::Default
+                    }))
+                    .when_some(value, |this, value| {
+                        this.child(
+                            Label::new(format!("=  {value}"))
+                                .single_line()
+                                .truncate()
+                                .size(LabelSize::Small)
+                                .color(value_color),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("watch-list-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+impl Focusable for WatchList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for WatchList {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.build_entries(cx);
+
+        v_flex()
+            .key_context("WatchList")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::confirm_expression))
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .child(
+                        uniform_list(
+                            cx.entity().clone(),
+                            "watch-list",
+                            self.entries.len(),
+                            |this, range, _window, cx| this.render_entries(range, cx),
+                        )
+                        .track_scroll(self.list_handle.clone())
+                        .size_full(),
+                    )
+                    .child(self.render_vertical_scrollbar(cx)),
+            )
+            .child(Divider::horizontal())
+            .child(div().px_2().py_1().child(self.expression_editor.clone()))
+    }
+}
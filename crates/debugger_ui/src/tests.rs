@@ -9,9 +9,13 @@ use crate::{debugger_panel::DebugPanel, session::DebugSession};
 mod attach_modal;
 mod console;
 mod debugger_panel;
+mod exception_view;
+mod memory_view;
 mod module_list;
+mod replay;
 mod stack_frame_list;
 mod variable_list;
+mod watch_list;
 
 pub fn init_test(cx: &mut gpui::TestAppContext) {
     if std::env::var("RUST_LOG").is_ok() {
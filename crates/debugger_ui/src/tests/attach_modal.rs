@@ -115,16 +115,19 @@ async fn test_show_attach_modal_and_select_process(
                             pid: 0,
                             name: "fake-binary-1".into(),
                             command: vec![],
+                            cwd: None,
                         },
                         Candidate {
                             pid: 3,
                             name: "real-binary-1".into(),
                             command: vec![],
+                            cwd: None,
                         },
                         Candidate {
                             pid: 1,
                             name: "fake-binary-2".into(),
                             command: vec![],
+                            cwd: None,
                         },
                     ]
                     .into_iter()
@@ -187,3 +190,83 @@ async fn test_show_attach_modal_and_select_process(
 
     shutdown_session.await.unwrap();
 }
+
+#[gpui::test]
+async fn test_attach_modal_matches_command_line_and_cwd(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree("/project", json!({ "main.py": "print('hello')" }))
+        .await;
+
+    let project = Project::test(fs, ["/project".as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let attach_modal = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                AttachModal::with_processes(
+                    project.clone(),
+                    DebugTaskDefinition {
+                        adapter: FakeAdapter::ADAPTER_NAME.into(),
+                        request: dap::DebugRequest::Attach(AttachRequest::default()),
+                        label: "attach example".into(),
+                        initialize_args: None,
+                        tcp_connection: Some(TcpArgumentsTemplate::default()),
+                        stop_on_entry: None,
+                    },
+                    vec![
+                        Candidate {
+                            pid: 10,
+                            name: "python3".into(),
+                            command: vec!["python3".into(), "server.py".into()],
+                            cwd: Some("/project".into()),
+                        },
+                        Candidate {
+                            pid: 11,
+                            name: "python3".into(),
+                            command: vec!["python3".into(), "client.py".into()],
+                            cwd: Some("/elsewhere".into()),
+                        },
+                    ]
+                    .into_iter()
+                    .collect(),
+                    true,
+                    window,
+                    cx,
+                )
+            });
+
+            workspace.active_modal::<AttachModal>(cx).unwrap()
+        })
+        .unwrap();
+    cx.run_until_parked();
+
+    for (query, expected) in [
+        ("server.py", vec!["python3 server.py 10 python3 /project"]),
+        ("elsewhere", vec!["python3 client.py 11 python3 /elsewhere"]),
+    ] {
+        workspace
+            .update(cx, |_, window, cx| {
+                attach_modal.update(cx, |this, cx| {
+                    this.picker.update(cx, |this, cx| {
+                        this.set_query(query, window, cx);
+                    })
+                })
+            })
+            .unwrap();
+        cx.run_until_parked();
+
+        workspace
+            .update(cx, |_, _, cx| {
+                let names =
+                    attach_modal.update(cx, |modal, cx| attach_modal::_process_names(&modal, cx));
+                assert_eq!(expected, names);
+            })
+            .unwrap();
+    }
+}
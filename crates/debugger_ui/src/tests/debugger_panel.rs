@@ -4,8 +4,9 @@ use dap::{
     StartDebuggingRequestArguments, StartDebuggingRequestArgumentsRequest,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, DataBreakpointInfo, Disconnect, Initialize, Launch, Next, RunInTerminal,
+        SetBreakpoints, SetDataBreakpoints, SetFunctionBreakpoints, StackTrace, StartDebugging,
+        StepBack, StepIn, StepOut, Threads,
     },
 };
 use editor::{
@@ -15,13 +16,16 @@ use editor::{
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::DataBreakpointAccessType,
+        session::{ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
     path::Path,
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicBool, Ordering},
     },
 };
@@ -1388,3 +1392,126 @@ async fn test_debug_session_is_shutdown_when_attach_and_launch_request_fails(
         );
     });
 }
+
+#[gpui::test]
+async fn test_send_function_and_data_breakpoints(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "fn main() {}" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = debugger::test::start_debug_session(&project, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_function_breakpoints: Some(true),
+                supports_data_breakpoints: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .await
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let function_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetFunctionBreakpoints, _>({
+        let function_breakpoints = function_breakpoints.clone();
+        move |_, args| {
+            *function_breakpoints.lock().unwrap() = args
+                .breakpoints
+                .into_iter()
+                .map(|breakpoint| breakpoint.name)
+                .collect::<Vec<_>>();
+            Ok(dap::SetFunctionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+    client.on_request::<DataBreakpointInfo, _>(move |_, args| {
+        assert_eq!(Some(1), args.variables_reference);
+        assert_eq!("counter", args.name);
+        Ok(dap::DataBreakpointInfoResponse {
+            data_id: Some("counter-id".into()),
+            description: "counter".into(),
+            access_types: Some(vec![dap::DataBreakpointAccessType::Write]),
+            can_persist: Some(false),
+        })
+    });
+    let data_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetDataBreakpoints, _>({
+        let data_breakpoints = data_breakpoints.clone();
+        move |_, args| {
+            *data_breakpoints.lock().unwrap() = args
+                .breakpoints
+                .into_iter()
+                .map(|breakpoint| breakpoint.data_id)
+                .collect::<Vec<_>>();
+            Ok(dap::SetDataBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.toggle_function_breakpoint("main", cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        vec!["main".to_string()],
+        *function_breakpoints.lock().unwrap()
+    );
+
+    session
+        .update(cx, |session, cx| {
+            session.add_data_breakpoint(1, "counter".into(), DataBreakpointAccessType::Write, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    assert_eq!(
+        vec!["counter-id".to_string()],
+        *data_breakpoints.lock().unwrap()
+    );
+
+    // The adapter only supports breaking on writes.
+    assert!(
+        session
+            .update(cx, |session, cx| {
+                session.add_data_breakpoint(1, "counter".into(), DataBreakpointAccessType::Read, cx)
+            })
+            .await
+            .is_err()
+    );
+
+    // Disabled breakpoints aren't sent to the adapter.
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.invert_function_breakpoint_state("main", cx);
+    });
+    cx.run_until_parked();
+
+    assert!(function_breakpoints.lock().unwrap().is_empty());
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+
+    // The data id was only valid in the session that has ended.
+    breakpoint_store.read_with(cx, |breakpoint_store, _| {
+        assert!(breakpoint_store.data_breakpoints().is_empty());
+    });
+}
@@ -0,0 +1,176 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::requests::{ExceptionInfo, Initialize, Scopes, SetExceptionBreakpoints, StackTrace};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger};
+use serde_json::json;
+use util::path;
+
+#[gpui::test]
+async fn test_exception_filter_conditions_and_exception_info(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({ "main.py": "raise ValueError()" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = debugger::test::start_debug_session(&project, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_exception_info_request: Some(true),
+                supports_exception_filter_options: Some(true),
+                exception_breakpoint_filters: Some(vec![dap::ExceptionBreakpointsFilter {
+                    filter: "raised".into(),
+                    label: "Raised Exceptions".into(),
+                    description: None,
+                    default: Some(false),
+                    supports_condition: Some(true),
+                    condition_description: Some("Comma separated exception types".into()),
+                }]),
+                ..Default::default()
+            })
+        });
+    })
+    .await
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let sent_filters = Arc::new(Mutex::new(None));
+    client.on_request::<SetExceptionBreakpoints, _>({
+        let sent_filters = sent_filters.clone();
+        move |_, args| {
+            *sent_filters.lock().unwrap() = args.filter_options;
+            Ok(dap::SetExceptionBreakpointsResponse { breakpoints: None })
+        }
+    });
+
+    session.update(cx, |session, cx| {
+        session.toggle_exception_breakpoint("raised", cx);
+        session.set_exception_breakpoint_condition("raised", Some("ValueError".into()), cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        Some(vec![dap::ExceptionFilterOptions {
+            filter_id: "raised".into(),
+            condition: Some("ValueError".into()),
+            mode: None,
+        }]),
+        sent_filters.lock().unwrap().clone(),
+        "The condition of an enabled filter should be sent to the debug adapter"
+    );
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::new(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: Vec::new() }));
+
+    let inner_exception = dap::ExceptionDetails {
+        message: Some("invalid literal".into()),
+        type_name: Some("TypeError".into()),
+        full_type_name: None,
+        evaluate_name: None,
+        stack_trace: Some("File \"main.py\", line 1".into()),
+        inner_exception: None,
+    };
+    let details = dap::ExceptionDetails {
+        message: Some("bad value".into()),
+        type_name: Some("ValueError".into()),
+        full_type_name: None,
+        evaluate_name: None,
+        stack_trace: Some("File \"main.py\", line 1, in <module>".into()),
+        inner_exception: Some(vec![inner_exception]),
+    };
+    client.on_request::<ExceptionInfo, _>({
+        let details = details.clone();
+        move |_, args| {
+            assert_eq!(1, args.thread_id);
+            Ok(dap::ExceptionInfoResponse {
+                exception_id: "ValueError".into(),
+                description: Some("bad value".into()),
+                break_mode: dap::ExceptionBreakMode::Always,
+                details: Some(details.clone()),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Exception,
+            description: Some("ValueError".into()),
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: Some("bad value".into()),
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert_eq!(
+            Some(&project::debugger::session::ExceptionInfo {
+                thread_id: project::debugger::session::ThreadId(1),
+                exception_id: "ValueError".into(),
+                description: Some("bad value".into()),
+                details: Some(details),
+            }),
+            session.exception_info()
+        );
+    });
+
+    let running_state = active_debug_session_panel(workspace, cx).update(cx, |item, _| {
+        item.mode()
+            .as_running()
+            .expect("Session should be running by this point")
+            .clone()
+    });
+    running_state.update(cx, |running_state, cx| {
+        assert_eq!(
+            Some(&true),
+            running_state
+                .pane_items_status(cx)
+                .get(&DebuggerPaneItem::Exception),
+            "Stopping on an exception should open the exception view"
+        );
+    });
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
@@ -0,0 +1,360 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::{
+    StackFrame,
+    requests::{
+        Disassemble, Initialize, ReadMemory, Scopes, SetInstructionBreakpoints, StackTrace,
+        WriteMemory,
+    },
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger};
+use serde_json::json;
+use util::path;
+
+fn stopped_event() -> dap::messages::Events {
+    dap::messages::Events::Stopped(dap::StoppedEvent {
+        reason: dap::StoppedEventReason::Pause,
+        description: None,
+        thread_id: Some(1),
+        preserve_focus_hint: None,
+        text: None,
+        all_threads_stopped: None,
+        hit_breakpoint_ids: None,
+    })
+}
+
+fn stack_frame(instruction_pointer_reference: Option<String>) -> StackFrame {
+    StackFrame {
+        id: 1,
+        name: "main".into(),
+        source: Some(dap::Source {
+            name: Some("main.c".into()),
+            path: Some(path!("/project/main.c").into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line: 1,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference,
+        module_id: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_memory_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.c": "int main() {}" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = debugger::test::start_debug_session(&project, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                supports_write_memory_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .await
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![stack_frame(None)],
+            total_frames: None,
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: Vec::new() }));
+
+    let written = Arc::new(AtomicBool::new(false));
+    client.on_request::<ReadMemory, _>({
+        let written = written.clone();
+        move |_, args| {
+            assert_eq!("0x1000", args.memory_reference);
+            assert_eq!(Some(0), args.offset);
+            assert_eq!(256, args.count);
+
+            Ok(dap::ReadMemoryResponse {
+                address: "0x1000".into(),
+                // "Hello" and "Jello", followed by three bytes that can't be read.
+                data: Some(if written.load(Ordering::SeqCst) {
+                    "SmVsbG8=".into()
+                } else {
+                    "SGVsbG8=".into()
+                }),
+                unreadable_bytes: Some(3),
+            })
+        }
+    });
+    client.on_request::<WriteMemory, _>({
+        let written = written.clone();
+        move |_, args| {
+            assert_eq!("0x1000", args.memory_reference);
+            assert_eq!(Some(0), args.offset);
+            assert_eq!("Sg==", args.data);
+            written.store(true, Ordering::SeqCst);
+
+            Ok(dap::WriteMemoryResponse {
+                offset: None,
+                bytes_written: Some(1),
+            })
+        }
+    });
+
+    client.fake_event(stopped_event()).await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+    cx.run_until_parked();
+
+    running_state.update(cx, |running_state, cx| {
+        assert_eq!(
+            Some(&false),
+            running_state
+                .pane_items_status(cx)
+                .get(&DebuggerPaneItem::Memory),
+            "The memory view is only opened on demand"
+        );
+    });
+
+    let memory_view =
+        running_state.update(cx, |running_state, _| running_state.memory_view().clone());
+    memory_view.update_in(cx, |memory_view, window, cx| {
+        memory_view.open("0x1000".into(), window, cx);
+    });
+    running_state.update_in(cx, |running_state, window, cx| {
+        running_state.activate_pane_item(DebuggerPaneItem::Memory, window, cx);
+    });
+    cx.run_until_parked();
+
+    running_state.update(cx, |running_state, cx| {
+        assert_eq!(
+            Some(&true),
+            running_state
+                .pane_items_status(cx)
+                .get(&DebuggerPaneItem::Memory)
+        );
+    });
+    memory_view.update(cx, |memory_view, _| {
+        assert_eq!(
+            vec!["0x0000000000001000  48 65 6c 6c 6f ?? ?? ??  Hello..."],
+            memory_view.rows()
+        );
+    });
+
+    // Writing a byte reloads the memory that is shown.
+    memory_view.update_in(cx, |memory_view, window, cx| {
+        memory_view.set_byte(0, "4a", window, cx);
+    });
+    cx.run_until_parked();
+
+    assert!(written.load(Ordering::SeqCst));
+    memory_view.update(cx, |memory_view, _| {
+        assert_eq!(
+            vec!["0x0000000000001000  4a 65 6c 6c 6f ?? ?? ??  Jello..."],
+            memory_view.rows()
+        );
+    });
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
+
+#[gpui::test]
+async fn test_disassembly_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.c": "int main() {}" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = debugger::test::start_debug_session(&project, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                supports_instruction_breakpoints: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .await
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![stack_frame(Some("0x1004".into()))],
+            total_frames: None,
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: Vec::new() }));
+
+    client.on_request::<Disassemble, _>(move |_, args| {
+        assert_eq!("0x1004", args.memory_reference);
+        assert_eq!(Some(-20), args.instruction_offset);
+
+        let instruction = |address: &str, instruction: &str| dap::DisassembledInstruction {
+            address: address.into(),
+            instruction_bytes: None,
+            instruction: instruction.into(),
+            symbol: None,
+            location: None,
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            presentation_hint: None,
+        };
+        Ok(dap::DisassembleResponse {
+            instructions: vec![
+                instruction("0x1000", "push rbp"),
+                instruction("0x1004", "mov rbp, rsp"),
+                instruction("0x1008", "xor eax, eax"),
+            ],
+        })
+    });
+
+    let instruction_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetInstructionBreakpoints, _>({
+        let instruction_breakpoints = instruction_breakpoints.clone();
+        move |_, args| {
+            *instruction_breakpoints.lock().unwrap() = args
+                .breakpoints
+                .into_iter()
+                .map(|breakpoint| breakpoint.instruction_reference)
+                .collect::<Vec<_>>();
+            Ok(dap::SetInstructionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+
+    client.fake_event(stopped_event()).await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+    cx.run_until_parked();
+
+    let disassembly_view = running_state.update(cx, |running_state, _| {
+        running_state.disassembly_view().clone()
+    });
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        assert_eq!(
+            vec![
+                "   0x1000 push rbp",
+                "-> 0x1004 mov rbp, rsp",
+                "   0x1008 xor eax, eax",
+            ],
+            disassembly_view.rows(cx)
+        );
+    });
+
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        disassembly_view.toggle_breakpoint_at(2, cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        vec!["0x1008".to_string()],
+        *instruction_breakpoints.lock().unwrap()
+    );
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        assert_eq!(
+            vec![
+                "   0x1000 push rbp",
+                "-> 0x1004 mov rbp, rsp",
+                "*  0x1008 xor eax, eax",
+            ],
+            disassembly_view.rows(cx)
+        );
+    });
+
+    // Toggling it again removes the breakpoint.
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        disassembly_view.toggle_breakpoint_at(2, cx);
+    });
+    cx.run_until_parked();
+
+    assert!(instruction_breakpoints.lock().unwrap().is_empty());
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
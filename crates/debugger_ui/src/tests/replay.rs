@@ -0,0 +1,104 @@
+use crate::{
+    DebugPanel,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::recording::DapRecording;
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger};
+use serde_json::json;
+use util::path;
+
+/// A debug session as recorded from the DAP log: the debug adapter stops right after launching.
+fn recording() -> DapRecording {
+    let stack_frame = json!({
+        "id": 1,
+        "name": "main",
+        "line": 2,
+        "column": 1,
+        "source": { "name": "main.rs", "path": path!("/project/main.rs") },
+    });
+    DapRecording::from_json(
+        &json!({
+            "adapter_name": "CodeLLDB",
+            "messages": [
+                { "direction": "to_adapter", "message": { "seq": 1, "type": "request", "command": "initialize", "arguments": {} } },
+                { "direction": "from_adapter", "message": { "seq": 1, "type": "response", "request_seq": 1, "command": "initialize", "success": true, "body": {} } },
+                { "direction": "to_adapter", "message": { "seq": 2, "type": "request", "command": "launch", "arguments": {} } },
+                { "direction": "from_adapter", "message": { "seq": 2, "type": "event", "event": "initialized" } },
+                { "direction": "from_adapter", "message": { "seq": 3, "type": "response", "request_seq": 2, "command": "launch", "success": true } },
+                { "direction": "to_adapter", "message": { "seq": 3, "type": "request", "command": "setExceptionBreakpoints", "arguments": { "filters": [] } } },
+                { "direction": "from_adapter", "message": { "seq": 4, "type": "response", "request_seq": 3, "command": "setExceptionBreakpoints", "success": true, "body": {} } },
+                { "direction": "from_adapter", "message": { "seq": 5, "type": "event", "event": "stopped", "body": { "reason": "entry", "threadId": 1, "allThreadsStopped": true } } },
+                { "direction": "to_adapter", "message": { "seq": 4, "type": "request", "command": "threads" } },
+                { "direction": "from_adapter", "message": { "seq": 6, "type": "response", "request_seq": 4, "command": "threads", "success": true, "body": { "threads": [{ "id": 1, "name": "main" }] } } },
+                { "direction": "to_adapter", "message": { "seq": 5, "type": "request", "command": "stackTrace", "arguments": { "threadId": 1 } } },
+                { "direction": "from_adapter", "message": { "seq": 7, "type": "response", "request_seq": 5, "command": "stackTrace", "success": true, "body": { "stackFrames": [stack_frame], "totalFrames": 1 } } },
+                { "direction": "to_adapter", "message": { "seq": 6, "type": "request", "command": "scopes", "arguments": { "frameId": 1 } } },
+                { "direction": "from_adapter", "message": { "seq": 8, "type": "response", "request_seq": 6, "command": "scopes", "success": true, "body": { "scopes": [] } } },
+                { "direction": "to_adapter", "message": { "seq": 7, "type": "request", "command": "disconnect", "arguments": {} } },
+                { "direction": "from_adapter", "message": { "seq": 9, "type": "response", "request_seq": 7, "command": "disconnect", "success": true } },
+            ],
+        })
+        .to_string(),
+    )
+    .unwrap()
+}
+
+#[gpui::test]
+async fn test_replay_recorded_debug_session(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({ "main.rs": "fn main() {\n    println!(\"Hello\");\n}" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = debugger::test::replay_debug_session(&project, cx, recording())
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+    cx.run_until_parked();
+
+    let stack_frame_list = running_state.update(cx, |running_state, _| {
+        running_state.stack_frame_list().clone()
+    });
+    stack_frame_list.update(cx, |stack_frame_list, cx| {
+        assert_eq!(Some(1), stack_frame_list.selected_stack_frame_id());
+        assert_eq!(
+            vec!["main".to_string()],
+            stack_frame_list
+                .dap_stack_frames(cx)
+                .into_iter()
+                .map(|stack_frame| stack_frame.name)
+                .collect::<Vec<_>>()
+        );
+    });
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
@@ -0,0 +1,233 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    DebugPanel,
+    tests::{active_debug_session_panel, init_test, init_test_workspace},
+};
+use dap::{
+    ErrorResponse, StackFrame, Variable,
+    requests::{Evaluate, Scopes, StackTrace, Variables},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger};
+use serde_json::json;
+use util::path;
+
+#[gpui::test]
+async fn test_watch_expressions(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "const user = { name: \"Alice\" };\nlet count = 0;",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = debugger::test::start_debug_session(&project, cx, |_| {})
+        .await
+        .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: Vec::new() }));
+
+    let stop_count = Arc::new(AtomicUsize::new(0));
+    client.on_request::<Evaluate, _>({
+        let stop_count = stop_count.clone();
+        move |_, args| {
+            assert_eq!(Some(dap::EvaluateArgumentsContext::Watch), args.context);
+            assert_eq!(Some(1), args.frame_id);
+
+            let (result, variables_reference) = match args.expression.as_str() {
+                "user" => ("{name: 'Alice'}".to_string(), 3),
+                "count" => (stop_count.load(Ordering::SeqCst).to_string(), 0),
+                _ => {
+                    return Err(ErrorResponse {
+                        error: Some(dap::Message {
+                            id: 1,
+                            format: "not defined".into(),
+                            variables: None,
+                            send_telemetry: None,
+                            show_user: None,
+                            url: None,
+                            url_label: None,
+                        }),
+                    });
+                }
+            };
+
+            Ok(dap::EvaluateResponse {
+                result,
+                type_: None,
+                presentation_hint: None,
+                variables_reference,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                value_location_reference: None,
+            })
+        }
+    });
+
+    client.on_request::<Variables, _>(move |_, args| {
+        assert_eq!(3, args.variables_reference);
+
+        Ok(dap::VariablesResponse {
+            variables: vec![Variable {
+                name: "name".into(),
+                value: "'Alice'".into(),
+                type_: None,
+                presentation_hint: None,
+                evaluate_name: None,
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                declaration_location_reference: None,
+                value_location_reference: None,
+            }],
+        })
+    });
+
+    let stopped_event = || {
+        dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        })
+    };
+
+    stop_count.fetch_add(1, Ordering::SeqCst);
+    client.fake_event(stopped_event()).await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.mode()
+                .as_running()
+                .expect("Session should be running by this point")
+                .clone()
+        });
+    cx.run_until_parked();
+
+    let watch_list =
+        running_state.update(cx, |running_state, _| running_state.watch_list().clone());
+    watch_list.update(cx, |watch_list, cx| {
+        watch_list.add_watch("user".into(), cx);
+        watch_list.add_watch("count".into(), cx);
+        watch_list.add_watch("missing".into(), cx);
+        // Duplicates and blank expressions are ignored.
+        watch_list.add_watch(" user ".into(), cx);
+        watch_list.add_watch("  ".into(), cx);
+    });
+    cx.run_until_parked();
+
+    watch_list.update(cx, |watch_list, cx| {
+        watch_list.assert_visual_entries(
+            vec![
+                "> user = {name: 'Alice'}",
+                "  count = 1",
+                "  missing = <error>",
+            ],
+            cx,
+        );
+    });
+
+    watch_list.update(cx, |watch_list, cx| watch_list.toggle_path(&["user"], cx));
+    cx.run_until_parked();
+
+    watch_list.update(cx, |watch_list, cx| {
+        watch_list.assert_visual_entries(
+            vec![
+                "v user = {name: 'Alice'}",
+                "      name = 'Alice'",
+                "  count = 1",
+                "  missing = <error>",
+            ],
+            cx,
+        );
+    });
+
+    // Watches are re-evaluated every time the debuggee stops.
+    stop_count.fetch_add(1, Ordering::SeqCst);
+    client.fake_event(stopped_event()).await;
+    cx.run_until_parked();
+
+    watch_list.update(cx, |watch_list, cx| {
+        watch_list.assert_visual_entries(
+            vec![
+                "v user = {name: 'Alice'}",
+                "      name = 'Alice'",
+                "  count = 2",
+                "  missing = <error>",
+            ],
+            cx,
+        );
+    });
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+}
@@ -789,9 +789,12 @@ impl CodeActionContents {
     ) -> Self {
         if !cx.has_flag::<Debugger>() {
             if let Some(tasks) = &mut tasks {
-                tasks
-                    .templates
-                    .retain(|(_, task)| !matches!(task.task_type(), task::TaskType::Debug(_)));
+                tasks.templates.retain(|(_, task)| {
+                    !matches!(
+                        task.task_type(),
+                        task::TaskType::Debug(_) | task::TaskType::DebugCompound
+                    )
+                });
             }
         }
 
@@ -26,6 +26,7 @@ mod hover_links;
 pub mod hover_popover;
 mod indent_guides;
 mod inlay_hint_cache;
+mod inline_values;
 pub mod items;
 mod jsx_tag_auto_close;
 mod linked_editing_ranges;
@@ -100,6 +101,7 @@ use indent_guides::ActiveIndentGuidesState;
 use inlay_hint_cache::{InlayHintCache, InlaySplice, InvalidationStrategy};
 pub use inline_completion::Direction;
 use inline_completion::{EditPredictionProvider, InlineCompletionProviderHandle};
+use inline_values::InlineValues;
 pub use items::MAX_TAB_TITLE_LEN;
 use itertools::Itertools;
 use language::{
@@ -124,13 +126,13 @@ use project::{
     },
 };
 
-pub use git::blame::BlameRenderer;
+pub use git::blame::{BlameRenderer, BlameRevision};
 pub use proposed_changes_editor::{
     ProposedChangeLocation, ProposedChangesEditor, ProposedChangesEditorToolbar,
 };
 use smallvec::smallvec;
 use std::{cell::OnceCell, iter::Peekable};
-use task::{ResolvedTask, RunnableTag, TaskTemplate, TaskVariables};
+use task::{ResolvedTask, TaskTemplate, TaskVariables};
 
 pub use lsp::CompletionContext;
 use lsp::{
@@ -153,7 +155,7 @@ use parking_lot::Mutex;
 use project::{
     CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, InlayHint,
     Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectTransaction,
-    TaskSourceKind,
+    TaskSourceKind, TestStatus,
     debugger::breakpoint_store::Breakpoint,
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
     project_settings::{GitGutterSetting, ProjectSettings},
@@ -250,6 +252,7 @@ const COLUMNAR_SELECTION_MODIFIERS: Modifiers = Modifiers {
 pub enum InlayId {
     InlineCompletion(usize),
     Hint(usize),
+    DebuggerValue(usize),
 }
 
 impl InlayId {
@@ -257,6 +260,7 @@ impl InlayId {
         match self {
             Self::InlineCompletion(id) => *id,
             Self::Hint(id) => *id,
+            Self::DebuggerValue(id) => *id,
         }
     }
 }
@@ -869,6 +873,7 @@ pub struct Editor {
     show_selection_menu: Option<bool>,
     blame: Option<Entity<GitBlame>>,
     blame_subscription: Option<Subscription>,
+    blame_revision: Option<BlameRevision>,
     custom_context_menu: Option<
         Box<
             dyn 'static
@@ -886,6 +891,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    inline_values: InlineValues,
     /// Allow's a user to create a breakpoint by selecting this indicator
     /// It should be None while a user is not hovering over the gutter
     /// Otherwise it represents the point that the breakpoint will be shown
@@ -1481,6 +1487,7 @@ impl Editor {
                     window,
                     |editor, _, event, window, cx| match event {
                         BreakpointStoreEvent::ActiveDebugLineChanged => {
+                            editor.refresh_inline_values(cx);
                             if editor.go_to_active_debug_line(window, cx) {
                                 cx.stop_propagation();
                             }
@@ -1656,9 +1663,11 @@ impl Editor {
                 .restore_unsaved_buffers,
             blame: None,
             blame_subscription: None,
+            blame_revision: None,
             tasks: Default::default(),
 
             breakpoint_store,
+            inline_values: InlineValues::default(),
             gutter_breakpoint_indicator: (None, None),
             _subscriptions: vec![
                 cx.observe(&buffer, Self::on_buffer_changed),
@@ -1767,6 +1776,7 @@ impl Editor {
             }
 
             this.go_to_active_debug_line(window, cx);
+            this.refresh_inline_values(cx);
 
             if let Some(buffer) = buffer.read(cx).as_singleton() {
                 if let Some(project) = this.project.as_ref() {
@@ -5031,7 +5041,10 @@ impl Editor {
                                 .templates
                                 .iter()
                                 .filter(|task| {
-                                    if matches!(task.1.task_type(), task::TaskType::Debug(_)) {
+                                    if matches!(
+                                        task.1.task_type(),
+                                        task::TaskType::Debug(_) | task::TaskType::DebugCompound
+                                    ) {
                                         debugger_flag
                                     } else {
                                         true
@@ -5121,9 +5134,14 @@ impl Editor {
 
                         Some(Task::ready(Ok(())))
                     }),
-                    task::TaskType::Debug(_) => {
+                    task::TaskType::Debug(_) | task::TaskType::DebugCompound => {
                         workspace.update(cx, |workspace, cx| {
-                            workspace.schedule_debug_task(resolved_task, window, cx);
+                            workspace.schedule_debug_task(
+                                task_source_kind,
+                                resolved_task,
+                                window,
+                                cx,
+                            );
                         });
                         Some(Task::ready(Ok(())))
                     }
@@ -6898,12 +6916,18 @@ impl Editor {
         is_active: bool,
         row: DisplayRow,
         breakpoint: Option<(Anchor, Breakpoint)>,
+        test_status: Option<TestStatus>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        let color = Color::Muted;
+        let (icon, color) = match test_status {
+            None => (ui::IconName::Play, Color::Muted),
+            Some(TestStatus::Running) => (ui::IconName::Play, Color::Accent),
+            Some(TestStatus::Passed) => (ui::IconName::Check, Color::Success),
+            Some(TestStatus::Failed) => (ui::IconName::XCircle, Color::Error),
+        };
         let position = breakpoint.as_ref().map(|(anchor, _)| *anchor);
 
-        IconButton::new(("run_indicator", row.0 as usize), ui::IconName::Play)
+        IconButton::new(("run_indicator", row.0 as usize), icon)
             .shape(ui::IconButtonShape::Square)
             .icon_size(IconSize::XSmall)
             .icon_color(color)
@@ -12936,39 +12960,16 @@ impl Editor {
             )
         });
 
-        let mut templates_with_tags = mem::take(&mut runnable.tags)
-            .into_iter()
-            .flat_map(|RunnableTag(tag)| {
-                inventory
-                    .as_ref()
-                    .into_iter()
-                    .flat_map(|inventory| {
-                        inventory.read(cx).list_tasks(
-                            file.clone(),
-                            Some(runnable.language.clone()),
-                            worktree_id,
-                            cx,
-                        )
-                    })
-                    .filter(move |(_, template)| {
-                        template.tags.iter().any(|source_tag| source_tag == &tag)
-                    })
-            })
-            .sorted_by_key(|(kind, _)| kind.to_owned())
-            .collect::<Vec<_>>();
-        if let Some((leading_tag_source, _)) = templates_with_tags.first() {
-            // Strongest source wins; if we have worktree tag binding, prefer that to
-            // global and language bindings;
-            // if we have a global binding, prefer that to language binding.
-            let first_mismatch = templates_with_tags
-                .iter()
-                .position(|(tag_source, _)| tag_source != leading_tag_source);
-            if let Some(index) = first_mismatch {
-                templates_with_tags.truncate(index);
-            }
-        }
-
-        templates_with_tags
+        let Some(inventory) = inventory else {
+            return Vec::new();
+        };
+        inventory.read(cx).list_tagged_tasks(
+            file,
+            runnable.language.clone(),
+            worktree_id,
+            mem::take(&mut runnable.tags),
+            cx,
+        )
     }
 
     pub fn move_to_enclosing_bracket(
@@ -15571,6 +15572,60 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_lines(&mut self, _: &::git::StageLines, _: &mut Window, cx: &mut Context<Self>) {
+        self.stage_or_unstage_selected_lines(true, cx);
+    }
+
+    pub fn unstage_lines(
+        &mut self,
+        _: &::git::UnstageLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(false, cx);
+    }
+
+    /// Stages or unstages only the changed lines touched by the selections, including
+    /// removed lines that are shown in expanded diff hunks.
+    pub fn stage_or_unstage_selected_lines(&mut self, stage: bool, cx: &mut Context<Self>) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut rows_by_buffer =
+                    HashMap::<BufferId, (Vec<Range<u32>>, Vec<Range<u32>>)>::default();
+                for range in &ranges {
+                    for (buffer, range, excerpt_id, deleted_hunk_anchor) in
+                        snapshot.range_to_buffer_ranges_with_deleted_hunks(range.clone())
+                    {
+                        let Some(buffer_id) = snapshot.buffer_id_for_excerpt(excerpt_id) else {
+                            continue;
+                        };
+                        let start = buffer.offset_to_point(range.start);
+                        let end = buffer.offset_to_point(range.end);
+                        let end_row = if end.column == 0 && end.row > start.row {
+                            end.row
+                        } else {
+                            end.row + 1
+                        };
+                        let (buffer_rows, base_rows) = rows_by_buffer.entry(buffer_id).or_default();
+                        if deleted_hunk_anchor.is_some() {
+                            base_rows.push(start.row..end_row);
+                        } else {
+                            buffer_rows.push(start.row..end_row);
+                        }
+                    }
+                }
+                for (buffer_id, (buffer_rows, base_rows)) in rows_by_buffer {
+                    this.do_stage_or_unstage_lines(stage, buffer_id, &buffer_rows, &base_rows, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -15688,10 +15743,10 @@ impl Editor {
                 stage,
                 &hunks
                     .map(|hunk| buffer_diff::DiffHunk {
+                        range: hunk.buffer_range.to_point(&buffer_snapshot),
                         buffer_range: hunk.buffer_range,
                         diff_base_byte_range: hunk.diff_base_byte_range,
                         secondary_status: hunk.secondary_status,
-                        range: Point::zero()..Point::zero(), // unused
                     })
                     .collect::<Vec<_>>(),
                 &buffer_snapshot,
@@ -15702,6 +15757,34 @@ impl Editor {
         None
     }
 
+    fn do_stage_or_unstage_lines(
+        &self,
+        stage: bool,
+        buffer_id: BufferId,
+        buffer_rows: &[Range<u32>],
+        base_rows: &[Range<u32>],
+        cx: &mut App,
+    ) -> Option<()> {
+        let project = self.project.as_ref()?;
+        let buffer = project.read(cx).buffer_for_id(buffer_id, cx)?;
+        let diff = self.buffer.read(cx).diff_for(buffer_id)?;
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        let file_exists = buffer_snapshot
+            .file()
+            .is_some_and(|file| file.disk_state().exists());
+        diff.update(cx, |diff, cx| {
+            diff.stage_or_unstage_lines(
+                stage,
+                buffer_rows,
+                base_rows,
+                &buffer_snapshot,
+                file_exists,
+                cx,
+            )
+        });
+        None
+    }
+
     pub fn expand_selected_diff_hunks(&mut self, cx: &mut Context<Self>) {
         let ranges: Vec<_> = self.selections.disjoint.iter().map(|s| s.range()).collect();
         self.buffer
@@ -16277,8 +16360,10 @@ impl Editor {
         maybe!({
             let breakpoint_store = self.breakpoint_store.as_ref()?;
 
-            let Some((_, _, active_position)) =
-                breakpoint_store.read(cx).active_position().cloned()
+            let Some(active_position) = breakpoint_store
+                .read(cx)
+                .active_position()
+                .map(|active_stack_frame| active_stack_frame.position)
             else {
                 self.clear_row_highlights::<DebugCurrentRowHighlight>();
                 return None;
@@ -16452,7 +16537,12 @@ impl Editor {
             let focused = self.focus_handle(cx).contains_focused(window, cx);
 
             let project = project.clone();
-            let blame = cx.new(|cx| GitBlame::new(buffer, project, user_triggered, focused, cx));
+            let blame = match self.blame_revision.clone() {
+                Some(revision) => {
+                    cx.new(|cx| GitBlame::for_revision(buffer, project, revision, focused, cx))
+                }
+                None => cx.new(|cx| GitBlame::new(buffer, project, user_triggered, focused, cx)),
+            };
             self.blame_subscription =
                 Some(cx.observe_in(&blame, window, |_, _, _, cx| cx.notify()));
             self.blame = Some(blame);
@@ -16496,6 +16586,23 @@ impl Editor {
         }
     }
 
+    /// Blames the given commit instead of the buffer's contents, for editors that show a file
+    /// as it was in that commit.
+    pub fn set_blame_revision(
+        &mut self,
+        revision: BlameRevision,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.blame_revision = Some(revision);
+        self.blame = None;
+        self.blame_subscription = None;
+        if self.show_git_blame_gutter || self.git_blame_inline_enabled {
+            self.start_git_blame(false, window, cx);
+        }
+        cx.notify();
+    }
+
     pub fn blame(&self) -> Option<&Entity<GitBlame>> {
         self.blame.as_ref()
     }
@@ -17463,6 +17570,7 @@ impl Editor {
         self.tasks_update_task = Some(self.refresh_runnables(window, cx));
         self.update_edit_prediction_settings(cx);
         self.refresh_inline_completion(true, false, window, cx);
+        self.refresh_inline_values(cx);
         self.refresh_inlay_hints(
             InlayHintRefreshReason::SettingsChange(inlay_hint_settings(
                 self.selections.newest_anchor().head(),
@@ -17027,6 +17027,42 @@ async fn test_partially_staged_hunk(cx: &mut TestAppContext) {
     "}));
 }
 
+#[gpui::test]
+async fn test_stage_lines_then_whole_hunk(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let base_text = indoc! {"
+        one
+        two
+        five
+    "};
+    cx.set_head_text(base_text);
+    cx.set_index_text(base_text);
+    cx.set_state(indoc! {"
+        one
+        two
+        ˇTHREE
+        FOUR
+        five
+    "});
+    cx.run_until_parked();
+
+    // Stage the whole hunk before the index containing the staged line is reloaded.
+    cx.update_editor(|editor, _, cx| {
+        editor.stage_or_unstage_selected_lines(true, cx);
+        editor.stage_or_unstage_diff_hunks(true, vec![Anchor::min()..Anchor::max()], cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        two
+        THREE
+        FOUR
+        five
+    "}));
+}
+
 #[gpui::test]
 fn test_crease_insertion_and_rendering(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -434,6 +434,8 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
+        register_action(editor, window, Editor::stage_lines);
+        register_action(editor, window, Editor::unstage_lines);
         register_action(editor, window, Editor::expand_all_diff_hunks);
         register_action(editor, window, Editor::go_to_previous_change);
         register_action(editor, window, Editor::go_to_next_change);
@@ -2108,7 +2110,7 @@ impl EditorElement {
             editor
                 .tasks
                 .iter()
-                .filter_map(|(_, tasks)| {
+                .filter_map(|((_, buffer_row), tasks)| {
                     let multibuffer_point = tasks.offset.to_point(&snapshot.buffer_snapshot);
                     if multibuffer_point < offset_range_start
                         || multibuffer_point > offset_range_end
@@ -2147,11 +2149,18 @@ impl EditorElement {
                         return None;
                     }
 
+                    let test_status = snapshot
+                        .buffer_snapshot
+                        .buffer_line_for_row(multibuffer_row)
+                        .and_then(|(buffer_snapshot, _)| {
+                            editor.test_status(buffer_snapshot, *buffer_row, cx)
+                        });
                     let button = editor.render_run_indicator(
                         &self.style,
                         Some(display_row) == active_task_indicator_row,
                         display_row,
                         breakpoints.remove(&display_row),
+                        test_status,
                         cx,
                     );
 
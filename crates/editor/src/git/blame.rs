@@ -1,14 +1,15 @@
 use crate::Editor;
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::HashMap;
 use git::{
     GitHostingProviderRegistry, GitRemote, Oid,
     blame::{Blame, BlameEntry, ParsedCommitMessage},
     parse_git_remote_url,
+    repository::RepoPath,
 };
 use gpui::{
-    AnyElement, App, AppContext as _, Context, Entity, Hsla, Subscription, Task, TextStyle,
-    WeakEntity, Window,
+    AnyElement, App, AppContext as _, Context, Entity, Hsla, SharedString, Subscription, Task,
+    TextStyle, WeakEntity, Window,
 };
 use language::{Bias, Buffer, BufferSnapshot, Edit};
 use multi_buffer::RowInfo;
@@ -62,9 +63,19 @@ impl<'a> sum_tree::Dimension<'a, GitBlameEntrySummary> for u32 {
     }
 }
 
+/// A commit to blame instead of the buffer's contents, for buffers that show a file as it was
+/// in that commit.
+#[derive(Clone)]
+pub struct BlameRevision {
+    pub repository: Entity<Repository>,
+    pub path: RepoPath,
+    pub commit: SharedString,
+}
+
 pub struct GitBlame {
     project: Entity<Project>,
     buffer: Entity<Buffer>,
+    revision: Option<BlameRevision>,
     entries: SumTree<GitBlameEntry>,
     commit_details: HashMap<Oid, ParsedCommitMessage>,
     buffer_snapshot: BufferSnapshot,
@@ -170,6 +181,27 @@ impl GitBlame {
         user_triggered: bool,
         focused: bool,
         cx: &mut Context<Self>,
+    ) -> Self {
+        Self::build(buffer, project, None, user_triggered, focused, cx)
+    }
+
+    pub fn for_revision(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        revision: BlameRevision,
+        focused: bool,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self::build(buffer, project, Some(revision), true, focused, cx)
+    }
+
+    fn build(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        revision: Option<BlameRevision>,
+        user_triggered: bool,
+        focused: bool,
+        cx: &mut Context<Self>,
     ) -> Self {
         let entries = SumTree::from_item(
             GitBlameEntry {
@@ -227,6 +259,7 @@ impl GitBlame {
         let mut this = Self {
             project,
             buffer,
+            revision,
             buffer_snapshot,
             entries,
             buffer_edits,
@@ -248,6 +281,9 @@ impl GitBlame {
     }
 
     pub fn repository(&self, cx: &App) -> Option<Entity<Repository>> {
+        if let Some(revision) = &self.revision {
+            return Some(revision.repository.clone());
+        }
         self.project
             .read(cx)
             .git_store()
@@ -436,9 +472,20 @@ impl GitBlame {
         }
         let buffer_edits = self.buffer.update(cx, |buffer, _| buffer.subscribe());
         let snapshot = self.buffer.read(cx).snapshot();
-        let blame = self.project.update(cx, |project, cx| {
-            project.blame_buffer(&self.buffer, None, cx)
-        });
+        let blame = match &self.revision {
+            Some(revision) => {
+                let blame = revision.repository.update(cx, |repository, _| {
+                    repository.blame_at_revision(revision.path.clone(), revision.commit.to_string())
+                });
+                cx.background_spawn(async move {
+                    let blame = blame.await.context("blame job was dropped")??;
+                    anyhow::Ok(Some(blame))
+                })
+            }
+            None => self.project.update(cx, |project, cx| {
+                project.blame_buffer(&self.buffer, None, cx)
+            }),
+        };
         let provider_registry = GitHostingProviderRegistry::default_global(cx);
 
         self.task = cx.spawn(async move |this, cx| {
@@ -0,0 +1,240 @@
+use collections::{BTreeMap, HashMap};
+use dap::{client::SessionId, debugger_settings::DebuggerSettings};
+use gpui::{Context, Subscription};
+use language::{BufferSnapshot, TextObject, TreeSitterOptions};
+use multi_buffer::ExcerptRange;
+use project::debugger::session::SessionEvent;
+use settings::Settings as _;
+use text::{Point, ToOffset as _};
+use util::post_inc;
+
+use crate::{Editor, InlayId, display_map::Inlay};
+
+/// Values longer than this are truncated.
+const MAX_INLINE_VALUE_LEN: usize = 40;
+
+#[derive(Default)]
+pub(crate) struct InlineValues {
+    inlays: Vec<InlayId>,
+    /// Refreshes the inline values whenever variables of the active debug session are fetched.
+    session_subscription: Option<(SessionId, Subscription)>,
+}
+
+impl Editor {
+    /// Shows the values of the variables of the active stack frame at the end of the lines
+    /// of its function that refer to them, up to the line the debugger is stopped at.
+    pub(crate) fn refresh_inline_values(&mut self, cx: &mut Context<Self>) {
+        let to_remove = std::mem::take(&mut self.inline_values.inlays);
+        let to_insert = self.inline_value_inlays(cx).unwrap_or_default();
+        self.inline_values.inlays = to_insert.iter().map(|inlay| inlay.id).collect();
+        if !to_remove.is_empty() || !to_insert.is_empty() {
+            self.splice_inlays(&to_remove, to_insert, cx);
+        }
+    }
+
+    fn inline_value_inlays(&mut self, cx: &mut Context<Self>) -> Option<Vec<Inlay>> {
+        let active_stack_frame = self
+            .breakpoint_store
+            .as_ref()?
+            .read(cx)
+            .active_position()
+            .cloned();
+        let Some(active_stack_frame) =
+            active_stack_frame.filter(|_| DebuggerSettings::get_global(cx).inline_values)
+        else {
+            self.inline_values.session_subscription = None;
+            return None;
+        };
+
+        let project = self.project.clone()?;
+        let session = project
+            .read(cx)
+            .dap_store()
+            .read(cx)
+            .session_by_id(active_stack_frame.session_id)?;
+        if self
+            .inline_values
+            .session_subscription
+            .as_ref()
+            .map(|(session_id, _)| *session_id)
+            != Some(active_stack_frame.session_id)
+        {
+            let subscription = cx.subscribe(&session, |editor, _, event, cx| {
+                if let SessionEvent::Variables = event {
+                    editor.refresh_inline_values(cx);
+                }
+            });
+            self.inline_values.session_subscription =
+                Some((active_stack_frame.session_id, subscription));
+        }
+
+        let buffer_id = active_stack_frame.position.buffer_id?;
+        let excerpts = self.buffer.read(cx).excerpts_for_buffer(buffer_id, cx);
+        if excerpts.is_empty() {
+            return None;
+        }
+
+        // Variables of inner scopes come first, and shadow those of outer scopes.
+        let mut values = HashMap::default();
+        for variable in session
+            .read(cx)
+            .variables_by_stack_frame_id(active_stack_frame.stack_frame_id)
+        {
+            values.entry(variable.name).or_insert(variable.value);
+        }
+        if values.is_empty() {
+            return None;
+        }
+
+        let buffer = project
+            .read(cx)
+            .buffer_for_id(buffer_id, cx)?
+            .read(cx)
+            .snapshot();
+        let lines = inline_value_lines(
+            &buffer,
+            active_stack_frame.position.to_offset(&buffer),
+            &values,
+        );
+
+        let multibuffer = self.buffer.read(cx).snapshot(cx);
+        let mut inlays = Vec::new();
+        for (excerpt_id, ExcerptRange { context, .. }) in excerpts {
+            for (row, text) in &lines {
+                let position = buffer.anchor_after(Point::new(*row, buffer.line_len(*row)));
+                if context.start.cmp(&position, &buffer).is_gt()
+                    || context.end.cmp(&position, &buffer).is_lt()
+                {
+                    continue;
+                }
+                let Some(position) = multibuffer.anchor_in_excerpt(excerpt_id, position) else {
+                    continue;
+                };
+                inlays.push(Inlay::debugger_value(
+                    post_inc(&mut self.next_inlay_id),
+                    position,
+                    text.as_str(),
+                ));
+            }
+        }
+        Some(inlays)
+    }
+}
+
+/// Returns the text to show at the end of each line from the start of the function containing
+/// `offset` up to the line of `offset`, listing the values of the variables named on that line.
+fn inline_value_lines(
+    buffer: &BufferSnapshot,
+    offset: usize,
+    values: &HashMap<String, String>,
+) -> Vec<(u32, String)> {
+    let active_row = buffer.offset_to_point(offset).row;
+    let start = buffer
+        .text_object_ranges(offset..offset, TreeSitterOptions::default())
+        .filter(|(range, text_object)| {
+            *text_object == TextObject::AroundFunction && range.contains(&offset)
+        })
+        .map(|(range, _)| range.start)
+        .max()
+        .unwrap_or_else(|| buffer.point_to_offset(Point::new(active_row, 0)));
+    let end = buffer.point_to_offset(Point::new(active_row, buffer.line_len(active_row)));
+    let Some(layer) = buffer.syntax_layer_at(offset) else {
+        return Vec::new();
+    };
+
+    let mut names_by_row = BTreeMap::<u32, Vec<String>>::new();
+    let mut cursor = layer.node().walk();
+    loop {
+        let node = cursor.node();
+        let overlaps = node.start_byte() < end && node.end_byte() > start;
+        if overlaps && node.kind() == "identifier" {
+            let name = buffer.text_for_range(node.byte_range()).collect::<String>();
+            if values.contains_key(&name) {
+                let names = names_by_row
+                    .entry(buffer.offset_to_point(node.start_byte()).row)
+                    .or_default();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        if overlaps && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return names_by_row
+                    .into_iter()
+                    .map(|(row, names)| {
+                        let values = names
+                            .iter()
+                            .map(|name| format!("{name} = {}", truncate_value(&values[name])))
+                            .collect::<Vec<_>>();
+                        (row, format!("  {}", values.join(", ")))
+                    })
+                    .collect();
+            }
+        }
+    }
+}
+
+fn truncate_value(value: &str) -> String {
+    let value = value.lines().next().unwrap_or_default().trim();
+    if value.chars().count() > MAX_INLINE_VALUE_LEN {
+        let truncated = value.chars().take(MAX_INLINE_VALUE_LEN).collect::<String>();
+        format!("{truncated}…")
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::editor_lsp_test_context::rust_lang;
+    use gpui::{AppContext as _, TestAppContext};
+    use indoc::indoc;
+    use language::Buffer;
+
+    #[gpui::test]
+    async fn test_inline_value_lines(cx: &mut TestAppContext) {
+        let text = indoc! {r#"
+            fn other(a: i32) -> i32 {
+                a
+            }
+
+            fn main() {
+                let a = 1;
+                let b = a + 2;
+                let long = "";
+                let c = other(b);
+                let d = c;
+            }
+        "#};
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(rust_lang(), cx));
+        cx.run_until_parked();
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+
+        let values = [
+            ("a", "1".to_string()),
+            ("b", "3".to_string()),
+            ("long", "x".repeat(50)),
+            ("c", "4\n5".to_string()),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect::<HashMap<_, _>>();
+
+        let offset = snapshot.point_to_offset(Point::new(8, 4));
+        assert_eq!(
+            vec![
+                (5, "  a = 1".to_string()),
+                (6, "  b = 3, a = 1".to_string()),
+                (7, format!("  long = {}…", "x".repeat(40))),
+                (8, "  c = 4, b = 3".to_string()),
+            ],
+            inline_value_lines(&snapshot, offset, &values),
+            "only lines of the active function up to the active line should have values"
+        );
+    }
+}
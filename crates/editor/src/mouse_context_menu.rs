@@ -236,6 +236,12 @@ pub fn deploy_context_menu(
                 .action("Copy and trim", Box::new(CopyAndTrim))
                 .action("Paste", Box::new(Paste))
                 .separator()
+                .when(has_git_repo && has_selections, |builder| {
+                    builder
+                        .action("Stage Selected Lines", Box::new(::git::StageLines))
+                        .action("Unstage Selected Lines", Box::new(::git::UnstageLines))
+                        .separator()
+                })
                 .map(|builder| {
                     let reveal_in_finder_label = if cfg!(target_os = "macos") {
                         "Reveal in Finder"
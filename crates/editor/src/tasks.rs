@@ -2,8 +2,9 @@ use crate::Editor;
 
 use collections::HashMap;
 use gpui::{App, Task, Window};
+use language::{BufferRow, BufferSnapshot};
 use lsp::LanguageServerName;
-use project::{Location, project_settings::ProjectSettings};
+use project::{Location, TestStatus, project_settings::ProjectSettings};
 use settings::Settings as _;
 use task::{TaskContext, TaskVariables, VariableName};
 use text::{BufferId, ToOffset, ToPoint};
@@ -74,6 +75,18 @@ impl Editor {
         })
     }
 
+    /// The status of the latest run of the test whose runnable starts on the given row.
+    pub(crate) fn test_status(
+        &self,
+        buffer: &BufferSnapshot,
+        row: BufferRow,
+        cx: &App,
+    ) -> Option<TestStatus> {
+        let project = self.project.as_ref()?.read(cx);
+        let inventory = project.task_store().read(cx).task_inventory()?;
+        inventory.read(cx).test_status(buffer, row)
+    }
+
     pub fn lsp_task_sources(&self, cx: &App) -> HashMap<LanguageServerName, Vec<BufferId>> {
         let lsp_settings = &ProjectSettings::get_global(cx).lsp;
 
@@ -5,8 +5,9 @@ use futures::future::{self, BoxFuture};
 use git::{
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDetails, CommitOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode,
+        AskPassDelegate, Branch, CommitDetails, CommitOptions, CommitSignature, ConflictTexts,
+        GitRepository, GitRepositoryCheckpoint, GitWorktree, LogEntry, LogOptions, PushOptions,
+        RebaseState, RebaseTodoEntry, Remote, RepoPath, ResetMode, StashEntry, StashOptions, Tag,
     },
     status::{FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
@@ -87,6 +88,10 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn load_conflict_texts(&self, _path: RepoPath) -> BoxFuture<Result<ConflictTexts>> {
+        unimplemented!()
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<Option<String>> {
         async {
             self.with_state_async(false, move |state| {
@@ -102,6 +107,14 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn load_text_at_revision(
+        &self,
+        _commit: String,
+        _path: RepoPath,
+    ) -> BoxFuture<Result<Option<String>>> {
+        unimplemented!()
+    }
+
     fn load_commit(
         &self,
         _commit: String,
@@ -144,6 +157,10 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn commit_signature(&self, _commit: String) -> BoxFuture<Result<Option<CommitSignature>>> {
+        future::ready(Ok(None)).boxed()
+    }
+
     fn reset(
         &self,
         _commit: String,
@@ -162,6 +179,18 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn cherry_pick(
+        &self,
+        _commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn revert(&self, _commit: String, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
     fn path(&self) -> PathBuf {
         self.repository_dir_path.clone()
     }
@@ -335,6 +364,58 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn rename_branch(
+        &self,
+        old_name: String,
+        new_name: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            if !state.branches.remove(&old_name) {
+                return Err(anyhow!("no branch named {old_name}"));
+            }
+            if state.current_branch_name.as_ref() == Some(&old_name) {
+                state.current_branch_name = Some(new_name.clone());
+            }
+            state.branches.insert(new_name);
+            Ok(())
+        })
+    }
+
+    fn delete_branch(
+        &self,
+        name: String,
+        _force: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.current_branch_name.as_ref() == Some(&name) {
+                return Err(anyhow!("cannot delete the checked out branch {name}"));
+            }
+            if !state.branches.remove(&name) {
+                return Err(anyhow!("no branch named {name}"));
+            }
+            Ok(())
+        })
+    }
+
+    fn set_upstream(
+        &self,
+        _branch: String,
+        _upstream: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn compare_with_branch(
+        &self,
+        _branch: String,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<git::repository::CommitDiff>> {
+        unimplemented!()
+    }
+
     fn blame(&self, path: RepoPath, _content: Rope) -> BoxFuture<Result<git::blame::Blame>> {
         self.with_state_async(false, move |state| {
             state
@@ -345,6 +426,14 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn blame_at_revision(
+        &self,
+        _path: RepoPath,
+        _commit: String,
+    ) -> BoxFuture<Result<git::blame::Blame>> {
+        unimplemented!()
+    }
+
     fn stage_paths(
         &self,
         _paths: Vec<RepoPath>,
@@ -407,6 +496,154 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn tags(&self) -> BoxFuture<Result<Vec<Tag>>> {
+        unimplemented!()
+    }
+
+    fn create_tag(
+        &self,
+        _name: String,
+        _commit: Option<String>,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn delete_tag(
+        &self,
+        _name: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn push_tag(
+        &self,
+        _name: String,
+        _remote_name: String,
+        _ask_pass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
+    fn stash_entries(&self) -> BoxFuture<Result<Vec<StashEntry>>> {
+        future::ready(Ok(Vec::new())).boxed()
+    }
+
+    fn stash_push(
+        &self,
+        _message: Option<String>,
+        _options: StashOptions,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_apply(
+        &self,
+        _index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_pop(
+        &self,
+        _index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_drop(
+        &self,
+        _index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_show(
+        &self,
+        _index: usize,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<git::repository::CommitDiff>> {
+        unimplemented!()
+    }
+
+    fn rebase_state(&self) -> BoxFuture<Option<RebaseState>> {
+        future::ready(None).boxed()
+    }
+
+    fn rebase_todo(&self, _base: String) -> BoxFuture<Result<Vec<RebaseTodoEntry>>> {
+        unimplemented!()
+    }
+
+    fn rebase_start(
+        &self,
+        _base: String,
+        _todo: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase_continue(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase_skip(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase_abort(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn log(&self, _options: LogOptions) -> BoxFuture<Result<Vec<LogEntry>>> {
+        unimplemented!()
+    }
+
+    fn file_history(
+        &self,
+        _path: RepoPath,
+        _skip: usize,
+        _limit: usize,
+    ) -> BoxFuture<Result<Vec<git::repository::FileHistoryEntry>>> {
+        unimplemented!()
+    }
+
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        unimplemented!()
+    }
+
+    fn create_worktree(
+        &self,
+        _path: PathBuf,
+        _branch: String,
+        _new_branch: bool,
+        _base: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn remove_worktree(
+        &self,
+        _path: PathBuf,
+        _force: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
+    fn prune_worktrees(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        unimplemented!()
+    }
+
     fn check_for_pushed_commit(&self) -> BoxFuture<Result<Vec<gpui::SharedString>>> {
         future::ready(Ok(Vec::new())).boxed()
     }
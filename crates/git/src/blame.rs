@@ -38,7 +38,43 @@ impl Blame {
         remote_url: Option<String>,
     ) -> Result<Self> {
         let output = run_git_blame(git_binary, working_directory, path, content).await?;
-        let mut entries = parse_git_blame(&output)?;
+        Self::from_output(working_directory, &output, remote_url).await
+    }
+
+    /// Blames `path` as it was in `revision`, instead of the contents of the working copy.
+    pub async fn for_revision(
+        git_binary: &Path,
+        working_directory: &Path,
+        path: &Path,
+        revision: &str,
+        remote_url: Option<String>,
+    ) -> Result<Self> {
+        let output = util::command::new_smol_command(git_binary)
+            .current_dir(working_directory)
+            .arg("blame")
+            .arg("--incremental")
+            .arg(revision)
+            .arg("--")
+            .arg(path.as_os_str())
+            .output()
+            .await
+            .map_err(|e| anyhow!("Failed to read git blame output: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("git blame process failed: {}", stderr));
+        }
+
+        let output = String::from_utf8(output.stdout)?;
+        Self::from_output(working_directory, &output, remote_url).await
+    }
+
+    async fn from_output(
+        working_directory: &Path,
+        output: &str,
+        remote_url: Option<String>,
+    ) -> Result<Self> {
+        let mut entries = parse_git_blame(output)?;
         entries.sort_unstable_by(|a, b| a.range.start.cmp(&b.range.start));
 
         let mut unique_shas = HashSet::default();
@@ -0,0 +1,188 @@
+use gpui::SharedString;
+
+/// Lays out the branch and merge graph of a commit list one row at a time, so that
+/// pages of `git log` output can be appended as they are loaded.
+///
+/// Commits must be pushed in topological order, children before their parents.
+#[derive(Clone, Debug, Default)]
+pub struct CommitGraph {
+    /// The commit that each lane is waiting for, or `None` for a free lane.
+    lanes: Vec<Option<SharedString>>,
+}
+
+/// How to draw the graph next to a single commit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphRow {
+    /// The lane the commit is drawn in.
+    pub lane: usize,
+    /// Lanes that run past the commit without touching it.
+    pub passing: Vec<usize>,
+    /// Lanes coming from above that end at the commit.
+    pub incoming: Vec<usize>,
+    /// Lanes leaving the commit downwards, one per parent.
+    pub outgoing: Vec<usize>,
+    /// The number of lanes this row needs to be drawn.
+    pub width: usize,
+}
+
+impl CommitGraph {
+    pub fn push(&mut self, sha: &SharedString, parents: &[SharedString]) -> GraphRow {
+        let incoming = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, expected)| expected.as_ref() == Some(sha))
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+        let lane = match incoming.first() {
+            Some(lane) => *lane,
+            None => self.free_lane(),
+        };
+        for ix in &incoming {
+            self.lanes[*ix] = None;
+        }
+
+        let passing = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, expected)| expected.is_some())
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+
+        let mut outgoing = Vec::with_capacity(parents.len());
+        for (parent_ix, parent) in parents.iter().enumerate() {
+            // Join the lane of another child of the same parent.
+            if let Some(ix) = self
+                .lanes
+                .iter()
+                .position(|expected| expected.as_ref() == Some(parent))
+            {
+                outgoing.push(ix);
+                continue;
+            }
+
+            let ix = if parent_ix == 0 {
+                lane
+            } else {
+                self.free_lane()
+            };
+            self.lanes[ix] = Some(parent.clone());
+            outgoing.push(ix);
+        }
+
+        while self.lanes.last().is_some_and(Option::is_none) {
+            self.lanes.pop();
+        }
+
+        let width = passing
+            .iter()
+            .chain(&incoming)
+            .chain(&outgoing)
+            .copied()
+            .chain([lane])
+            .max()
+            .map_or(0, |ix| ix + 1);
+        GraphRow {
+            lane,
+            passing,
+            incoming,
+            outgoing,
+            width,
+        }
+    }
+
+    fn free_lane(&mut self) -> usize {
+        match self.lanes.iter().position(Option::is_none) {
+            Some(ix) => ix,
+            None => {
+                self.lanes.push(None);
+                self.lanes.len() - 1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(commits: &[(&str, &[&str])]) -> Vec<GraphRow> {
+        let mut graph = CommitGraph::default();
+        commits
+            .iter()
+            .map(|(sha, parents)| {
+                let parents = parents
+                    .iter()
+                    .map(|parent| SharedString::from(parent.to_string()))
+                    .collect::<Vec<_>>();
+                graph.push(&SharedString::from(sha.to_string()), &parents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_linear_history() {
+        let rows = layout(&[("c", &["b"]), ("b", &["a"]), ("a", &[])]);
+        assert_eq!(
+            rows,
+            vec![
+                GraphRow {
+                    lane: 0,
+                    passing: vec![],
+                    incoming: vec![],
+                    outgoing: vec![0],
+                    width: 1,
+                },
+                GraphRow {
+                    lane: 0,
+                    passing: vec![],
+                    incoming: vec![0],
+                    outgoing: vec![0],
+                    width: 1,
+                },
+                GraphRow {
+                    lane: 0,
+                    passing: vec![],
+                    incoming: vec![0],
+                    outgoing: vec![],
+                    width: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_history() {
+        let rows = layout(&[("m", &["b", "c"]), ("b", &["a"]), ("c", &["a"]), ("a", &[])]);
+        assert_eq!(rows[0].lane, 0);
+        assert_eq!(rows[0].outgoing, vec![0, 1]);
+        assert_eq!(rows[0].width, 2);
+
+        assert_eq!(rows[1].lane, 0);
+        assert_eq!(rows[1].passing, vec![1]);
+        assert_eq!(rows[1].outgoing, vec![0]);
+
+        // The second branch joins the first one at their common parent.
+        assert_eq!(rows[2].lane, 1);
+        assert_eq!(rows[2].incoming, vec![1]);
+        assert_eq!(rows[2].passing, vec![0]);
+        assert_eq!(rows[2].outgoing, vec![0]);
+
+        assert_eq!(rows[3].lane, 0);
+        assert_eq!(rows[3].incoming, vec![0]);
+        assert_eq!(rows[3].width, 1);
+    }
+
+    #[test]
+    fn test_branch_heads() {
+        // Two branch heads that share a parent, followed by an unrelated root.
+        let rows = layout(&[("x", &["a"]), ("y", &["a"]), ("a", &[]), ("z", &[])]);
+        assert_eq!(rows[0].lane, 0);
+        assert_eq!(rows[1].lane, 1);
+        assert_eq!(rows[1].outgoing, vec![0]);
+        assert_eq!(rows[2].lane, 0);
+        assert_eq!(rows[3].lane, 0);
+        assert_eq!(rows[3].incoming, Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,230 @@
+use std::ops::Range;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// A region of a file delimited by merge conflict markers.
+///
+/// All ranges are byte offsets into the text the region was parsed from. The
+/// ranges of each side exclude the marker lines, while `range` covers the whole
+/// region, including the trailing newline of the closing marker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictRegion {
+    pub range: Range<usize>,
+    pub ours: Range<usize>,
+    /// Only present for conflicts written with the `diff3` or `zdiff3` conflict styles.
+    pub base: Option<Range<usize>>,
+    pub theirs: Range<usize>,
+    pub ours_label: String,
+    pub theirs_label: String,
+}
+
+impl ConflictRegion {
+    /// Returns the text that results from keeping both sides, ours first.
+    pub fn both_text(&self, text: &str) -> String {
+        let mut result = text[self.ours.clone()].to_string();
+        result.push_str(&text[self.theirs.clone()]);
+        result
+    }
+}
+
+/// Finds the conflict regions in `text`. Unterminated regions are ignored.
+pub fn parse_conflicts(text: &str) -> Vec<ConflictRegion> {
+    enum State {
+        Outside,
+        Ours {
+            start: usize,
+            label: String,
+            ours_start: usize,
+        },
+        Base {
+            start: usize,
+            label: String,
+            ours: Range<usize>,
+            base_start: usize,
+        },
+        Theirs {
+            start: usize,
+            label: String,
+            ours: Range<usize>,
+            base: Option<Range<usize>>,
+            theirs_start: usize,
+        },
+    }
+
+    let mut regions = Vec::new();
+    let mut state = State::Outside;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        let line_end = offset + line.len();
+        offset = line_end;
+        let content = line.trim_end_matches(['\n', '\r']);
+
+        state = match state {
+            State::Outside => match marker_label(content, OURS_MARKER) {
+                Some(label) => State::Ours {
+                    start: line_start,
+                    label,
+                    ours_start: line_end,
+                },
+                None => State::Outside,
+            },
+            State::Ours {
+                start,
+                label,
+                ours_start,
+            } => {
+                if marker_label(content, BASE_MARKER).is_some() {
+                    State::Base {
+                        start,
+                        label,
+                        ours: ours_start..line_start,
+                        base_start: line_end,
+                    }
+                } else if content == SEPARATOR_MARKER {
+                    State::Theirs {
+                        start,
+                        label,
+                        ours: ours_start..line_start,
+                        base: None,
+                        theirs_start: line_end,
+                    }
+                } else if let Some(label) = marker_label(content, OURS_MARKER) {
+                    // A new region started before the previous one was closed.
+                    State::Ours {
+                        start: line_start,
+                        label,
+                        ours_start: line_end,
+                    }
+                } else {
+                    State::Ours {
+                        start,
+                        label,
+                        ours_start,
+                    }
+                }
+            }
+            State::Base {
+                start,
+                label,
+                ours,
+                base_start,
+            } => {
+                if content == SEPARATOR_MARKER {
+                    State::Theirs {
+                        start,
+                        label,
+                        ours,
+                        base: Some(base_start..line_start),
+                        theirs_start: line_end,
+                    }
+                } else {
+                    State::Base {
+                        start,
+                        label,
+                        ours,
+                        base_start,
+                    }
+                }
+            }
+            State::Theirs {
+                start,
+                label,
+                ours,
+                base,
+                theirs_start,
+            } => match marker_label(content, THEIRS_MARKER) {
+                Some(theirs_label) => {
+                    regions.push(ConflictRegion {
+                        range: start..line_end,
+                        ours,
+                        base,
+                        theirs: theirs_start..line_start,
+                        ours_label: label,
+                        theirs_label,
+                    });
+                    State::Outside
+                }
+                None => State::Theirs {
+                    start,
+                    label,
+                    ours,
+                    base,
+                    theirs_start,
+                },
+            },
+        };
+    }
+
+    regions
+}
+
+fn marker_label(line: &str, marker: &str) -> Option<String> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some(String::new())
+    } else {
+        rest.strip_prefix(' ').map(ToString::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unindent::Unindent as _;
+
+    #[test]
+    fn test_parse_conflicts() {
+        let text = "
+            fn main() {
+            <<<<<<< HEAD
+                println!(\"ours\");
+            =======
+                println!(\"theirs\");
+                println!(\"more theirs\");
+            >>>>>>> feature
+            }
+            <<<<<<< HEAD
+            a
+            ||||||| base
+            b
+            =======
+            c
+            >>>>>>> feature
+            "
+        .unindent();
+
+        let regions = parse_conflicts(&text);
+        assert_eq!(regions.len(), 2);
+
+        let first = &regions[0];
+        assert_eq!(first.ours_label, "HEAD");
+        assert_eq!(first.theirs_label, "feature");
+        assert_eq!(&text[first.ours.clone()], "    println!(\"ours\");\n");
+        assert_eq!(
+            &text[first.theirs.clone()],
+            "    println!(\"theirs\");\n    println!(\"more theirs\");\n"
+        );
+        assert_eq!(first.base, None);
+        assert!(text[first.range.clone()].starts_with("<<<<<<< HEAD\n"));
+        assert!(text[first.range.clone()].ends_with(">>>>>>> feature\n"));
+
+        let second = &regions[1];
+        assert_eq!(&text[second.ours.clone()], "a\n");
+        assert_eq!(second.base.clone().map(|base| &text[base]), Some("b\n"));
+        assert_eq!(&text[second.theirs.clone()], "c\n");
+        assert_eq!(second.both_text(&text), "a\nc\n");
+    }
+
+    #[test]
+    fn test_parse_unterminated_conflict() {
+        let text = "<<<<<<< HEAD\na\n=======\nb\n";
+        assert_eq!(parse_conflicts(text), Vec::new());
+
+        let text = "======= not a marker\n>>>>>>> nope\n";
+        assert_eq!(parse_conflicts(text), Vec::new());
+    }
+}
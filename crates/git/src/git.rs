@@ -1,5 +1,7 @@
 pub mod blame;
 pub mod commit;
+pub mod commit_graph;
+pub mod conflict;
 mod hosting_provider;
 mod remote;
 pub mod repository;
@@ -36,6 +38,9 @@ actions!(
         ToggleStaged,
         StageAndNext,
         UnstageAndNext,
+        // per-line
+        StageLines,
+        UnstageLines,
         // per-file
         StageFile,
         UnstageFile,
@@ -44,6 +49,17 @@ actions!(
         UnstageAll,
         RestoreTrackedFiles,
         TrashUntrackedFiles,
+        StashAll,
+        StashStaged,
+        StashPop,
+        InteractiveRebase,
+        RebaseContinue,
+        RebaseSkip,
+        RebaseAbort,
+        ResolveConflicts,
+        ViewHistory,
+        ViewLineHistory,
+        FileHistory,
         Uncommit,
         Push,
         ForcePush,
@@ -13,6 +13,7 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use std::borrow::{Borrow, Cow};
 use std::ffi::{OsStr, OsString};
+use std::ops::Range;
 use std::path::Component;
 use std::process::{ExitStatus, Stdio};
 use std::sync::LazyLock;
@@ -27,8 +28,8 @@ use std::{
 };
 use sum_tree::MapSeekTarget;
 use thiserror::Error;
-use util::ResultExt;
 use util::command::{new_smol_command, new_std_command};
+use util::{ResultExt, maybe};
 use uuid::Uuid;
 
 pub use askpass::{AskPassDelegate, AskPassResult, AskPassSession};
@@ -77,6 +78,8 @@ impl Upstream {
 #[derive(Clone, Copy, Default)]
 pub struct CommitOptions {
     pub amend: bool,
+    /// Signs the commit even if `commit.gpgsign` isn't set. Otherwise git signs it according to its config.
+    pub sign: bool,
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -142,6 +145,59 @@ pub struct CommitDetails {
     pub author_name: SharedString,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    /// The name of the signer, as reported by GPG or the allowed signers file.
+    pub signer: SharedString,
+    pub key: SharedString,
+}
+
+/// The result of verifying a commit signature, as reported by `git log --format=%G?`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SignatureStatus {
+    Good,
+    /// The signature is valid, but the key isn't trusted.
+    GoodUnknownValidity,
+    Bad,
+    ExpiredSignature,
+    ExpiredKey,
+    RevokedKey,
+    /// The signature couldn't be checked, for example because the key is missing.
+    CannotCheck,
+}
+
+impl SignatureStatus {
+    pub fn is_verified(&self) -> bool {
+        matches!(self, SignatureStatus::Good)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SignatureStatus::Good => "Good signature",
+            SignatureStatus::GoodUnknownValidity => "Good signature from an untrusted key",
+            SignatureStatus::Bad => "Bad signature",
+            SignatureStatus::ExpiredSignature => "Expired signature",
+            SignatureStatus::ExpiredKey => "Signed with an expired key",
+            SignatureStatus::RevokedKey => "Signed with a revoked key",
+            SignatureStatus::CannotCheck => "The signature could not be checked",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points at.
+    pub sha: SharedString,
+    /// Annotated tags carry their own message, author and date.
+    pub is_annotated: bool,
+    /// The subject of the tag message, or of the commit for lightweight tags.
+    pub subject: SharedString,
+    /// This is a unix timestamp
+    pub timestamp: i64,
+}
+
 #[derive(Debug)]
 pub struct CommitDiff {
     pub files: Vec<CommitFile>,
@@ -165,6 +221,165 @@ pub struct Remote {
     pub name: SharedString,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StashEntry {
+    /// The position of the entry in the stash, where `0` is the most recently stashed.
+    pub index: usize,
+    pub sha: SharedString,
+    pub message: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+}
+
+impl StashEntry {
+    /// Returns the `stash@{n}` revision for this entry.
+    pub fn reference(&self) -> String {
+        stash_reference(self.index)
+    }
+}
+
+pub fn stash_reference(index: usize) -> String {
+    format!("stash@{{{index}}}")
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StashOptions {
+    /// Also stash untracked files.
+    pub include_untracked: bool,
+    /// Only stash the changes that are currently staged, leaving unstaged changes in place.
+    pub staged: bool,
+}
+
+/// A commit listed by [`GitRepository::log`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LogEntry {
+    pub sha: SharedString,
+    /// The parents of the commit, first parent first. Merge commits have more than one.
+    pub parents: Vec<SharedString>,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+    pub subject: SharedString,
+    /// The branches and tags pointing at this commit, as printed by `git log --decorate`.
+    pub refs: Vec<SharedString>,
+}
+
+/// A revision of a single file, as listed by [`GitRepository::file_history`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FileHistoryEntry {
+    pub commit: LogEntry,
+    /// The path of the file in this commit, which differs from the requested path when the
+    /// file has been renamed since.
+    pub path: RepoPath,
+    /// The path of the file in the commit's parent, when this commit renamed or copied it.
+    pub old_path: Option<RepoPath>,
+}
+
+impl FileHistoryEntry {
+    /// The path of the file in the commit's parent.
+    pub fn parent_path(&self) -> &RepoPath {
+        self.old_path.as_ref().unwrap_or(&self.path)
+    }
+}
+
+/// Restricts which commits are returned by [`GitRepository::log`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct LogOptions {
+    /// Only list commits that touch this path.
+    pub path: Option<RepoPath>,
+    /// Only list commits that touch these 1-based, inclusive lines of `path`.
+    pub line_range: Option<Range<u32>>,
+    /// A regular expression matched case-insensitively against the author name and email.
+    pub author: Option<String>,
+    /// Text matched case-insensitively against the commit message.
+    pub message: Option<String>,
+    /// Any date accepted by `git log --since`, such as `2024-01-31` or `2 weeks ago`.
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub skip: usize,
+    pub limit: usize,
+}
+
+/// The versions of a conflicted file that are recorded in the index during a merge.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictTexts {
+    /// The common ancestor, or `None` when both sides added the file.
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+/// A working tree attached to the repository, as listed by `git worktree list`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    pub head_sha: Option<SharedString>,
+    /// The short name of the checked-out branch, or `None` for a detached HEAD.
+    pub branch: Option<SharedString>,
+    /// Whether this is the main working tree, rather than a linked one.
+    pub is_main: bool,
+    pub is_locked: bool,
+    /// Whether the working tree's directory is missing, so that `git worktree prune`
+    /// would remove it.
+    pub is_prunable: bool,
+}
+
+/// What to do with a commit when replaying it during an interactive rebase.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 5] = [
+        RebaseAction::Pick,
+        RebaseAction::Reword,
+        RebaseAction::Squash,
+        RebaseAction::Fixup,
+        RebaseAction::Drop,
+    ];
+
+    /// The command used for this action in a `git-rebase-todo` file.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The new commit message to use when the action is [`RebaseAction::Reword`].
+    pub message: Option<String>,
+}
+
+/// The progress of a rebase that is currently stopped or running in the repository.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct RebaseState {
+    /// The branch being rebased, or `None` when rebasing a detached HEAD.
+    pub head_name: Option<SharedString>,
+    pub onto: SharedString,
+    /// The 1-based index of the step that is being applied.
+    pub current_step: usize,
+    pub total_steps: usize,
+    /// The commit that the rebase stopped at, if it is waiting on the user.
+    pub stopped_sha: Option<SharedString>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ResetMode {
     /// Reset the branch pointer, leave index and worktree unchanged (this will make it look like things that were
     /// committed are now staged).
@@ -172,6 +387,8 @@ pub enum ResetMode {
     /// Reset the branch pointer and index, leave worktree unchanged (this makes it look as though things that were
     /// committed are now unstaged).
     Mixed,
+    /// Reset the branch pointer, index and worktree, discarding all uncommitted changes.
+    Hard,
 }
 
 pub trait GitRepository: Send + Sync {
@@ -187,6 +404,18 @@ pub trait GitRepository: Send + Sync {
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<Option<String>>;
 
+    /// Returns the contents of `path` as of `commit`, or None if the commit has no entry for the given path.
+    ///
+    /// Also returns `None` for symlinks and binary files.
+    fn load_text_at_revision(
+        &self,
+        commit: String,
+        path: RepoPath,
+    ) -> BoxFuture<Result<Option<String>>>;
+
+    /// Returns the base, ours and theirs versions of a path that has merge conflicts.
+    fn load_conflict_texts(&self, path: RepoPath) -> BoxFuture<Result<ConflictTexts>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -211,6 +440,34 @@ pub trait GitRepository: Send + Sync {
     fn change_branch(&self, name: String) -> BoxFuture<Result<()>>;
     fn create_branch(&self, name: String) -> BoxFuture<Result<()>>;
 
+    fn rename_branch(
+        &self,
+        old_name: String,
+        new_name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Deletes a local branch.
+    ///
+    /// Unless `force` is set, this fails for branches that aren't merged into their upstream or HEAD.
+    fn delete_branch(
+        &self,
+        name: String,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Sets the remote-tracking branch of a local branch, or removes it when `upstream` is `None`.
+    fn set_upstream(
+        &self,
+        branch: String,
+        upstream: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Returns the changes made on HEAD since it diverged from `branch`, like `git diff branch...HEAD`.
+    fn compare_with_branch(&self, branch: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
+
     fn reset(
         &self,
         commit: String,
@@ -225,11 +482,36 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>>;
 
+    /// Applies the changes introduced by `commit` on top of HEAD.
+    ///
+    /// If they don't apply cleanly, the conflicts are left in the index with `CHERRY_PICK_HEAD`
+    /// set, the same way as for a conflicted merge.
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Creates a commit that undoes the changes introduced by `commit`.
+    ///
+    /// Conflicts are left in the index with `REVERT_HEAD` set.
+    fn revert(&self, commit: String, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
     fn show(&self, commit: String) -> BoxFuture<Result<CommitDetails>>;
 
+    /// Verifies the commit's signature, returning `None` when the commit isn't signed.
+    fn commit_signature(&self, commit: String) -> BoxFuture<Result<Option<CommitSignature>>>;
+
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<Result<crate::blame::Blame>>;
 
+    /// Blames `path` as it was in `commit`, rather than its contents in the working copy.
+    fn blame_at_revision(
+        &self,
+        path: RepoPath,
+        commit: String,
+    ) -> BoxFuture<Result<crate::blame::Blame>>;
+
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
     fn path(&self) -> PathBuf;
@@ -253,6 +535,10 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>>;
 
+    /// Commits the staged changes.
+    ///
+    /// The commit is signed when `commit.gpgsign` is set or [`CommitOptions::sign`] asks for it,
+    /// with GPG, SSH or X.509 keys depending on `gpg.format`.
     fn commit(
         &self,
         message: SharedString,
@@ -295,6 +581,115 @@ pub trait GitRepository: Send + Sync {
 
     fn get_remotes(&self, branch_name: Option<String>) -> BoxFuture<Result<Vec<Remote>>>;
 
+    /// Returns the tags in the repository, most recently created first.
+    fn tags(&self) -> BoxFuture<Result<Vec<Tag>>>;
+
+    /// Creates a tag pointing at `commit`, or at HEAD.
+    ///
+    /// The tag is annotated when a message is given, and signed if `tag.gpgSign` is set.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    fn delete_tag(&self, name: String, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
+    /// Returns the entries in the stash, most recent first.
+    fn stash_entries(&self) -> BoxFuture<Result<Vec<StashEntry>>>;
+
+    /// Records the current changes in a new stash entry and reverts them in the working tree.
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        options: StashOptions,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Applies the stash entry at `index` without removing it from the stash.
+    fn stash_apply(&self, index: usize, env: Arc<HashMap<String, String>>)
+    -> BoxFuture<Result<()>>;
+
+    /// Applies the stash entry at `index` and removes it from the stash.
+    fn stash_pop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    fn stash_drop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    /// Loads the changes recorded in the stash entry at `index`, relative to the commit it was created on.
+    /// Files that were untracked when stashed with `--include-untracked` are shown as added.
+    fn stash_show(&self, index: usize, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
+
+    /// Returns the state of the rebase in progress, if any.
+    fn rebase_state(&self) -> BoxFuture<Option<RebaseState>>;
+
+    /// Returns the commits that an interactive rebase onto `base` would replay, oldest first.
+    fn rebase_todo(&self, base: String) -> BoxFuture<Result<Vec<RebaseTodoEntry>>>;
+
+    /// Starts an interactive rebase onto `base`, replaying the commits as described by `todo`.
+    fn rebase_start(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
+    /// Lists the commits reachable from HEAD, newest first, in topological order.
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<LogEntry>>>;
+
+    /// Lists the commits that touch `path`, newest first, following the file across renames.
+    fn file_history(
+        &self,
+        path: RepoPath,
+        skip: usize,
+        limit: usize,
+    ) -> BoxFuture<Result<Vec<FileHistoryEntry>>>;
+
+    /// Lists the main working tree followed by all linked worktrees.
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>>;
+
+    /// Checks out `branch` into a new linked worktree at `path`.
+    ///
+    /// When `new_branch` is set, the branch is created first, starting at `base` or at HEAD.
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        branch: String,
+        new_branch: bool,
+        base: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Removes the linked worktree at `path`. Unless `force` is set, this fails when the
+    /// worktree has uncommitted changes.
+    fn remove_worktree(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Cleans up the administrative files of worktrees whose directories were deleted.
+    fn prune_worktrees(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>>;
+
     /// returns a list of remote branches that contain HEAD
     fn check_for_pushed_commit(&self) -> BoxFuture<Result<Vec<SharedString>>>;
 
@@ -370,6 +765,228 @@ impl RealGitRepository {
             .context("failed to read git work directory")
             .map(Path::to_path_buf)
     }
+
+    fn commit_details(repo: &git2::Repository, commit: &str) -> Result<CommitDetails> {
+        let Ok(commit) = repo.revparse_single(commit)?.into_commit() else {
+            anyhow::bail!("{} is not a commit", commit);
+        };
+        Ok(CommitDetails {
+            sha: commit.id().to_string().into(),
+            message: String::from_utf8_lossy(commit.message_raw_bytes())
+                .to_string()
+                .into(),
+            commit_timestamp: commit.time().seconds(),
+            author_email: String::from_utf8_lossy(commit.author().email_bytes())
+                .to_string()
+                .into(),
+            author_name: String::from_utf8_lossy(commit.author().name_bytes())
+                .to_string()
+                .into(),
+        })
+    }
+
+    fn run_git_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to run git {}:\n{}",
+                        args.join(" "),
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    /// Runs a `git rebase` subcommand without ever opening an editor.
+    ///
+    /// A rebase that stops to let the user resolve conflicts exits with a failure
+    /// status, so failures are not reported when the command itself stopped the
+    /// rebase at a new step. A `--continue` failing on the same unresolved step is.
+    fn run_rebase_command(
+        &self,
+        args: Vec<String>,
+        sequence_editor: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let mut command = new_smol_command(&git_binary_path);
+                command
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args(&args);
+                if let Some(sequence_editor) = sequence_editor {
+                    command.env("GIT_SEQUENCE_EDITOR", sequence_editor);
+                }
+                let state_before = read_rebase_state(&git_dir);
+                let output = command.output().await?;
+
+                let state_after = read_rebase_state(&git_dir);
+                if state_after.is_none() {
+                    std::fs::remove_dir_all(git_dir.join(REBASE_MESSAGES_DIR)).ok();
+                }
+                let stopped = state_after.is_some() && state_after != state_before;
+                if !output.status.success() && !stopped {
+                    return Err(anyhow!(
+                        "Failed to run git {}:\n{}",
+                        args.join(" "),
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    /// Runs `git cherry-pick` or `git revert` for a single commit without opening an editor.
+    ///
+    /// When the commit doesn't apply cleanly, git stops with `head_file` pointing at it. That is
+    /// not treated as an error, so that the conflicts can be resolved and committed like those
+    /// of a merge.
+    fn run_sequencer_command(
+        &self,
+        subcommand: &'static str,
+        commit: String,
+        head_file: &'static str,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        let git_dir = self.path();
+        // Merge commits can only be applied relative to one of their parents.
+        let is_merge = self
+            .repository
+            .lock()
+            .revparse_single(&commit)
+            .and_then(|object| object.peel_to_commit())
+            .is_ok_and(|commit| commit.parent_count() > 1);
+        self.executor
+            .spawn(async move {
+                // A conflict stops the command with `head_file` set. When it was already set, the
+                // command failed because another one is in progress.
+                let was_in_progress = git_dir.join(head_file).exists();
+                let mut args = vec![subcommand, "--no-edit"];
+                if is_merge {
+                    args.push("--mainline=1");
+                }
+                args.push(&commit);
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                if !output.status.success()
+                    && (was_in_progress || !git_dir.join(head_file).exists())
+                {
+                    return Err(anyhow!(
+                        "Failed to run git {}:\n{}",
+                        args.join(" "),
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+}
+
+/// Directory inside `.git` holding the todo list and reworded messages of a rebase started by Zed.
+const REBASE_MESSAGES_DIR: &str = "zed-rebase";
+
+fn read_rebase_state(git_dir: &Path) -> Option<RebaseState> {
+    let read = |dir: &Path, name: &str| -> Option<String> {
+        let contents = std::fs::read_to_string(dir.join(name)).ok()?;
+        Some(contents.trim().to_string())
+    };
+
+    let (dir, current_step, total_steps) = {
+        let merge_dir = git_dir.join("rebase-merge");
+        let apply_dir = git_dir.join("rebase-apply");
+        if merge_dir.is_dir() {
+            let current = read(&merge_dir, "msgnum");
+            let total = read(&merge_dir, "end");
+            (merge_dir, current, total)
+        } else if apply_dir.is_dir() {
+            let current = read(&apply_dir, "next");
+            let total = read(&apply_dir, "last");
+            (apply_dir, current, total)
+        } else {
+            return None;
+        }
+    };
+
+    let head_name = read(&dir, "head-name")
+        .filter(|name| name != "detached HEAD")
+        .map(|name| {
+            name.strip_prefix("refs/heads/")
+                .unwrap_or(&name)
+                .to_string()
+                .into()
+        });
+
+    Some(RebaseState {
+        head_name,
+        onto: read(&dir, "onto").unwrap_or_default().into(),
+        current_step: current_step.and_then(|n| n.parse().ok()).unwrap_or(0),
+        total_steps: total_steps.and_then(|n| n.parse().ok()).unwrap_or(0),
+        stopped_sha: read(&dir, "stopped-sha")
+            .filter(|sha| !sha.is_empty())
+            .map(Into::into),
+    })
+}
+
+/// Builds the contents of a `git-rebase-todo` file.
+///
+/// Reworded commits are picked and then amended with the message stored at
+/// `message_path(ix)`, so that the rebase never needs to open an editor.
+fn rebase_todo_contents(
+    todo: &[RebaseTodoEntry],
+    message_path: impl Fn(usize) -> PathBuf,
+) -> String {
+    let mut contents = String::new();
+    for (ix, entry) in todo.iter().enumerate() {
+        match (entry.action, &entry.message) {
+            (RebaseAction::Reword, Some(_)) => {
+                contents.push_str(&format!("pick {} {}\n", entry.sha, entry.subject));
+                contents.push_str(&format!(
+                    "exec git commit --amend --only --allow-empty --no-verify -F \"{}\"\n",
+                    message_path(ix).display()
+                ));
+            }
+            (action, _) => {
+                contents.push_str(&format!(
+                    "{} {} {}\n",
+                    action.as_str(),
+                    entry.sha,
+                    entry.subject
+                ));
+            }
+        }
+    }
+    contents
 }
 
 #[derive(Clone, Debug)]
@@ -398,33 +1015,34 @@ impl GitRepository for RealGitRepository {
     fn show(&self, commit: String) -> BoxFuture<Result<CommitDetails>> {
         let repo = self.repository.clone();
         self.executor
-            .spawn(async move {
-                let repo = repo.lock();
-                let Ok(commit) = repo.revparse_single(&commit)?.into_commit() else {
-                    anyhow::bail!("{} is not a commit", commit);
-                };
-                let details = CommitDetails {
-                    sha: commit.id().to_string().into(),
-                    message: String::from_utf8_lossy(commit.message_raw_bytes())
-                        .to_string()
-                        .into(),
-                    commit_timestamp: commit.time().seconds(),
-                    author_email: String::from_utf8_lossy(commit.author().email_bytes())
-                        .to_string()
-                        .into(),
-                    author_name: String::from_utf8_lossy(commit.author().name_bytes())
-                        .to_string()
-                        .into(),
-                };
-                Ok(details)
-            })
+            .spawn(async move { Self::commit_details(&repo.lock(), &commit) })
             .boxed()
     }
 
-    fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
-        let Some(working_directory) = self.repository.lock().workdir().map(ToOwned::to_owned)
-        else {
-            return future::ready(Err(anyhow!("no working directory"))).boxed();
+    fn commit_signature(&self, commit: String) -> BoxFuture<Result<Option<CommitSignature>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["log", "-1", "--no-color", SIGNATURE_FORMAT, &commit])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "failed to verify the signature of {commit}: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_signature(&String::from_utf8_lossy(&output.stdout)))
+            })
+            .boxed()
+    }
+
+    fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
+        let Some(working_directory) = self.repository.lock().workdir().map(ToOwned::to_owned)
+        else {
+            return future::ready(Err(anyhow!("no working directory"))).boxed();
         };
         cx.background_spawn(async move {
             let show_output = util::command::new_std_command("git")
@@ -449,75 +1067,7 @@ impl GitRepository for RealGitRepository {
             let parent_sha = lines.next().unwrap().trim().trim_end_matches('\0');
             let changes = parse_git_diff_name_status(lines.next().unwrap_or(""));
 
-            let mut cat_file_process = util::command::new_std_command("git")
-                .current_dir(&working_directory)
-                .args(["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|e| anyhow!("Failed to start git cat-file process: {e}"))?;
-
-            use std::io::Write as _;
-            let mut files = Vec::<CommitFile>::new();
-            let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
-            let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
-            let mut info_line = String::new();
-            let mut newline = [b'\0'];
-            for (path, status_code) in changes {
-                match status_code {
-                    StatusCode::Modified => {
-                        writeln!(&mut stdin, "{commit}:{}", path.display())?;
-                        writeln!(&mut stdin, "{parent_sha}:{}", path.display())?;
-                    }
-                    StatusCode::Added => {
-                        writeln!(&mut stdin, "{commit}:{}", path.display())?;
-                    }
-                    StatusCode::Deleted => {
-                        writeln!(&mut stdin, "{parent_sha}:{}", path.display())?;
-                    }
-                    _ => continue,
-                }
-                stdin.flush()?;
-
-                info_line.clear();
-                stdout.read_line(&mut info_line)?;
-
-                let len = info_line.trim_end().parse().with_context(|| {
-                    format!("invalid object size output from cat-file {info_line}")
-                })?;
-                let mut text = vec![0; len];
-                stdout.read_exact(&mut text)?;
-                stdout.read_exact(&mut newline)?;
-                let text = String::from_utf8_lossy(&text).to_string();
-
-                let mut old_text = None;
-                let mut new_text = None;
-                match status_code {
-                    StatusCode::Modified => {
-                        info_line.clear();
-                        stdout.read_line(&mut info_line)?;
-                        let len = info_line.trim_end().parse().with_context(|| {
-                            format!("invalid object size output from cat-file {}", info_line)
-                        })?;
-                        let mut parent_text = vec![0; len];
-                        stdout.read_exact(&mut parent_text)?;
-                        stdout.read_exact(&mut newline)?;
-                        old_text = Some(String::from_utf8_lossy(&parent_text).to_string());
-                        new_text = Some(text);
-                    }
-                    StatusCode::Added => new_text = Some(text),
-                    StatusCode::Deleted => old_text = Some(text),
-                    _ => continue,
-                }
-
-                files.push(CommitFile {
-                    path: path.into(),
-                    old_text,
-                    new_text,
-                })
-            }
-
+            let files = load_changed_files(&working_directory, &commit, parent_sha, changes)?;
             Ok(CommitDiff { files })
         })
         .boxed()
@@ -535,6 +1085,7 @@ impl GitRepository for RealGitRepository {
             let mode_flag = match mode {
                 ResetMode::Mixed => "--mixed",
                 ResetMode::Soft => "--soft",
+                ResetMode::Hard => "--hard",
             };
 
             let output = new_smol_command(&self.git_binary_path)
@@ -618,6 +1169,35 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_conflict_texts(&self, path: RepoPath) -> BoxFuture<Result<ConflictTexts>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                check_path_to_repo_path_errors(&path)?;
+                let repo = repo.lock();
+                let mut index = repo.index()?;
+                index.read(false)?;
+
+                let load_stage = |stage: i32| -> Result<Option<String>> {
+                    let Some(entry) = index.get_path(&path, stage) else {
+                        return Ok(None);
+                    };
+                    let content = repo.find_blob(entry.id)?.content().to_owned();
+                    Ok(String::from_utf8(content).ok())
+                };
+
+                const STAGE_BASE: i32 = 1;
+                const STAGE_OURS: i32 = 2;
+                const STAGE_THEIRS: i32 = 3;
+                Ok(ConflictTexts {
+                    base: load_stage(STAGE_BASE)?,
+                    ours: load_stage(STAGE_OURS)?,
+                    theirs: load_stage(STAGE_THEIRS)?,
+                })
+            })
+            .boxed()
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<Option<String>> {
         let repo = self.repository.clone();
         self.executor
@@ -634,6 +1214,31 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_text_at_revision(
+        &self,
+        commit: String,
+        path: RepoPath,
+    ) -> BoxFuture<Result<Option<String>>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                let tree = repo
+                    .revparse_single(&commit)
+                    .with_context(|| format!("unknown revision {commit}"))?
+                    .peel_to_tree()?;
+                let Ok(entry) = tree.get_path(&path) else {
+                    return Ok(None);
+                };
+                if entry.filemode() == i32::from(git2::FileMode::Link) {
+                    return Ok(None);
+                }
+                let content = repo.find_blob(entry.id())?.content().to_owned();
+                Ok(String::from_utf8(content).ok())
+            })
+            .boxed()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -719,14 +1324,16 @@ impl GitRepository for RealGitRepository {
                 true
             })
             .ok();
-        if let Some(oid) = self
-            .repository
-            .lock()
-            .find_reference("CHERRY_PICK_HEAD")
-            .ok()
-            .and_then(|reference| reference.target())
-        {
-            shas.push(oid.to_string())
+        for head in ["CHERRY_PICK_HEAD", "REVERT_HEAD"] {
+            if let Some(oid) = self
+                .repository
+                .lock()
+                .find_reference(head)
+                .ok()
+                .and_then(|reference| reference.target())
+            {
+                shas.push(oid.to_string())
+            }
         }
         shas
     }
@@ -868,6 +1475,113 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn rename_branch(
+        &self,
+        old_name: String,
+        new_name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_git_command(
+            vec![
+                "branch".into(),
+                "--move".into(),
+                "--".into(),
+                old_name,
+                new_name,
+            ],
+            env,
+        )
+    }
+
+    fn delete_branch(
+        &self,
+        name: String,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["branch".to_string(), "--delete".to_string()];
+        if force {
+            args.push("--force".to_string());
+        }
+        args.push("--".to_string());
+        args.push(name);
+        self.run_git_command(args, env)
+    }
+
+    fn set_upstream(
+        &self,
+        branch: String,
+        upstream: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let args = match upstream {
+            Some(upstream) => vec![
+                "branch".into(),
+                format!("--set-upstream-to={upstream}"),
+                "--".into(),
+                branch,
+            ],
+            None => vec![
+                "branch".into(),
+                "--unset-upstream".into(),
+                "--".into(),
+                branch,
+            ],
+        };
+        self.run_git_command(args, env)
+    }
+
+    fn compare_with_branch(&self, branch: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let working_directory = working_directory?;
+            let merge_base_output = new_std_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args(["--no-optional-locks", "merge-base", "HEAD"])
+                .arg(&branch)
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|e| anyhow!("Failed to start git merge-base process: {e}"))?;
+            if !merge_base_output.status.success() {
+                return Err(anyhow!(
+                    "HEAD has no common ancestor with {branch}:\n{}",
+                    String::from_utf8_lossy(&merge_base_output.stderr)
+                ));
+            }
+            let merge_base = String::from_utf8_lossy(&merge_base_output.stdout)
+                .trim()
+                .to_string();
+
+            let diff_output = new_std_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args([
+                    "--no-optional-locks",
+                    "diff",
+                    "-z",
+                    "--no-renames",
+                    "--name-status",
+                ])
+                .arg(&merge_base)
+                .arg("HEAD")
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|e| anyhow!("Failed to start git diff process: {e}"))?;
+            if !diff_output.status.success() {
+                return Err(anyhow!(
+                    "Failed to compare with {branch}:\n{}",
+                    String::from_utf8_lossy(&diff_output.stderr)
+                ));
+            }
+
+            let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
+            let changes = parse_git_diff_name_status(&diff_stdout);
+            let files = load_changed_files(&working_directory, "HEAD", &merge_base, changes)?;
+            Ok(CommitDiff { files })
+        })
+        .boxed()
+    }
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<Result<crate::blame::Blame>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -889,6 +1603,31 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn blame_at_revision(
+        &self,
+        path: RepoPath,
+        commit: String,
+    ) -> BoxFuture<Result<crate::blame::Blame>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+
+        const REMOTE_NAME: &str = "origin";
+        let remote_url = self.remote_url(REMOTE_NAME);
+
+        self.executor
+            .spawn(async move {
+                crate::blame::Blame::for_revision(
+                    &git_binary_path,
+                    &working_directory?,
+                    &path,
+                    &commit,
+                    remote_url,
+                )
+                .await
+            })
+            .boxed()
+    }
+
     fn diff(&self, diff: DiffType) -> BoxFuture<Result<String>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -986,6 +1725,16 @@ impl GitRepository for RealGitRepository {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<Result<()>> {
         let working_directory = self.working_directory();
+        // Git signs the commit itself when `commit.gpgsign` is set, this only tells whether
+        // a failure could come from signing. Read through git2 so that repository-local and
+        // conditional includes are honored.
+        let sign = options.sign
+            || self
+                .repository
+                .lock()
+                .config()
+                .and_then(|config| config.get_bool("commit.gpgsign"))
+                .unwrap_or(false);
         self.executor
             .spawn(async move {
                 let mut cmd = new_smol_command("git");
@@ -993,159 +1742,635 @@ impl GitRepository for RealGitRepository {
                     .envs(env.iter())
                     .args(["commit", "--quiet", "-m"])
                     .arg(&message.to_string())
-                    .arg("--cleanup=strip");
+                    .arg("--cleanup=strip")
+                    // The signing program must not wait for input on a terminal we don't have.
+                    .stdin(smol::process::Stdio::null());
 
                 if options.amend {
                     cmd.arg("--amend");
                 }
 
+                if options.sign {
+                    cmd.arg("--gpg-sign");
+                }
+
                 if let Some((name, email)) = name_and_email {
                     cmd.arg("--author").arg(&format!("{name} <{email}>"));
                 }
 
                 let output = cmd.output().await?;
 
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    if sign && stderr.contains("failed to sign") {
+                        return Err(anyhow!(
+                            "Failed to sign the commit. Check that your signing key is \
+                            available and doesn't need a passphrase from a terminal:\n{}",
+                            stderr
+                        ));
+                    }
+                    return Err(anyhow!(
+                        "Failed to commit:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn push(
+        &self,
+        branch_name: String,
+        remote_name: String,
+        options: Option<PushOptions>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let working_directory = working_directory?;
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory)
+                .args(["push"])
+                .args(options.map(|option| match option {
+                    PushOptions::SetUpstream => "--set-upstream",
+                    PushOptions::Force => "--force-with-lease",
+                }))
+                .arg(remote_name)
+                .arg(format!("{}:{}", branch_name, branch_name))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn pull(
+        &self,
+        branch_name: String,
+        remote_name: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["pull"])
+                .arg(remote_name)
+                .arg(branch_name)
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn fetch(
+        &self,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["fetch", "--all"])
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn get_remotes(&self, branch_name: Option<String>) -> BoxFuture<Result<Vec<Remote>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                if let Some(branch_name) = branch_name {
+                    let output = new_smol_command(&git_binary_path)
+                        .current_dir(&working_directory)
+                        .args(["config", "--get"])
+                        .arg(format!("branch.{}.remote", branch_name))
+                        .output()
+                        .await?;
+
+                    if output.status.success() {
+                        let remote_name = String::from_utf8_lossy(&output.stdout);
+
+                        return Ok(vec![Remote {
+                            name: remote_name.trim().to_string().into(),
+                        }]);
+                    }
+                }
+
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["remote"])
+                    .output()
+                    .await?;
+
+                if output.status.success() {
+                    let remote_names = String::from_utf8_lossy(&output.stdout)
+                        .split('\n')
+                        .filter(|name| !name.is_empty())
+                        .map(|name| Remote {
+                            name: name.trim().to_string().into(),
+                        })
+                        .collect();
+
+                    return Ok(remote_names);
+                } else {
+                    return Err(anyhow!(
+                        "Failed to get remotes:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+            })
+            .boxed()
+    }
+
+    fn tags(&self) -> BoxFuture<Result<Vec<Tag>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "for-each-ref",
+                        "--sort=-creatordate",
+                        TAG_FORMAT,
+                        "refs/tags",
+                    ])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list tags:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                parse_tag_list(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["tag".to_string()];
+        if let Some(message) = message {
+            args.push("--annotate".to_string());
+            args.push("--message".to_string());
+            args.push(message);
+        }
+        args.push("--".to_string());
+        args.push(name);
+        args.extend(commit);
+        self.run_git_command(args, env)
+    }
+
+    fn delete_tag(&self, name: String, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_git_command(
+            vec!["tag".into(), "--delete".into(), "--".into(), name],
+            env,
+        )
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["push"])
+                .arg(remote_name)
+                .arg(format!("refs/tags/{name}:refs/tags/{name}"))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn stash_entries(&self) -> BoxFuture<Result<Vec<StashEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["stash", "list", "--format=%H%x00%ct%x00%gs"])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list stash entries:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                parse_stash_list(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        options: StashOptions,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["stash".to_string(), "push".to_string()];
+        if options.include_untracked {
+            args.push("--include-untracked".to_string());
+        }
+        if options.staged {
+            args.push("--staged".to_string());
+        }
+        if let Some(message) = message {
+            args.push("--message".to_string());
+            args.push(message);
+        }
+        self.run_git_command(args, env)
+    }
+
+    fn stash_apply(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        self.run_git_command(
+            vec!["stash".into(), "apply".into(), stash_reference(index)],
+            env,
+        )
+    }
+
+    fn stash_pop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_git_command(
+            vec!["stash".into(), "pop".into(), stash_reference(index)],
+            env,
+        )
+    }
+
+    fn stash_drop(&self, index: usize, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_git_command(
+            vec!["stash".into(), "drop".into(), stash_reference(index)],
+            env,
+        )
+    }
+
+    fn stash_show(&self, index: usize, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let working_directory = working_directory?;
+            let stash = stash_reference(index);
+            let parent = format!("{stash}^1");
+            let diff_output = new_std_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args([
+                    "--no-optional-locks",
+                    "diff",
+                    "-z",
+                    "--no-renames",
+                    "--name-status",
+                ])
+                .arg(&parent)
+                .arg(&stash)
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|e| anyhow!("Failed to start git diff process: {e}"))?;
+
+            if !diff_output.status.success() {
+                return Err(anyhow!(
+                    "Failed to show {stash}:\n{}",
+                    String::from_utf8_lossy(&diff_output.stderr)
+                ));
+            }
+
+            let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
+            let changes = parse_git_diff_name_status(&diff_stdout);
+            let mut files = load_changed_files(&working_directory, &stash, &parent, changes)?;
+
+            // Untracked files are stored in a third parent, which only exists when they were stashed.
+            let untracked = format!("{stash}^3");
+            let has_untracked = new_std_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args(["--no-optional-locks", "rev-parse", "--verify", "--quiet"])
+                .arg(format!("{untracked}^{{commit}}"))
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|e| anyhow!("Failed to start git rev-parse process: {e}"))?
+                .status
+                .success();
+            if has_untracked {
+                let ls_tree_output = new_std_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["--no-optional-locks", "ls-tree", "-r", "-z", "--name-only"])
+                    .arg(&untracked)
+                    .stdin(Stdio::null())
+                    .output()
+                    .map_err(|e| anyhow!("Failed to start git ls-tree process: {e}"))?;
+                if !ls_tree_output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list the untracked files of {stash}:\n{}",
+                        String::from_utf8_lossy(&ls_tree_output.stderr)
+                    ));
+                }
+
+                let ls_tree_stdout = String::from_utf8_lossy(&ls_tree_output.stdout);
+                let untracked_files = ls_tree_stdout
+                    .split('\0')
+                    .filter(|path| !path.is_empty())
+                    .map(|path| (Path::new(path), StatusCode::Added));
+                files.extend(load_changed_files(
+                    &working_directory,
+                    &untracked,
+                    &parent,
+                    untracked_files,
+                )?);
+                files.sort_by(|a, b| a.path.cmp(&b.path));
+            }
+
+            Ok(CommitDiff { files })
+        })
+        .boxed()
+    }
+
+    fn rebase_state(&self) -> BoxFuture<Option<RebaseState>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { read_rebase_state(&git_dir) })
+            .boxed()
+    }
+
+    fn rebase_todo(&self, base: String) -> BoxFuture<Result<Vec<RebaseTodoEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "log",
+                        "--reverse",
+                        "--no-merges",
+                        "--topo-order",
+                        "--format=%H%x00%s",
+                    ])
+                    .arg(format!("{base}..HEAD"))
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to list commits since {base}:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                Ok(String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter_map(|line| {
+                        let (sha, subject) = line.split_once('\0')?;
+                        Some(RebaseTodoEntry {
+                            action: RebaseAction::Pick,
+                            sha: sha.to_string().into(),
+                            subject: subject.to_string().into(),
+                            message: None,
+                        })
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
+    fn rebase_start(
+        &self,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let messages_dir = self.path().join(REBASE_MESSAGES_DIR);
+        let message_path = |ix: usize| messages_dir.join(format!("message-{ix}"));
+        let todo_path = messages_dir.join("git-rebase-todo");
+        let result = maybe!({
+            std::fs::remove_dir_all(&messages_dir).ok();
+            std::fs::create_dir_all(&messages_dir)?;
+            for (ix, entry) in todo.iter().enumerate() {
+                if let (RebaseAction::Reword, Some(message)) = (entry.action, &entry.message) {
+                    std::fs::write(message_path(ix), message)?;
+                }
+            }
+            std::fs::write(&todo_path, rebase_todo_contents(&todo, &message_path))?;
+            anyhow::Ok(())
+        });
+        if let Err(error) = result {
+            return future::ready(Err(error)).boxed();
+        }
+
+        self.run_rebase_command(
+            vec!["rebase".into(), "--interactive".into(), base],
+            Some(format!("cp \"{}\"", todo_path.display())),
+            env,
+        )
+    }
+
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_rebase_command(vec!["rebase".into(), "--continue".into()], None, env)
+    }
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_rebase_command(vec!["rebase".into(), "--skip".into()], None, env)
+    }
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_rebase_command(vec!["rebase".into(), "--abort".into()], None, env)
+    }
+
+    fn log(&self, options: LogOptions) -> BoxFuture<Result<Vec<LogEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(log_args(&options))
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(anyhow!(
+                        "Failed to load git log:\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                parse_log(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn file_history(
+        &self,
+        path: RepoPath,
+        skip: usize,
+        limit: usize,
+    ) -> BoxFuture<Result<Vec<FileHistoryEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut args = vec![
+                    "-c".to_string(),
+                    "core.quotePath=false".to_string(),
+                    "log".to_string(),
+                    LOG_FORMAT.to_string(),
+                    "--follow".to_string(),
+                    "--name-status".to_string(),
+                    "--no-color".to_string(),
+                    format!("--skip={skip}"),
+                ];
+                if limit > 0 {
+                    args.push(format!("--max-count={limit}"));
+                }
+                args.push("--".to_string());
+                args.push(path.to_str().unwrap_or_default().to_string());
+
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(args)
+                    .output()
+                    .await?;
+
                 if !output.status.success() {
                     return Err(anyhow!(
-                        "Failed to commit:\n{}",
+                        "Failed to load file history:\n{}",
                         String::from_utf8_lossy(&output.stderr)
                     ));
                 }
-                Ok(())
+
+                parse_file_history(&String::from_utf8_lossy(&output.stdout), &path)
             })
             .boxed()
     }
 
-    fn push(
-        &self,
-        branch_name: String,
-        remote_name: String,
-        options: Option<PushOptions>,
-        ask_pass: AskPassDelegate,
-        env: Arc<HashMap<String, String>>,
-        cx: AsyncApp,
-    ) -> BoxFuture<Result<RemoteCommandOutput>> {
-        let working_directory = self.working_directory();
-        let executor = cx.background_executor().clone();
-        async move {
-            let working_directory = working_directory?;
-            let mut command = new_smol_command("git");
-            command
-                .envs(env.iter())
-                .current_dir(&working_directory)
-                .args(["push"])
-                .args(options.map(|option| match option {
-                    PushOptions::SetUpstream => "--set-upstream",
-                    PushOptions::Force => "--force-with-lease",
-                }))
-                .arg(remote_name)
-                .arg(format!("{}:{}", branch_name, branch_name))
-                .stdin(smol::process::Stdio::null())
-                .stdout(smol::process::Stdio::piped())
-                .stderr(smol::process::Stdio::piped());
-
-            run_git_command(env, ask_pass, command, &executor).await
-        }
-        .boxed()
-    }
-
-    fn pull(
+    fn cherry_pick(
         &self,
-        branch_name: String,
-        remote_name: String,
-        ask_pass: AskPassDelegate,
+        commit: String,
         env: Arc<HashMap<String, String>>,
-        cx: AsyncApp,
-    ) -> BoxFuture<Result<RemoteCommandOutput>> {
-        let working_directory = self.working_directory();
-        let executor = cx.background_executor().clone();
-        async move {
-            let mut command = new_smol_command("git");
-            command
-                .envs(env.iter())
-                .current_dir(&working_directory?)
-                .args(["pull"])
-                .arg(remote_name)
-                .arg(branch_name)
-                .stdout(smol::process::Stdio::piped())
-                .stderr(smol::process::Stdio::piped());
-
-            run_git_command(env, ask_pass, command, &executor).await
-        }
-        .boxed()
+    ) -> BoxFuture<Result<()>> {
+        self.run_sequencer_command("cherry-pick", commit, "CHERRY_PICK_HEAD", env)
     }
 
-    fn fetch(
-        &self,
-        ask_pass: AskPassDelegate,
-        env: Arc<HashMap<String, String>>,
-        cx: AsyncApp,
-    ) -> BoxFuture<Result<RemoteCommandOutput>> {
-        let working_directory = self.working_directory();
-        let executor = cx.background_executor().clone();
-        async move {
-            let mut command = new_smol_command("git");
-            command
-                .envs(env.iter())
-                .current_dir(&working_directory?)
-                .args(["fetch", "--all"])
-                .stdout(smol::process::Stdio::piped())
-                .stderr(smol::process::Stdio::piped());
-
-            run_git_command(env, ask_pass, command, &executor).await
-        }
-        .boxed()
+    fn revert(&self, commit: String, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_sequencer_command("revert", commit, "REVERT_HEAD", env)
     }
 
-    fn get_remotes(&self, branch_name: Option<String>) -> BoxFuture<Result<Vec<Remote>>> {
+    fn worktrees(&self) -> BoxFuture<Result<Vec<GitWorktree>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
         self.executor
             .spawn(async move {
-                let working_directory = working_directory?;
-                if let Some(branch_name) = branch_name {
-                    let output = new_smol_command(&git_binary_path)
-                        .current_dir(&working_directory)
-                        .args(["config", "--get"])
-                        .arg(format!("branch.{}.remote", branch_name))
-                        .output()
-                        .await?;
-
-                    if output.status.success() {
-                        let remote_name = String::from_utf8_lossy(&output.stdout);
-
-                        return Ok(vec![Remote {
-                            name: remote_name.trim().to_string().into(),
-                        }]);
-                    }
-                }
-
                 let output = new_smol_command(&git_binary_path)
-                    .current_dir(&working_directory)
-                    .args(["remote"])
+                    .current_dir(&working_directory?)
+                    .args(["worktree", "list", "--porcelain"])
                     .output()
                     .await?;
 
-                if output.status.success() {
-                    let remote_names = String::from_utf8_lossy(&output.stdout)
-                        .split('\n')
-                        .filter(|name| !name.is_empty())
-                        .map(|name| Remote {
-                            name: name.trim().to_string().into(),
-                        })
-                        .collect();
-
-                    return Ok(remote_names);
-                } else {
+                if !output.status.success() {
                     return Err(anyhow!(
-                        "Failed to get remotes:\n{}",
+                        "Failed to list worktrees:\n{}",
                         String::from_utf8_lossy(&output.stderr)
                     ));
                 }
+
+                parse_worktree_list(&String::from_utf8_lossy(&output.stdout))
             })
             .boxed()
     }
 
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        branch: String,
+        new_branch: bool,
+        base: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["worktree".to_string(), "add".to_string()];
+        if new_branch {
+            args.push("-b".to_string());
+            args.push(branch);
+            args.push("--".to_string());
+            args.push(path.to_string_lossy().into_owned());
+            args.extend(base);
+        } else {
+            args.push("--".to_string());
+            args.push(path.to_string_lossy().into_owned());
+            args.push(branch);
+        }
+        self.run_git_command(args, env)
+    }
+
+    fn remove_worktree(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["worktree".to_string(), "remove".to_string()];
+        if force {
+            args.push("--force".to_string());
+        }
+        args.push("--".to_string());
+        args.push(path.to_string_lossy().into_owned());
+        self.run_git_command(args, env)
+    }
+
+    fn prune_worktrees(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<Result<()>> {
+        self.run_git_command(vec!["worktree".into(), "prune".into()], env)
+    }
+
     fn check_for_pushed_commit(&self) -> BoxFuture<Result<Vec<SharedString>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -1349,6 +2574,87 @@ impl GitRepository for RealGitRepository {
     }
 }
 
+/// Loads the old and new contents of each changed path, reading the new text from
+/// `commit` and the old text from `parent`.
+fn load_changed_files<'a>(
+    working_directory: &Path,
+    commit: &str,
+    parent: &str,
+    changes: impl Iterator<Item = (&'a Path, StatusCode)>,
+) -> Result<Vec<CommitFile>> {
+    let mut cat_file_process = util::command::new_std_command("git")
+        .current_dir(working_directory)
+        .args(["--no-optional-locks", "cat-file", "--batch=%(objectsize)"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start git cat-file process: {e}"))?;
+
+    use std::io::Write as _;
+    let mut files = Vec::<CommitFile>::new();
+    let mut stdin = BufWriter::with_capacity(512, cat_file_process.stdin.take().unwrap());
+    let mut stdout = BufReader::new(cat_file_process.stdout.take().unwrap());
+    let mut info_line = String::new();
+    let mut newline = [b'\0'];
+    for (path, status_code) in changes {
+        match status_code {
+            StatusCode::Modified => {
+                writeln!(&mut stdin, "{commit}:{}", path.display())?;
+                writeln!(&mut stdin, "{parent}:{}", path.display())?;
+            }
+            StatusCode::Added => {
+                writeln!(&mut stdin, "{commit}:{}", path.display())?;
+            }
+            StatusCode::Deleted => {
+                writeln!(&mut stdin, "{parent}:{}", path.display())?;
+            }
+            _ => continue,
+        }
+        stdin.flush()?;
+
+        info_line.clear();
+        stdout.read_line(&mut info_line)?;
+
+        let len = info_line
+            .trim_end()
+            .parse()
+            .with_context(|| format!("invalid object size output from cat-file {info_line}"))?;
+        let mut text = vec![0; len];
+        stdout.read_exact(&mut text)?;
+        stdout.read_exact(&mut newline)?;
+        let text = String::from_utf8_lossy(&text).to_string();
+
+        let mut old_text = None;
+        let mut new_text = None;
+        match status_code {
+            StatusCode::Modified => {
+                info_line.clear();
+                stdout.read_line(&mut info_line)?;
+                let len = info_line.trim_end().parse().with_context(|| {
+                    format!("invalid object size output from cat-file {}", info_line)
+                })?;
+                let mut parent_text = vec![0; len];
+                stdout.read_exact(&mut parent_text)?;
+                stdout.read_exact(&mut newline)?;
+                old_text = Some(String::from_utf8_lossy(&parent_text).to_string());
+                new_text = Some(text);
+            }
+            StatusCode::Added => new_text = Some(text),
+            StatusCode::Deleted => old_text = Some(text),
+            _ => continue,
+        }
+
+        files.push(CommitFile {
+            path: path.into(),
+            old_text,
+            new_text,
+        })
+    }
+
+    Ok(files)
+}
+
 fn git_status_args(path_prefixes: &[RepoPath]) -> Vec<OsString> {
     let mut args = vec![
         OsString::from("--no-optional-locks"),
@@ -1646,55 +2952,313 @@ impl MapSeekTarget<RepoPath> for RepoPathDescendants<'_> {
     }
 }
 
-fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
-    let mut branches = Vec::new();
-    for line in input.split('\n') {
+fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
+    let mut branches = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let is_current_branch = fields.next().context("no HEAD")? == "*";
+        let head_sha: SharedString = fields.next().context("no objectname")?.to_string().into();
+        let parent_sha: SharedString = fields.next().context("no parent")?.to_string().into();
+        let raw_ref_name = fields.next().context("no refname")?;
+        let ref_name: SharedString =
+            if let Some(ref_name) = raw_ref_name.strip_prefix("refs/heads/") {
+                ref_name.to_string().into()
+            } else if let Some(ref_name) = raw_ref_name.strip_prefix("refs/remotes/") {
+                ref_name.to_string().into()
+            } else {
+                return Err(anyhow!("unexpected format for refname"));
+            };
+        let upstream_name = fields.next().context("no upstream")?.to_string();
+        let upstream_tracking = parse_upstream_track(fields.next().context("no upstream:track")?)?;
+        let commiterdate = fields.next().context("no committerdate")?.parse::<i64>()?;
+        let subject: SharedString = fields
+            .next()
+            .context("no contents:subject")?
+            .to_string()
+            .into();
+
+        branches.push(Branch {
+            is_head: is_current_branch,
+            name: ref_name,
+            most_recent_commit: Some(CommitSummary {
+                sha: head_sha,
+                subject,
+                commit_timestamp: commiterdate,
+                has_parent: !parent_sha.is_empty(),
+            }),
+            upstream: if upstream_name.is_empty() {
+                None
+            } else {
+                Some(Upstream {
+                    ref_name: upstream_name.into(),
+                    tracking: upstream_tracking,
+                })
+            },
+        })
+    }
+
+    Ok(branches)
+}
+
+/// Each record starts with a record separator, so that the patches `git log -L`
+/// always prints after a commit can be skipped.
+const LOG_FORMAT: &str = "--format=%x1e%H%x00%P%x00%an%x00%ae%x00%ct%x00%D%x00%s";
+
+fn log_args(options: &LogOptions) -> Vec<String> {
+    let mut args = vec![
+        "log".to_string(),
+        LOG_FORMAT.to_string(),
+        "--topo-order".to_string(),
+        "--no-color".to_string(),
+        format!("--skip={}", options.skip),
+    ];
+    if options.limit > 0 {
+        args.push(format!("--max-count={}", options.limit));
+    }
+    // These flags apply to both patterns, so the message is escaped rather than matched with
+    // `--fixed-strings`, which would also apply to the author.
+    if options.author.is_some() || options.message.is_some() {
+        args.push("--regexp-ignore-case".to_string());
+    }
+    if let Some(author) = &options.author {
+        args.push(format!("--author={author}"));
+    }
+    if let Some(message) = &options.message {
+        args.push("--basic-regexp".to_string());
+        args.push(format!("--grep={}", escape_basic_regex(message)));
+    }
+    if let Some(since) = &options.since {
+        args.push(format!("--since={since}"));
+    }
+    if let Some(until) = &options.until {
+        args.push(format!("--until={until}"));
+    }
+    if options.path.is_some() {
+        // Point the parents of each listed commit at the closest listed ancestors, so that the
+        // graph stays connected when commits that don't touch the path are left out.
+        args.push("--parents".to_string());
+    }
+    match (&options.path, &options.line_range) {
+        (Some(path), Some(line_range)) => {
+            args.push(format!(
+                "-L{},{}:{}",
+                line_range.start,
+                line_range.end,
+                path.to_str().unwrap_or_default()
+            ));
+        }
+        (Some(path), None) => {
+            args.push("--".to_string());
+            args.push(path.to_str().unwrap_or_default().to_string());
+        }
+        (None, _) => {}
+    }
+    args
+}
+
+/// Escapes the characters that have a special meaning in a POSIX basic regular expression.
+fn escape_basic_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '.' | '*' | '[' | ']' | '^' | '$') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn parse_log(input: &str) -> Result<Vec<LogEntry>> {
+    let mut entries = Vec::new();
+    for record in input.split('\x1e') {
+        let Some(line) = record.lines().next().filter(|line| !line.is_empty()) else {
+            continue;
+        };
+        entries.push(parse_log_entry(line)?);
+    }
+    Ok(entries)
+}
+
+/// Parses the output of `git log --follow --name-status` for `path`.
+///
+/// Merge commits have no name-status line, and keep the path of the newer revision.
+fn parse_file_history(input: &str, path: &RepoPath) -> Result<Vec<FileHistoryEntry>> {
+    let mut entries = Vec::new();
+    let mut current_path = path.clone();
+    for record in input.split('\x1e') {
+        let mut lines = record.lines();
+        let Some(line) = lines.next().filter(|line| !line.is_empty()) else {
+            continue;
+        };
+        let commit = parse_log_entry(line)?;
+        let (path, old_path) = match lines.find(|line| !line.is_empty()) {
+            Some(status) => {
+                let mut fields = status.split('\t').skip(1);
+                match (fields.next(), fields.next()) {
+                    (Some(old_path), Some(new_path)) => (
+                        RepoPath::from_str(new_path),
+                        Some(RepoPath::from_str(old_path)),
+                    ),
+                    (Some(path), None) => (RepoPath::from_str(path), None),
+                    _ => return Err(anyhow!("invalid name-status line {status:?}")),
+                }
+            }
+            None => (current_path.clone(), None),
+        };
+        let entry = FileHistoryEntry {
+            commit,
+            path,
+            old_path,
+        };
+        current_path = entry.parent_path().clone();
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_log_entry(line: &str) -> Result<LogEntry> {
+    let mut fields = line.split('\x00');
+    let sha: SharedString = fields.next().context("no objectname")?.to_string().into();
+    let parents = fields
+        .next()
+        .context("no parents")?
+        .split_whitespace()
+        .map(|parent| SharedString::from(parent.to_string()))
+        .collect();
+    let author_name = fields.next().context("no author name")?.to_string().into();
+    let author_email = fields.next().context("no author email")?.to_string().into();
+    let commit_timestamp = fields.next().context("no committerdate")?.parse::<i64>()?;
+    let refs = fields
+        .next()
+        .context("no decorations")?
+        .split(", ")
+        .filter(|reference| !reference.is_empty())
+        .map(|reference| SharedString::from(reference.to_string()))
+        .collect();
+    let subject = fields.next().context("no subject")?.to_string().into();
+    Ok(LogEntry {
+        sha,
+        parents,
+        author_name,
+        author_email,
+        commit_timestamp,
+        subject,
+        refs,
+    })
+}
+
+fn parse_worktree_list(input: &str) -> Result<Vec<GitWorktree>> {
+    let mut worktrees = Vec::new();
+    for record in input.split("\n\n") {
+        let mut lines = record.lines().filter(|line| !line.is_empty()).peekable();
+        if lines.peek().is_none() {
+            continue;
+        }
+        let path = lines
+            .next()
+            .and_then(|line| line.strip_prefix("worktree "))
+            .context("no worktree path")?;
+        let mut worktree = GitWorktree {
+            path: PathBuf::from(path),
+            head_sha: None,
+            branch: None,
+            is_main: worktrees.is_empty(),
+            is_locked: false,
+            is_prunable: false,
+        };
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "HEAD" => worktree.head_sha = Some(value.to_string().into()),
+                "branch" => {
+                    let name = value.strip_prefix("refs/heads/").unwrap_or(value);
+                    worktree.branch = Some(name.to_string().into());
+                }
+                "locked" => worktree.is_locked = true,
+                "prunable" => worktree.is_prunable = true,
+                _ => {}
+            }
+        }
+        worktrees.push(worktree);
+    }
+    Ok(worktrees)
+}
+
+const TAG_FORMAT: &str = "--format=%(refname:short)%00%(objecttype)%00%(*objectname)%00%(objectname)%00%(creatordate:unix)%00%(contents:subject)";
+
+fn parse_tag_list(input: &str) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    for line in input.lines() {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let name: SharedString = fields.next().context("no refname")?.to_string().into();
+        let object_type = fields.next().context("no objecttype")?;
+        let peeled_sha = fields.next().context("no peeled objectname")?;
+        let sha = fields.next().context("no objectname")?;
+        let timestamp = fields.next().context("no creatordate")?.parse::<i64>()?;
+        let subject = fields.next().context("no subject")?.to_string().into();
+        let is_annotated = object_type == "tag";
+        tags.push(Tag {
+            name,
+            sha: if is_annotated { peeled_sha } else { sha }
+                .to_string()
+                .into(),
+            is_annotated,
+            subject,
+            timestamp,
+        });
+    }
+    Ok(tags)
+}
+
+const SIGNATURE_FORMAT: &str = "--format=%G?%x00%GS%x00%GK";
+
+fn parse_signature(input: &str) -> Option<CommitSignature> {
+    let mut fields = input.trim_end_matches('\n').split('\x00');
+    let status = match fields.next()? {
+        "G" => SignatureStatus::Good,
+        "U" => SignatureStatus::GoodUnknownValidity,
+        "B" => SignatureStatus::Bad,
+        "X" => SignatureStatus::ExpiredSignature,
+        "Y" => SignatureStatus::ExpiredKey,
+        "R" => SignatureStatus::RevokedKey,
+        "E" => SignatureStatus::CannotCheck,
+        _ => return None,
+    };
+    Some(CommitSignature {
+        status,
+        signer: fields.next().unwrap_or_default().to_string().into(),
+        key: fields.next().unwrap_or_default().to_string().into(),
+    })
+}
+
+fn parse_stash_list(input: &str) -> Result<Vec<StashEntry>> {
+    let mut entries = Vec::new();
+    for (index, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
         let mut fields = line.split('\x00');
-        let is_current_branch = fields.next().context("no HEAD")? == "*";
-        let head_sha: SharedString = fields.next().context("no objectname")?.to_string().into();
-        let parent_sha: SharedString = fields.next().context("no parent")?.to_string().into();
-        let raw_ref_name = fields.next().context("no refname")?;
-        let ref_name: SharedString =
-            if let Some(ref_name) = raw_ref_name.strip_prefix("refs/heads/") {
-                ref_name.to_string().into()
-            } else if let Some(ref_name) = raw_ref_name.strip_prefix("refs/remotes/") {
-                ref_name.to_string().into()
-            } else {
-                return Err(anyhow!("unexpected format for refname"));
-            };
-        let upstream_name = fields.next().context("no upstream")?.to_string();
-        let upstream_tracking = parse_upstream_track(fields.next().context("no upstream:track")?)?;
-        let commiterdate = fields.next().context("no committerdate")?.parse::<i64>()?;
-        let subject: SharedString = fields
+        let sha: SharedString = fields.next().context("no objectname")?.to_string().into();
+        let commit_timestamp = fields.next().context("no committerdate")?.parse::<i64>()?;
+        let message: SharedString = fields
             .next()
-            .context("no contents:subject")?
+            .context("no reflog subject")?
             .to_string()
             .into();
-
-        branches.push(Branch {
-            is_head: is_current_branch,
-            name: ref_name,
-            most_recent_commit: Some(CommitSummary {
-                sha: head_sha,
-                subject,
-                commit_timestamp: commiterdate,
-                has_parent: !parent_sha.is_empty(),
-            }),
-            upstream: if upstream_name.is_empty() {
-                None
-            } else {
-                Some(Upstream {
-                    ref_name: upstream_name.into(),
-                    tracking: upstream_tracking,
-                })
-            },
-        })
+        entries.push(StashEntry {
+            index,
+            sha,
+            message,
+            commit_timestamp,
+        });
     }
-
-    Ok(branches)
+    Ok(entries)
 }
 
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
@@ -1964,7 +3528,577 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_stash_list_parsing() {
+        let input = concat!(
+            "4c1f9bd7a5a3cf2e5b8c7ad2b1d46e9a6f3a1b2c\x001733187470\x00WIP on main: 060964d generated protobuf\n",
+            "9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d\x001733180000\x00On main: experiment\n",
+        );
+        assert_eq!(
+            parse_stash_list(input).unwrap(),
+            vec![
+                StashEntry {
+                    index: 0,
+                    sha: "4c1f9bd7a5a3cf2e5b8c7ad2b1d46e9a6f3a1b2c".into(),
+                    message: "WIP on main: 060964d generated protobuf".into(),
+                    commit_timestamp: 1733187470,
+                },
+                StashEntry {
+                    index: 1,
+                    sha: "9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d".into(),
+                    message: "On main: experiment".into(),
+                    commit_timestamp: 1733180000,
+                },
+            ]
+        );
+        assert_eq!(parse_stash_list("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let input = "\x1eaaa\x00bbb ccc\x00Jane Doe\x00jane@example.com\x001700000000\x00HEAD -> main, tag: v1.0\x00Merge branch 'feature'\n\
+            \x1ebbb\x00\x00John Doe\x00john@example.com\x001600000000\x00\x00Initial commit\n\
+            \n\
+            diff --git a/file.txt b/file.txt\n";
+        let entries = parse_log(input).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].sha, "aaa");
+        assert_eq!(
+            entries[0].parents,
+            vec![SharedString::from("bbb"), SharedString::from("ccc")]
+        );
+        assert_eq!(entries[0].author_name, "Jane Doe");
+        assert_eq!(
+            entries[0].refs,
+            vec![
+                SharedString::from("HEAD -> main"),
+                SharedString::from("tag: v1.0")
+            ]
+        );
+        assert_eq!(entries[0].subject, "Merge branch 'feature'");
+        assert_eq!(entries[1].parents, Vec::<SharedString>::new());
+        assert_eq!(entries[1].commit_timestamp, 1600000000);
+        assert_eq!(entries[1].refs, Vec::<SharedString>::new());
+        assert_eq!(entries[1].subject, "Initial commit");
+    }
+
+    #[test]
+    fn test_parse_file_history() {
+        let input = "\x1eaaa\x00bbb\x00Jane Doe\x00jane@example.com\x001700000000\x00\x00Edit\n\
+            \n\
+            M\tsrc/new.rs\n\
+            \x1ebbb\x00ccc\x00Jane Doe\x00jane@example.com\x001600000000\x00\x00Rename\n\
+            \n\
+            R087\tsrc/old.rs\tsrc/new.rs\n\
+            \x1emmm\x00ddd eee\x00John Doe\x00john@example.com\x001550000000\x00\x00Merge\n\
+            \x1eccc\x00\x00John Doe\x00john@example.com\x001500000000\x00\x00Add\n\
+            \n\
+            A\tsrc/old.rs\n";
+        let entries = parse_file_history(input, &RepoPath::from_str("src/new.rs")).unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (
+                    entry.commit.sha.as_ref(),
+                    entry.path.clone(),
+                    entry.old_path.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("aaa", RepoPath::from_str("src/new.rs"), None),
+                (
+                    "bbb",
+                    RepoPath::from_str("src/new.rs"),
+                    Some(RepoPath::from_str("src/old.rs"))
+                ),
+                ("mmm", RepoPath::from_str("src/old.rs"), None),
+                ("ccc", RepoPath::from_str("src/old.rs"), None),
+            ]
+        );
+        assert_eq!(entries[1].parent_path(), &RepoPath::from_str("src/old.rs"));
+        assert_eq!(entries[3].commit.subject, "Add");
+    }
+
+    #[test]
+    fn test_parse_tag_list() {
+        let input = "v1.0\x00tag\x00aaa\x00ttt\x001700000000\x00Release 1.0\n\
+            nightly\x00commit\x00\x00bbb\x001600000000\x00Fix the build\n";
+        assert_eq!(
+            parse_tag_list(input).unwrap(),
+            vec![
+                Tag {
+                    name: "v1.0".into(),
+                    sha: "aaa".into(),
+                    is_annotated: true,
+                    subject: "Release 1.0".into(),
+                    timestamp: 1700000000,
+                },
+                Tag {
+                    name: "nightly".into(),
+                    sha: "bbb".into(),
+                    is_annotated: false,
+                    subject: "Fix the build".into(),
+                    timestamp: 1600000000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_log_args() {
+        let args = log_args(&LogOptions {
+            path: Some(RepoPath::from_str("src/main.rs")),
+            author: Some("Jane".to_string()),
+            message: Some("fix [ui].*".to_string()),
+            limit: 10,
+            ..LogOptions::default()
+        });
+        assert_eq!(
+            &args[2..],
+            [
+                "--topo-order",
+                "--no-color",
+                "--skip=0",
+                "--max-count=10",
+                "--regexp-ignore-case",
+                "--author=Jane",
+                "--basic-regexp",
+                "--grep=fix \\[ui\\]\\.\\*",
+                "--parents",
+                "--",
+                "src/main.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_signature() {
+        assert_eq!(
+            parse_signature("G\x00Jane Doe <jane@example.com>\x00ABCDEF\n"),
+            Some(CommitSignature {
+                status: SignatureStatus::Good,
+                signer: "Jane Doe <jane@example.com>".into(),
+                key: "ABCDEF".into(),
+            })
+        );
+        assert_eq!(
+            parse_signature("E\x00\x00SHA256:abc\n").map(|signature| signature.status),
+            Some(SignatureStatus::CannotCheck)
+        );
+        assert_eq!(parse_signature("N\x00\x00\n"), None);
+    }
+
+    #[test]
+    fn test_parse_worktree_list() {
+        let input = "worktree /repo\n\
+            HEAD aaa\n\
+            branch refs/heads/main\n\
+            \n\
+            worktree /repo-feature\n\
+            HEAD bbb\n\
+            branch refs/heads/feature/login\n\
+            locked\n\
+            \n\
+            worktree /tmp/gone\n\
+            HEAD ccc\n\
+            detached\n\
+            prunable gitdir file points to non-existent location\n\
+            \n";
+        let worktrees = parse_worktree_list(input).unwrap();
+        assert_eq!(
+            worktrees,
+            vec![
+                GitWorktree {
+                    path: PathBuf::from("/repo"),
+                    head_sha: Some("aaa".into()),
+                    branch: Some("main".into()),
+                    is_main: true,
+                    is_locked: false,
+                    is_prunable: false,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/repo-feature"),
+                    head_sha: Some("bbb".into()),
+                    branch: Some("feature/login".into()),
+                    is_main: false,
+                    is_locked: true,
+                    is_prunable: false,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/tmp/gone"),
+                    head_sha: Some("ccc".into()),
+                    branch: None,
+                    is_main: false,
+                    is_locked: false,
+                    is_prunable: true,
+                },
+            ]
+        );
+        assert_eq!(parse_worktree_list("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_rebase_todo_contents() {
+        let entry = |action, sha: &str, subject: &str, message: Option<&str>| RebaseTodoEntry {
+            action,
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            message: message.map(ToString::to_string),
+        };
+        let todo = [
+            entry(RebaseAction::Pick, "aaa", "first", None),
+            entry(
+                RebaseAction::Reword,
+                "bbb",
+                "second",
+                Some("Second, reworded"),
+            ),
+            entry(RebaseAction::Reword, "ccc", "third", None),
+            entry(RebaseAction::Fixup, "ddd", "fixup! third", None),
+            entry(RebaseAction::Drop, "eee", "wip", None),
+        ];
+        let contents = rebase_todo_contents(&todo, |ix| {
+            PathBuf::from(format!("/repo/.git/message-{ix}"))
+        });
+        assert_eq!(
+            contents,
+            concat!(
+                "pick aaa first\n",
+                "pick bbb second\n",
+                "exec git commit --amend --only --allow-empty --no-verify -F \"/repo/.git/message-1\"\n",
+                "reword ccc third\n",
+                "fixup ddd fixup! third\n",
+                "drop eee wip\n",
+            )
+        );
+    }
+
+    #[gpui::test]
+    async fn test_rebase_stopped_on_conflict(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let base = repo.commit_file("file", "first", "First").await;
+        repo.commit_file("file", "second", "Second").await;
+        repo.commit_file("file", "third", "Third").await;
+
+        // Replaying the third commit before the second one conflicts.
+        let mut todo = repo.rebase_todo(base.clone()).await.unwrap();
+        todo.reverse();
+        repo.rebase_start(base, todo, env.clone()).await.unwrap();
+        let state = repo.rebase_state().await.expect("rebase should be stopped");
+
+        // Continuing without resolving the conflict fails, and leaves the rebase where it was.
+        repo.rebase_continue(env.clone()).await.unwrap_err();
+        assert_eq!(repo.rebase_state().await, Some(state));
+
+        repo.rebase_abort(env).await.unwrap();
+        assert_eq!(repo.rebase_state().await, None);
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let read = |path: &str| std::fs::read_to_string(repo_dir.path().join(path)).unwrap();
+
+        let base = repo.commit_file("file", "one", "Base").await;
+        let feature = repo.commit_file("other", "feature", "Feature").await;
+        repo.reset(base.clone(), ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        assert!(!repo_dir.path().join("other").exists());
+
+        repo.cherry_pick(feature.clone(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(read("other"), "feature");
+        assert_eq!(repo.show("HEAD~1".into()).await.unwrap().sha, base);
+        assert!(repo.merge_head_shas().is_empty());
+
+        let picked = repo.head_sha().unwrap();
+        repo.revert(picked.clone(), env.clone()).await.unwrap();
+        assert!(!repo_dir.path().join("other").exists());
+        assert_eq!(repo.show("HEAD~1".into()).await.unwrap().sha, picked);
+        assert!(repo.merge_head_shas().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert_stop_on_conflict(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let base = repo.commit_file("file", "one", "Base").await;
+        let two = repo.commit_file("file", "two", "Two").await;
+        repo.commit_file("file", "three", "Three").await;
+
+        // Reverting "two" conflicts with "three", which changed the same line.
+        let head = repo.head_sha().unwrap();
+        repo.revert(two.clone(), env.clone()).await.unwrap();
+        assert_eq!(repo.head_sha().unwrap(), head);
+        assert_eq!(repo.merge_head_shas(), [two.clone()]);
+        repo.reset(head, ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        assert!(repo.merge_head_shas().is_empty());
+
+        // Picking "two" onto a different change of the same line conflicts, too.
+        repo.reset(base, ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        let other = repo.commit_file("file", "other", "Other").await;
+        repo.cherry_pick(two.clone(), env.clone()).await.unwrap();
+        assert_eq!(repo.head_sha().unwrap(), other);
+        assert_eq!(repo.merge_head_shas(), [two.clone()]);
+        let status = repo.status(&[]).await.unwrap();
+        assert!(
+            status
+                .entries
+                .iter()
+                .any(|(path, status)| *path == RepoPath::from_str("file") && status.is_conflicted())
+        );
+
+        // Another pick fails while this one is still in progress.
+        repo.cherry_pick(two, env.clone()).await.unwrap_err();
+
+        // Picking a commit that doesn't exist fails, too.
+        repo.reset(other, ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        repo.cherry_pick("does-not-exist".into(), env)
+            .await
+            .unwrap_err();
+    }
+
+    #[gpui::test]
+    async fn test_reset_to_commit(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let read = |path: &str| std::fs::read_to_string(repo_dir.path().join(path)).unwrap();
+        let file = RepoPath::from_str("file");
+
+        let base = repo.commit_file("file", "one", "Base").await;
+        let two = repo.commit_file("file", "two", "Two").await;
+
+        // A soft reset keeps the changes staged.
+        repo.reset(base.clone(), ResetMode::Soft, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.head_sha().unwrap(), base);
+        assert_eq!(
+            repo.load_index_text(file.clone()).await.as_deref(),
+            Some("two")
+        );
+        assert_eq!(read("file"), "two");
+
+        // A mixed reset unstages them, but leaves them in the working tree.
+        repo.reset(two.clone(), ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        repo.reset(base.clone(), ResetMode::Mixed, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.head_sha().unwrap(), base);
+        assert_eq!(
+            repo.load_index_text(file.clone()).await.as_deref(),
+            Some("one")
+        );
+        assert_eq!(read("file"), "two");
+
+        // A hard reset discards them.
+        repo.reset(base.clone(), ResetMode::Hard, env)
+            .await
+            .unwrap();
+        assert_eq!(repo.head_sha().unwrap(), base);
+        assert_eq!(read("file"), "one");
+    }
+
+    #[gpui::test]
+    async fn test_rename_and_delete_branches(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        let branch_names = async || {
+            let mut names = repo
+                .branches()
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|branch| !branch.is_head)
+                .map(|branch| branch.name.to_string())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        let base = repo.commit_file("file", "one", "Base").await;
+        repo.create_branch("old".into()).await.unwrap();
+        repo.rename_branch("old".into(), "new".into(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(branch_names().await, ["new"]);
+
+        // A merged branch can be deleted without forcing it.
+        repo.delete_branch("new".into(), false, env.clone())
+            .await
+            .unwrap();
+        assert!(branch_names().await.is_empty());
+
+        // An unmerged branch is only deleted when forced.
+        repo.commit_file("file", "two", "Two").await;
+        repo.create_branch("unmerged".into()).await.unwrap();
+        repo.reset(base, ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        repo.delete_branch("unmerged".into(), false, env.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(branch_names().await, ["unmerged"]);
+        repo.delete_branch("unmerged".into(), true, env)
+            .await
+            .unwrap();
+        assert!(branch_names().await.is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_compare_with_branch(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let base = repo.commit_file("file", "base", "Base").await;
+        repo.commit_file("theirs", "theirs", "Theirs").await;
+        repo.create_branch("other".into()).await.unwrap();
+        repo.reset(base, ResetMode::Hard, env).await.unwrap();
+        repo.commit_file("file", "changed", "Change").await;
+        repo.commit_file("ours", "ours", "Ours").await;
+
+        // Only the changes made on HEAD since it diverged from the branch are listed.
+        let diff = repo
+            .compare_with_branch("other".into(), cx.to_async())
+            .await
+            .unwrap();
+        let files = diff
+            .files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.clone(),
+                    file.old_text.as_deref(),
+                    file.new_text.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            [
+                (RepoPath::from_str("file"), Some("base"), Some("changed")),
+                (RepoPath::from_str("ours"), None, Some("ours")),
+            ]
+        );
+
+        repo.compare_with_branch("missing".into(), cx.to_async())
+            .await
+            .unwrap_err();
+    }
+
+    #[gpui::test]
+    async fn test_stash_show_includes_untracked_files(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        repo.commit_file("tracked", "old", "Initial").await;
+        smol::fs::write(repo_dir.path().join("tracked"), "new")
+            .await
+            .unwrap();
+        smol::fs::write(repo_dir.path().join("untracked"), "added")
+            .await
+            .unwrap();
+        repo.stash_push(
+            None,
+            StashOptions {
+                include_untracked: true,
+                staged: false,
+            },
+            env,
+        )
+        .await
+        .unwrap();
+
+        let diff = repo.stash_show(0, cx.to_async()).await.unwrap();
+        let files = diff
+            .files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.clone(),
+                    file.old_text.as_deref(),
+                    file.new_text.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            [
+                (RepoPath::from_str("tracked"), Some("old"), Some("new")),
+                (RepoPath::from_str("untracked"), None, Some("added")),
+            ]
+        );
+    }
+
     impl RealGitRepository {
+        /// Writes `contents` to `path`, then stages and commits it, returning the new HEAD.
+        async fn commit_file(&self, path: &str, contents: &str, message: &str) -> String {
+            smol::fs::write(self.working_directory().unwrap().join(path), contents)
+                .await
+                .unwrap();
+            self.stage_paths(vec![RepoPath::from_str(path)], Arc::new(HashMap::default()))
+                .await
+                .unwrap();
+            self.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                Arc::new(checkpoint_author_envs()),
+            )
+            .await
+            .unwrap();
+            self.head_sha().unwrap()
+        }
+
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<Result<()>> {
             let working_directory = self.working_directory();
@@ -1,13 +1,17 @@
-use crate::{commit_tooltip::CommitTooltip, commit_view::CommitView};
+use crate::{
+    commit_tooltip::CommitTooltip,
+    commit_view::{CommitOperation, CommitView, run_commit_operation},
+    file_revision_view::{FileRevisionView, RevisionBase},
+};
 use editor::{BlameRenderer, Editor};
 use git::{
     blame::{BlameEntry, ParsedCommitMessage},
-    repository::CommitSummary,
+    repository::{CommitSummary, RepoPath, ResetMode},
 };
 use gpui::{
     AnyElement, App, AppContext as _, ClipboardItem, Element as _, Entity, Hsla,
-    InteractiveElement as _, MouseButton, Pixels, StatefulInteractiveElement as _, Styled as _,
-    Subscription, TextStyle, WeakEntity, Window, div,
+    InteractiveElement as _, MouseButton, Pixels, SharedString, StatefulInteractiveElement as _,
+    Styled as _, Subscription, TextStyle, WeakEntity, Window, div,
 };
 use project::{git_store::Repository, project_settings::ProjectSettings};
 use settings::Settings as _;
@@ -65,10 +69,14 @@ impl BlameRenderer for GitBlameRenderer {
                 .on_mouse_down(MouseButton::Right, {
                     let blame_entry = blame_entry.clone();
                     let details = details.clone();
+                    let repository = repository.clone();
+                    let workspace = workspace.clone();
                     move |event, window, cx| {
                         deploy_blame_entry_context_menu(
                             &blame_entry,
                             details.as_ref(),
+                            repository.clone(),
+                            workspace.clone(),
                             editor.clone(),
                             event.position,
                             window,
@@ -189,16 +197,37 @@ impl BlameRenderer for GitBlameRenderer {
 fn deploy_blame_entry_context_menu(
     blame_entry: &BlameEntry,
     details: Option<&ParsedCommitMessage>,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
     editor: Entity<Editor>,
     position: gpui::Point<Pixels>,
     window: &mut Window,
     cx: &mut App,
 ) {
+    // `git blame` records the parent commit and the file's path in it, unless the line was
+    // introduced by the root commit.
+    let previous = blame_entry.previous.as_ref().and_then(|previous| {
+        let (sha, path) = previous.split_once(' ')?;
+        Some((
+            SharedString::from(sha.to_string()),
+            RepoPath::from_str(path),
+        ))
+    });
     let context_menu = ContextMenu::build(window, cx, move |menu, _, _| {
         let sha = format!("{}", blame_entry.sha);
+        let commit_operation = |operation| {
+            let sha = SharedString::from(sha.clone());
+            let repository = repository.clone();
+            move |window: &mut Window, cx: &mut App| {
+                run_commit_operation(operation, sha.clone(), repository.clone(), window, cx)
+            }
+        };
         menu.on_blur_subscription(Subscription::new(|| {}))
-            .entry("Copy commit SHA", None, move |_, cx| {
-                cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+            .entry("Copy commit SHA", None, {
+                let sha = sha.clone();
+                move |_, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(sha.clone()));
+                }
             })
             .when_some(
                 details.and_then(|details| details.permalink.clone()),
@@ -208,6 +237,47 @@ fn deploy_blame_entry_context_menu(
                     })
                 },
             )
+            .when_some(previous, |this, (parent_sha, parent_path)| {
+                let repository = repository.clone();
+                this.entry("Blame at Parent", None, move |window, cx| {
+                    FileRevisionView::open(
+                        repository.clone(),
+                        parent_sha.clone(),
+                        parent_path.clone(),
+                        RevisionBase::Parent(parent_path.clone()),
+                        true,
+                        workspace.clone(),
+                        window,
+                        cx,
+                    )
+                })
+            })
+            .separator()
+            .entry(
+                "Cherry-Pick Commit",
+                None,
+                commit_operation(CommitOperation::CherryPick),
+            )
+            .entry(
+                "Revert Commit",
+                None,
+                commit_operation(CommitOperation::Revert),
+            )
+            .entry(
+                "Soft Reset to Commit",
+                None,
+                commit_operation(CommitOperation::Reset(ResetMode::Soft)),
+            )
+            .entry(
+                "Mixed Reset to Commit",
+                None,
+                commit_operation(CommitOperation::Reset(ResetMode::Mixed)),
+            )
+            .entry(
+                "Hard Reset to Commit…",
+                None,
+                commit_operation(CommitOperation::Reset(ResetMode::Hard)),
+            )
     });
 
     editor.update(cx, move |editor, cx| {
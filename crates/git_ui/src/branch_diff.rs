@@ -0,0 +1,284 @@
+use anyhow::anyhow;
+use editor::{Editor, EditorEvent, MultiBuffer};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, SharedString, WeakEntity, Window,
+};
+use language::{Anchor, Capability, OffsetRangeExt as _};
+use multi_buffer::PathKey;
+use project::{Project, git_store::Repository};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+use crate::commit_view::{GitBlob, build_buffer, build_buffer_diff};
+
+/// Shows the changes made on HEAD since it diverged from another branch, as
+/// `git diff <branch>...HEAD` would.
+pub struct BranchDiff {
+    base_branch: SharedString,
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+    is_empty: Option<bool>,
+}
+
+impl BranchDiff {
+    pub fn open(
+        base_branch: SharedString,
+        repo: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        workspace
+            .update(cx, |workspace, cx| {
+                let existing = workspace.active_pane().read(cx).items().find_map(|item| {
+                    item.downcast::<BranchDiff>()
+                        .filter(|view| view.read(cx).base_branch == base_branch)
+                });
+                if let Some(existing) = existing {
+                    workspace.activate_item(&existing, true, true, window, cx);
+                    return;
+                }
+
+                let project = workspace.project().clone();
+                let branch_diff =
+                    cx.new(|cx| BranchDiff::new(base_branch, repo, project, window, cx));
+                workspace.add_item_to_active_pane(Box::new(branch_diff), None, true, window, cx);
+            })
+            .log_err();
+    }
+
+    fn new(
+        base_branch: SharedString,
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let language_registry = project.read(cx).languages().clone();
+        let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadOnly));
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        });
+
+        let first_worktree_id = project
+            .read(cx)
+            .worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).id());
+
+        let branch_diff = repository.update(cx, |repo, _| {
+            repo.compare_with_branch(base_branch.to_string())
+        });
+        cx.spawn(async move |this, cx| {
+            let branch_diff = branch_diff.await??;
+            this.update(cx, |this, cx| {
+                this.is_empty = Some(branch_diff.files.is_empty());
+                cx.notify();
+            })?;
+            for file in branch_diff.files {
+                let is_deleted = file.new_text.is_none();
+                let new_text = file.new_text.unwrap_or_default();
+                let worktree_id = repository
+                    .update(cx, |repository, cx| {
+                        repository
+                            .repo_path_to_project_path(&file.path, cx)
+                            .map(|path| path.worktree_id)
+                            .or(first_worktree_id)
+                    })?
+                    .ok_or_else(|| anyhow!("project has no worktrees"))?;
+                let blob = Arc::new(GitBlob {
+                    path: file.path.clone(),
+                    is_deleted,
+                    worktree_id,
+                }) as Arc<dyn language::File>;
+
+                let buffer = build_buffer(new_text, blob, &language_registry, cx).await?;
+                let buffer_diff =
+                    build_buffer_diff(file.old_text, &buffer, &language_registry, cx).await?;
+
+                this.update(cx, |this, cx| {
+                    this.multibuffer.update(cx, |multibuffer, cx| {
+                        let snapshot = buffer.read(cx).snapshot();
+                        let diff = buffer_diff.read(cx);
+                        let diff_hunk_ranges = diff
+                            .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx)
+                            .map(|diff_hunk| diff_hunk.buffer_range.to_point(&snapshot))
+                            .collect::<Vec<_>>();
+                        multibuffer.set_excerpts_for_path(
+                            PathKey::namespaced(0, file.path.0.clone()),
+                            buffer,
+                            diff_hunk_ranges,
+                            editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                            cx,
+                        );
+                        multibuffer.add_diff(buffer_diff, cx);
+                    });
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        Self {
+            base_branch,
+            editor,
+            multibuffer,
+            is_empty: None,
+        }
+    }
+}
+
+impl EventEmitter<EditorEvent> for BranchDiff {}
+
+impl Focusable for BranchDiff {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for BranchDiff {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        Label::new(format!("Changes since {}", self.base_branch))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "Changes on HEAD since it diverged from {}",
+                self.base_branch
+            )
+            .into(),
+        )
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Branch Diff Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for BranchDiff {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.is_empty == Some(true) {
+            return v_flex()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .bg(cx.theme().colors().editor_background)
+                .child(
+                    Label::new(format!("No changes since {}", self.base_branch))
+                        .color(Color::Muted),
+                )
+                .into_any_element();
+        }
+        div()
+            .size_full()
+            .child(self.editor.clone())
+            .into_any_element()
+    }
+}
@@ -3,20 +3,23 @@ use fuzzy::StringMatchCandidate;
 
 use git::repository::Branch;
 use gpui::{
-    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, Render, SharedString, Styled,
-    Subscription, Task, Window, rems,
+    App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, PromptLevel,
+    Render, SharedString, Styled, Subscription, Task, WeakEntity, Window, rems,
 };
 use picker::{Picker, PickerDelegate, PickerEditorPosition};
 use project::git_store::Repository;
 use std::sync::Arc;
 use time::OffsetDateTime;
 use time_format::format_local_timestamp;
-use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
 use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
+use crate::branch_diff::BranchDiff;
+use crate::picker_prompt;
+
 pub fn register(workspace: &mut Workspace) {
     workspace.register_action(open);
     workspace.register_action(switch);
@@ -49,8 +52,16 @@ pub fn open(
 ) {
     let repository = workspace.project().read(cx).active_repository(cx).clone();
     let style = BranchListStyle::Modal;
+    let workspace_handle = workspace.weak_handle();
     workspace.toggle_modal(window, cx, |window, cx| {
-        BranchList::new(repository, style, rems(34.), window, cx)
+        BranchList::new(
+            repository,
+            Some(workspace_handle),
+            style,
+            rems(34.),
+            window,
+            cx,
+        )
     })
 }
 
@@ -60,7 +71,14 @@ pub fn popover(
     cx: &mut App,
 ) -> Entity<BranchList> {
     cx.new(|cx| {
-        let list = BranchList::new(repository, BranchListStyle::Popover, rems(20.), window, cx);
+        let list = BranchList::new(
+            repository,
+            None,
+            BranchListStyle::Popover,
+            rems(20.),
+            window,
+            cx,
+        );
         list.focus_handle(cx).focus(window);
         list
     })
@@ -81,41 +99,22 @@ pub struct BranchList {
 impl BranchList {
     fn new(
         repository: Option<Entity<Repository>>,
+        workspace: Option<WeakEntity<Workspace>>,
         style: BranchListStyle,
         width: Rems,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        let all_branches_request = repository
-            .clone()
-            .map(|repository| repository.update(cx, |repository, _| repository.branches()));
-
-        cx.spawn_in(window, async move |this, cx| {
-            let mut all_branches = all_branches_request
-                .context("No active repository")?
-                .await??;
-
-            all_branches.sort_by_key(|branch| {
-                branch
-                    .most_recent_commit
-                    .as_ref()
-                    .map(|commit| 0 - commit.commit_timestamp)
-            });
-
-            this.update_in(cx, |this, window, cx| {
-                this.picker.update(cx, |picker, cx| {
-                    picker.delegate.all_branches = Some(all_branches);
-                    picker.refresh(window, cx);
-                })
-            })?;
+        let delegate = BranchListDelegate::new(repository.clone(), workspace, style);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
 
-            anyhow::Ok(())
+        let weak_picker = picker.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let repository = repository.context("No active repository")?;
+            BranchListDelegate::reload(weak_picker, repository, cx).await
         })
         .detach_and_log_err(cx);
 
-        let delegate = BranchListDelegate::new(repository.clone(), style);
-        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
-
         let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
             cx.emit(DismissEvent);
         });
@@ -173,25 +172,203 @@ pub struct BranchListDelegate {
     matches: Vec<BranchEntry>,
     all_branches: Option<Vec<Branch>>,
     repo: Option<Entity<Repository>>,
+    workspace: Option<WeakEntity<Workspace>>,
     style: BranchListStyle,
     selected_index: usize,
     last_query: String,
     modifiers: Modifiers,
+    /// The branch being renamed, while its new name is typed into the query.
+    renaming: Option<SharedString>,
 }
 
 impl BranchListDelegate {
-    fn new(repo: Option<Entity<Repository>>, style: BranchListStyle) -> Self {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: Option<WeakEntity<Workspace>>,
+        style: BranchListStyle,
+    ) -> Self {
         Self {
             matches: vec![],
             repo,
+            workspace,
             style,
             all_branches: None,
             selected_index: 0,
             last_query: Default::default(),
             modifiers: Default::default(),
+            renaming: None,
         }
     }
 
+    async fn reload(
+        picker: WeakEntity<Picker<Self>>,
+        repo: Entity<Repository>,
+        cx: &mut AsyncWindowContext,
+    ) -> anyhow::Result<()> {
+        let mut all_branches = repo.update(cx, |repo, _| repo.branches())?.await??;
+
+        all_branches.sort_by_key(|branch| {
+            branch
+                .most_recent_commit
+                .as_ref()
+                .map(|commit| 0 - commit.commit_timestamp)
+        });
+
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.all_branches = Some(all_branches);
+            picker.refresh(window, cx);
+        })
+    }
+
+    fn selected_branch(&self) -> Option<&Branch> {
+        self.matches
+            .get(self.selected_index)
+            .filter(|entry| !entry.is_new)
+            .map(|entry| &entry.branch)
+    }
+
+    fn start_rename(
+        &mut self,
+        branch: &Branch,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.renaming = Some(branch.name.clone());
+        let name = branch.name.clone();
+        cx.defer_in(window, move |picker, window, cx| {
+            picker.refresh_placeholder(window, cx);
+            picker.set_query(name.as_ref(), window, cx);
+        });
+    }
+
+    fn rename_branch(
+        &self,
+        old_name: SharedString,
+        new_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| {
+                repo.rename_branch(old_name.to_string(), new_name.to_string())
+            })?
+            .await?
+        })
+        .detach_and_prompt_err("Failed to rename branch", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_branch(&self, branch: Branch, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        if branch.is_head {
+            return;
+        }
+        cx.spawn_in(window, async move |picker, cx| {
+            let result = repo
+                .update(cx, |repo, _| {
+                    repo.delete_branch(branch.name.to_string(), false)
+                })?
+                .await?;
+            // Git refuses to delete branches with commits that would be lost.
+            if let Err(error) = result {
+                let answer = cx
+                    .update(|window, cx| {
+                        window.prompt(
+                            PromptLevel::Warning,
+                            &format!("Delete unmerged branch {}?", branch.name),
+                            Some(&error.to_string()),
+                            &["Delete", "Cancel"],
+                            cx,
+                        )
+                    })?
+                    .await?;
+                if answer != 0 {
+                    return Ok(());
+                }
+                repo.update(cx, |repo, _| {
+                    repo.delete_branch(branch.name.to_string(), true)
+                })?
+                .await??;
+            }
+            Self::reload(picker, repo, cx).await
+        })
+        .detach_and_prompt_err("Failed to delete branch", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn set_upstream(&self, branch: Branch, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let (Some(repo), Some(workspace)) = (self.repo.clone(), self.workspace.clone()) else {
+            return;
+        };
+        let all_branches = self.all_branches.clone().unwrap_or_default();
+        cx.spawn_in(window, async move |_, cx| {
+            let remotes = repo.update(cx, |repo, _| repo.get_remotes(None))?.await??;
+            let mut options: Vec<SharedString> = all_branches
+                .iter()
+                .filter(|candidate| {
+                    remotes.iter().any(|remote| {
+                        candidate
+                            .name
+                            .strip_prefix(remote.name.as_ref())
+                            .is_some_and(|rest| rest.starts_with('/'))
+                    })
+                })
+                .map(|candidate| candidate.name.clone())
+                .collect();
+            let has_upstream = branch.upstream.is_some();
+            if has_upstream {
+                options.insert(0, "Unset upstream".into());
+            }
+            let selection = cx
+                .update(|window, cx| {
+                    picker_prompt::prompt(
+                        &format!("Set the upstream of {}", branch.name),
+                        options.clone(),
+                        workspace,
+                        window,
+                        cx,
+                    )
+                })?
+                .await;
+            let Some(selection) = selection else {
+                return Ok(());
+            };
+            let upstream = if has_upstream && selection == 0 {
+                None
+            } else {
+                Some(options[selection].to_string())
+            };
+            repo.update(cx, |repo, _| {
+                repo.set_upstream(branch.name.to_string(), upstream)
+            })?
+            .await?
+        })
+        .detach_and_prompt_err("Failed to set upstream", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn compare_with_branch(
+        &self,
+        branch: Branch,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let (Some(repo), Some(workspace)) = (self.repo.clone(), self.workspace.clone()) else {
+            return;
+        };
+        BranchDiff::open(branch.name, repo, workspace, window, cx);
+        cx.emit(DismissEvent);
+    }
+
     fn create_branch(
         &self,
         new_branch_name: SharedString,
@@ -224,7 +401,10 @@ impl PickerDelegate for BranchListDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Select branch...".into()
+        match &self.renaming {
+            Some(name) => format!("New name for {name}...").into(),
+            None => "Select branch...".into(),
+        }
     }
 
     fn editor_position(&self) -> PickerEditorPosition {
@@ -257,6 +437,26 @@ impl PickerDelegate for BranchListDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Task<()> {
+        if let Some(old_name) = &self.renaming {
+            let new_name = query.trim();
+            self.matches = if new_name.is_empty() || new_name == old_name.as_ref() {
+                Vec::new()
+            } else {
+                vec![BranchEntry {
+                    branch: Branch {
+                        name: new_name.to_string().into(),
+                        is_head: false,
+                        upstream: None,
+                        most_recent_commit: None,
+                    },
+                    positions: Vec::new(),
+                    is_new: true,
+                }]
+            };
+            self.selected_index = 0;
+            self.last_query = query;
+            return Task::ready(());
+        }
         let Some(all_branches) = self.all_branches.clone() else {
             return Task::ready(());
         };
@@ -330,14 +530,22 @@ impl PickerDelegate for BranchListDelegate {
         })
     }
 
-    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
         let Some(entry) = self.matches.get(self.selected_index()) else {
             return;
         };
+        if let Some(old_name) = self.renaming.clone() {
+            self.rename_branch(old_name, entry.branch.name.clone(), window, cx);
+            return;
+        }
         if entry.is_new {
             self.create_branch(entry.branch.name.clone(), window, cx);
             return;
         }
+        if secondary && self.style == BranchListStyle::Modal {
+            self.delete_branch(entry.branch.clone(), window, cx);
+            return;
+        }
 
         let current_branch = self.repo.as_ref().map(|repo| {
             repo.update(cx, |repo, _| {
@@ -391,7 +599,82 @@ impl PickerDelegate for BranchListDelegate {
     }
 
     fn render_header(&self, _: &mut Window, _cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
-        None
+        let old_name = self.renaming.as_ref()?;
+        Some(
+            h_flex()
+                .w_full()
+                .px_3()
+                .pt_2()
+                .child(
+                    Label::new(format!("Rename branch \"{old_name}\""))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any(),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        if self.style != BranchListStyle::Modal || self.renaming.is_some() {
+            return None;
+        }
+        let selected_branch = self.selected_branch();
+        let can_modify = selected_branch.is_some();
+        let is_other_branch = selected_branch.is_some_and(|branch| !branch.is_head);
+        Some(
+            h_flex()
+                .w_full()
+                .p_2()
+                .gap_2()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("compare", "Compare")
+                        .disabled(!is_other_branch)
+                        .tooltip(Tooltip::text(
+                            "Show the changes on HEAD since it diverged from this branch",
+                        ))
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(branch) = picker.delegate.selected_branch().cloned() {
+                                picker.delegate.compare_with_branch(branch, window, cx);
+                            }
+                        })),
+                )
+                .child(
+                    Button::new("upstream", "Upstream…")
+                        .disabled(!can_modify)
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(branch) = picker.delegate.selected_branch().cloned() {
+                                picker.delegate.set_upstream(branch, window, cx);
+                            }
+                        })),
+                )
+                .child(
+                    Button::new("rename", "Rename")
+                        .disabled(!can_modify)
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(branch) = picker.delegate.selected_branch().cloned() {
+                                picker.delegate.start_rename(&branch, window, cx);
+                            }
+                        })),
+                )
+                .child(
+                    Button::new("delete", "Delete")
+                        .disabled(!is_other_branch)
+                        .key_binding(KeyBinding::for_action(&menu::SecondaryConfirm, window, cx))
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(branch) = picker.delegate.selected_branch().cloned() {
+                                picker.delegate.delete_branch(branch, window, cx);
+                            }
+                        })),
+                )
+                .into_any(),
+        )
     }
 
     fn render_match(
@@ -440,7 +723,16 @@ impl PickerDelegate for BranchListDelegate {
                                 .gap_2()
                                 .justify_between()
                                 .child(div().flex_shrink().overflow_x_hidden().child(
-                                    if entry.is_new {
+                                    if let Some(old_name) =
+                                        self.renaming.as_ref().filter(|_| entry.is_new)
+                                    {
+                                        Label::new(format!(
+                                            "Rename \"{}\" to \"{}\"",
+                                            old_name, entry.branch.name
+                                        ))
+                                        .single_line()
+                                        .into_any_element()
+                                    } else if entry.is_new {
                                         Label::new(format!(
                                             "Create branch \"{}\"…",
                                             entry.branch.name
@@ -467,7 +759,9 @@ impl PickerDelegate for BranchListDelegate {
                         )
                         .when(self.style == BranchListStyle::Modal, |el| {
                             el.child(div().max_w_96().child({
-                                let message = if entry.is_new {
+                                let message = if self.renaming.is_some() {
+                                    String::new()
+                                } else if entry.is_new {
                                     if let Some(current_branch) =
                                         self.repo.as_ref().and_then(|repo| {
                                             repo.read(cx).branch.as_ref().map(|b| b.name.clone())
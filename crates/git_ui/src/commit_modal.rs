@@ -418,7 +418,10 @@ impl CommitModal {
                                     this.git_panel.update(cx, |git_panel, cx| {
                                         git_panel.set_amend_pending(false, cx);
                                         git_panel.commit_changes(
-                                            CommitOptions { amend: true },
+                                            CommitOptions {
+                                                amend: true,
+                                                ..Default::default()
+                                            },
                                             window,
                                             cx,
                                         );
@@ -444,7 +447,7 @@ impl CommitModal {
                                     telemetry::event!("Git Committed", source = "Git Modal");
                                     this.git_panel.update(cx, |git_panel, cx| {
                                         git_panel.commit_changes(
-                                            CommitOptions { amend: false },
+                                            CommitOptions::default(),
                                             window,
                                             cx,
                                         )
@@ -504,7 +507,7 @@ impl CommitModal {
                                             );
                                             this.git_panel.update(cx, |git_panel, cx| {
                                                 git_panel.commit_changes(
-                                                    CommitOptions { amend: false },
+                                                    CommitOptions::default(),
                                                     window,
                                                     cx,
                                                 )
@@ -533,7 +536,7 @@ impl CommitModal {
         }
         telemetry::event!("Git Committed", source = "Git Modal");
         self.git_panel.update(cx, |git_panel, cx| {
-            git_panel.commit_changes(CommitOptions { amend: false }, window, cx)
+            git_panel.commit_changes(CommitOptions::default(), window, cx)
         });
         cx.emit(DismissEvent);
     }
@@ -558,7 +561,14 @@ impl CommitModal {
             telemetry::event!("Git Amended", source = "Git Modal");
             self.git_panel.update(cx, |git_panel, cx| {
                 git_panel.set_amend_pending(false, cx);
-                git_panel.commit_changes(CommitOptions { amend: true }, window, cx);
+                git_panel.commit_changes(
+                    CommitOptions {
+                        amend: true,
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                );
             });
             cx.emit(DismissEvent);
         }
@@ -1,10 +1,17 @@
 use anyhow::{Result, anyhow};
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::{Editor, EditorEvent, MultiBuffer};
-use git::repository::{CommitDetails, CommitDiff, CommitSummary, RepoPath};
+use futures::channel::oneshot;
+use git::{
+    SHORT_SHA_LENGTH,
+    repository::{
+        CommitDetails, CommitDiff, CommitSignature, CommitSummary, RepoPath, ResetMode, StashEntry,
+    },
+};
 use gpui::{
-    AnyElement, AnyView, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter,
-    FocusHandle, Focusable, IntoElement, Render, WeakEntity, Window,
+    Action as _, AnyElement, AnyView, App, AppContext as _, AsyncApp, Context, Corner, Entity,
+    EventEmitter, FocusHandle, Focusable, IntoElement, PromptLevel, Render, SharedString,
+    WeakEntity, Window, actions,
 };
 use language::{
     Anchor, Buffer, Capability, DiskState, File, LanguageRegistry, LineEnding, OffsetRangeExt as _,
@@ -19,24 +26,100 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use ui::{Color, Icon, IconName, Label, LabelCommon as _};
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
 use util::{ResultExt, truncate_and_trailoff};
 use workspace::{
     Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
     item::{BreadcrumbText, ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr as _,
     searchable::SearchableItemHandle,
 };
 
+actions!(
+    commit_view,
+    [
+        CherryPick,
+        Revert,
+        SoftResetToCommit,
+        MixedResetToCommit,
+        HardResetToCommit
+    ]
+);
+
 pub struct CommitView {
     commit: CommitDetails,
+    /// Loaded after the view opens, since verifying it shells out to git.
+    signature: Option<CommitSignature>,
     editor: Entity<Editor>,
     multibuffer: Entity<MultiBuffer>,
+    repository: Entity<Repository>,
+    /// Stash entries aren't part of the branch history, so they can't be picked or reset to.
+    is_stash: bool,
 }
 
-struct GitBlob {
-    path: RepoPath,
-    worktree_id: WorktreeId,
-    is_deleted: bool,
+/// An operation that applies or moves to a commit from the history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CommitOperation {
+    CherryPick,
+    Revert,
+    Reset(ResetMode),
+}
+
+impl CommitOperation {
+    fn error_message(&self) -> &'static str {
+        match self {
+            CommitOperation::CherryPick => "Failed to cherry-pick commit",
+            CommitOperation::Revert => "Failed to revert commit",
+            CommitOperation::Reset(_) => "Failed to reset to commit",
+        }
+    }
+}
+
+/// Cherry-picks, reverts or resets to `sha`, asking for confirmation before a hard reset.
+///
+/// Conflicts are left in the working tree for the git panel to show, as with a merge.
+pub(crate) fn run_commit_operation(
+    operation: CommitOperation,
+    sha: SharedString,
+    repository: Entity<Repository>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let prompt = (operation == CommitOperation::Reset(ResetMode::Hard)).then(|| {
+        let short_sha = sha.get(..SHORT_SHA_LENGTH).unwrap_or(&sha);
+        window.prompt(
+            PromptLevel::Warning,
+            &format!("Reset to {short_sha} and discard all uncommitted changes?"),
+            Some("This cannot be undone."),
+            &["Reset", "Cancel"],
+            cx,
+        )
+    });
+
+    window
+        .spawn(cx, async move |cx| {
+            if let Some(prompt) = prompt {
+                if prompt.await? != 0 {
+                    return Ok(());
+                }
+            }
+            let sha = sha.to_string();
+            let job = repository.update(cx, |repository, cx| match operation {
+                CommitOperation::CherryPick => repository.cherry_pick(sha, cx),
+                CommitOperation::Revert => repository.revert(sha, cx),
+                CommitOperation::Reset(mode) => repository.reset(sha, mode, cx),
+            })?;
+            job.await?
+        })
+        .detach_and_prompt_err(operation.error_message(), window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+pub(crate) struct GitBlob {
+    pub(crate) path: RepoPath,
+    pub(crate) worktree_id: WorktreeId,
+    pub(crate) is_deleted: bool,
 }
 
 struct CommitMetadataFile {
@@ -58,9 +141,39 @@ impl CommitView {
         let commit_diff = repo
             .update(cx, |repo, _| repo.load_commit_diff(commit.sha.to_string()))
             .ok();
-        let commit_details = repo
-            .update(cx, |repo, _| repo.show(commit.sha.to_string()))
-            .ok();
+        Self::open_with_diff(commit.sha, commit_diff, false, repo, workspace, window, cx);
+    }
+
+    /// Opens the changes recorded in a stash entry, relative to the commit it was created on.
+    pub fn open_stash(
+        stash: &StashEntry,
+        repo: WeakEntity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let stash_diff = repo.update(cx, |repo, _| repo.stash_show(stash.index)).ok();
+        Self::open_with_diff(
+            stash.sha.clone(),
+            stash_diff,
+            true,
+            repo,
+            workspace,
+            window,
+            cx,
+        );
+    }
+
+    fn open_with_diff(
+        sha: SharedString,
+        commit_diff: Option<oneshot::Receiver<Result<CommitDiff>>>,
+        is_stash: bool,
+        repo: WeakEntity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let commit_details = repo.update(cx, |repo, _| repo.show(sha.to_string())).ok();
 
         window
             .spawn(cx, async move |cx| {
@@ -76,6 +189,7 @@ impl CommitView {
                             CommitView::new(
                                 commit_details,
                                 commit_diff,
+                                is_stash,
                                 repo,
                                 project.clone(),
                                 window,
@@ -87,8 +201,7 @@ impl CommitView {
                         pane.update(cx, |pane, cx| {
                             let ix = pane.items().position(|item| {
                                 let commit_view = item.downcast::<CommitView>();
-                                commit_view
-                                    .map_or(false, |view| view.read(cx).commit.sha == commit.sha)
+                                commit_view.map_or(false, |view| view.read(cx).commit.sha == sha)
                             });
                             if let Some(ix) = ix {
                                 pane.activate_item(ix, true, true, window, cx);
@@ -106,6 +219,7 @@ impl CommitView {
     fn new(
         commit: CommitDetails,
         commit_diff: CommitDiff,
+        is_stash: bool,
         repository: Entity<Repository>,
         project: Entity<Project>,
         window: &mut Window,
@@ -160,59 +274,149 @@ impl CommitView {
             });
         }
 
-        cx.spawn(async move |this, mut cx| {
-            for file in commit_diff.files {
-                let is_deleted = file.new_text.is_none();
-                let new_text = file.new_text.unwrap_or_default();
-                let old_text = file.old_text;
-                let worktree_id = repository
-                    .update(cx, |repository, cx| {
-                        repository
-                            .repo_path_to_project_path(&file.path, cx)
-                            .map(|path| path.worktree_id)
-                            .or(first_worktree_id)
-                    })?
-                    .ok_or_else(|| anyhow!("project has no worktrees"))?;
-                let file = Arc::new(GitBlob {
-                    path: file.path.clone(),
-                    is_deleted,
-                    worktree_id,
-                }) as Arc<dyn language::File>;
-
-                let buffer = build_buffer(new_text, file, &language_registry, &mut cx).await?;
-                let buffer_diff =
-                    build_buffer_diff(old_text, &buffer, &language_registry, &mut cx).await?;
-
-                this.update(cx, |this, cx| {
-                    this.multibuffer.update(cx, |multibuffer, cx| {
-                        let snapshot = buffer.read(cx).snapshot();
-                        let diff = buffer_diff.read(cx);
-                        let diff_hunk_ranges = diff
-                            .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx)
-                            .map(|diff_hunk| diff_hunk.buffer_range.to_point(&snapshot))
-                            .collect::<Vec<_>>();
-                        let path = snapshot.file().unwrap().path().clone();
-                        let _is_newly_added = multibuffer.set_excerpts_for_path(
-                            PathKey::namespaced(FILE_NAMESPACE, path),
-                            buffer,
-                            diff_hunk_ranges,
-                            editor::DEFAULT_MULTIBUFFER_CONTEXT,
-                            cx,
-                        );
-                        multibuffer.add_diff(buffer_diff, cx);
-                    });
-                })?;
+        cx.spawn({
+            let repository = repository.clone();
+            async move |this, mut cx| {
+                for file in commit_diff.files {
+                    let is_deleted = file.new_text.is_none();
+                    let new_text = file.new_text.unwrap_or_default();
+                    let old_text = file.old_text;
+                    let worktree_id = repository
+                        .update(cx, |repository, cx| {
+                            repository
+                                .repo_path_to_project_path(&file.path, cx)
+                                .map(|path| path.worktree_id)
+                                .or(first_worktree_id)
+                        })?
+                        .ok_or_else(|| anyhow!("project has no worktrees"))?;
+                    let file = Arc::new(GitBlob {
+                        path: file.path.clone(),
+                        is_deleted,
+                        worktree_id,
+                    }) as Arc<dyn language::File>;
+
+                    let buffer = build_buffer(new_text, file, &language_registry, &mut cx).await?;
+                    let buffer_diff =
+                        build_buffer_diff(old_text, &buffer, &language_registry, &mut cx).await?;
+
+                    this.update(cx, |this, cx| {
+                        this.multibuffer.update(cx, |multibuffer, cx| {
+                            let snapshot = buffer.read(cx).snapshot();
+                            let diff = buffer_diff.read(cx);
+                            let diff_hunk_ranges = diff
+                                .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx)
+                                .map(|diff_hunk| diff_hunk.buffer_range.to_point(&snapshot))
+                                .collect::<Vec<_>>();
+                            let path = snapshot.file().unwrap().path().clone();
+                            let _is_newly_added = multibuffer.set_excerpts_for_path(
+                                PathKey::namespaced(FILE_NAMESPACE, path),
+                                buffer,
+                                diff_hunk_ranges,
+                                editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                                cx,
+                            );
+                            multibuffer.add_diff(buffer_diff, cx);
+                        });
+                    })?;
+                }
+                anyhow::Ok(())
             }
-            anyhow::Ok(())
         })
         .detach();
 
+        if !is_stash {
+            let signature = repository.update(cx, |repository, _| {
+                repository.commit_signature(commit.sha.to_string())
+            });
+            cx.spawn(async move |this, cx| {
+                let signature = signature.await??;
+                this.update(cx, |this, cx| {
+                    this.signature = signature;
+                    cx.notify();
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+
         Self {
             commit,
+            signature: None,
             editor,
             multibuffer,
+            repository,
+            is_stash,
         }
     }
+
+    fn run_operation(
+        &mut self,
+        operation: CommitOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        run_commit_operation(
+            operation,
+            self.commit.sha.clone(),
+            self.repository.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn render_commit_actions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.editor.focus_handle(cx);
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_end()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(self.signature.as_ref().map(render_signature_badge))
+            .child(div().flex_1())
+            .child(
+                Button::new("cherry-pick", "Cherry-Pick")
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Apply this commit on top of HEAD",
+                        &CherryPick,
+                        &focus_handle,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.run_operation(CommitOperation::CherryPick, window, cx)
+                    })),
+            )
+            .child(
+                Button::new("revert", "Revert")
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Create a commit undoing this commit",
+                        &Revert,
+                        &focus_handle,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.run_operation(CommitOperation::Revert, window, cx)
+                    })),
+            )
+            .child(
+                PopoverMenu::new("reset-to-commit")
+                    .trigger(
+                        Button::new("reset", "Reset to Here")
+                            .icon(IconName::ChevronDown)
+                            .icon_position(IconPosition::End)
+                            .icon_size(IconSize::Small),
+                    )
+                    .anchor(Corner::TopRight)
+                    .menu(move |window, cx| {
+                        let focus_handle = focus_handle.clone();
+                        Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                            menu.context(focus_handle)
+                                .action("Soft Reset", SoftResetToCommit.boxed_clone())
+                                .action("Mixed Reset", MixedResetToCommit.boxed_clone())
+                                .action("Hard Reset…", HardResetToCommit.boxed_clone())
+                        }))
+                    }),
+            )
+    }
 }
 
 impl language::File for GitBlob {
@@ -287,7 +491,7 @@ impl language::File for CommitMetadataFile {
     }
 }
 
-async fn build_buffer(
+pub(crate) async fn build_buffer(
     mut text: String,
     blob: Arc<dyn File>,
     language_registry: &Arc<language::LanguageRegistry>,
@@ -320,7 +524,7 @@ async fn build_buffer(
     Ok(buffer)
 }
 
-async fn build_buffer_diff(
+pub(crate) async fn build_buffer_diff(
     mut old_text: Option<String>,
     buffer: &Entity<Buffer>,
     language_registry: &Arc<LanguageRegistry>,
@@ -394,6 +598,34 @@ fn format_commit(commit: &CommitDetails) -> String {
     result
 }
 
+/// Renders a "Verified" or "Unverified" label for a signed commit, with the signer in a tooltip.
+fn render_signature_badge(signature: &CommitSignature) -> impl IntoElement + use<> {
+    let verified = signature.status.is_verified();
+    let (icon, color, label) = if verified {
+        (IconName::Check, Color::Success, "Verified")
+    } else {
+        (IconName::Warning, Color::Warning, "Unverified")
+    };
+    let description = signature.status.description();
+    let signer = signature.signer.clone();
+    let key = signature.key.clone();
+
+    h_flex()
+        .id("commit-signature")
+        .gap_0p5()
+        .child(Icon::new(icon).size(IconSize::XSmall).color(color))
+        .child(Label::new(label).size(LabelSize::Small).color(color))
+        .tooltip(move |window, cx| {
+            let meta = match (signer.is_empty(), key.is_empty()) {
+                (false, false) => format!("{signer}\nKey: {key}"),
+                (false, true) => signer.to_string(),
+                (true, false) => format!("Key: {key}"),
+                (true, true) => return Tooltip::simple(description, cx),
+            };
+            Tooltip::with_meta(description, None, meta, window, cx)
+        })
+}
+
 impl EventEmitter<EditorEvent> for CommitView {}
 
 impl Focusable for CommitView {
@@ -513,7 +745,27 @@ impl Item for CommitView {
 }
 
 impl Render for CommitView {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        self.editor.clone()
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .when(!self.is_stash, |el| {
+                el.on_action(cx.listener(|this, _: &CherryPick, window, cx| {
+                    this.run_operation(CommitOperation::CherryPick, window, cx)
+                }))
+                .on_action(cx.listener(|this, _: &Revert, window, cx| {
+                    this.run_operation(CommitOperation::Revert, window, cx)
+                }))
+                .on_action(cx.listener(|this, _: &SoftResetToCommit, window, cx| {
+                    this.run_operation(CommitOperation::Reset(ResetMode::Soft), window, cx)
+                }))
+                .on_action(cx.listener(|this, _: &MixedResetToCommit, window, cx| {
+                    this.run_operation(CommitOperation::Reset(ResetMode::Mixed), window, cx)
+                }))
+                .on_action(cx.listener(|this, _: &HardResetToCommit, window, cx| {
+                    this.run_operation(CommitOperation::Reset(ResetMode::Hard), window, cx)
+                }))
+                .child(self.render_commit_actions(cx))
+            })
+            .child(div().flex_1().min_h_0().child(self.editor.clone()))
     }
 }
@@ -0,0 +1,707 @@
+use crate::commit_view::build_buffer_diff;
+use anyhow::Result;
+use editor::{Editor, EditorEvent, MultiBuffer, scroll::Autoscroll};
+use git::{
+    conflict::{ConflictRegion, parse_conflicts},
+    repository::{ConflictTexts, RepoPath},
+};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, ParentElement, PromptLevel, Render, SharedString, Styled, Subscription, Task,
+    WeakEntity, Window, actions,
+};
+use language::{Buffer, BufferEvent, Capability, Language};
+use project::{Project, git_store::Repository};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::{Divider, Tooltip, prelude::*};
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+actions!(
+    conflict_view,
+    [
+        AcceptOurs,
+        AcceptTheirs,
+        AcceptBoth,
+        NextConflict,
+        PreviousConflict,
+        MarkResolved
+    ]
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Resolution {
+    Ours,
+    Theirs,
+    Both,
+}
+
+struct ConflictHighlight;
+
+/// Shows both sides of a merge conflict next to their common ancestor, above
+/// the editable merge result.
+pub struct ConflictView {
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    result_buffer: Entity<Buffer>,
+    result_editor: Entity<Editor>,
+    ours_editor: Entity<Editor>,
+    base_editor: Entity<Editor>,
+    theirs_editor: Entity<Editor>,
+    conflicts: Vec<ConflictRegion>,
+    resolved: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ConflictView {
+    pub fn open(
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(project_path) = repository
+            .read(cx)
+            .repo_path_to_project_path(&repo_path, cx)
+        else {
+            return;
+        };
+        let texts = repository.update(cx, |repository, _| {
+            repository.load_conflict_texts(repo_path.clone())
+        });
+
+        window
+            .spawn(cx, async move |cx| {
+                let texts = texts.await??;
+                let (project, open_buffer) = workspace.update(cx, |workspace, cx| {
+                    let project = workspace.project().clone();
+                    let open_buffer =
+                        project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+                    (project, open_buffer)
+                })?;
+                let result_buffer = open_buffer.await?;
+
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let existing = workspace
+                        .active_pane()
+                        .read(cx)
+                        .items()
+                        .filter_map(|item| item.downcast::<ConflictView>())
+                        .find(|view| view.read(cx).result_buffer == result_buffer);
+                    if let Some(existing) = existing {
+                        workspace.activate_item(&existing, true, true, window, cx);
+                        return;
+                    }
+
+                    let view = cx.new(|cx| {
+                        ConflictView::new(
+                            repository,
+                            repo_path,
+                            result_buffer,
+                            texts,
+                            project,
+                            window,
+                            cx,
+                        )
+                    });
+                    workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+                })
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        result_buffer: Entity<Buffer>,
+        texts: ConflictTexts,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let language = result_buffer.read(cx).language().cloned();
+        let language_registry = project.read(cx).languages().clone();
+
+        let base_editor = Self::side_editor(
+            texts.base.clone().unwrap_or_default(),
+            language.clone(),
+            window,
+            cx,
+        );
+        let ours_editor =
+            Self::side_editor(texts.ours.unwrap_or_default(), language.clone(), window, cx);
+        let theirs_editor =
+            Self::side_editor(texts.theirs.unwrap_or_default(), language, window, cx);
+
+        // Show each side as a diff against the common ancestor.
+        for editor in [&ours_editor, &theirs_editor] {
+            let multibuffer = editor.read(cx).buffer().clone();
+            let Some(buffer) = multibuffer.read(cx).as_singleton() else {
+                continue;
+            };
+            let base_text = texts.base.clone();
+            let language_registry = language_registry.clone();
+            cx.spawn(async move |_, cx| {
+                let diff = build_buffer_diff(base_text, &buffer, &language_registry, cx).await?;
+                multibuffer.update(cx, |multibuffer, cx| multibuffer.add_diff(diff, cx))
+            })
+            .detach_and_log_err(cx);
+        }
+
+        let result_editor =
+            cx.new(|cx| Editor::for_buffer(result_buffer.clone(), Some(project), window, cx));
+
+        let subscriptions = vec![
+            cx.subscribe(&result_buffer, |this, _, event: &BufferEvent, cx| {
+                if matches!(event, BufferEvent::Edited | BufferEvent::Reloaded) {
+                    this.refresh_conflicts(cx);
+                }
+            }),
+            cx.subscribe(&result_editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone())
+            }),
+        ];
+
+        let mut this = Self {
+            repository,
+            repo_path,
+            result_buffer,
+            result_editor,
+            ours_editor,
+            base_editor,
+            theirs_editor,
+            conflicts: Vec::new(),
+            resolved: false,
+            _subscriptions: subscriptions,
+        };
+        this.refresh_conflicts(cx);
+        this
+    }
+
+    fn side_editor(
+        text: String,
+        language: Option<Arc<Language>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local(text, cx);
+            buffer.set_language(language, cx);
+            buffer.set_capability(Capability::ReadOnly, cx);
+            buffer
+        });
+        let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, None, window, cx);
+            editor.set_read_only(true);
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        })
+    }
+
+    fn refresh_conflicts(&mut self, cx: &mut Context<Self>) {
+        self.conflicts = parse_conflicts(&self.result_buffer.read(cx).text());
+        if !self.conflicts.is_empty() {
+            self.resolved = false;
+        }
+
+        let snapshot = self.result_editor.read(cx).buffer().read(cx).snapshot(cx);
+        let ranges = self
+            .conflicts
+            .iter()
+            .map(|conflict| {
+                snapshot.anchor_after(conflict.range.start)
+                    ..snapshot.anchor_before(conflict.range.end)
+            })
+            .collect::<Vec<_>>();
+        self.result_editor.update(cx, |editor, cx| {
+            editor.highlight_background::<ConflictHighlight>(
+                &ranges,
+                |colors| colors.version_control_conflict.opacity(0.15),
+                cx,
+            );
+        });
+        cx.notify();
+    }
+
+    fn cursor_offset(&self, cx: &mut App) -> usize {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.selections.newest::<usize>(cx).head()
+        })
+    }
+
+    /// The conflict containing the cursor, or the first one after it.
+    fn current_conflict_ix(&self, cx: &mut App) -> Option<usize> {
+        let cursor = self.cursor_offset(cx);
+        self.conflicts
+            .iter()
+            .position(|conflict| conflict.range.end > cursor)
+            .or_else(|| self.conflicts.len().checked_sub(1))
+    }
+
+    fn resolve_conflict(
+        &mut self,
+        ix: usize,
+        resolution: Resolution,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(conflict) = self.conflicts.get(ix).cloned() else {
+            return;
+        };
+        self.result_buffer.update(cx, |buffer, cx| {
+            let side_text = |range| buffer.text_for_range(range).collect::<String>();
+            let replacement = match resolution {
+                Resolution::Ours => side_text(conflict.ours.clone()),
+                Resolution::Theirs => side_text(conflict.theirs.clone()),
+                Resolution::Both => {
+                    side_text(conflict.ours.clone()) + &side_text(conflict.theirs.clone())
+                }
+            };
+            buffer.edit([(conflict.range.clone(), replacement)], None, cx);
+        });
+        window.focus(&self.result_editor.focus_handle(cx));
+    }
+
+    fn resolve_current(
+        &mut self,
+        resolution: Resolution,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.current_conflict_ix(cx) {
+            self.resolve_conflict(ix, resolution, window, cx);
+        }
+    }
+
+    fn accept_ours(&mut self, _: &AcceptOurs, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Ours, window, cx);
+    }
+
+    fn accept_theirs(&mut self, _: &AcceptTheirs, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Theirs, window, cx);
+    }
+
+    fn accept_both(&mut self, _: &AcceptBoth, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Both, window, cx);
+    }
+
+    fn go_to_conflict(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(conflict) = self.conflicts.get(ix) else {
+            return;
+        };
+        let offset = conflict.range.start;
+        self.result_editor.update(cx, |editor, cx| {
+            editor.change_selections(Some(Autoscroll::center()), window, cx, |selections| {
+                selections.select_ranges([offset..offset])
+            });
+        });
+        window.focus(&self.result_editor.focus_handle(cx));
+    }
+
+    fn next_conflict(&mut self, _: &NextConflict, window: &mut Window, cx: &mut Context<Self>) {
+        let cursor = self.cursor_offset(cx);
+        let ix = self
+            .conflicts
+            .iter()
+            .position(|conflict| conflict.range.start > cursor)
+            .unwrap_or(0);
+        self.go_to_conflict(ix, window, cx);
+    }
+
+    fn previous_conflict(
+        &mut self,
+        _: &PreviousConflict,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cursor = self.cursor_offset(cx);
+        let ix = self
+            .conflicts
+            .iter()
+            .rposition(|conflict| conflict.range.end <= cursor)
+            .or_else(|| self.conflicts.len().checked_sub(1));
+        if let Some(ix) = ix {
+            self.go_to_conflict(ix, window, cx);
+        }
+    }
+
+    fn mark_resolved(&mut self, _: &MarkResolved, window: &mut Window, cx: &mut Context<Self>) {
+        let prompt = (!self.conflicts.is_empty()).then(|| {
+            window.prompt(
+                PromptLevel::Warning,
+                &format!(
+                    "{} still contains {} conflict{}. Mark it as resolved anyway?",
+                    self.repo_path.display(),
+                    self.conflicts.len(),
+                    if self.conflicts.len() == 1 { "" } else { "s" }
+                ),
+                None,
+                &["Mark as Resolved", "Cancel"],
+                cx,
+            )
+        });
+        let repository = self.repository.clone();
+        let repo_path = self.repo_path.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            if let Some(prompt) = prompt {
+                if prompt.await? != 0 {
+                    return Ok(());
+                }
+            }
+            // Staging saves the buffer before adding it to the index.
+            repository
+                .update(cx, |repository, cx| {
+                    repository.stage_entries(vec![repo_path], cx)
+                })?
+                .await?;
+            this.update(cx, |this, cx| {
+                this.resolved = true;
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn ours_label(&self) -> SharedString {
+        self.conflicts
+            .first()
+            .map(|conflict| conflict.ours_label.as_str())
+            .filter(|label| !label.is_empty())
+            .map_or("Ours".into(), |label| format!("Ours ({label})").into())
+    }
+
+    fn theirs_label(&self) -> SharedString {
+        self.conflicts
+            .first()
+            .map(|conflict| conflict.theirs_label.as_str())
+            .filter(|label| !label.is_empty())
+            .map_or("Theirs".into(), |label| format!("Theirs ({label})").into())
+    }
+
+    fn render_toolbar(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.result_editor.focus_handle(cx);
+        let has_conflicts = !self.conflicts.is_empty();
+        let status = if self.resolved {
+            Label::new("Resolved").color(Color::Created)
+        } else if has_conflicts {
+            Label::new(format!(
+                "{} conflict{}",
+                self.conflicts.len(),
+                if self.conflicts.len() == 1 { "" } else { "s" }
+            ))
+            .color(Color::Conflict)
+        } else {
+            Label::new("No conflicts remaining").color(Color::Muted)
+        };
+
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(self.repo_path.display().to_string()))
+            .child(status.size(LabelSize::Small))
+            .child(div().flex_1())
+            .child(
+                IconButton::new("previous-conflict", IconName::ArrowUp)
+                    .disabled(!has_conflicts)
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Previous Conflict",
+                        &PreviousConflict,
+                        &focus_handle,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.previous_conflict(&PreviousConflict, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("next-conflict", IconName::ArrowDown)
+                    .disabled(!has_conflicts)
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Next Conflict",
+                        &NextConflict,
+                        &focus_handle,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.next_conflict(&NextConflict, window, cx)
+                    })),
+            )
+            .child(Divider::vertical())
+            .child(
+                Button::new("accept-ours", "Accept Ours")
+                    .disabled(!has_conflicts)
+                    .key_binding(KeyBinding::for_action_in(
+                        &AcceptOurs,
+                        &focus_handle,
+                        window,
+                        cx,
+                    ))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.accept_ours(&AcceptOurs, window, cx)
+                        }),
+                    ),
+            )
+            .child(
+                Button::new("accept-theirs", "Accept Theirs")
+                    .disabled(!has_conflicts)
+                    .key_binding(KeyBinding::for_action_in(
+                        &AcceptTheirs,
+                        &focus_handle,
+                        window,
+                        cx,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.accept_theirs(&AcceptTheirs, window, cx)
+                    })),
+            )
+            .child(
+                Button::new("accept-both", "Accept Both")
+                    .disabled(!has_conflicts)
+                    .key_binding(KeyBinding::for_action_in(
+                        &AcceptBoth,
+                        &focus_handle,
+                        window,
+                        cx,
+                    ))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.accept_both(&AcceptBoth, window, cx)
+                        }),
+                    ),
+            )
+            .child(Divider::vertical())
+            .child(
+                Button::new("mark-resolved", "Mark as Resolved")
+                    .style(ButtonStyle::Filled)
+                    .disabled(self.resolved)
+                    .key_binding(KeyBinding::for_action_in(
+                        &MarkResolved,
+                        &focus_handle,
+                        window,
+                        cx,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.mark_resolved(&MarkResolved, window, cx)
+                    })),
+            )
+    }
+
+    fn render_pane(
+        label: SharedString,
+        editor: &Entity<Editor>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .min_w_0()
+            .h_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_0p5()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(label).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(div().flex_1().min_h_0().child(editor.clone()))
+    }
+}
+
+impl EventEmitter<EditorEvent> for ConflictView {}
+
+impl Focusable for ConflictView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Render for ConflictView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border;
+        v_flex()
+            .key_context("ConflictView")
+            .on_action(cx.listener(Self::accept_ours))
+            .on_action(cx.listener(Self::accept_theirs))
+            .on_action(cx.listener(Self::accept_both))
+            .on_action(cx.listener(Self::next_conflict))
+            .on_action(cx.listener(Self::previous_conflict))
+            .on_action(cx.listener(Self::mark_resolved))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_toolbar(window, cx))
+            .child(
+                h_flex()
+                    .w_full()
+                    .h(relative(0.4))
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(Self::render_pane(self.ours_label(), &self.ours_editor, cx))
+                    .child(
+                        div()
+                            .h_full()
+                            .flex_1()
+                            .min_w_0()
+                            .border_l_1()
+                            .border_r_1()
+                            .border_color(border_color)
+                            .child(Self::render_pane("Base".into(), &self.base_editor, cx)),
+                    )
+                    .child(Self::render_pane(
+                        self.theirs_label(),
+                        &self.theirs_editor,
+                        cx,
+                    )),
+            )
+            .child(Self::render_pane("Result".into(), &self.result_editor, cx))
+    }
+}
+
+impl Item for ConflictView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Conflict))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        let file_name = self
+            .repo_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.repo_path.display().to_string());
+        Label::new(format!("Conflicts: {file_name}"))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("Resolve conflicts in {}", self.repo_path.display()).into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Conflict View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        format: bool,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor.save(format, project, window, cx)
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor.reload(project, window, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.result_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.result_editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.result_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.result_editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
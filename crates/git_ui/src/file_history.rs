@@ -0,0 +1,476 @@
+use crate::{
+    commit_view::CommitView,
+    file_revision_view::{FileRevisionView, RevisionBase},
+};
+use editor::Editor;
+use git::repository::{CommitSummary, FileHistoryEntry, RepoPath};
+use gpui::{
+    App, ClickEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext, Pixels,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use menu::{Confirm, SecondaryConfirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    ProjectPath,
+    git_store::{Repository, RepositoryEvent},
+};
+use theme::ActiveTheme;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{Tooltip, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+};
+
+const PAGE_SIZE: usize = 200;
+const ROW_HEIGHT: Pixels = px(26.);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::FileHistory, window, cx| {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let git_store = workspace.project().read(cx).git_store().clone();
+        let Some((repository, path)) = editor.update(cx, |editor, cx| {
+            let buffer = editor.buffer().read(cx).as_singleton()?;
+            let file = buffer.read(cx).file()?;
+            let project_path = ProjectPath {
+                worktree_id: file.worktree_id(cx),
+                path: file.path().clone(),
+            };
+            git_store
+                .read(cx)
+                .repository_and_path_for_project_path(&project_path, cx)
+        }) else {
+            return;
+        };
+        FileHistoryView::open(repository, path, workspace, window, cx);
+    });
+}
+
+/// Lists the commits that touched a single file, following it across renames.
+pub struct FileHistoryView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    path: RepoPath,
+    entries: Vec<FileHistoryEntry>,
+    has_more: bool,
+    error: Option<SharedString>,
+    selected_index: Option<usize>,
+    head_sha: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    load_task: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl FileHistoryView {
+    pub fn open(
+        repository: Entity<Repository>,
+        path: RepoPath,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<FileHistoryView>())
+            .find(|view| {
+                let view = view.read(cx);
+                view.repository == repository && view.path == path
+            });
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| FileHistoryView::new(repository, path, workspace_handle, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        path: RepoPath,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe(
+            &repository,
+            |this, repository, event: &RepositoryEvent, cx| {
+                if let RepositoryEvent::Updated { .. } = event {
+                    let head_sha = Self::head_sha(&repository, cx);
+                    if head_sha != this.head_sha {
+                        this.head_sha = head_sha;
+                        this.reload(cx);
+                    }
+                }
+            },
+        );
+
+        let head_sha = Self::head_sha(&repository, cx);
+        let mut this = Self {
+            repository,
+            workspace,
+            path,
+            entries: Vec::new(),
+            has_more: true,
+            error: None,
+            selected_index: None,
+            head_sha,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            load_task: None,
+            _subscription: subscription,
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn head_sha(repository: &Entity<Repository>, cx: &App) -> Option<SharedString> {
+        repository
+            .read(cx)
+            .head_commit
+            .as_ref()
+            .map(|commit| commit.sha.clone())
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.selected_index = None;
+        self.has_more = true;
+        self.load_task = None;
+        self.load_more(cx);
+        cx.notify();
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.load_task.is_some() || !self.has_more {
+            return;
+        }
+        let path = self.path.clone();
+        let skip = self.entries.len();
+        let history = self.repository.update(cx, |repository, _| {
+            repository.file_history(path, skip, PAGE_SIZE)
+        });
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let result = history.await;
+            this.update(cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(Ok(entries)) => {
+                        this.error = None;
+                        this.has_more = entries.len() == PAGE_SIZE;
+                        this.entries.extend(entries);
+                    }
+                    Ok(Err(error)) => {
+                        this.error = Some(error.to_string().trim().to_string().into());
+                        this.has_more = false;
+                    }
+                    Err(_) => return,
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = ix.min(self.entries.len() - 1);
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, gpui::ScrollStrategy::Top);
+        if ix + 1 == self.entries.len() {
+            self.load_more(cx);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(self.entries.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_revision(ix, false, window, cx);
+        }
+    }
+
+    fn secondary_confirm(
+        &mut self,
+        _: &SecondaryConfirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.selected_index {
+            self.open_revision(ix, true, window, cx);
+        }
+    }
+
+    /// Opens the file as it was in the selected revision, compared with its parent or, when
+    /// `with_working_copy` is set, with the file as it is now.
+    fn open_revision(
+        &mut self,
+        ix: usize,
+        with_working_copy: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let base = if with_working_copy {
+            RevisionBase::WorkingCopy(self.path.clone())
+        } else {
+            RevisionBase::Parent(entry.parent_path().clone())
+        };
+        FileRevisionView::open(
+            self.repository.clone(),
+            entry.commit.sha.clone(),
+            entry.path.clone(),
+            base,
+            false,
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let commit = CommitSummary {
+            sha: entry.commit.sha.clone(),
+            subject: entry.commit.subject.clone(),
+            commit_timestamp: entry.commit.commit_timestamp,
+            has_parent: !entry.commit.parents.is_empty(),
+        };
+        CommitView::open(
+            commit,
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn render_entry(&self, ix: usize, window: &Window, cx: &Context<Self>) -> AnyElement {
+        let entry = &self.entries[ix];
+        let selected = self.selected_index == Some(ix);
+        let hover_bg = cx.theme().colors().ghost_element_hover;
+        let active_bg = cx.theme().colors().ghost_element_active;
+        let commit_time = OffsetDateTime::from_unix_timestamp(entry.commit.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let relative_time = format_local_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+        let short_sha = entry
+            .commit
+            .sha
+            .get(..git::SHORT_SHA_LENGTH)
+            .unwrap_or(&entry.commit.sha)
+            .to_string();
+        let rename = entry
+            .old_path
+            .as_ref()
+            .map(|old_path| format!("renamed from {}", old_path.display()))
+            .or_else(|| (entry.path != self.path).then(|| format!("as {}", entry.path.display())));
+
+        h_flex()
+            .id(("file-history-entry", ix))
+            .h(ROW_HEIGHT)
+            .w_full()
+            .px_2()
+            .gap_2()
+            .overflow_hidden()
+            .border_1()
+            .border_color(gpui::transparent_black())
+            .when(selected && self.focus_handle.is_focused(window), |el| {
+                el.border_color(cx.theme().colors().border_focused)
+            })
+            .when(selected, |el| el.bg(cx.theme().status().info.alpha(0.08)))
+            .hover(|el| el.bg(hover_bg))
+            .active(|el| el.bg(active_bg))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.selected_index = Some(ix);
+                cx.notify();
+                this.open_revision(ix, false, window, cx);
+            }))
+            .child(
+                Label::new(short_sha)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(
+                div().flex_1().min_w_0().overflow_hidden().child(
+                    Label::new(entry.commit.subject.clone())
+                        .single_line()
+                        .truncate(),
+                ),
+            )
+            .when_some(rename, |el, rename| {
+                el.child(
+                    Label::new(rename)
+                        .size(LabelSize::Small)
+                        .color(Color::Accent)
+                        .single_line(),
+                )
+            })
+            .child(
+                div()
+                    .id(("file-history-author", ix))
+                    .flex_none()
+                    .child(
+                        Label::new(entry.commit.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .single_line(),
+                    )
+                    .tooltip(Tooltip::text(entry.commit.author_email.clone())),
+            )
+            .child(
+                Label::new(relative_time)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .single_line(),
+            )
+            .child(
+                IconButton::new(("compare-working-copy", ix), IconName::Diff)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Compare with Working Copy"))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_revision(ix, true, window, cx);
+                    })),
+            )
+            .child(
+                IconButton::new(("open-commit", ix), IconName::FileGit)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Open Commit"))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_commit(ix, window, cx);
+                    })),
+            )
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for FileHistoryView {}
+
+impl Focusable for FileHistoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for FileHistoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+        let status = if let Some(error) = &self.error {
+            Some(Label::new(error.clone()).color(Color::Error))
+        } else if entry_count == 0 && self.load_task.is_none() {
+            Some(Label::new("No commits touch this file").color(Color::Muted))
+        } else {
+            None
+        };
+
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("GitFileHistory");
+
+        v_flex()
+            .key_context(key_context)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::secondary_confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        Label::new(format!("File History: {}", self.path.display()))
+                            .single_line()
+                            .truncate(),
+                    ),
+            )
+            .when_some(status, |el, status| el.child(div().p_2().child(status)))
+            .child(
+                uniform_list(cx.entity(), "file-history-entries", entry_count, {
+                    move |this, range, window, cx| {
+                        // Load the next page before the user reaches the end of the list.
+                        if range.end + PAGE_SIZE / 4 >= this.entries.len() {
+                            this.load_more(cx);
+                        }
+                        range.map(|ix| this.render_entry(ix, window, cx)).collect()
+                    }
+                })
+                .flex_1()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+    }
+}
+
+impl Item for FileHistoryView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string());
+        Label::new(format!("{file_name} History"))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("File History: {}", self.path.display()).into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Git File History Opened")
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
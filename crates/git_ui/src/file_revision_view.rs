@@ -0,0 +1,337 @@
+use anyhow::{Context as _, anyhow};
+use buffer_diff::BufferDiff;
+use editor::{BlameRevision, Editor, EditorEvent, MultiBuffer};
+use git::{SHORT_SHA_LENGTH, repository::RepoPath};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, SharedString, Task, WeakEntity, Window,
+};
+use language::{Buffer, Capability};
+use project::{Project, git_store::Repository};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::prelude::*;
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr as _,
+    searchable::SearchableItemHandle,
+};
+
+use crate::commit_view::{GitBlob, build_buffer, build_buffer_diff};
+
+/// What a [`FileRevisionView`] compares its revision against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevisionBase {
+    /// The file at this path in the revision's first parent.
+    Parent(RepoPath),
+    /// The current contents of the file at this path, including unsaved edits.
+    WorkingCopy(RepoPath),
+}
+
+/// A read-only buffer holding a file as it was in a single commit, with the differences from
+/// its [`RevisionBase`] expanded inline.
+pub struct FileRevisionView {
+    sha: SharedString,
+    path: RepoPath,
+    base: RevisionBase,
+    editor: Entity<Editor>,
+}
+
+impl FileRevisionView {
+    pub fn open(
+        repository: Entity<Repository>,
+        sha: SharedString,
+        path: RepoPath,
+        base: RevisionBase,
+        show_blame: bool,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(project) = workspace
+            .update(cx, |workspace, cx| {
+                let existing = workspace.active_pane().read(cx).items().find_map(|item| {
+                    item.downcast::<FileRevisionView>().filter(|view| {
+                        let view = view.read(cx);
+                        view.sha == sha && view.path == path && view.base == base
+                    })
+                });
+                if let Some(existing) = existing {
+                    workspace.activate_item(&existing, true, true, window, cx);
+                    return None;
+                }
+                Some(workspace.project().clone())
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        let text = repository.update(cx, |repository, _| {
+            repository.load_text_at_revision(sha.to_string(), path.clone())
+        });
+        let base_text: Task<anyhow::Result<Option<String>>> = match &base {
+            RevisionBase::Parent(parent_path) => {
+                let parent_text = repository.update(cx, |repository, _| {
+                    repository.load_text_at_revision(format!("{sha}^"), parent_path.clone())
+                });
+                // The root commit has no parent, so everything in it shows up as added.
+                cx.background_spawn(async move { Ok(parent_text.await?.ok().flatten()) })
+            }
+            RevisionBase::WorkingCopy(current_path) => {
+                match repository
+                    .read(cx)
+                    .repo_path_to_project_path(current_path, cx)
+                {
+                    Some(project_path) => {
+                        let buffer =
+                            project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+                        cx.spawn(async move |cx| {
+                            let Ok(buffer) = buffer.await else {
+                                return Ok(None);
+                            };
+                            Ok(Some(buffer.read_with(cx, |buffer, _| buffer.text())?))
+                        })
+                    }
+                    None => Task::ready(Ok(None)),
+                }
+            }
+        };
+
+        window
+            .spawn(cx, async move |cx| {
+                let short_sha = sha.get(..SHORT_SHA_LENGTH).unwrap_or(&sha).to_string();
+                let text = text
+                    .await??
+                    .with_context(|| format!("{} does not exist in {short_sha}", path.display()))?;
+                let base_text = base_text.await?;
+
+                let (language_registry, worktree_id) = project.update(cx, |project, cx| {
+                    let worktree_id = repository
+                        .read(cx)
+                        .repo_path_to_project_path(&path, cx)
+                        .map(|project_path| project_path.worktree_id)
+                        .or_else(|| {
+                            project
+                                .worktrees(cx)
+                                .next()
+                                .map(|worktree| worktree.read(cx).id())
+                        });
+                    (project.languages().clone(), worktree_id)
+                })?;
+                let worktree_id = worktree_id.ok_or_else(|| anyhow!("project has no worktrees"))?;
+                let blob = Arc::new(GitBlob {
+                    path: path.clone(),
+                    is_deleted: false,
+                    worktree_id,
+                }) as Arc<dyn language::File>;
+
+                let buffer = build_buffer(text, blob, &language_registry, cx).await?;
+                let buffer_diff =
+                    build_buffer_diff(base_text, &buffer, &language_registry, cx).await?;
+
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let view = cx.new(|cx| {
+                        FileRevisionView::new(
+                            repository,
+                            sha,
+                            path,
+                            base,
+                            buffer,
+                            buffer_diff,
+                            project,
+                            show_blame,
+                            window,
+                            cx,
+                        )
+                    });
+                    workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+                })
+            })
+            .detach_and_prompt_err("Failed to open file revision", window, cx, |_, _, _| None);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        sha: SharedString,
+        path: RepoPath,
+        base: RevisionBase,
+        buffer: Entity<Buffer>,
+        buffer_diff: Entity<BufferDiff>,
+        project: Entity<Project>,
+        show_blame: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        buffer.update(cx, |buffer, cx| {
+            buffer.set_capability(Capability::ReadOnly, cx)
+        });
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(buffer, cx);
+            multibuffer.add_diff(buffer_diff, cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_blame_revision(
+                BlameRevision {
+                    repository,
+                    path: path.clone(),
+                    commit: sha.clone(),
+                },
+                window,
+                cx,
+            );
+            if show_blame && !editor.show_git_blame_gutter() {
+                editor.toggle_git_blame(&git::Blame, window, cx);
+            }
+            editor
+        });
+
+        Self {
+            sha,
+            path,
+            base,
+            editor,
+        }
+    }
+
+    fn title(&self) -> String {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string());
+        let short_sha = self.sha.get(..SHORT_SHA_LENGTH).unwrap_or(&self.sha);
+        format!("{file_name} @ {short_sha}")
+    }
+}
+
+impl EventEmitter<EditorEvent> for FileRevisionView {}
+
+impl Focusable for FileRevisionView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for FileRevisionView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        Label::new(self.title())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        let compared_with = match &self.base {
+            RevisionBase::Parent(_) => "its parent",
+            RevisionBase::WorkingCopy(_) => "the working copy",
+        };
+        Some(
+            format!(
+                "{} at {}, compared with {compared_with}",
+                self.path.display(),
+                self.sha
+            )
+            .into(),
+        )
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("File Revision Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for FileRevisionView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div().size_full().child(self.editor.clone())
+    }
+}
@@ -2,7 +2,9 @@ use crate::askpass_modal::AskPassModal;
 use crate::commit_modal::CommitModal;
 use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
+use crate::conflict_view::ConflictView;
 use crate::git_panel_settings::StatusStyle;
+use crate::log_view::{LogScope, LogView};
 use crate::project_diff::{self, Diff, ProjectDiff};
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::{branch_picker, picker_prompt, render_remote_button};
@@ -19,14 +21,19 @@ use editor::{
     scroll::ScrollbarAutoHide,
 };
 use futures::StreamExt as _;
+use futures::channel::oneshot;
 use git::blame::ParsedCommitMessage;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, PushOptions, Remote,
-    RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    RemoteCommandOutput, ResetMode, StashEntry, StashOptions, Upstream, UpstreamTracking,
+    UpstreamTrackingStatus,
 };
 use git::status::StageStatus;
 use git::{Amend, ToggleStaged, repository::RepoPath, status::FileStatus};
-use git::{ExpandCommitEditor, RestoreTrackedFiles, StageAll, TrashUntrackedFiles, UnstageAll};
+use git::{
+    ExpandCommitEditor, ResolveConflicts, RestoreTrackedFiles, SHORT_SHA_LENGTH, StageAll,
+    StashAll, StashPop, StashStaged, TrashUntrackedFiles, UnstageAll,
+};
 use gpui::{
     Action, Animation, AnimationExt as _, Axis, ClickEvent, Corner, DismissEvent, Entity,
     EventEmitter, FocusHandle, Focusable, KeyContext, ListHorizontalSizingBehavior,
@@ -110,6 +117,7 @@ struct GitMenuState {
     has_staged_changes: bool,
     has_unstaged_changes: bool,
     has_new_changes: bool,
+    has_stashes: bool,
 }
 
 fn git_panel_context_menu(
@@ -155,6 +163,33 @@ fn git_panel_context_menu(
                     menu.disabled_action("Trash Untracked Files", TrashUntrackedFiles.boxed_clone())
                 }
             })
+            .separator()
+            .map(|menu| {
+                if state.has_tracked_changes || state.has_new_changes {
+                    menu.action("Stash All", StashAll.boxed_clone())
+                } else {
+                    menu.disabled_action("Stash All", StashAll.boxed_clone())
+                }
+            })
+            .map(|menu| {
+                if state.has_staged_changes {
+                    menu.action("Stash Staged", StashStaged.boxed_clone())
+                } else {
+                    menu.disabled_action("Stash Staged", StashStaged.boxed_clone())
+                }
+            })
+            .map(|menu| {
+                if state.has_stashes {
+                    menu.action("Pop Latest Stash", StashPop.boxed_clone())
+                } else {
+                    menu.disabled_action("Pop Latest Stash", StashPop.boxed_clone())
+                }
+            })
+            .separator()
+            .action("View History", git::ViewHistory.boxed_clone())
+            .action("Interactive Rebase…", git::InteractiveRebase.boxed_clone())
+            .action("Worktrees…", zed_actions::git::Worktrees.boxed_clone())
+            .action("Tags…", zed_actions::git::Tags.boxed_clone())
     })
 }
 
@@ -186,6 +221,7 @@ enum Section {
     Conflict,
     Tracked,
     New,
+    Stash,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -201,6 +237,7 @@ impl GitHeaderEntry {
             Section::Conflict => repo.has_conflict(&status_entry.repo_path),
             Section::Tracked => !status.is_created(),
             Section::New => status.is_created(),
+            Section::Stash => false,
         }
     }
     pub fn title(&self) -> &'static str {
@@ -208,6 +245,7 @@ impl GitHeaderEntry {
             Section::Conflict => "Conflicts",
             Section::Tracked => "Tracked",
             Section::New => "Untracked",
+            Section::Stash => "Stashes",
         }
     }
 }
@@ -216,6 +254,7 @@ impl GitHeaderEntry {
 enum GitListEntry {
     GitStatusEntry(GitStatusEntry),
     Header(GitHeaderEntry),
+    Stash(StashEntry),
 }
 
 impl GitListEntry {
@@ -225,6 +264,13 @@ impl GitListEntry {
             _ => None,
         }
     }
+
+    fn stash_entry(&self) -> Option<&StashEntry> {
+        match self {
+            GitListEntry::Stash(entry) => Some(entry),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -350,6 +396,8 @@ pub struct GitPanel {
     tracked_count: usize,
     tracked_staged_count: usize,
     update_visible_entries_task: Task<()>,
+    stash_entries: Vec<StashEntry>,
+    reload_stash_entries_task: Task<()>,
     width: Option<Pixels>,
     workspace: WeakEntity<Workspace>,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
@@ -505,6 +553,8 @@ impl GitPanel {
             tracked_count: 0,
             tracked_staged_count: 0,
             update_visible_entries_task: Task::ready(()),
+            stash_entries: Vec::new(),
+            reload_stash_entries_task: Task::ready(()),
             width: None,
             context_menu: None,
             workspace,
@@ -840,6 +890,11 @@ impl GitPanel {
     }
 
     fn open_diff(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(stash) = self.get_selected_entry().and_then(|e| e.stash_entry()) {
+            let stash = stash.clone();
+            self.open_stash(&stash, window, cx);
+            return;
+        }
         maybe!({
             let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
             let workspace = self.workspace.upgrade()?;
@@ -1260,6 +1315,7 @@ impl GitPanel {
 
                 (goal_staged_state, entries)
             }
+            GitListEntry::Stash(_) => return,
         };
         self.change_file_stage(stage, repo_paths, cx);
     }
@@ -1394,7 +1450,7 @@ impl GitPanel {
             .contains_focused(window, cx)
         {
             telemetry::event!("Git Committed", source = "Git Panel");
-            self.commit_changes(CommitOptions { amend: false }, window, cx)
+            self.commit_changes(CommitOptions::default(), window, cx)
         } else {
             cx.propagate();
         }
@@ -1418,7 +1474,14 @@ impl GitPanel {
                 } else {
                     telemetry::event!("Git Amended", source = "Git Panel");
                     self.set_amend_pending(false, cx);
-                    self.commit_changes(CommitOptions { amend: true }, window, cx);
+                    self.commit_changes(
+                        CommitOptions {
+                            amend: true,
+                            ..Default::default()
+                        },
+                        window,
+                        cx,
+                    );
                 }
             }
         } else {
@@ -1785,6 +1848,187 @@ impl GitPanel {
         }));
     }
 
+    fn stash_all(&mut self, _: &StashAll, window: &mut Window, cx: &mut Context<Self>) {
+        let options = StashOptions {
+            include_untracked: true,
+            staged: false,
+        };
+        self.stash_push(options, window, cx);
+    }
+
+    fn stash_staged(&mut self, _: &StashStaged, window: &mut Window, cx: &mut Context<Self>) {
+        let options = StashOptions {
+            include_untracked: false,
+            staged: true,
+        };
+        self.stash_push(options, window, cx);
+    }
+
+    fn stash_push(&mut self, options: StashOptions, window: &mut Window, cx: &mut Context<Self>) {
+        telemetry::event!("Git Stashed");
+        self.run_repository_operation(
+            "stash",
+            move |repo, _| repo.stash_push(None, options),
+            window,
+            cx,
+        );
+    }
+
+    fn stash_pop(&mut self, _: &StashPop, window: &mut Window, cx: &mut Context<Self>) {
+        if self.stash_entries.is_empty() {
+            return;
+        }
+        self.pop_stash(0, window, cx);
+    }
+
+    fn apply_stash(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_repository_operation(
+            "stash apply",
+            move |repo, _| repo.stash_apply(index),
+            window,
+            cx,
+        );
+    }
+
+    fn pop_stash(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_repository_operation(
+            "stash pop",
+            move |repo, _| repo.stash_pop(index),
+            window,
+            cx,
+        );
+    }
+
+    fn drop_stash(&mut self, stash: &StashEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let index = stash.index;
+        let prompt = window.prompt(
+            PromptLevel::Warning,
+            &format!("Are you sure you want to drop {}?", stash.reference()),
+            Some(&stash.message),
+            &["Drop", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            if prompt.await? != 0 {
+                return anyhow::Ok(());
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.run_repository_operation(
+                    "stash drop",
+                    move |repo, _| repo.stash_drop(index),
+                    window,
+                    cx,
+                );
+            })?;
+            Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn run_repository_operation(
+        &mut self,
+        action: &'static str,
+        operation: impl FnOnce(&mut Repository, &mut App) -> oneshot::Receiver<Result<()>> + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let result = repo.update(cx, |repo, cx| operation(repo, cx));
+        cx.spawn_in(window, async move |this, cx| {
+            let result = result.await?;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    log::error!("Error while running git {action}: {e:?}");
+                    this.show_error_toast(action, e, cx);
+                }
+                this.reload_stash_entries(cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn rebase_continue(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_repository_operation(
+            "rebase --continue",
+            |repo, cx| repo.rebase_continue(cx),
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn rebase_skip(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_repository_operation("rebase --skip", |repo, cx| repo.rebase_skip(cx), window, cx);
+    }
+
+    pub(crate) fn rebase_abort(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let prompt = window.prompt(
+            PromptLevel::Warning,
+            "Are you sure you want to abort the rebase?",
+            Some("The branch will be restored to the state it was in before the rebase started."),
+            &["Abort Rebase", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            if prompt.await? != 0 {
+                return anyhow::Ok(());
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.run_repository_operation(
+                    "rebase --abort",
+                    |repo, cx| repo.rebase_abort(cx),
+                    window,
+                    cx,
+                );
+            })?;
+            Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn resolve_conflicts(
+        &mut self,
+        _: &ResolveConflicts,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.get_selected_entry().and_then(|e| e.status_entry()) else {
+            return;
+        };
+        if !entry.status.is_conflicted() {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let repo_path = entry.repo_path.clone();
+        ConflictView::open(repo, repo_path, self.workspace.clone(), window, cx);
+    }
+
+    fn view_history(&mut self, path: RepoPath, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let scope = LogScope {
+            path: Some(path),
+            line_range: None,
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                LogView::open(repo, scope, workspace, window, cx)
+            })
+            .ok();
+    }
+
+    fn open_stash(&mut self, stash: &StashEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.as_ref() else {
+            return;
+        };
+        CommitView::open_stash(stash, repo.downgrade(), self.workspace.clone(), window, cx);
+    }
+
     pub(crate) fn fetch(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if !self.can_push_and_pull(cx) {
             return;
@@ -2024,6 +2268,58 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let remote = self.get_current_remote(window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(tag_name.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::PushTag(tag_name, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -2230,12 +2526,38 @@ impl GitPanel {
                         }
                         git_panel.update_visible_entries(cx);
                         git_panel.update_scrollbar_properties(window, cx);
+                        git_panel.reload_stash_entries(cx);
                     })
                     .ok();
             }
         });
     }
 
+    fn reload_stash_entries(&mut self, cx: &mut Context<Self>) {
+        let Some(repo) = self.active_repository.clone() else {
+            self.stash_entries.clear();
+            return;
+        };
+        let stash_entries = repo.update(cx, |repo, _| repo.stash_entries());
+        self.reload_stash_entries_task = cx.spawn(async move |this, cx| {
+            let stash_entries = match stash_entries.await {
+                Ok(Ok(stash_entries)) => stash_entries,
+                Ok(Err(error)) => {
+                    log::error!("failed to load stash entries: {error:?}");
+                    Vec::new()
+                }
+                Err(_) => return,
+            };
+            this.update(cx, |this, cx| {
+                if this.stash_entries != stash_entries {
+                    this.stash_entries = stash_entries;
+                    this.update_visible_entries(cx);
+                }
+            })
+            .ok();
+        });
+    }
+
     fn reopen_commit_buffer(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repo) = self.active_repository.as_ref() else {
             return;
@@ -2423,13 +2745,20 @@ impl GitPanel {
             self.entries
                 .extend(new_entries.into_iter().map(GitListEntry::GitStatusEntry));
         }
+        if self.stash_entries.len() > 0 {
+            self.entries.push(GitListEntry::Header(GitHeaderEntry {
+                header: Section::Stash,
+            }));
+            self.entries
+                .extend(self.stash_entries.iter().cloned().map(GitListEntry::Stash));
+        }
 
         if let Some((repo_path, _)) = max_width_item {
             self.max_width_item_index = self.entries.iter().position(|entry| match entry {
                 GitListEntry::GitStatusEntry(git_status_entry) => {
                     git_status_entry.repo_path == repo_path
                 }
-                GitListEntry::Header(_) => false,
+                GitListEntry::Header(_) | GitListEntry::Stash(_) => false,
             });
         }
 
@@ -2452,6 +2781,7 @@ impl GitPanel {
             Section::New => (self.new_staged_count, self.new_count),
             Section::Tracked => (self.tracked_staged_count, self.tracked_count),
             Section::Conflict => (self.conflicted_staged_count, self.conflicted_count),
+            Section::Stash => return ToggleState::Unselected,
         };
         if staged_count == 0 {
             ToggleState::Unselected
@@ -2905,6 +3235,98 @@ impl GitPanel {
         )
     }
 
+    fn render_rebase_status(
+        &self,
+        has_write_access: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let rebase = repo.rebase_state.as_ref()?;
+
+        let mut title = match &rebase.head_name {
+            Some(head_name) => format!("Rebasing {head_name}"),
+            None => "Rebasing detached HEAD".to_string(),
+        };
+        if rebase.total_steps > 0 {
+            title.push_str(&format!(
+                " ({}/{})",
+                rebase.current_step, rebase.total_steps
+            ));
+        }
+        let detail = if self.conflicted_count > 0 {
+            Some("Resolve the conflicts and stage them to continue".to_string())
+        } else {
+            rebase
+                .stopped_sha
+                .as_ref()
+                .map(|sha| format!("Stopped at {}", &sha[..SHORT_SHA_LENGTH.min(sha.len())]))
+        };
+
+        Some(
+            v_flex()
+                .px_2()
+                .py_1p5()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .gap_1p5()
+                        .child(
+                            Icon::new(IconName::GitBranch)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(Label::new(title).size(LabelSize::Small)),
+                )
+                .children(detail.map(|detail| {
+                    Label::new(detail)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                }))
+                .when(has_write_access, |this| {
+                    this.child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                panel_filled_button("Continue")
+                                    .disabled(self.has_unstaged_conflicts())
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "git rebase --continue",
+                                        &git::RebaseContinue,
+                                        &self.focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.rebase_continue(window, cx)
+                                    })),
+                            )
+                            .child(
+                                panel_button("Skip")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "git rebase --skip",
+                                        &git::RebaseSkip,
+                                        &self.focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.rebase_skip(window, cx)
+                                    })),
+                            )
+                            .child(
+                                panel_button("Abort")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "git rebase --abort",
+                                        &git::RebaseAbort,
+                                        &self.focus_handle,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.rebase_abort(window, cx)
+                                    })),
+                            ),
+                    )
+                }),
+        )
+    }
+
     pub(crate) fn render_remote_button(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
         let branch = self.active_repository.as_ref()?.read(cx).branch.clone();
         if !self.can_push_and_pull(cx) {
@@ -3068,6 +3490,7 @@ impl GitPanel {
                                                                         git_panel.commit_changes(
                                                                             CommitOptions {
                                                                                 amend: true,
+                                                                                ..Default::default()
                                                                             },
                                                                             window,
                                                                             cx,
@@ -3105,7 +3528,7 @@ impl GitPanel {
                                                         git_panel
                                                             .update(cx, |git_panel, cx| {
                                                                 git_panel.commit_changes(
-                                                                    CommitOptions { amend: false },
+                                                                    CommitOptions::default(),
                                                                     window,
                                                                     cx,
                                                                 );
@@ -3172,9 +3595,8 @@ impl GitPanel {
                                                                 git_panel
                                                                     .update(cx, |git_panel, cx| {
                                                                         git_panel.commit_changes(
-                                                                            CommitOptions {
-                                                                                amend: false,
-                                                                            },
+                                                                            CommitOptions::default(
+                                                                            ),
                                                                             window,
                                                                             cx,
                                                                         );
@@ -3576,6 +3998,15 @@ impl GitPanel {
                                                 cx,
                                             ));
                                         }
+                                        Some(GitListEntry::Stash(stash)) => {
+                                            items.push(this.render_stash_entry(
+                                                ix,
+                                                stash,
+                                                has_write_access,
+                                                window,
+                                                cx,
+                                            ));
+                                        }
                                         None => {}
                                     }
                                 }
@@ -3710,6 +4141,83 @@ impl GitPanel {
             .into_any_element()
     }
 
+    fn render_stash_entry(
+        &self,
+        ix: usize,
+        stash: &StashEntry,
+        has_write_access: bool,
+        window: &Window,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let id: ElementId = ElementId::Name(format!("stash_{}", ix).into());
+        let selected = self.selected_entry == Some(ix);
+        let handle = cx.weak_entity();
+        let hover_bg = cx.theme().colors().ghost_element_hover;
+        let active_bg = cx.theme().colors().ghost_element_active;
+
+        h_flex()
+            .id(id)
+            .h(self.list_item_height())
+            .w_full()
+            .items_center()
+            .border_1()
+            .when(selected && self.focus_handle.is_focused(window), |el| {
+                el.border_color(cx.theme().colors().border_focused)
+            })
+            .px(rems(0.75)) // ~12px
+            .overflow_hidden()
+            .flex_none()
+            .gap_1p5()
+            .when(selected, |el| el.bg(cx.theme().status().info.alpha(0.08)))
+            .hover(|this| this.bg(hover_bg))
+            .active(|this| this.bg(active_bg))
+            .on_click({
+                let stash = stash.clone();
+                cx.listener(move |this, _: &ClickEvent, window, cx| {
+                    this.selected_entry = Some(ix);
+                    cx.notify();
+                    this.open_stash(&stash, window, cx);
+                    this.focus_handle.focus(window);
+                })
+            })
+            .on_mouse_down(
+                MouseButton::Right,
+                move |event: &MouseDownEvent, window, cx| {
+                    if event.button != MouseButton::Right {
+                        return;
+                    }
+
+                    let Some(this) = handle.upgrade() else {
+                        return;
+                    };
+                    this.update(cx, |this, cx| {
+                        this.deploy_stash_context_menu(
+                            event.position,
+                            ix,
+                            has_write_access,
+                            window,
+                            cx,
+                        );
+                    });
+                    cx.stop_propagation();
+                },
+            )
+            .child(
+                Label::new(stash.reference())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small)
+                    .single_line(),
+            )
+            .child(
+                div().flex_1().overflow_hidden().child(
+                    Label::new(stash.message.clone())
+                        .size(LabelSize::Small)
+                        .truncate(),
+                ),
+            )
+            .into_any_element()
+    }
+
     pub fn load_commit_details(
         &self,
         sha: String,
@@ -3744,14 +4252,71 @@ impl GitPanel {
         } else {
             "Restore File"
         };
+        let is_conflicted = entry.status.is_conflicted();
+        let repo_path = entry.repo_path.clone();
+        let this = cx.weak_entity();
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             context_menu
                 .context(self.focus_handle.clone())
+                .when(is_conflicted, |menu| {
+                    menu.action("Resolve Conflicts…", ResolveConflicts.boxed_clone())
+                        .separator()
+                })
                 .action(stage_title, ToggleStaged.boxed_clone())
                 .action(restore_title, git::RestoreFile::default().boxed_clone())
                 .separator()
                 .action("Open Diff", Confirm.boxed_clone())
                 .action("Open File", SecondaryConfirm.boxed_clone())
+                .entry("View History", None, move |window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.view_history(repo_path.clone(), window, cx)
+                    })
+                    .ok();
+                })
+        });
+        self.selected_entry = Some(ix);
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn deploy_stash_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        has_write_access: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(stash) = self.entries.get(ix).and_then(|e| e.stash_entry()).cloned() else {
+            return;
+        };
+        let this = cx.weak_entity();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .action("View Changes", Confirm.boxed_clone())
+                .when(has_write_access, |menu| {
+                    menu.separator()
+                        .entry("Apply", None, {
+                            let this = this.clone();
+                            move |window, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.apply_stash(stash.index, window, cx)
+                                })
+                                .ok();
+                            }
+                        })
+                        .entry("Pop", None, {
+                            let this = this.clone();
+                            move |window, cx| {
+                                this.update(cx, |this, cx| this.pop_stash(stash.index, window, cx))
+                                    .ok();
+                            }
+                        })
+                        .entry("Drop", None, move |window, cx| {
+                            this.update(cx, |this, cx| this.drop_stash(&stash, window, cx))
+                                .ok();
+                        })
+                })
         });
         self.selected_entry = Some(ix);
         self.set_context_menu(context_menu, position, window, cx);
@@ -3770,6 +4335,7 @@ impl GitPanel {
                 has_staged_changes: self.has_staged_changes(),
                 has_unstaged_changes: self.has_unstaged_changes(),
                 has_new_changes: self.new_count > 0,
+                has_stashes: !self.stash_entries.is_empty(),
             },
             window,
             cx,
@@ -4087,6 +4653,10 @@ impl Render for GitPanel {
                     .on_action(cx.listener(Self::revert_selected))
                     .on_action(cx.listener(Self::clean_all))
                     .on_action(cx.listener(Self::generate_commit_message_action))
+                    .on_action(cx.listener(Self::stash_all))
+                    .on_action(cx.listener(Self::stash_staged))
+                    .on_action(cx.listener(Self::stash_pop))
+                    .on_action(cx.listener(Self::resolve_conflicts))
             })
             .on_action(cx.listener(Self::select_first))
             .on_action(cx.listener(Self::select_next))
@@ -4117,6 +4687,7 @@ impl Render for GitPanel {
                 v_flex()
                     .size_full()
                     .children(self.render_panel_header(window, cx))
+                    .children(self.render_rebase_status(has_write_access, cx))
                     .map(|this| {
                         if has_entries {
                             this.child(self.render_entries(has_write_access, window, cx))
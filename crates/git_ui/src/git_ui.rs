@@ -16,17 +16,25 @@ use ui::prelude::*;
 use workspace::Workspace;
 
 mod askpass_modal;
+mod branch_diff;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
 mod commit_view;
+mod conflict_view;
+mod file_history;
+pub mod file_revision_view;
 pub mod git_panel;
 mod git_panel_settings;
+mod log_view;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+mod rebase_editor;
 pub(crate) mod remote_output;
 pub mod repository_selector;
+pub mod tag_picker;
+pub mod worktree_picker;
 
 actions!(git, [ResetOnboarding]);
 
@@ -41,6 +49,11 @@ pub fn init(cx: &mut App) {
         git_panel::register(workspace);
         repository_selector::register(workspace);
         branch_picker::register(workspace);
+        tag_picker::register(workspace);
+        worktree_picker::register(workspace);
+        rebase_editor::register(workspace);
+        log_view::register(workspace);
+        file_history::register(workspace);
 
         let project = workspace.project().read(cx);
         if project.is_read_only(cx) {
@@ -96,6 +109,30 @@ pub fn init(cx: &mut App) {
                 panel.unstage_all(action, window, cx);
             });
         });
+        workspace.register_action(|workspace, _: &git::RebaseContinue, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_continue(window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseSkip, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_skip(window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseAbort, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_abort(window, cx);
+            });
+        });
         CommandPaletteFilter::update_global(cx, |filter, _cx| {
             filter.hide_action_types(&[
                 zed_actions::OpenGitIntegrationOnboarding.type_id(),
@@ -0,0 +1,608 @@
+use crate::commit_view::CommitView;
+use editor::{Editor, EditorEvent};
+use git::{
+    commit_graph::{CommitGraph, GraphRow},
+    repository::{CommitSummary, LogEntry, LogOptions, RepoPath},
+};
+use gpui::{
+    App, Bounds, ClickEvent, Entity, EventEmitter, FocusHandle, Focusable, Hsla, KeyContext,
+    PathBuilder, Pixels, Point, Subscription, Task, UniformListScrollHandle, WeakEntity, canvas,
+    fill, point, size, uniform_list,
+};
+use language::Point as BufferPoint;
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    ProjectPath,
+    git_store::{Repository, RepositoryEvent},
+};
+use std::{ops::Range, time::Duration};
+use theme::ActiveTheme;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{Tooltip, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+};
+
+const PAGE_SIZE: usize = 200;
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(300);
+const LANE_WIDTH: Pixels = px(14.);
+const ROW_HEIGHT: Pixels = px(26.);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ViewHistory, window, cx| {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        LogView::open(repository, LogScope::default(), workspace, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::ViewLineHistory, window, cx| {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let git_store = workspace.project().read(cx).git_store().clone();
+        let Some((repository, path, line_range)) = editor.update(cx, |editor, cx| {
+            let buffer = editor.buffer().read(cx).as_singleton()?;
+            let file = buffer.read(cx).file()?;
+            let project_path = ProjectPath {
+                worktree_id: file.worktree_id(cx),
+                path: file.path().clone(),
+            };
+            let (repository, path) = git_store
+                .read(cx)
+                .repository_and_path_for_project_path(&project_path, cx)?;
+            let selection = editor.selections.newest::<BufferPoint>(cx);
+            let mut end_row = selection.end.row;
+            // A selection ending at the start of a line doesn't include that line.
+            if selection.end.column == 0 && end_row > selection.start.row {
+                end_row -= 1;
+            }
+            Some((repository, path, selection.start.row + 1..end_row + 1))
+        }) else {
+            return;
+        };
+        let scope = LogScope {
+            path: Some(path),
+            line_range: Some(line_range),
+        };
+        LogView::open(repository, scope, workspace, window, cx);
+    });
+}
+
+/// The part of the repository whose history is shown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogScope {
+    pub path: Option<RepoPath>,
+    /// 1-based, inclusive lines of `path`.
+    pub line_range: Option<Range<u32>>,
+}
+
+impl LogScope {
+    fn title(&self) -> SharedString {
+        match (&self.path, &self.line_range) {
+            (Some(path), Some(lines)) => {
+                format!("{}:{}-{}", path.display(), lines.start, lines.end).into()
+            }
+            (Some(path), None) => path.display().to_string().into(),
+            (None, _) => "Repository".into(),
+        }
+    }
+}
+
+/// Pages through `git log`, drawing the branch and merge graph next to each commit.
+pub struct LogView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    scope: LogScope,
+    author_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    since_editor: Entity<Editor>,
+    until_editor: Entity<Editor>,
+    entries: Vec<LogEntry>,
+    graph: CommitGraph,
+    rows: Vec<GraphRow>,
+    graph_width: usize,
+    has_more: bool,
+    error: Option<SharedString>,
+    selected_index: Option<usize>,
+    head_sha: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    load_task: Option<Task<()>>,
+    reload_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl LogView {
+    pub fn open(
+        repository: Entity<Repository>,
+        scope: LogScope,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<LogView>())
+            .find(|view| {
+                let view = view.read(cx);
+                view.repository == repository && view.scope == scope
+            });
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| LogView::new(repository, scope, workspace_handle, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        scope: LogScope,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let filter_editor = |placeholder: &str, window: &mut Window, cx: &mut Context<Self>| {
+            let placeholder = placeholder.to_string();
+            cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(placeholder, cx);
+                editor
+            })
+        };
+        let author_editor = filter_editor("Author", window, cx);
+        let message_editor = filter_editor("Message", window, cx);
+        let since_editor = filter_editor("Since (e.g. 2 weeks ago)", window, cx);
+        let until_editor = filter_editor("Until (e.g. 2024-01-31)", window, cx);
+
+        let mut subscriptions = [
+            &author_editor,
+            &message_editor,
+            &since_editor,
+            &until_editor,
+        ]
+        .into_iter()
+        .map(|editor| {
+            cx.subscribe(editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.schedule_reload(FILTER_DEBOUNCE, cx);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+        subscriptions.push(cx.subscribe(
+            &repository,
+            |this, repository, event: &RepositoryEvent, cx| {
+                if let RepositoryEvent::Updated { .. } = event {
+                    let head_sha = Self::head_sha(&repository, cx);
+                    if head_sha != this.head_sha {
+                        this.head_sha = head_sha;
+                        this.schedule_reload(Duration::ZERO, cx);
+                    }
+                }
+            },
+        ));
+
+        let head_sha = Self::head_sha(&repository, cx);
+        let mut this = Self {
+            repository,
+            workspace,
+            scope,
+            author_editor,
+            message_editor,
+            since_editor,
+            until_editor,
+            entries: Vec::new(),
+            graph: CommitGraph::default(),
+            rows: Vec::new(),
+            graph_width: 0,
+            has_more: true,
+            error: None,
+            selected_index: None,
+            head_sha,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            load_task: None,
+            reload_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn head_sha(repository: &Entity<Repository>, cx: &App) -> Option<SharedString> {
+        repository
+            .read(cx)
+            .head_commit
+            .as_ref()
+            .map(|commit| commit.sha.clone())
+    }
+
+    fn filter_text(editor: &Entity<Editor>, cx: &App) -> Option<String> {
+        let text = editor.read(cx).text(cx);
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn log_options(&self, cx: &App) -> LogOptions {
+        LogOptions {
+            path: self.scope.path.clone(),
+            line_range: self.scope.line_range.clone(),
+            author: Self::filter_text(&self.author_editor, cx),
+            message: Self::filter_text(&self.message_editor, cx),
+            since: Self::filter_text(&self.since_editor, cx),
+            until: Self::filter_text(&self.until_editor, cx),
+            skip: self.entries.len(),
+            limit: PAGE_SIZE,
+        }
+    }
+
+    fn schedule_reload(&mut self, delay: Duration, cx: &mut Context<Self>) {
+        self.reload_task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            this.update(cx, |this, cx| {
+                this.entries.clear();
+                this.rows.clear();
+                this.graph = CommitGraph::default();
+                this.graph_width = 0;
+                this.selected_index = None;
+                this.has_more = true;
+                this.load_task = None;
+                this.load_more(cx);
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.load_task.is_some() || !self.has_more {
+            return;
+        }
+        let options = self.log_options(cx);
+        let log = self
+            .repository
+            .update(cx, |repository, _| repository.log(options));
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let result = log.await;
+            this.update(cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(Ok(entries)) => {
+                        this.error = None;
+                        this.has_more = entries.len() == PAGE_SIZE;
+                        for entry in &entries {
+                            let row = this.graph.push(&entry.sha, &entry.parents);
+                            this.graph_width = this.graph_width.max(row.width);
+                            this.rows.push(row);
+                        }
+                        this.entries.extend(entries);
+                    }
+                    Ok(Err(error)) => {
+                        this.error = Some(error.to_string().trim().to_string().into());
+                        this.has_more = false;
+                    }
+                    Err(_) => return,
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = ix.min(self.entries.len() - 1);
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, gpui::ScrollStrategy::Top);
+        if ix + 1 == self.entries.len() {
+            self.load_more(cx);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix + 1);
+        self.select_index(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(self.entries.len().saturating_sub(1), cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_commit(ix, window, cx);
+        }
+    }
+
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let commit = CommitSummary {
+            sha: entry.sha.clone(),
+            subject: entry.subject.clone(),
+            commit_timestamp: entry.commit_timestamp,
+            has_parent: !entry.parents.is_empty(),
+        };
+        CommitView::open(
+            commit,
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn render_filter(&self, editor: &Entity<Editor>, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .flex_1()
+            .min_w_0()
+            .px_2()
+            .py_1()
+            .rounded_sm()
+            .border_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(editor.clone())
+    }
+
+    fn render_entry(&self, ix: usize, window: &Window, cx: &Context<Self>) -> AnyElement {
+        let entry = &self.entries[ix];
+        let row = self.rows[ix].clone();
+        let selected = self.selected_index == Some(ix);
+        let hover_bg = cx.theme().colors().ghost_element_hover;
+        let active_bg = cx.theme().colors().ghost_element_active;
+        let commit_time = OffsetDateTime::from_unix_timestamp(entry.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let relative_time = format_local_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+        let short_sha = entry
+            .sha
+            .get(..git::SHORT_SHA_LENGTH)
+            .unwrap_or(&entry.sha)
+            .to_string();
+
+        h_flex()
+            .id(("log-entry", ix))
+            .h(ROW_HEIGHT)
+            .w_full()
+            .pr_2()
+            .gap_2()
+            .overflow_hidden()
+            .border_1()
+            .border_color(gpui::transparent_black())
+            .when(selected && self.focus_handle.is_focused(window), |el| {
+                el.border_color(cx.theme().colors().border_focused)
+            })
+            .when(selected, |el| el.bg(cx.theme().status().info.alpha(0.08)))
+            .hover(|el| el.bg(hover_bg))
+            .active(|el| el.bg(active_bg))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.selected_index = Some(ix);
+                cx.notify();
+                this.open_commit(ix, window, cx);
+            }))
+            .child(render_graph(row, self.graph_width, cx))
+            .child(
+                Label::new(short_sha)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .children(entry.refs.iter().map(|reference| {
+                div()
+                    .flex_none()
+                    .px_1()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        Label::new(reference.clone())
+                            .size(LabelSize::XSmall)
+                            .color(Color::Accent)
+                            .single_line(),
+                    )
+            }))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .child(Label::new(entry.subject.clone()).single_line().truncate()),
+            )
+            .child(
+                div()
+                    .id(("log-author", ix))
+                    .flex_none()
+                    .child(
+                        Label::new(entry.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .single_line(),
+                    )
+                    .tooltip(Tooltip::text(entry.author_email.clone())),
+            )
+            .child(
+                Label::new(relative_time)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .single_line(),
+            )
+            .into_any_element()
+    }
+}
+
+/// Draws the lanes of the commit graph that cross a single row.
+fn render_graph(row: GraphRow, width: usize, cx: &App) -> impl IntoElement {
+    let accents = cx.theme().accents().clone();
+    let lane_color = move |lane: usize| -> Hsla { accents.color_for_index(lane as u32) };
+
+    canvas(
+        |_, _, _| {},
+        move |bounds: Bounds<Pixels>, _, window, _| {
+            let lane_x = |lane: usize| bounds.origin.x + LANE_WIDTH * (lane as f32 + 0.5);
+            let top = bounds.origin.y;
+            let bottom = bounds.bottom();
+            let center = point(lane_x(row.lane), bounds.center().y);
+            let mut line = |from: Point<Pixels>, to: Point<Pixels>, color: Hsla| {
+                let mut builder = PathBuilder::stroke(px(1.5));
+                builder.move_to(from);
+                builder.line_to(to);
+                if let Ok(path) = builder.build() {
+                    window.paint_path(path, color);
+                }
+            };
+
+            for lane in &row.passing {
+                let x = lane_x(*lane);
+                line(point(x, top), point(x, bottom), lane_color(*lane));
+            }
+            for lane in &row.incoming {
+                line(point(lane_x(*lane), top), center, lane_color(*lane));
+            }
+            for lane in &row.outgoing {
+                line(center, point(lane_x(*lane), bottom), lane_color(*lane));
+            }
+
+            let dot_size = px(8.);
+            window.paint_quad(
+                fill(
+                    Bounds::centered_at(center, size(dot_size, dot_size)),
+                    lane_color(row.lane),
+                )
+                .corner_radii(dot_size / 2.),
+            );
+        },
+    )
+    .flex_none()
+    .h_full()
+    .w(LANE_WIDTH * width.max(1) as f32)
+}
+
+impl EventEmitter<ItemEvent> for LogView {}
+
+impl Focusable for LogView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LogView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+        let status = if let Some(error) = &self.error {
+            Some(Label::new(error.clone()).color(Color::Error))
+        } else if entry_count == 0 && self.load_task.is_none() {
+            Some(Label::new("No commits match the current filters").color(Color::Muted))
+        } else {
+            None
+        };
+
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("GitLogView");
+        if self.focus_handle.is_focused(window) {
+            key_context.add("not_editing");
+        }
+
+        v_flex()
+            .key_context(key_context)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        Label::new(format!("History: {}", self.scope.title()))
+                            .single_line()
+                            .truncate(),
+                    )
+                    .child(self.render_filter(&self.author_editor, cx))
+                    .child(self.render_filter(&self.message_editor, cx))
+                    .child(self.render_filter(&self.since_editor, cx))
+                    .child(self.render_filter(&self.until_editor, cx)),
+            )
+            .when_some(status, |el, status| el.child(div().p_2().child(status)))
+            .child(
+                uniform_list(cx.entity(), "log-entries", entry_count, {
+                    move |this, range, window, cx| {
+                        // Load the next page before the user reaches the end of the list.
+                        if range.end + PAGE_SIZE / 4 >= this.entries.len() {
+                            this.load_more(cx);
+                        }
+                        range.map(|ix| this.render_entry(ix, window, cx)).collect()
+                    }
+                })
+                .flex_1()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+    }
+}
+
+impl Item for LogView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        let title = match &self.scope.path {
+            Some(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string()),
+            None => "Git History".to_string(),
+        };
+        Label::new(title)
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("History: {}", self.scope.title()).into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Git Log Opened")
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    conflict_view::ConflictView,
     git_panel::{GitPanel, GitPanelAddon, GitStatusEntry},
     remote_button::{render_publish_button, render_push_button},
 };
@@ -12,8 +13,9 @@ use editor::{
 };
 use futures::StreamExt;
 use git::{
-    Commit, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    Commit, ResolveConflicts, StageAll, StageAndNext, StageLines, ToggleStaged, UnstageAll,
+    UnstageAndNext, UnstageLines,
+    repository::{Branch, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::FileStatus,
 };
 use gpui::{
@@ -24,7 +26,7 @@ use language::{Anchor, Buffer, Capability, OffsetRangeExt};
 use multi_buffer::{MultiBuffer, PathKey};
 use project::{
     Project, ProjectPath,
-    git_store::{GitStore, GitStoreEvent, RepositoryEvent},
+    git_store::{GitStore, GitStoreEvent, Repository, RepositoryEvent},
 };
 use std::any::{Any, TypeId};
 use theme::ActiveTheme;
@@ -221,6 +223,27 @@ impl ProjectDiff {
         })
     }
 
+    /// Returns the repository path of the active file if it has merge conflicts.
+    fn active_conflict(&self, cx: &App) -> Option<(Entity<Repository>, RepoPath)> {
+        let project_path = self.active_path(cx)?;
+        let repo = self.git_store.read(cx).active_repository()?;
+        let repo_path = repo.read(cx).project_path_to_repo_path(&project_path, cx)?;
+        repo.read(cx)
+            .has_conflict(&repo_path)
+            .then_some((repo, repo_path))
+    }
+
+    fn resolve_conflicts(
+        &mut self,
+        _: &ResolveConflicts,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((repo, repo_path)) = self.active_conflict(cx) {
+            ConflictView::open(repo, repo_path, self.workspace.clone(), window, cx);
+        }
+    }
+
     fn move_to_path(&mut self, path_key: PathKey, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(position) = self.multibuffer.read(cx).location_for_path(&path_key, cx) {
             self.editor.update(cx, |editor, cx| {
@@ -292,6 +315,7 @@ impl ProjectDiff {
             selection,
             stage_all,
             unstage_all,
+            conflict: self.active_conflict(cx).is_some(),
         };
     }
 
@@ -669,6 +693,7 @@ impl Render for ProjectDiff {
         div()
             .track_focus(&self.focus_handle)
             .key_context(if is_empty { "EmptyPane" } else { "GitDiff" })
+            .on_action(cx.listener(Self::resolve_conflicts))
             .bg(cx.theme().colors().editor_background)
             .flex()
             .items_center()
@@ -860,6 +885,7 @@ struct ButtonStates {
     selection: bool,
     stage_all: bool,
     unstage_all: bool,
+    conflict: bool,
 }
 
 impl Render for ProjectDiffToolbar {
@@ -880,6 +906,19 @@ impl Render for ProjectDiffToolbar {
             .justify_between()
             .child(
                 h_group_sm()
+                    .when(button_states.conflict, |el| {
+                        el.child(
+                            Button::new("resolve-conflicts", "Resolve Conflicts")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Open the three-way conflict view",
+                                    &ResolveConflicts,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&ResolveConflicts, window, cx)
+                                })),
+                        )
+                    })
                     .when(button_states.selection, |el| {
                         el.child(
                             Button::new("stage", "Toggle Staged")
@@ -893,6 +932,30 @@ impl Render for ProjectDiffToolbar {
                                     this.dispatch_action(&ToggleStaged, window, cx)
                                 })),
                         )
+                        .child(
+                            Button::new("stage-lines", "Stage Lines")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Stage the selected lines",
+                                    &StageLines,
+                                    &focus_handle,
+                                ))
+                                .disabled(!button_states.stage)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&StageLines, window, cx)
+                                })),
+                        )
+                        .child(
+                            Button::new("unstage-lines", "Unstage Lines")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Unstage the selected lines",
+                                    &UnstageLines,
+                                    &focus_handle,
+                                ))
+                                .disabled(!button_states.unstage)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&UnstageLines, window, cx)
+                                })),
+                        )
                     })
                     .when(!button_states.selection, |el| {
                         el.child(
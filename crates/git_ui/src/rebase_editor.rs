@@ -0,0 +1,585 @@
+use editor::{Editor, EditorEvent};
+use git::repository::{RebaseAction, RebaseTodoEntry};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext, ScrollHandle,
+    Subscription, Task, actions,
+};
+use project::git_store::Repository;
+use std::time::Duration;
+use ui::{KeyBinding, Tooltip, prelude::*};
+use workspace::{ModalView, Workspace};
+
+actions!(
+    rebase_editor,
+    [
+        MarkPick,
+        MarkReword,
+        MarkSquash,
+        MarkFixup,
+        MarkDrop,
+        MoveUp,
+        MoveDown,
+        StartRebase
+    ]
+);
+
+const LOAD_DEBOUNCE: Duration = Duration::from_millis(200);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+        RebaseEditor::toggle(workspace, window, cx);
+    });
+}
+
+/// A modal for editing the todo list of an interactive rebase before starting it.
+pub struct RebaseEditor {
+    repository: Entity<Repository>,
+    base_editor: Entity<Editor>,
+    entries: Vec<RebaseTodoEntry>,
+    selected_index: usize,
+    /// The message editor of the selected reworded commit, with the message it was loaded with.
+    reword_editor: Option<(usize, Entity<Editor>, String)>,
+    messages: Vec<Option<String>>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    load_task: Task<()>,
+    start_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<DismissEvent> for RebaseEditor {}
+impl ModalView for RebaseEditor {}
+
+impl Focusable for RebaseEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        if self.entries.is_empty() {
+            self.base_editor.focus_handle(cx)
+        } else {
+            self.focus_handle.clone()
+        }
+    }
+}
+
+impl RebaseEditor {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let base = repository
+            .read(cx)
+            .branch
+            .as_ref()
+            .and_then(|branch| branch.upstream.as_ref())
+            .map(|upstream| upstream.ref_name.to_string())
+            .unwrap_or_default();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(repository, base, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        base: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let base_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Branch, tag or commit to rebase onto…", cx);
+            editor.set_text(base.clone(), window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&base_editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::BufferEdited = event {
+                this.schedule_load(LOAD_DEBOUNCE, cx);
+            }
+        });
+
+        let mut this = Self {
+            repository,
+            base_editor,
+            entries: Vec::new(),
+            selected_index: 0,
+            reword_editor: None,
+            messages: Vec::new(),
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            load_task: Task::ready(()),
+            start_task: None,
+            _subscriptions: vec![subscription],
+        };
+        if !base.is_empty() {
+            this.schedule_load(Duration::ZERO, cx);
+        }
+        this
+    }
+
+    fn base(&self, cx: &App) -> String {
+        self.base_editor.read(cx).text(cx).trim().to_string()
+    }
+
+    fn schedule_load(&mut self, delay: Duration, cx: &mut Context<Self>) {
+        let base = self.base(cx);
+        if base.is_empty() {
+            self.entries.clear();
+            self.messages.clear();
+            self.reword_editor = None;
+            self.error = None;
+            cx.notify();
+            return;
+        }
+        let repository = self.repository.clone();
+        self.load_task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            let Ok(todo) = repository.update(cx, |repository, _| repository.rebase_todo(base))
+            else {
+                return;
+            };
+            let result = todo.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(Ok(entries)) => {
+                        this.error = None;
+                        this.messages = vec![None; entries.len()];
+                        this.entries = entries;
+                    }
+                    Ok(Err(error)) => {
+                        this.error = Some(error.to_string().trim().to_string().into());
+                        this.entries.clear();
+                        this.messages.clear();
+                    }
+                    Err(_) => return,
+                }
+                this.selected_index = 0;
+                this.reword_editor = None;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn set_action(&mut self, action: RebaseAction, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get_mut(self.selected_index) else {
+            return;
+        };
+        entry.action = action;
+        if action == RebaseAction::Reword {
+            self.edit_message(self.selected_index, window, cx);
+        } else if self
+            .reword_editor
+            .as_ref()
+            .is_some_and(|(ix, _, _)| *ix == self.selected_index)
+        {
+            self.save_message(cx);
+            self.reword_editor = None;
+        }
+        cx.notify();
+    }
+
+    fn edit_message(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.save_message(cx);
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let sha = entry.sha.to_string();
+        let original = entry.subject.to_string();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(6, window, cx);
+            editor.set_text(original.clone(), window, cx);
+            editor
+        });
+        self.reword_editor = Some((ix, editor.clone(), original));
+        editor.focus_handle(cx).focus(window);
+
+        if let Some(message) = self.messages.get(ix).cloned().flatten() {
+            editor.update(cx, |editor, cx| editor.set_text(message, window, cx));
+            return;
+        }
+
+        // Load the full message, so that rewording doesn't drop the commit body.
+        let details = self
+            .repository
+            .update(cx, |repository, _| repository.show(sha));
+        cx.spawn_in(window, async move |this, cx| {
+            let details = details.await??;
+            let message = details.message.trim_end().to_string();
+            this.update_in(cx, |this, window, cx| {
+                if let Some((current_ix, editor, original)) = this.reword_editor.as_mut() {
+                    if *current_ix == ix && editor.read(cx).text(cx) == *original {
+                        editor.update(cx, |editor, cx| {
+                            editor.set_text(message.clone(), window, cx)
+                        });
+                        *original = message;
+                    }
+                }
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn save_message(&mut self, cx: &App) {
+        let Some((ix, editor, original)) = self.reword_editor.as_ref() else {
+            return;
+        };
+        let text = editor.read(cx).text(cx);
+        if let Some(message) = self.messages.get_mut(*ix) {
+            *message = (text.trim() != original.trim()).then_some(text);
+        }
+    }
+
+    fn mark_pick(&mut self, _: &MarkPick, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Pick, window, cx);
+    }
+
+    fn mark_reword(&mut self, _: &MarkReword, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Reword, window, cx);
+    }
+
+    fn mark_squash(&mut self, _: &MarkSquash, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Squash, window, cx);
+    }
+
+    fn mark_fixup(&mut self, _: &MarkFixup, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Fixup, window, cx);
+    }
+
+    fn mark_drop(&mut self, _: &MarkDrop, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Drop, window, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index > 0 && self.selected_index < self.entries.len() {
+            self.swap(self.selected_index, self.selected_index - 1);
+            self.selected_index -= 1;
+            cx.notify();
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index + 1 < self.entries.len() {
+            self.swap(self.selected_index, self.selected_index + 1);
+            self.selected_index += 1;
+            cx.notify();
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.entries.swap(a, b);
+        self.messages.swap(a, b);
+        if let Some((ix, _, _)) = self.reword_editor.as_mut() {
+            if *ix == a {
+                *ix = b;
+            } else if *ix == b {
+                *ix = a;
+            }
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index + 1 < self.entries.len() {
+            self.selected_index += 1;
+            self.scroll_handle.scroll_to_item(self.selected_index);
+            cx.notify();
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_index > 0 {
+            self.selected_index -= 1;
+            self.scroll_handle.scroll_to_item(self.selected_index);
+            cx.notify();
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.base_editor.focus_handle(cx).is_focused(window) {
+            self.schedule_load(Duration::ZERO, cx);
+            self.focus_handle.focus(window);
+        } else {
+            self.start_rebase(&StartRebase, window, cx);
+        }
+    }
+
+    fn start_rebase(&mut self, _: &StartRebase, window: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() || self.start_task.is_some() {
+            return;
+        }
+        if self
+            .entries
+            .first()
+            .is_some_and(|entry| matches!(entry.action, RebaseAction::Squash | RebaseAction::Fixup))
+        {
+            self.error = Some("The first commit has no previous commit to be combined with".into());
+            cx.notify();
+            return;
+        }
+
+        self.save_message(cx);
+        let base = self.base(cx);
+        let todo = self
+            .entries
+            .iter()
+            .zip(self.messages.iter())
+            .map(|(entry, message)| RebaseTodoEntry {
+                message: message
+                    .clone()
+                    .filter(|_| entry.action == RebaseAction::Reword),
+                ..entry.clone()
+            })
+            .collect();
+        let rebase = self
+            .repository
+            .update(cx, |repository, cx| repository.rebase_start(base, todo, cx));
+        self.start_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = rebase.await;
+            this.update(cx, |this, cx| {
+                this.start_task = None;
+                match result {
+                    Ok(Ok(())) => cx.emit(DismissEvent),
+                    Ok(Err(error)) => {
+                        this.error = Some(error.to_string().trim().to_string().into());
+                        cx.notify();
+                    }
+                    Err(_) => {}
+                }
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &RebaseTodoEntry,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let selected = ix == self.selected_index;
+        let action = entry.action;
+        let action_color = match action {
+            RebaseAction::Pick => Color::Default,
+            RebaseAction::Reword => Color::Accent,
+            RebaseAction::Squash | RebaseAction::Fixup => Color::Warning,
+            RebaseAction::Drop => Color::Disabled,
+        };
+        let next_action = RebaseAction::ALL
+            .iter()
+            .cycle()
+            .skip_while(|candidate| **candidate != action)
+            .nth(1)
+            .copied()
+            .unwrap_or_default();
+
+        let message_editor = self
+            .reword_editor
+            .as_ref()
+            .filter(|(editor_ix, _, _)| *editor_ix == ix && action == RebaseAction::Reword)
+            .map(|(_, editor, _)| editor.clone());
+
+        v_flex()
+            .id(("rebase-entry", ix))
+            .w_full()
+            .px_2()
+            .py_0p5()
+            .rounded_sm()
+            .when(selected, |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .hover(|this| this.bg(cx.theme().colors().element_hover))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_index = ix;
+                this.focus_handle.focus(window);
+                cx.notify();
+            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        Button::new(("rebase-action", ix), action.as_str())
+                            .label_size(LabelSize::Small)
+                            .color(action_color)
+                            .tooltip(Tooltip::text(format!("Change to {}", next_action.as_str())))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.selected_index = ix;
+                                this.set_action(next_action, window, cx);
+                            })),
+                    )
+                    .child(
+                        Label::new(entry.sha.chars().take(7).collect::<String>())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        div().flex_1().overflow_hidden().child(
+                            Label::new(entry.subject.clone())
+                                .size(LabelSize::Small)
+                                .when(action == RebaseAction::Drop, |label| {
+                                    label.strikethrough().color(Color::Disabled)
+                                })
+                                .truncate(),
+                        ),
+                    )
+                    .child(
+                        IconButton::new(("move-up", ix), IconName::ArrowUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(ix == 0)
+                            .tooltip(Tooltip::text("Move Up"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.selected_index = ix;
+                                this.move_up(&MoveUp, window, cx);
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("move-down", ix), IconName::ArrowDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(ix + 1 == self.entries.len())
+                            .tooltip(Tooltip::text("Move Down"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.selected_index = ix;
+                                this.move_down(&MoveDown, window, cx);
+                            })),
+                    ),
+            )
+            .when_some(message_editor, |this, editor| {
+                this.child(
+                    div()
+                        .ml_8()
+                        .my_1()
+                        .p_1()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .rounded_sm()
+                        .child(editor),
+                )
+            })
+    }
+}
+
+impl Render for RebaseEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let is_starting = self.start_task.is_some();
+        let entries = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| self.render_entry(ix, entry, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("RebaseEditor");
+        if self.focus_handle.is_focused(window) {
+            key_context.add("not_editing");
+        }
+
+        v_flex()
+            .key_context(key_context)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::mark_pick))
+            .on_action(cx.listener(Self::mark_reword))
+            .on_action(cx.listener(Self::mark_squash))
+            .on_action(cx.listener(Self::mark_fixup))
+            .on_action(cx.listener(Self::mark_drop))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::start_rebase))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new("Interactive Rebase").size(HeadlineSize::XSmall)),
+            )
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_1()
+                    .gap_2()
+                    .child(
+                        Label::new("Onto")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(div().flex_1().child(self.base_editor.clone())),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-entries")
+                    .track_scroll(&self.scroll_handle)
+                    .max_h(rems(24.))
+                    .overflow_y_scroll()
+                    .px_1()
+                    .py_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .when(entries.is_empty() && self.error.is_none(), |this| {
+                        this.child(
+                            div().px_2().py_1().child(
+                                Label::new("No commits to rebase")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                        )
+                    })
+                    .children(entries),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_1()
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+            })
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new("p pick · r reword · s squash · f fixup · d drop")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Button::new("start-rebase", "Start Rebase")
+                            .style(ButtonStyle::Filled)
+                            .disabled(self.entries.is_empty() || is_starting)
+                            .key_binding(KeyBinding::for_action_in(
+                                &StartRebase,
+                                &focus_handle,
+                                window,
+                                cx,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_rebase(&StartRebase, window, cx)
+                            })),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,532 @@
+use anyhow::Context as _;
+use fuzzy::StringMatchCandidate;
+
+use git::repository::{CommitSummary, Tag};
+use gpui::{
+    App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, PromptLevel, Render, SharedString, Styled,
+    Subscription, Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::commit_view::CommitView;
+use crate::git_panel::GitPanel;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::Tags,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx);
+    let workspace_handle = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TagList::new(repository, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TagList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = TagListDelegate::new(repository.clone(), workspace);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let weak_picker = picker.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let repository = repository.context("No active repository")?;
+            TagListDelegate::reload(weak_picker, repository, cx).await
+        })
+        .detach_and_log_err(cx);
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            width,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+
+impl Focusable for TagList {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(self.width)
+            .child(self.picker.clone())
+            .on_mouse_down_out({
+                cx.listener(move |this, _, window, cx| {
+                    this.picker.update(cx, |this, cx| {
+                        this.cancel(&Default::default(), window, cx);
+                    })
+                })
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum TagEntry {
+    Existing {
+        tag: Tag,
+        positions: Vec<usize>,
+    },
+    CreateLightweight {
+        name: SharedString,
+    },
+    /// Asks for the tag message before creating the tag.
+    CreateAnnotated {
+        name: SharedString,
+    },
+    Message {
+        name: SharedString,
+        message: String,
+    },
+}
+
+pub struct TagListDelegate {
+    matches: Vec<TagEntry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    /// The name of the annotated tag whose message is being typed into the query.
+    annotating: Option<SharedString>,
+    selected_index: usize,
+}
+
+impl TagListDelegate {
+    fn new(repo: Option<Entity<Repository>>, workspace: WeakEntity<Workspace>) -> Self {
+        Self {
+            matches: vec![],
+            all_tags: None,
+            repo,
+            workspace,
+            annotating: None,
+            selected_index: 0,
+        }
+    }
+
+    async fn reload(
+        picker: WeakEntity<Picker<Self>>,
+        repo: Entity<Repository>,
+        cx: &mut AsyncWindowContext,
+    ) -> anyhow::Result<()> {
+        let tags = repo.update(cx, |repo, _| repo.tags())?.await??;
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.all_tags = Some(tags);
+            picker.refresh(window, cx);
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: SharedString,
+        message: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| {
+                repo.create_tag(name.to_string(), None, message)
+            })?
+            .await?
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_tag(&self, tag: Tag, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn_in(window, async move |picker, cx| {
+            let answer = cx
+                .update(|window, cx| {
+                    window.prompt(
+                        PromptLevel::Warning,
+                        &format!("Delete tag {}?", tag.name),
+                        Some("Tags that were already pushed are not deleted from remotes."),
+                        &["Delete", "Cancel"],
+                        cx,
+                    )
+                })?
+                .await?;
+            if answer != 0 {
+                return Ok(());
+            }
+            repo.update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))?
+                .await??;
+            Self::reload(picker, repo, cx).await
+        })
+        .detach_and_prompt_err("Failed to delete tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn push_tag(&self, tag: Tag, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(panel) = self
+            .workspace
+            .read_with(cx, |workspace, cx| workspace.panel::<GitPanel>(cx))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        panel.update(cx, |panel, cx| panel.push_tag(tag.name, window, cx));
+        cx.emit(DismissEvent);
+    }
+
+    fn selected_tag(&self) -> Option<&Tag> {
+        match self.matches.get(self.selected_index)? {
+            TagEntry::Existing { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match &self.annotating {
+            Some(name) => format!("Message for tag {name}...").into(),
+            None => "Select or create a tag...".into(),
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if let Some(name) = self.annotating.clone() {
+            let message = query.trim().to_string();
+            self.matches = if message.is_empty() {
+                Vec::new()
+            } else {
+                vec![TagEntry::Message { name, message }]
+            };
+            self.selected_index = 0;
+            return Task::ready(());
+        }
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let query = query.trim().to_string();
+            let mut matches: Vec<TagEntry> = if query.is_empty() {
+                all_tags
+                    .iter()
+                    .cloned()
+                    .map(|tag| TagEntry::Existing {
+                        tag,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| TagEntry::Existing {
+                    tag: all_tags[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            let is_valid_name = !query.is_empty() && !query.contains(char::is_whitespace);
+            if is_valid_name && !all_tags.iter().any(|tag| tag.name.as_ref() == query) {
+                let name: SharedString = query.into();
+                matches.push(TagEntry::CreateLightweight { name: name.clone() });
+                matches.push(TagEntry::CreateAnnotated { name });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm_update_query(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<String> {
+        let TagEntry::CreateAnnotated { name } = self.matches.get(self.selected_index)? else {
+            return None;
+        };
+        self.annotating = Some(name.clone());
+        cx.defer_in(window, |picker, window, cx| {
+            picker.refresh_placeholder(window, cx)
+        });
+        Some(String::new())
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()).cloned() else {
+            return;
+        };
+        match entry {
+            TagEntry::CreateLightweight { name } => {
+                self.create_tag(name, None, window, cx);
+            }
+            TagEntry::Message { name, message } => {
+                self.create_tag(name, Some(message), window, cx);
+            }
+            TagEntry::CreateAnnotated { .. } => {}
+            TagEntry::Existing { tag, .. } if secondary => {
+                self.delete_tag(tag, window, cx);
+            }
+            TagEntry::Existing { tag, .. } => {
+                let Some(repo) = self.repo.as_ref() else {
+                    return;
+                };
+                CommitView::open(
+                    CommitSummary {
+                        sha: tag.sha,
+                        subject: tag.subject,
+                        commit_timestamp: tag.timestamp,
+                        has_parent: true,
+                    },
+                    repo.downgrade(),
+                    self.workspace.clone(),
+                    window,
+                    cx,
+                );
+                cx.emit(DismissEvent);
+            }
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+
+        let (title, subject, meta) = match entry {
+            TagEntry::Existing { tag, positions } => {
+                let date = OffsetDateTime::from_unix_timestamp(tag.timestamp)
+                    .map(|timestamp| {
+                        format_local_timestamp(
+                            timestamp,
+                            OffsetDateTime::now_utc(),
+                            time_format::TimestampFormat::Relative,
+                        )
+                    })
+                    .unwrap_or_default();
+                (
+                    HighlightedLabel::new(tag.name.clone(), positions.clone())
+                        .truncate()
+                        .into_any_element(),
+                    Some(tag.subject.clone()),
+                    if tag.is_annotated {
+                        format!("annotated · {date}")
+                    } else {
+                        date
+                    },
+                )
+            }
+            TagEntry::CreateLightweight { name } => (
+                Label::new(format!("Create tag \"{name}\" at HEAD"))
+                    .single_line()
+                    .into_any_element(),
+                None,
+                String::new(),
+            ),
+            TagEntry::CreateAnnotated { name } => (
+                Label::new(format!("Create annotated tag \"{name}\" at HEAD…"))
+                    .single_line()
+                    .into_any_element(),
+                None,
+                String::new(),
+            ),
+            TagEntry::Message { name, message } => (
+                Label::new(format!("Create annotated tag \"{name}\""))
+                    .single_line()
+                    .into_any_element(),
+                Some(message.clone().into()),
+                String::new(),
+            ),
+        };
+
+        Some(
+            ListItem::new(SharedString::from(format!("tag-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .w_full()
+                        .child(
+                            h_flex()
+                                .w_full()
+                                .flex_shrink()
+                                .overflow_x_hidden()
+                                .gap_2()
+                                .justify_between()
+                                .child(div().flex_shrink().overflow_x_hidden().child(title))
+                                .child(Label::new(meta).size(LabelSize::Small).color(Color::Muted)),
+                        )
+                        .when_some(subject, |el, subject| {
+                            el.child(
+                                div().max_w_96().child(
+                                    Label::new(subject)
+                                        .size(LabelSize::Small)
+                                        .truncate()
+                                        .color(Color::Muted),
+                                ),
+                            )
+                        }),
+                ),
+        )
+    }
+
+    fn render_header(
+        &self,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let name = self.annotating.as_ref()?;
+        Some(
+            h_flex()
+                .w_full()
+                .px_3()
+                .pt_2()
+                .child(
+                    Label::new(format!("New annotated tag \"{name}\""))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any(),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        if self.annotating.is_some() {
+            return None;
+        }
+        let has_selected_tag = self.selected_tag().is_some();
+        Some(
+            h_flex()
+                .w_full()
+                .p_2()
+                .gap_2()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("push", "Push")
+                        .disabled(!has_selected_tag)
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(tag) = picker.delegate.selected_tag().cloned() {
+                                picker.delegate.push_tag(tag, window, cx);
+                            }
+                        })),
+                )
+                .child(
+                    Button::new("delete", "Delete")
+                        .disabled(!has_selected_tag)
+                        .key_binding(KeyBinding::for_action(&menu::SecondaryConfirm, window, cx))
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(tag) = picker.delegate.selected_tag().cloned() {
+                                picker.delegate.delete_tag(tag, window, cx);
+                            }
+                        })),
+                )
+                .into_any(),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        None
+    }
+}
@@ -0,0 +1,549 @@
+use anyhow::{Context as _, anyhow};
+use fuzzy::StringMatchCandidate;
+
+use git::repository::{Branch, GitWorktree};
+use gpui::{
+    App, AsyncApp, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
+    Focusable, InteractiveElement, IntoElement, ParentElement, PromptLevel, Render, SharedString,
+    Styled, Subscription, Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{AppState, ModalView, OpenOptions, Workspace};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::Worktrees,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    let repository = project.active_repository(cx);
+    // Other worktrees of a remote project can't be opened in a new window from here.
+    let app_state = project.is_local().then(|| workspace.app_state().clone());
+    workspace.toggle_modal(window, cx, |window, cx| {
+        WorktreeList::new(repository, app_state, rems(34.), window, cx)
+    })
+}
+
+pub struct WorktreeList {
+    width: Rems,
+    pub picker: Entity<Picker<WorktreeListDelegate>>,
+    _subscription: Subscription,
+}
+
+impl WorktreeList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        app_state: Option<Arc<AppState>>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = WorktreeListDelegate::new(repository.clone(), app_state);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let weak_picker = picker.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let repository = repository.context("No active repository")?;
+            WorktreeListDelegate::reload(weak_picker, repository, cx).await
+        })
+        .detach_and_log_err(cx);
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            width,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for WorktreeList {}
+impl EventEmitter<DismissEvent> for WorktreeList {}
+
+impl Focusable for WorktreeList {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for WorktreeList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(self.width)
+            .child(self.picker.clone())
+            .on_mouse_down_out({
+                cx.listener(move |this, _, window, cx| {
+                    this.picker.update(cx, |this, cx| {
+                        this.cancel(&Default::default(), window, cx);
+                    })
+                })
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum WorktreeEntry {
+    Existing {
+        worktree: GitWorktree,
+        positions: Vec<usize>,
+    },
+    Create {
+        branch: SharedString,
+        /// Whether the branch doesn't exist yet and will be created from HEAD.
+        new_branch: bool,
+        path: PathBuf,
+    },
+}
+
+pub struct WorktreeListDelegate {
+    matches: Vec<WorktreeEntry>,
+    all_worktrees: Option<Vec<GitWorktree>>,
+    branches: Vec<Branch>,
+    repo: Option<Entity<Repository>>,
+    app_state: Option<Arc<AppState>>,
+    selected_index: usize,
+}
+
+impl WorktreeListDelegate {
+    fn new(repo: Option<Entity<Repository>>, app_state: Option<Arc<AppState>>) -> Self {
+        Self {
+            matches: vec![],
+            all_worktrees: None,
+            branches: vec![],
+            repo,
+            app_state,
+            selected_index: 0,
+        }
+    }
+
+    async fn reload(
+        picker: WeakEntity<Picker<Self>>,
+        repo: Entity<Repository>,
+        cx: &mut AsyncWindowContext,
+    ) -> anyhow::Result<()> {
+        let worktrees = repo.update(cx, |repo, _| repo.worktrees())?.await??;
+        let branches = repo.update(cx, |repo, _| repo.branches())?.await??;
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.all_worktrees = Some(worktrees);
+            picker.delegate.branches = branches;
+            picker.refresh(window, cx);
+        })
+    }
+
+    fn create_worktree(
+        &self,
+        branch: SharedString,
+        new_branch: bool,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let app_state = self.app_state.clone();
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| {
+                repo.create_worktree(path.clone(), branch.to_string(), new_branch, None)
+            })?
+            .await??;
+
+            if let Some(app_state) = app_state {
+                open_worktree(path, app_state, cx).await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to create worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn remove_worktree(
+        &self,
+        worktree: GitWorktree,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        if worktree.is_main {
+            return;
+        }
+        cx.spawn_in(window, async move |picker, cx| {
+            let result = repo
+                .update(cx, |repo, _| {
+                    repo.remove_worktree(worktree.path.clone(), false)
+                })?
+                .await?;
+            if let Err(error) = result {
+                let answer = cx
+                    .update(|window, cx| {
+                        window.prompt(
+                            PromptLevel::Warning,
+                            &format!("Remove worktree at {} anyway?", worktree.path.display()),
+                            Some(&error.to_string()),
+                            &["Remove", "Cancel"],
+                            cx,
+                        )
+                    })?
+                    .await?;
+                if answer != 0 {
+                    return Ok(());
+                }
+                repo.update(cx, |repo, _| repo.remove_worktree(worktree.path, true))?
+                    .await??;
+            }
+            Self::reload(picker, repo, cx).await
+        })
+        .detach_and_prompt_err("Failed to remove worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn prune_worktrees(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, _| repo.prune_worktrees())?.await??;
+            Self::reload(picker, repo, cx).await
+        })
+        .detach_and_prompt_err("Failed to prune worktrees", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn selected_removable_worktree(&self) -> Option<&GitWorktree> {
+        match self.matches.get(self.selected_index)? {
+            WorktreeEntry::Existing { worktree, .. } if !worktree.is_main => Some(worktree),
+            _ => None,
+        }
+    }
+}
+
+async fn open_worktree(
+    path: PathBuf,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncApp,
+) -> anyhow::Result<()> {
+    cx.update(|cx| {
+        workspace::open_paths(
+            &[path],
+            app_state,
+            OpenOptions {
+                open_new_workspace: Some(true),
+                ..Default::default()
+            },
+            cx,
+        )
+    })?
+    .await?;
+    Ok(())
+}
+
+/// The name a worktree is listed and matched by: its branch, or its HEAD when detached.
+fn worktree_label(worktree: &GitWorktree) -> SharedString {
+    if let Some(branch) = &worktree.branch {
+        return branch.clone();
+    }
+    match &worktree.head_sha {
+        Some(sha) => format!("detached at {}", &sha[..sha.len().min(7)]).into(),
+        None => worktree.path.to_string_lossy().into_owned().into(),
+    }
+}
+
+/// New worktrees are placed next to the main one, as `<repository>-<branch>`.
+fn default_worktree_path(main_worktree: &Path, branch: &str) -> PathBuf {
+    let repository_name = main_worktree
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let directory_name = format!("{repository_name}-{}", branch.replace('/', "-"));
+    match main_worktree.parent() {
+        Some(parent) => parent.join(directory_name),
+        None => PathBuf::from(directory_name),
+    }
+}
+
+impl PickerDelegate for WorktreeListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select or create a worktree...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_worktrees) = self.all_worktrees.clone() else {
+            return Task::ready(());
+        };
+        let branch_names = self
+            .branches
+            .iter()
+            .map(|branch| branch.name.clone())
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let query = query.trim().to_string();
+            let mut matches: Vec<WorktreeEntry> = if query.is_empty() {
+                all_worktrees
+                    .iter()
+                    .cloned()
+                    .map(|worktree| WorktreeEntry::Existing {
+                        worktree,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_worktrees
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, worktree)| StringMatchCandidate::new(ix, &worktree_label(worktree)))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| WorktreeEntry::Existing {
+                    worktree: all_worktrees[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+
+            // A branch can only be checked out in one worktree at a time.
+            let is_checked_out = all_worktrees
+                .iter()
+                .any(|worktree| worktree.branch.as_deref() == Some(query.as_str()));
+            let main_worktree = all_worktrees.iter().find(|worktree| worktree.is_main);
+            if let Some(main_worktree) =
+                main_worktree.filter(|_| !query.is_empty() && !is_checked_out)
+            {
+                matches.push(WorktreeEntry::Create {
+                    path: default_worktree_path(&main_worktree.path, &query),
+                    new_branch: !branch_names.iter().any(|name| *name == query),
+                    branch: query.into(),
+                });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()).cloned() else {
+            return;
+        };
+        match entry {
+            WorktreeEntry::Create {
+                branch,
+                new_branch,
+                path,
+            } => {
+                self.create_worktree(branch, new_branch, path, window, cx);
+            }
+            WorktreeEntry::Existing { worktree, .. } if secondary => {
+                self.remove_worktree(worktree, window, cx);
+            }
+            WorktreeEntry::Existing { worktree, .. } => {
+                let app_state = self.app_state.clone();
+                cx.spawn(async move |_, cx| {
+                    let app_state = app_state
+                        .ok_or_else(|| anyhow!("Worktrees of remote projects can't be opened"))?;
+                    open_worktree(worktree.path, app_state, cx).await
+                })
+                .detach_and_prompt_err(
+                    "Failed to open worktree",
+                    window,
+                    cx,
+                    |e, _, _| Some(e.to_string()),
+                );
+                cx.emit(DismissEvent);
+            }
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+
+        let (title, path, badges) = match entry {
+            WorktreeEntry::Existing {
+                worktree,
+                positions,
+            } => {
+                let badges = [
+                    (worktree.is_main, "main"),
+                    (worktree.is_locked, "locked"),
+                    (worktree.is_prunable, "prunable"),
+                ]
+                .into_iter()
+                .filter_map(|(applies, badge)| applies.then_some(badge))
+                .collect::<Vec<_>>();
+                (
+                    HighlightedLabel::new(worktree_label(worktree), positions.clone())
+                        .truncate()
+                        .into_any_element(),
+                    worktree.path.clone(),
+                    badges,
+                )
+            }
+            WorktreeEntry::Create {
+                branch,
+                new_branch,
+                path,
+            } => {
+                let title = if *new_branch {
+                    format!("Create worktree for new branch \"{branch}\"…")
+                } else {
+                    format!("Create worktree for branch \"{branch}\"…")
+                };
+                (
+                    Label::new(title).single_line().into_any_element(),
+                    path.clone(),
+                    Vec::new(),
+                )
+            }
+        };
+
+        Some(
+            ListItem::new(SharedString::from(format!("worktree-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .w_full()
+                        .child(
+                            h_flex()
+                                .w_full()
+                                .flex_shrink()
+                                .overflow_x_hidden()
+                                .gap_2()
+                                .justify_between()
+                                .child(div().flex_shrink().overflow_x_hidden().child(title))
+                                .child(h_flex().gap_1().children(badges.into_iter().map(
+                                    |badge| {
+                                        Label::new(badge).size(LabelSize::Small).color(Color::Muted)
+                                    },
+                                ))),
+                        )
+                        .child(
+                            div().max_w_96().child(
+                                Label::new(path.to_string_lossy().into_owned())
+                                    .size(LabelSize::Small)
+                                    .truncate()
+                                    .color(Color::Muted),
+                            ),
+                        ),
+                ),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        Some(
+            h_flex()
+                .w_full()
+                .p_2()
+                .gap_2()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("prune", "Prune")
+                        .tooltip(Tooltip::text(
+                            "Clean up worktrees whose directories were deleted",
+                        ))
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            picker.delegate.prune_worktrees(window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("remove", "Remove")
+                        .disabled(self.selected_removable_worktree().is_none())
+                        .key_binding(KeyBinding::for_action(&menu::SecondaryConfirm, window, cx))
+                        .on_click(cx.listener(|picker, _, window, cx| {
+                            if let Some(worktree) =
+                                picker.delegate.selected_removable_worktree().cloned()
+                            {
+                                picker.delegate.remove_worktree(worktree, window, cx);
+                            }
+                        })),
+                )
+                .into_any(),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        None
+    }
+}
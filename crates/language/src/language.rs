@@ -1689,6 +1689,13 @@ impl Language {
         self.grammar.as_ref()
     }
 
+    /// Whether the language can detect runnables, such as tests, in its buffers.
+    pub fn has_runnables(&self) -> bool {
+        self.grammar
+            .as_ref()
+            .is_some_and(|grammar| grammar.runnable_config.is_some())
+    }
+
     pub fn default_scope(self: &Arc<Self>) -> LanguageScope {
         LanguageScope {
             language: self.clone(),
@@ -43,6 +43,13 @@ pub trait ContextProvider: Send + Sync {
         None
     }
 
+    /// Names of the manifest files of the language, e.g. `Cargo.toml`.
+    /// The closest directory of the file that contains one of them, among the worktree entries,
+    /// is provided as [`task::VariableName::ManifestDir`] by the basic context.
+    fn manifest_file_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// A language server name, that can return tasks using LSP (ext) for this language.
     fn lsp_task_source(&self) -> Option<LanguageServerName> {
         None
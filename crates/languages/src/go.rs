@@ -15,7 +15,7 @@ use std::{
     borrow::Cow,
     ffi::{OsStr, OsString},
     ops::Range,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Output,
     str,
     sync::{
@@ -26,6 +26,8 @@ use std::{
 use task::{TaskTemplate, TaskTemplates, TaskVariables, VariableName};
 use util::{ResultExt, fs::remove_matching, maybe};
 
+use crate::task_context::{self, StandardTaskVariables};
+
 fn server_binary_arguments() -> Vec<OsString> {
     vec!["-mode=stdio".into()]
 }
@@ -439,6 +441,10 @@ const GO_SUBTEST_NAME_TASK_VARIABLE: VariableName =
     VariableName::Custom(Cow::Borrowed("GO_SUBTEST_NAME"));
 
 impl ContextProvider for GoContextProvider {
+    fn manifest_file_names(&self) -> &'static [&'static str] {
+        &["go.mod"]
+    }
+
     fn build_context(
         &self,
         variables: &TaskVariables,
@@ -494,7 +500,22 @@ impl ContextProvider for GoContextProvider {
         let go_subtest_variable = extract_subtest_name(_subtest_name.unwrap_or(""))
             .map(|subtest_name| (GO_SUBTEST_NAME_TASK_VARIABLE.clone(), subtest_name));
 
-        Task::ready(Ok(TaskVariables::from_iter(
+        let snapshot = location.buffer.read(cx).snapshot();
+        let test_name = task_context::enclosing_node_names(
+            &snapshot,
+            location.range.start.to_offset(&snapshot),
+            &["function_declaration"],
+        )
+        .pop()
+        .filter(|function_name| function_name.starts_with("Test"))
+        .map(
+            |function_name| match _subtest_name.and_then(extract_subtest_name) {
+                Some(subtest_name) => format!("{function_name}/{subtest_name}"),
+                None => function_name,
+            },
+        );
+
+        let mut go_variables = TaskVariables::from_iter(
             [
                 go_package_variable,
                 go_subtest_variable,
@@ -502,7 +523,17 @@ impl ContextProvider for GoContextProvider {
             ]
             .into_iter()
             .flatten(),
-        )))
+        );
+        let manifest_dir = variables.get(&VariableName::ManifestDir).map(PathBuf::from);
+        cx.background_spawn(async move {
+            if let Some(path) = local_abs_path {
+                go_variables.extend(
+                    go_standard_variables(&path, manifest_dir.as_deref(), test_name)
+                        .into_task_variables(),
+                );
+            }
+            Ok(go_variables)
+        })
     }
 
     fn associated_tasks(
@@ -632,6 +663,50 @@ impl ContextProvider for GoContextProvider {
     }
 }
 
+fn go_standard_variables(
+    file_abs_path: &Path,
+    manifest_dir: Option<&Path>,
+    test_name: Option<String>,
+) -> StandardTaskVariables {
+    let module = manifest_dir
+        .and_then(|manifest_dir| std::fs::read_to_string(manifest_dir.join("go.mod")).log_err())
+        .and_then(|go_mod| go_module_path(&go_mod));
+    // The import path of the file's package, e.g. `example.com/project/internal/parser`.
+    let package_import_path =
+        module
+            .as_ref()
+            .zip(manifest_dir)
+            .and_then(|(module, manifest_dir)| {
+                let package_dir = file_abs_path.parent()?.strip_prefix(manifest_dir).ok()?;
+                Some(
+                    std::iter::once(module.clone())
+                        .chain(
+                            package_dir.components().map(|component| {
+                                component.as_os_str().to_string_lossy().into_owned()
+                            }),
+                        )
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                )
+            });
+    StandardTaskVariables {
+        test_name,
+        package: module,
+        module_path: package_import_path,
+    }
+}
+
+/// The path of the module declared by the `module` directive of a `go.mod` file.
+fn go_module_path(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        if words.next()? != "module" {
+            return None;
+        }
+        Some(words.next()?.trim_matches('"').to_string())
+    })
+}
+
 fn extract_subtest_name(input: &str) -> Option<String> {
     let replaced_spaces = input.trim_matches('"').replace(' ', "_");
 
@@ -738,4 +813,17 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_go_module_path() {
+        assert_eq!(
+            go_module_path("// comment\nmodule example.com/project\n\ngo 1.22\n"),
+            Some("example.com/project".to_string())
+        );
+        assert_eq!(
+            go_module_path("module \"example.com/quoted\"\n"),
+            Some("example.com/quoted".to_string())
+        );
+        assert_eq!(go_module_path("go 1.22\nmodulex foo\n"), None);
+    }
 }
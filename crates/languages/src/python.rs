@@ -38,6 +38,8 @@ use std::{
 use task::{TaskTemplate, TaskTemplates, VariableName};
 use util::ResultExt;
 
+use crate::task_context::StandardTaskVariables;
+
 pub(crate) struct PyprojectTomlManifestProvider;
 
 impl ManifestProvider for PyprojectTomlManifestProvider {
@@ -361,6 +363,10 @@ const PYTHON_MODULE_NAME_TASK_VARIABLE: VariableName =
     VariableName::Custom(Cow::Borrowed("PYTHON_MODULE_NAME"));
 
 impl ContextProvider for PythonContextProvider {
+    fn manifest_file_names(&self) -> &'static [&'static str] {
+        &["pyproject.toml", "setup.py", "setup.cfg"]
+    }
+
     fn build_context(
         &self,
         variables: &task::TaskVariables,
@@ -377,6 +383,34 @@ impl ContextProvider for PythonContextProvider {
         let module_target = self.build_module_target(variables);
         let worktree_id = location.buffer.read(cx).file().map(|f| f.worktree_id(cx));
 
+        let targets_test = [
+            "_unittest_class_name",
+            "_unittest_method_name",
+            "_pytest_class_name",
+            "_pytest_method_name",
+        ]
+        .into_iter()
+        .any(|capture| {
+            variables
+                .get(&VariableName::Custom(Cow::Borrowed(capture)))
+                .is_some()
+        });
+        let test_name = test_target
+            .as_ref()
+            .filter(|_| targets_test)
+            .map(|(_, test_target)| test_target.clone());
+        let local_abs_path = location
+            .buffer
+            .read(cx)
+            .file()
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)));
+        let manifest_dir = variables.get(&VariableName::ManifestDir).map(PathBuf::from);
+        let standard_variables = cx.background_spawn(async move {
+            local_abs_path
+                .map(|path| python_standard_variables(&path, manifest_dir.as_deref(), test_name))
+                .unwrap_or_default()
+        });
+
         cx.spawn(async move |cx| {
             let active_toolchain = if let Some(worktree_id) = worktree_id {
                 toolchains
@@ -391,12 +425,14 @@ impl ContextProvider for PythonContextProvider {
             };
             let toolchain = (PYTHON_ACTIVE_TOOLCHAIN_PATH, active_toolchain);
 
-            Ok(task::TaskVariables::from_iter(
+            let mut python_variables = task::TaskVariables::from_iter(
                 test_target
                     .into_iter()
                     .chain(module_target.into_iter())
                     .chain([toolchain]),
-            ))
+            );
+            python_variables.extend(standard_variables.await.into_task_variables());
+            Ok(python_variables)
         })
     }
 
@@ -596,6 +632,58 @@ fn python_module_name_from_relative_path(relative_path: &str) -> String {
         .to_string()
 }
 
+fn python_standard_variables(
+    file_abs_path: &Path,
+    manifest_dir: Option<&Path>,
+    test_name: Option<String>,
+) -> StandardTaskVariables {
+    let package = manifest_dir
+        .and_then(|manifest_dir| {
+            python_project_name(&fs::read_to_string(manifest_dir.join("pyproject.toml")).ok()?)
+        })
+        .or_else(|| Some(manifest_dir?.file_name()?.to_string_lossy().into_owned()));
+    let module_path = manifest_dir.and_then(|manifest_dir| {
+        python_module_path(file_abs_path.strip_prefix(manifest_dir).ok()?)
+    });
+    StandardTaskVariables {
+        test_name,
+        package,
+        module_path,
+    }
+}
+
+/// The name of the project declared in `pyproject.toml`, either by PEP 621 or by Poetry.
+fn python_project_name(pyproject_toml: &str) -> Option<String> {
+    let pyproject: toml::Table = toml::from_str(pyproject_toml).log_err()?;
+    pyproject
+        .get("project")
+        .and_then(|project| project.get("name"))
+        .or_else(|| pyproject.get("tool")?.get("poetry")?.get("name"))?
+        .as_str()
+        .map(ToOwned::to_owned)
+}
+
+/// The dotted path of the module defined by the file, relative to the project root.
+/// The `src` directory of projects with the src layout is not a part of the module path.
+fn python_module_path(relative_path: &Path) -> Option<String> {
+    let relative_path = relative_path
+        .strip_prefix("src")
+        .unwrap_or(relative_path)
+        .with_extension("");
+    let mut components = relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if components.last().map(String::as_str) == Some("__init__") {
+        components.pop();
+    }
+    if components.is_empty() {
+        None
+    } else {
+        Some(components.join("."))
+    }
+}
+
 fn python_env_kind_display(k: &PythonEnvironmentKind) -> &'static str {
     match k {
         PythonEnvironmentKind::Conda => "Conda",
@@ -1185,7 +1273,35 @@ mod tests {
     use gpui::{AppContext as _, BorrowAppContext, Context, TestAppContext};
     use language::{AutoindentMode, Buffer, language_settings::AllLanguageSettings};
     use settings::SettingsStore;
-    use std::num::NonZeroU32;
+    use std::{num::NonZeroU32, path::Path};
+
+    use super::{python_module_path, python_project_name};
+
+    #[test]
+    fn test_python_module_path() {
+        assert_eq!(
+            python_module_path(Path::new("app/models/user.py")),
+            Some("app.models.user".to_string())
+        );
+        assert_eq!(
+            python_module_path(Path::new("src/app/__init__.py")),
+            Some("app".to_string())
+        );
+        assert_eq!(python_module_path(Path::new("src/__init__.py")), None);
+    }
+
+    #[test]
+    fn test_python_project_name() {
+        assert_eq!(
+            python_project_name("[project]\nname = \"my-app\"\nversion = \"0.1.0\"\n"),
+            Some("my-app".to_string())
+        );
+        assert_eq!(
+            python_project_name("[tool.poetry]\nname = \"poetry-app\"\n"),
+            Some("poetry-app".to_string())
+        );
+        assert_eq!(python_project_name("[build-system]\nrequires = []\n"), None);
+    }
 
     #[gpui::test]
     async fn test_python_autoindent(cx: &mut TestAppContext) {
@@ -25,6 +25,7 @@ use util::merge_json_value_into;
 use util::{ResultExt, fs::remove_matching, maybe};
 
 use crate::language_settings::language_settings;
+use crate::task_context::{self, StandardTaskVariables};
 
 pub struct RustLspAdapter;
 
@@ -534,6 +535,10 @@ const RUST_TEST_NAME_TASK_VARIABLE: VariableName =
     VariableName::Custom(Cow::Borrowed("RUST_TEST_NAME"));
 
 impl ContextProvider for RustContextProvider {
+    fn manifest_file_names(&self) -> &'static [&'static str] {
+        &["Cargo.toml"]
+    }
+
     fn build_context(
         &self,
         task_variables: &TaskVariables,
@@ -550,6 +555,16 @@ impl ContextProvider for RustContextProvider {
 
         let mut variables = TaskVariables::default();
 
+        let snapshot = location.buffer.read(cx).snapshot();
+        let inline_modules = task_context::enclosing_node_names(
+            &snapshot,
+            location.range.start.to_offset(&snapshot),
+            &["mod_item"],
+        );
+        let test_name = task_variables
+            .get(&VariableName::Custom(Cow::Borrowed("_test_name")))
+            .map(ToOwned::to_owned);
+
         if let (Some(path), Some(stem)) = (&local_abs_path, task_variables.get(&VariableName::Stem))
         {
             let fragment = test_fragment(&variables, &path, stem);
@@ -601,6 +616,34 @@ impl ContextProvider for RustContextProvider {
                     }
                 }
             }
+            if let Some(path) = local_abs_path.as_deref() {
+                let manifest_dir = task_variables
+                    .get(&VariableName::ManifestDir)
+                    .map(Path::new);
+                let module_path = manifest_dir
+                    .and_then(|manifest_dir| rust_module_path(manifest_dir, path))
+                    .map(|mut module_path| {
+                        module_path.extend(inline_modules);
+                        module_path
+                    });
+                let standard_variables = StandardTaskVariables {
+                    test_name: test_name.zip(module_path.as_ref()).map(
+                        |(test_name, module_path)| {
+                            module_path
+                                .iter()
+                                .chain([&test_name])
+                                .map(String::as_str)
+                                .collect::<Vec<_>>()
+                                .join("::")
+                        },
+                    ),
+                    package: variables
+                        .get(&RUST_PACKAGE_TASK_VARIABLE)
+                        .map(ToOwned::to_owned),
+                    module_path: module_path.map(|module_path| module_path.join("::")),
+                };
+                variables.extend(standard_variables.into_task_variables());
+            }
             Ok(variables)
         })
     }
@@ -1010,6 +1053,36 @@ async fn get_cached_server_binary(container_dir: PathBuf) -> Option<LanguageServ
     .log_err()
 }
 
+/// The path of the module defined by the file, relative to the root of its crate.
+/// Crate roots are the `lib.rs` and `main.rs` files in `src`, and the files and directories
+/// in `src/bin`, `tests`, `benches` and `examples`.
+fn rust_module_path(manifest_dir: &Path, file_abs_path: &Path) -> Option<Vec<String>> {
+    let relative_path = file_abs_path
+        .strip_prefix(manifest_dir)
+        .ok()?
+        .with_extension("");
+    let components = relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    let target_dir_depth = match components.as_slice() {
+        [src, bin, ..] if src == "src" && bin == "bin" => 3,
+        [src, ..] if src == "src" => 1,
+        [target_dir, ..] if matches!(target_dir.as_str(), "tests" | "benches" | "examples") => 2,
+        _ => return None,
+    };
+    let mut module_path = components
+        .into_iter()
+        .skip(target_dir_depth)
+        .collect::<Vec<_>>();
+    if matches!(module_path.as_slice(), [root] if root == "lib" || root == "main") {
+        module_path.clear();
+    } else if module_path.last().map(String::as_str) == Some("mod") {
+        module_path.pop();
+    }
+    Some(module_path)
+}
+
 fn test_fragment(variables: &TaskVariables, path: &Path, stem: &str) -> String {
     let fragment = if stem == "lib" {
         // This isn't quite right---it runs the tests for the entire library, rather than
@@ -1342,6 +1415,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rust_module_path() {
+        let manifest_dir = Path::new(path!("/project/crates/foo"));
+        for (file, expected_module_path) in [
+            ("src/lib.rs", Some(vec![])),
+            ("src/main.rs", Some(vec![])),
+            ("src/parser.rs", Some(vec!["parser"])),
+            ("src/parser/mod.rs", Some(vec!["parser"])),
+            ("src/parser/lexer.rs", Some(vec!["parser", "lexer"])),
+            ("src/bin/tool.rs", Some(vec![])),
+            ("src/bin/tool/main.rs", Some(vec![])),
+            ("src/bin/tool/args.rs", Some(vec!["args"])),
+            ("tests/integration.rs", Some(vec![])),
+            ("tests/integration/fixtures.rs", Some(vec!["fixtures"])),
+            ("build.rs", None),
+        ] {
+            let file_abs_path = manifest_dir.join(file);
+            assert_eq!(
+                rust_module_path(manifest_dir, &file_abs_path),
+                expected_module_path
+                    .map(|module_path| module_path.into_iter().map(String::from).collect()),
+                "unexpected module path for {file}"
+            );
+        }
+    }
+
     #[test]
     fn test_target_info_from_metadata() {
         for (input, absolute_path, expected) in [
@@ -0,0 +1,50 @@
+use language::BufferSnapshot;
+use task::{TaskVariables, VariableName};
+
+/// The language-agnostic task variables that every built-in language provides, when applicable,
+/// so that the same task template (e.g. "run the test under cursor") works across languages.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct StandardTaskVariables {
+    /// See [`VariableName::TestName`].
+    pub test_name: Option<String>,
+    /// See [`VariableName::Package`].
+    pub package: Option<String>,
+    /// See [`VariableName::ModulePath`].
+    pub module_path: Option<String>,
+}
+
+impl StandardTaskVariables {
+    pub fn into_task_variables(self) -> TaskVariables {
+        TaskVariables::from_iter(
+            [
+                self.test_name.map(|name| (VariableName::TestName, name)),
+                self.package.map(|name| (VariableName::Package, name)),
+                self.module_path
+                    .map(|path| (VariableName::ModulePath, path)),
+            ]
+            .into_iter()
+            .flatten(),
+        )
+    }
+}
+
+/// Names of the syntax nodes of the given kinds that enclose the offset, outermost first.
+/// The name of a node is the text of its `name` field.
+pub(crate) fn enclosing_node_names(
+    snapshot: &BufferSnapshot,
+    offset: usize,
+    node_kinds: &[&str],
+) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut node = snapshot.syntax_ancestor(offset..offset);
+    while let Some(current) = node {
+        if node_kinds.contains(&current.kind()) {
+            if let Some(name) = current.child_by_field_name("name") {
+                names.push(snapshot.text_for_range(name.byte_range()).collect());
+            }
+        }
+        node = current.parent();
+    }
+    names.reverse();
+    names
+}
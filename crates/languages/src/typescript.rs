@@ -3,12 +3,14 @@ use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use async_trait::async_trait;
 use collections::HashMap;
-use gpui::AsyncApp;
+use gpui::{App, AsyncApp, Task};
 use http_client::github::{AssetKind, GitHubLspBinaryVersion, build_asset_url};
-use language::{LanguageToolchainStore, LspAdapter, LspAdapterDelegate};
+use language::{
+    BufferSnapshot, ContextProvider, LanguageToolchainStore, Location, LspAdapter,
+    LspAdapterDelegate, ToOffset,
+};
 use lsp::{CodeActionKind, LanguageServerBinary, LanguageServerName};
 use node_runtime::NodeRuntime;
-use project::ContextProviderWithTasks;
 use project::{Fs, lsp_store::language_server_settings};
 use serde_json::{Value, json};
 use smol::{fs, io::BufReader, stream::StreamExt};
@@ -18,11 +20,139 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use task::{TaskTemplate, TaskTemplates, VariableName};
+use task::{TaskTemplate, TaskTemplates, TaskVariables, VariableName};
 use util::{ResultExt, fs::remove_matching, maybe};
 
-pub(super) fn typescript_task_context() -> ContextProviderWithTasks {
-    ContextProviderWithTasks::new(TaskTemplates(vec![
+use crate::task_context::StandardTaskVariables;
+
+pub(crate) struct TypeScriptContextProvider;
+
+/// Functions that define tests and test suites in Jest, Mocha, `node:test` and `bun:test`.
+const TEST_FUNCTION_NAMES: &[&str] = &["it", "test", "describe", "context", "suite"];
+
+impl ContextProvider for TypeScriptContextProvider {
+    fn manifest_file_names(&self) -> &'static [&'static str] {
+        &["package.json"]
+    }
+
+    fn build_context(
+        &self,
+        variables: &TaskVariables,
+        location: &Location,
+        _: Option<HashMap<String, String>>,
+        _: Arc<dyn LanguageToolchainStore>,
+        cx: &mut App,
+    ) -> Task<Result<TaskVariables>> {
+        let local_abs_path = location
+            .buffer
+            .read(cx)
+            .file()
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)));
+        let snapshot = location.buffer.read(cx).snapshot();
+        let test_name = js_test_name(&snapshot, location.range.start.to_offset(&snapshot));
+        let manifest_dir = variables.get(&VariableName::ManifestDir).map(PathBuf::from);
+        cx.background_spawn(async move {
+            Ok(local_abs_path
+                .map(|path| js_standard_variables(&path, manifest_dir.as_deref(), test_name))
+                .unwrap_or_default()
+                .into_task_variables())
+        })
+    }
+
+    fn associated_tasks(
+        &self,
+        _: Option<Arc<dyn language::File>>,
+        _: &App,
+    ) -> Option<TaskTemplates> {
+        Some(typescript_task_templates())
+    }
+}
+
+/// The full name of the test at the offset, as Jest reports it:
+/// the names of the enclosing test suites and of the test itself, separated by spaces.
+fn js_test_name(snapshot: &BufferSnapshot, offset: usize) -> Option<String> {
+    let mut names = Vec::new();
+    let mut node = snapshot.syntax_ancestor(offset..offset);
+    while let Some(current) = node {
+        node = current.parent();
+        if current.kind() != "call_expression" {
+            continue;
+        }
+        let Some(mut function) = current.child_by_field_name("function") else {
+            continue;
+        };
+        // `test.only`, `describe.each(table)` and similar modifiers.
+        loop {
+            let inner = match function.kind() {
+                "member_expression" => function.child_by_field_name("object"),
+                "call_expression" => function.child_by_field_name("function"),
+                _ => None,
+            };
+            match inner {
+                Some(inner) => function = inner,
+                None => break,
+            }
+        }
+        let function_name = snapshot
+            .text_for_range(function.byte_range())
+            .collect::<String>();
+        if !TEST_FUNCTION_NAMES.contains(&function_name.as_str()) {
+            continue;
+        }
+        if let Some(name) = current
+            .child_by_field_name("arguments")
+            .and_then(|arguments| arguments.named_child(0))
+            .filter(|argument| argument.kind() == "string")
+            .and_then(|string| string.named_child(0))
+        {
+            names.push(
+                snapshot
+                    .text_for_range(name.byte_range())
+                    .collect::<String>(),
+            );
+        }
+    }
+    names.reverse();
+    if names.is_empty() {
+        None
+    } else {
+        Some(names.join(" "))
+    }
+}
+
+fn js_standard_variables(
+    file_abs_path: &Path,
+    manifest_dir: Option<&Path>,
+    test_name: Option<String>,
+) -> StandardTaskVariables {
+    let package = manifest_dir.and_then(|manifest_dir| {
+        let package_json = std::fs::read_to_string(manifest_dir.join("package.json")).ok()?;
+        let package_json: Value = serde_json::from_str(&package_json).log_err()?;
+        Some(package_json.get("name")?.as_str()?.to_owned())
+    });
+    // The path used to import the file, relative to the package root, e.g. `src/utils/format`.
+    let module_path = manifest_dir.and_then(|manifest_dir| {
+        let relative_path = file_abs_path
+            .strip_prefix(manifest_dir)
+            .ok()?
+            .with_extension("");
+        Some(
+            relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    });
+    StandardTaskVariables {
+        test_name,
+        package,
+        module_path,
+    }
+}
+
+fn typescript_task_templates() -> TaskTemplates {
+    TaskTemplates(vec![
         TaskTemplate {
             label: "jest file test".to_owned(),
             command: "npx jest".to_owned(),
@@ -49,7 +179,7 @@ pub(super) fn typescript_task_context() -> ContextProviderWithTasks {
             ],
             ..TaskTemplate::default()
         },
-    ]))
+    ])
 }
 
 fn typescript_server_binary_arguments(server_path: &Path) -> Vec<OsString> {
@@ -635,4 +765,37 @@ mod tests {
             ]
         );
     }
+
+    #[gpui::test]
+    async fn test_js_test_name(cx: &mut TestAppContext) {
+        let language = crate::language(
+            "typescript",
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        );
+
+        let text = r#"
+            describe("parser", () => {
+              describe.each([1, 2])("numbers", (n) => {
+                it("parses ints", () => {});
+              });
+              test.only("parses strings", () => {});
+            });
+            const notATest = fn("helper");
+        "#
+        .unindent();
+
+        let buffer = cx.new(|cx| language::Buffer::local(&text, cx).with_language(language, cx));
+        let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+        let test_name_at =
+            |needle: &str| super::js_test_name(&snapshot, text.find(needle).unwrap());
+        assert_eq!(
+            test_name_at("parses ints").as_deref(),
+            Some("parser numbers parses ints")
+        );
+        assert_eq!(
+            test_name_at("parses strings").as_deref(),
+            Some("parser parses strings")
+        );
+        assert_eq!(test_name_at("helper"), None);
+    }
 }
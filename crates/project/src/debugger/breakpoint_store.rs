@@ -4,7 +4,7 @@
 use anyhow::{Result, anyhow};
 use breakpoints_in_file::BreakpointsInFile;
 use collections::BTreeMap;
-use dap::client::SessionId;
+use dap::{StackFrameId, client::SessionId};
 use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
 use itertools::Itertools;
 use language::{Buffer, BufferSnapshot, proto::serialize_anchor as serialize_text_anchor};
@@ -108,10 +108,22 @@ enum BreakpointStoreMode {
     Local(LocalBreakpointStore),
     Remote(RemoteBreakpointStore),
 }
+
+/// The stack frame that the debugger UI shows, whose line is highlighted in editors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveStackFrame {
+    pub session_id: SessionId,
+    pub stack_frame_id: StackFrameId,
+    pub path: Arc<Path>,
+    pub position: text::Anchor,
+}
+
 pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    function_breakpoints: Vec<FunctionBreakpoint>,
+    data_breakpoints: Vec<DataBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
-    active_stack_frame: Option<(SessionId, Arc<Path>, text::Anchor)>,
+    active_stack_frame: Option<ActiveStackFrame>,
     // E.g ssh
     mode: BreakpointStoreMode,
 }
@@ -124,6 +136,8 @@ impl BreakpointStore {
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Local(LocalBreakpointStore {
                 worktree_store,
                 buffer_store,
@@ -136,6 +150,8 @@ impl BreakpointStore {
     pub(crate) fn remote(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 _upstream_project_id: upstream_project_id,
@@ -465,6 +481,125 @@ impl BreakpointStore {
         let breakpoint_paths = self.breakpoints.keys().cloned().collect();
         self.breakpoints.clear();
         cx.emit(BreakpointStoreEvent::BreakpointsCleared(breakpoint_paths));
+
+        if !self.function_breakpoints.is_empty() {
+            self.function_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        }
+        if !self.data_breakpoints.is_empty() {
+            self.data_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        }
+    }
+
+    pub fn function_breakpoints(&self) -> &[FunctionBreakpoint] {
+        &self.function_breakpoints
+    }
+
+    /// Adds a breakpoint on the function with the given name, or removes it if there already is one.
+    pub fn toggle_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        if let Some(ix) = self
+            .function_breakpoints
+            .iter()
+            .position(|breakpoint| &*breakpoint.name == name)
+        {
+            self.function_breakpoints.remove(ix);
+        } else {
+            self.function_breakpoints.push(FunctionBreakpoint {
+                name: name.into(),
+                condition: None,
+                hit_condition: None,
+                state: BreakpointState::Enabled,
+            });
+        }
+
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn invert_function_breakpoint_state(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(breakpoint) = self
+            .function_breakpoints
+            .iter_mut()
+            .find(|breakpoint| &*breakpoint.name == name)
+        {
+            breakpoint.state = breakpoint.state.inverted();
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    pub fn data_breakpoints(&self) -> &[DataBreakpoint] {
+        &self.data_breakpoints
+    }
+
+    /// Adds the data breakpoint, or removes it if there already is one for the same data and access type.
+    pub fn toggle_data_breakpoint(&mut self, breakpoint: DataBreakpoint, cx: &mut Context<Self>) {
+        if let Some(ix) = self.data_breakpoints.iter().position(|existing| {
+            existing.data_id == breakpoint.data_id && existing.access_type == breakpoint.access_type
+        }) {
+            self.data_breakpoints.remove(ix);
+        } else {
+            self.data_breakpoints.push(breakpoint);
+        }
+
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn invert_data_breakpoint_state(
+        &mut self,
+        data_id: &str,
+        access_type: DataBreakpointAccessType,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(breakpoint) = self.data_breakpoints.iter_mut().find(|breakpoint| {
+            &*breakpoint.data_id == data_id && breakpoint.access_type == access_type
+        }) {
+            breakpoint.state = breakpoint.state.inverted();
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    /// Removes the data breakpoints whose data id is only valid in the given session, e.g. once it has ended.
+    pub fn remove_session_data_breakpoints(
+        &mut self,
+        session_id: SessionId,
+        cx: &mut Context<Self>,
+    ) {
+        let old_len = self.data_breakpoints.len();
+        self.data_breakpoints
+            .retain(|breakpoint| breakpoint.session_id != Some(session_id));
+        if self.data_breakpoints.len() != old_len {
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    /// Data breakpoints which stay valid across debug sessions, and can thus be persisted.
+    pub fn persistent_data_breakpoints(&self) -> Vec<DataBreakpoint> {
+        self.data_breakpoints
+            .iter()
+            .filter(|breakpoint| breakpoint.session_id.is_none())
+            .cloned()
+            .collect()
+    }
+
+    pub fn with_serialized_function_and_data_breakpoints(
+        &mut self,
+        function_breakpoints: Vec<FunctionBreakpoint>,
+        data_breakpoints: Vec<DataBreakpoint>,
+        cx: &mut Context<BreakpointStore>,
+    ) {
+        self.function_breakpoints = function_breakpoints;
+        self.data_breakpoints = data_breakpoints;
+        cx.notify();
     }
 
     pub fn breakpoints<'a>(
@@ -493,7 +628,7 @@ impl BreakpointStore {
             })
     }
 
-    pub fn active_position(&self) -> Option<&(SessionId, Arc<Path>, text::Anchor)> {
+    pub fn active_position(&self) -> Option<&ActiveStackFrame> {
         self.active_stack_frame.as_ref()
     }
 
@@ -504,7 +639,7 @@ impl BreakpointStore {
     ) {
         if let Some(session_id) = session_id {
             self.active_stack_frame
-                .take_if(|(id, _, _)| *id == session_id);
+                .take_if(|active_stack_frame| active_stack_frame.session_id == session_id);
         } else {
             self.active_stack_frame.take();
         }
@@ -515,10 +650,10 @@ impl BreakpointStore {
 
     pub fn set_active_position(
         &mut self,
-        position: (SessionId, Arc<Path>, text::Anchor),
+        active_stack_frame: ActiveStackFrame,
         cx: &mut Context<Self>,
     ) {
-        self.active_stack_frame = Some(position);
+        self.active_stack_frame = Some(active_stack_frame);
         cx.emit(BreakpointStoreEvent::ActiveDebugLineChanged);
         cx.notify();
     }
@@ -688,6 +823,8 @@ pub enum BreakpointStoreEvent {
     ActiveDebugLineChanged,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    FunctionBreakpointsUpdated,
+    DataBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -727,6 +864,14 @@ impl BreakpointState {
             BreakpointState::Disabled => 1,
         }
     }
+
+    #[inline]
+    pub fn inverted(&self) -> Self {
+        match self {
+            BreakpointState::Enabled => BreakpointState::Disabled,
+            BreakpointState::Disabled => BreakpointState::Enabled,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -832,3 +977,101 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// Breakpoint on a function, identified by its name.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBreakpoint {
+    pub name: Arc<str>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl From<FunctionBreakpoint> for dap::FunctionBreakpoint {
+    fn from(bp: FunctionBreakpoint) -> Self {
+        Self {
+            name: String::from(bp.name.as_ref()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DataBreakpointAccessType {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl DataBreakpointAccessType {
+    #[inline]
+    pub fn to_int(&self) -> i32 {
+        match self {
+            DataBreakpointAccessType::Read => 0,
+            DataBreakpointAccessType::Write => 1,
+            DataBreakpointAccessType::ReadWrite => 2,
+        }
+    }
+
+    pub fn from_int(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(DataBreakpointAccessType::Read),
+            1 => Some(DataBreakpointAccessType::Write),
+            2 => Some(DataBreakpointAccessType::ReadWrite),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataBreakpointAccessType::Read => "Read",
+            DataBreakpointAccessType::Write => "Write",
+            DataBreakpointAccessType::ReadWrite => "Read/Write",
+        }
+    }
+}
+
+impl From<DataBreakpointAccessType> for dap::DataBreakpointAccessType {
+    fn from(access_type: DataBreakpointAccessType) -> Self {
+        match access_type {
+            DataBreakpointAccessType::Read => dap::DataBreakpointAccessType::Read,
+            DataBreakpointAccessType::Write => dap::DataBreakpointAccessType::Write,
+            DataBreakpointAccessType::ReadWrite => dap::DataBreakpointAccessType::ReadWrite,
+        }
+    }
+}
+
+/// Breakpoint on an access to some data, e.g. writes to a variable, also called a watchpoint.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DataBreakpoint {
+    /// The id the debug adapter gave the data in its `dataBreakpointInfo` response.
+    pub data_id: Arc<str>,
+    /// What the data is, e.g. the name of the variable.
+    pub description: Arc<str>,
+    pub access_type: DataBreakpointAccessType,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+    /// The session the data id is valid in, `None` when it's valid across sessions.
+    pub session_id: Option<SessionId>,
+}
+
+impl From<DataBreakpoint> for dap::DataBreakpoint {
+    fn from(bp: DataBreakpoint) -> Self {
+        Self {
+            data_id: String::from(bp.data_id.as_ref()),
+            access_type: Some(bp.access_type.into()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
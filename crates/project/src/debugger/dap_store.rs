@@ -771,6 +771,9 @@ impl DapStore {
         };
 
         let shutdown_task = session.update(cx, |this, cx| this.shutdown(cx));
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.remove_session_data_breakpoints(session_id, cx)
+        });
 
         cx.background_spawn(async move {
             if shutdown_children.len() > 0 {
@@ -809,6 +812,7 @@ impl DapStore {
     ) -> Result<proto::DebugTaskDefinition> {
         let template = DebugTaskTemplate {
             locator: Some(envelope.payload.locator),
+            depends_on: Vec::new(),
             definition: DebugTaskDefinition::from_proto(
                 envelope
                     .payload
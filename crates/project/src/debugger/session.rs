@@ -1,16 +1,20 @@
 use super::breakpoint_store::{
-    BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
+    BreakpointState, BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason,
+    DataBreakpoint, DataBreakpointAccessType, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
-    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
-    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, ExceptionInfoCommand, Initialize,
+    Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand, NextCommand,
+    PauseCommand, ReadMemoryCommand, RestartCommand, RestartStackFrameCommand, ScopesCommand,
+    SetExceptionBreakpoints, SetInstructionBreakpointsCommand, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
+    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    WriteMemoryCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
+use base64::prelude::*;
 use collections::{HashMap, HashSet, IndexMap, IndexSet};
 use dap::adapters::DebugAdapterBinary;
 use dap::messages::Response;
@@ -32,7 +36,7 @@ use rpc::AnyProtoClient;
 use serde_json::{Value, json};
 use smol::stream::StreamExt;
 use std::any::TypeId;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::u64;
 use std::{
     any::Any,
@@ -303,9 +307,63 @@ impl LocalMode {
         })
     }
 
+    fn send_function_breakpoints(
+        &self,
+        ignore_breakpoints: bool,
+        cx: &App,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            self.breakpoint_store
+                .read(cx)
+                .function_breakpoints()
+                .iter()
+                .filter(|bp| bp.state.is_enabled())
+                .cloned()
+                .map(Into::into)
+                .collect()
+        };
+
+        self.request(
+            dap_command::SetFunctionBreakpoints { breakpoints },
+            cx.background_executor().clone(),
+        )
+    }
+
+    fn send_data_breakpoints(
+        &self,
+        ignore_breakpoints: bool,
+        cx: &App,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let session_id = self.client.id();
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            self.breakpoint_store
+                .read(cx)
+                .data_breakpoints()
+                .iter()
+                .filter(|bp| {
+                    bp.state.is_enabled()
+                        && bp
+                            .session_id
+                            .is_none_or(|bp_session| bp_session == session_id)
+                })
+                .cloned()
+                .map(Into::into)
+                .collect()
+        };
+
+        self.request(
+            dap_command::SetDataBreakpoints { breakpoints },
+            cx.background_executor().clone(),
+        )
+    }
+
     fn send_exception_breakpoints(
         &self,
-        filters: Vec<ExceptionBreakpointsFilter>,
+        filters: Vec<(ExceptionBreakpointsFilter, Option<String>)>,
         supports_filter_options: bool,
         cx: &App,
     ) -> Task<Result<Vec<dap::Breakpoint>>> {
@@ -313,16 +371,19 @@ impl LocalMode {
             SetExceptionBreakpoints::WithOptions {
                 filters: filters
                     .into_iter()
-                    .map(|filter| ExceptionFilterOptions {
+                    .map(|(filter, condition)| ExceptionFilterOptions {
                         filter_id: filter.filter,
-                        condition: None,
+                        condition,
                         mode: None,
                     })
                     .collect(),
             }
         } else {
             SetExceptionBreakpoints::Plain {
-                filters: filters.into_iter().map(|filter| filter.filter).collect(),
+                filters: filters
+                    .into_iter()
+                    .map(|(filter, _)| filter.filter)
+                    .collect(),
             }
         };
         self.request(arg, cx.background_executor().clone())
@@ -419,13 +480,16 @@ impl LocalMode {
                 exception_filters
                     .iter()
                     .filter(|filter| filter.default == Some(true))
-                    .cloned()
+                    .map(|filter| (filter.clone(), None))
                     .collect::<Vec<_>>()
             })
             .unwrap_or_default();
         let supports_exception_filters = capabilities
             .supports_exception_filter_options
             .unwrap_or_default();
+        let supports_function_breakpoints =
+            dap_command::SetFunctionBreakpoints::is_supported(capabilities);
+        let supports_data_breakpoints = dap_command::SetDataBreakpoints::is_supported(capabilities);
         let configuration_sequence = cx.spawn({
             let this = self.clone();
             let worktree = self.worktree().clone();
@@ -471,6 +535,16 @@ impl LocalMode {
                 })?
                 .await
                 .ok();
+                if supports_function_breakpoints {
+                    cx.update(|cx| this.send_function_breakpoints(false, cx))?
+                        .await
+                        .log_err();
+                }
+                if supports_data_breakpoints {
+                    cx.update(|cx| this.send_data_breakpoints(false, cx))?
+                        .await
+                        .log_err();
+                }
                 let ret = if configuration_done_supported {
                     this.request(ConfigurationDone {}, cx.background_executor().clone())
                 } else {
@@ -626,6 +700,9 @@ pub struct Session {
     is_session_terminated: bool,
     requests: HashMap<TypeId, HashMap<RequestSlot, Shared<Task<Option<()>>>>>,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
+    exception_breakpoint_conditions: HashMap<String, String>,
+    exception_info: Option<ExceptionInfo>,
+    instruction_breakpoints: BTreeSet<String>,
     _background_tasks: Vec<Task<()>>,
 }
 
@@ -677,6 +754,26 @@ impl Hash for RequestSlot {
     }
 }
 
+/// The exception a thread stopped on, with the details from an `exceptionInfo` request when the
+/// debug adapter supports it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExceptionInfo {
+    pub thread_id: ThreadId,
+    pub exception_id: String,
+    pub description: Option<String>,
+    pub details: Option<dap::ExceptionDetails>,
+}
+
+/// A block of the debuggee's memory, read with a `readMemory` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryBlock {
+    /// The address of the first byte, in the format the debug adapter uses (usually hexadecimal).
+    pub address: String,
+    pub data: Vec<u8>,
+    /// How many bytes after `data` could not be read.
+    pub unreadable_bytes: u64,
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct CompletionsQuery {
     pub query: String,
@@ -703,11 +800,14 @@ impl CompletionsQuery {
 
 pub enum SessionEvent {
     Modules,
+    Memory,
+    InstructionBreakpoints,
     LoadedSources,
     Stopped(Option<ThreadId>),
     StackTrace,
     Variables,
     Threads,
+    ExceptionInfo,
 }
 
 pub(super) enum SessionStateEvent {
@@ -793,6 +893,9 @@ impl Session {
             locations: Default::default(),
             is_session_terminated: false,
             exception_breakpoints: Default::default(),
+            exception_breakpoint_conditions: Default::default(),
+            exception_info: None,
+            instruction_breakpoints: Default::default(),
         }
     }
 
@@ -1037,6 +1140,12 @@ impl Session {
         self.invalidate_generic();
         self.threads.clear();
         self.variables.clear();
+        self.exception_info = None;
+        if let (dap::StoppedEventReason::Exception, Some(thread_id)) =
+            (&event.reason, event.thread_id)
+        {
+            self.fetch_exception_info(ThreadId(thread_id), &event, cx);
+        }
         cx.emit(SessionEvent::Stopped(
             event
                 .thread_id
@@ -1132,7 +1241,7 @@ impl Session {
                 self.capabilities = self.capabilities.merge(event.capabilities);
                 cx.notify();
             }
-            Events::Memory(_) => {}
+            Events::Memory(_) => cx.emit(SessionEvent::Memory),
             Events::Process(_) => {}
             Events::ProgressEnd(_) => {}
             Events::ProgressStart(_) => {}
@@ -1347,6 +1456,8 @@ impl Session {
         }
 
         self.ignore_breakpoints = ignore;
+        self.send_function_breakpoints(cx);
+        self.send_data_breakpoints(cx);
 
         if let Some(local) = self.as_local() {
             local.send_source_breakpoints(ignore, cx)
@@ -1369,12 +1480,59 @@ impl Session {
         }
     }
 
+    pub fn exception_breakpoint_condition(&self, id: &str) -> Option<&str> {
+        self.exception_breakpoint_conditions
+            .get(id)
+            .map(String::as_str)
+    }
+
+    /// Sets the condition of an exception filter that supports conditions, which the debug adapter
+    /// checks before stopping on an exception that the filter matches.
+    /// Conditions are sent as exception filter options, so adapters without them ignore this.
+    pub fn set_exception_breakpoint_condition(
+        &mut self,
+        id: &str,
+        condition: Option<String>,
+        cx: &App,
+    ) {
+        let Some((filter, is_enabled)) = self.exception_breakpoints.get(id) else {
+            return;
+        };
+        if !filter.supports_condition.unwrap_or_default()
+            || !self
+                .capabilities
+                .supports_exception_filter_options
+                .unwrap_or_default()
+        {
+            return;
+        }
+        let is_enabled = *is_enabled;
+
+        let condition = condition.filter(|condition| !condition.trim().is_empty());
+        let previous_condition = match condition {
+            Some(condition) => self
+                .exception_breakpoint_conditions
+                .insert(id.to_owned(), condition),
+            None => self.exception_breakpoint_conditions.remove(id),
+        };
+        if is_enabled && previous_condition.as_deref() != self.exception_breakpoint_condition(id) {
+            self.send_exception_breakpoints(cx);
+        }
+    }
+
     fn send_exception_breakpoints(&mut self, cx: &App) {
         if let Some(local) = self.as_local() {
             let exception_filters = self
                 .exception_breakpoints
-                .values()
-                .filter_map(|(filter, is_enabled)| is_enabled.then(|| filter.clone()))
+                .iter()
+                .filter_map(|(id, (filter, is_enabled))| {
+                    is_enabled.then(|| {
+                        (
+                            filter.clone(),
+                            self.exception_breakpoint_conditions.get(id).cloned(),
+                        )
+                    })
+                })
                 .collect();
 
             let supports_exception_filters = self
@@ -1389,6 +1547,83 @@ impl Session {
         }
     }
 
+    fn send_function_breakpoints(&self, cx: &App) {
+        if !dap_command::SetFunctionBreakpoints::is_supported(&self.capabilities) {
+            return;
+        }
+        if let Some(local) = self.as_local() {
+            local
+                .send_function_breakpoints(self.ignore_breakpoints, cx)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn send_data_breakpoints(&self, cx: &App) {
+        if !dap_command::SetDataBreakpoints::is_supported(&self.capabilities) {
+            return;
+        }
+        if let Some(local) = self.as_local() {
+            local
+                .send_data_breakpoints(self.ignore_breakpoints, cx)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    /// Asks the debug adapter whether it can break on accesses to the given variable,
+    /// and adds a data breakpoint for it if so.
+    pub fn add_data_breakpoint(
+        &mut self,
+        variables_reference: VariableReference,
+        name: String,
+        access_type: DataBreakpointAccessType,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(breakpoint_store) = self.as_local().map(|local| local.breakpoint_store.clone())
+        else {
+            return Task::ready(Err(anyhow!(
+                "Data breakpoints are only supported in local sessions"
+            )));
+        };
+        let session_id = self.id;
+        let info = self.request_local(
+            DataBreakpointInfoCommand {
+                variables_reference,
+                name: name.clone(),
+            },
+            cx,
+        );
+
+        cx.spawn(async move |_, cx| {
+            let info = info.await?;
+            let data_id = info.data_id.with_context(|| {
+                format!("Can't break on accesses to `{name}`: {}", info.description)
+            })?;
+            if let Some(access_types) = info.access_types {
+                if !access_types.contains(&access_type.into()) {
+                    anyhow::bail!(
+                        "The debug adapter can't break on `{}` accesses to `{name}`",
+                        access_type.label()
+                    );
+                }
+            }
+
+            breakpoint_store.update(cx, |store, cx| {
+                store.toggle_data_breakpoint(
+                    DataBreakpoint {
+                        data_id: data_id.into(),
+                        description: info.description.into(),
+                        access_type,
+                        condition: None,
+                        hit_condition: None,
+                        state: BreakpointState::Enabled,
+                        session_id: (!info.can_persist.unwrap_or_default()).then_some(session_id),
+                    },
+                    cx,
+                );
+            })
+        })
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -1794,6 +2029,22 @@ impl Session {
             .unwrap_or_default()
     }
 
+    /// The variables of the scopes of a stack frame that have been fetched already,
+    /// leaving out the scopes the debug adapter considers expensive to fetch.
+    pub fn variables_by_stack_frame_id(&self, stack_frame_id: StackFrameId) -> Vec<dap::Variable> {
+        let Some(stack_frame) = self.stack_frames.get(&stack_frame_id) else {
+            return Vec::new();
+        };
+        stack_frame
+            .scopes
+            .iter()
+            .filter(|scope| !scope.expensive)
+            .filter_map(|scope| self.variables.get(&scope.variables_reference))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
     pub fn variables(
         &mut self,
         variables_reference: VariableReference,
@@ -1891,6 +2142,236 @@ impl Session {
         .detach();
     }
 
+    /// Evaluates a watch expression in the context of the given stack frame.
+    /// Unlike [`Self::evaluate`], the result is not echoed to the console output.
+    pub fn evaluate_watch(
+        &mut self,
+        expression: String,
+        frame_id: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<dap::EvaluateResponse>> {
+        if self.is_session_terminated {
+            return Task::ready(Err(anyhow!("Debug session has terminated")));
+        }
+
+        self.mode.request_dap(
+            self.id,
+            EvaluateCommand {
+                expression,
+                context: Some(EvaluateArgumentsContext::Watch),
+                frame_id,
+                source: None,
+            },
+            cx,
+        )
+    }
+
+    /// Sends a request that is not forwarded to remote collaborators yet.
+    fn request_local<R: LocalDapCommand>(
+        &self,
+        request: R,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<R::Response>>
+    where
+        <R::DapRequest as dap::requests::Request>::Response: 'static,
+        <R::DapRequest as dap::requests::Request>::Arguments: 'static + Send,
+    {
+        if !R::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support the `{}` request",
+                <R::DapRequest as dap::requests::Request>::COMMAND
+            )));
+        }
+
+        match self.as_local() {
+            Some(local) => local.request(request, cx.background_executor().clone()),
+            None => Task::ready(Err(anyhow!(
+                "Sending dap commands over RPC isn't supported yet"
+            ))),
+        }
+    }
+
+    /// The exception the session stopped on, while the thread that raised it is stopped.
+    pub fn exception_info(&self) -> Option<&ExceptionInfo> {
+        self.exception_info.as_ref().filter(|exception_info| {
+            self.thread_states.thread_status(exception_info.thread_id) == ThreadStatus::Stopped
+        })
+    }
+
+    fn fetch_exception_info(
+        &mut self,
+        thread_id: ThreadId,
+        event: &StoppedEvent,
+        cx: &mut Context<Self>,
+    ) {
+        self.exception_info = Some(ExceptionInfo {
+            thread_id,
+            exception_id: event
+                .description
+                .clone()
+                .unwrap_or_else(|| "Exception".to_owned()),
+            description: event.text.clone(),
+            details: None,
+        });
+        cx.emit(SessionEvent::ExceptionInfo);
+
+        if !ExceptionInfoCommand::is_supported(&self.capabilities) {
+            return;
+        }
+        let request = self.request_local(
+            ExceptionInfoCommand {
+                thread_id: thread_id.0,
+            },
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let response = request.await?;
+            this.update(cx, |this, cx| {
+                if this
+                    .exception_info
+                    .as_ref()
+                    .is_some_and(|exception_info| exception_info.thread_id == thread_id)
+                {
+                    this.exception_info = Some(ExceptionInfo {
+                        thread_id,
+                        exception_id: response.exception_id,
+                        description: response.description,
+                        details: response.details,
+                    });
+                    cx.emit(SessionEvent::ExceptionInfo);
+                    cx.notify();
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn read_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<MemoryBlock>> {
+        let request = self.request_local(
+            ReadMemoryCommand {
+                memory_reference,
+                offset,
+                count,
+            },
+            cx,
+        );
+
+        cx.background_spawn(async move {
+            let response = request.await?;
+            let data = match response.data {
+                Some(data) => BASE64_STANDARD
+                    .decode(data)
+                    .context("decoding memory returned by the debug adapter")?,
+                None => Vec::new(),
+            };
+
+            Ok(MemoryBlock {
+                address: response.address,
+                unreadable_bytes: response.unreadable_bytes.unwrap_or_default(),
+                data,
+            })
+        })
+    }
+
+    pub fn write_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        data: Vec<u8>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let request = self.request_local(
+            WriteMemoryCommand {
+                memory_reference,
+                offset,
+                data,
+            },
+            cx,
+        );
+
+        cx.spawn(async move |this, cx| {
+            request.await?;
+            this.update(cx, |this, cx| {
+                // Variables may point into the memory that was just written.
+                this.invalidate_command_type::<VariablesCommand>();
+                this.variables.clear();
+                cx.emit(SessionEvent::Memory);
+                cx.emit(SessionEvent::Variables);
+                cx.notify();
+            })
+        })
+    }
+
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<dap::DisassembledInstruction>>> {
+        self.request_local(
+            DisassembleCommand {
+                memory_reference,
+                instruction_offset,
+                instruction_count,
+            },
+            cx,
+        )
+    }
+
+    /// Instruction references of the instruction breakpoints set in this session.
+    pub fn instruction_breakpoints(&self) -> &BTreeSet<String> {
+        &self.instruction_breakpoints
+    }
+
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !SetInstructionBreakpointsCommand::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support instruction breakpoints"
+            )));
+        }
+
+        self.flip_instruction_breakpoint(instruction_reference.clone(), cx);
+        let request = self.request_local(
+            SetInstructionBreakpointsCommand {
+                instruction_references: self.instruction_breakpoints.iter().cloned().collect(),
+            },
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            if let Err(error) = request.await {
+                // Keep showing the breakpoints that the debug adapter knows about.
+                this.update(cx, |this, cx| {
+                    this.flip_instruction_breakpoint(instruction_reference, cx);
+                })?;
+                return Err(error);
+            }
+            Ok(())
+        })
+    }
+
+    fn flip_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.instruction_breakpoints.remove(&instruction_reference) {
+            self.instruction_breakpoints.insert(instruction_reference);
+        }
+        cx.emit(SessionEvent::InstructionBreakpoints);
+        cx.notify();
+    }
+
     pub fn location(
         &mut self,
         reference: u64,
@@ -1990,6 +2471,12 @@ fn create_local_session(
                 local.unset_breakpoints_from_paths(paths, cx).detach();
             }
         }
+        BreakpointStoreEvent::FunctionBreakpointsUpdated => {
+            this.send_function_breakpoints(cx);
+        }
+        BreakpointStoreEvent::DataBreakpointsUpdated => {
+            this.send_data_breakpoints(cx);
+        }
         BreakpointStoreEvent::ActiveDebugLineChanged => {}
     })
     .detach();
@@ -2012,6 +2499,9 @@ fn create_local_session(
         stack_frames: IndexMap::default(),
         locations: Default::default(),
         exception_breakpoints: Default::default(),
+        exception_breakpoint_conditions: Default::default(),
+        exception_info: None,
+        instruction_breakpoints: Default::default(),
         _background_tasks,
         is_session_terminated: false,
     }
@@ -1,7 +1,7 @@
 use std::{path::Path, sync::Arc};
 
 use anyhow::Result;
-use dap::{DebugRequest, client::DebugAdapterClient};
+use dap::{DebugRequest, client::DebugAdapterClient, recording::DapRecording};
 use gpui::{App, AppContext, Entity, Subscription, Task};
 use task::DebugTaskDefinition;
 
@@ -48,19 +48,40 @@ pub fn start_debug_session<T: Fn(&Arc<DebugAdapterClient>) + 'static>(
     cx: &mut gpui::TestAppContext,
     configure: T,
 ) -> Task<Result<Entity<Session>>> {
-    start_debug_session_with(
-        project,
-        cx,
-        DebugTaskDefinition {
-            adapter: "fake-adapter".to_string(),
-            request: DebugRequest::Launch(Default::default()),
-            label: "test".to_string(),
-            initialize_args: None,
-            tcp_connection: None,
-            stop_on_entry: None,
-        },
-        configure,
-    )
+    start_debug_session_with(project, cx, fake_debug_task_definition(), configure)
+}
+
+/// Starts a debug session whose debug adapter replays a recorded debug session.
+pub fn replay_debug_session(
+    project: &Entity<Project>,
+    cx: &mut gpui::TestAppContext,
+    recording: DapRecording,
+) -> Task<Result<Entity<Session>>> {
+    let subscription = cx.update(|cx| {
+        cx.observe_new::<Session>(move |session, _, _| {
+            let client = session.adapter_client().unwrap();
+            client.replay(&recording);
+        })
+    });
+    let task = project.update(cx, |project, cx| {
+        project.start_debug_session(fake_debug_task_definition(), cx)
+    });
+    cx.spawn(async move |_| {
+        let result = task.await;
+        drop(subscription);
+        result
+    })
+}
+
+fn fake_debug_task_definition() -> DebugTaskDefinition {
+    DebugTaskDefinition {
+        adapter: "fake-adapter".to_string(),
+        request: DebugRequest::Launch(Default::default()),
+        label: "test".to_string(),
+        initialize_args: None,
+        tcp_connection: None,
+        stop_on_entry: None,
+    }
 }
 
 fn register_default_handlers(session: &Session, client: &Arc<DebugAdapterClient>, cx: &mut App) {
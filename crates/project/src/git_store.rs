@@ -21,9 +21,11 @@ use git::{
     blame::Blame,
     parse_git_remote_url,
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
-        UpstreamTrackingStatus,
+        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, CommitSignature,
+        ConflictTexts, DiffType, FileHistoryEntry, GitRepository, GitRepositoryCheckpoint,
+        GitWorktree, LogEntry, LogOptions, PushOptions, RebaseAction, RebaseState, RebaseTodoEntry,
+        Remote, RemoteCommandOutput, RepoPath, ResetMode, SignatureStatus, StashEntry,
+        StashOptions, Tag, UpstreamTrackingStatus,
     },
     status::{
         FileStatus, GitSummary, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode,
@@ -234,6 +236,7 @@ pub struct RepositorySnapshot {
     pub head_commit: Option<CommitDetails>,
     pub merge_conflicts: TreeSet<RepoPath>,
     pub merge_head_shas: Vec<SharedString>,
+    pub rebase_state: Option<RebaseState>,
     pub scan_id: u64,
 }
 
@@ -402,6 +405,10 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_get_branches);
         client.add_entity_request_handler(Self::handle_change_branch);
         client.add_entity_request_handler(Self::handle_create_branch);
+        client.add_entity_request_handler(Self::handle_rename_branch);
+        client.add_entity_request_handler(Self::handle_delete_branch);
+        client.add_entity_request_handler(Self::handle_set_upstream);
+        client.add_entity_request_handler(Self::handle_compare_branch);
         client.add_entity_request_handler(Self::handle_git_init);
         client.add_entity_request_handler(Self::handle_push);
         client.add_entity_request_handler(Self::handle_pull);
@@ -413,6 +420,33 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_checkout_files);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_get_stash_entries);
+        client.add_entity_request_handler(Self::handle_stash);
+        client.add_entity_request_handler(Self::handle_stash_apply);
+        client.add_entity_request_handler(Self::handle_stash_pop);
+        client.add_entity_request_handler(Self::handle_stash_drop);
+        client.add_entity_request_handler(Self::handle_stash_show);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase_start);
+        client.add_entity_request_handler(Self::handle_rebase_continue);
+        client.add_entity_request_handler(Self::handle_rebase_skip);
+        client.add_entity_request_handler(Self::handle_rebase_abort);
+        client.add_entity_request_handler(Self::handle_load_conflict_texts);
+        client.add_entity_request_handler(Self::handle_git_log);
+        client.add_entity_request_handler(Self::handle_file_history);
+        client.add_entity_request_handler(Self::handle_load_text_at_revision);
+        client.add_entity_request_handler(Self::handle_blame_at_revision);
+        client.add_entity_request_handler(Self::handle_load_commit_signature);
+        client.add_entity_request_handler(Self::handle_git_worktrees);
+        client.add_entity_request_handler(Self::handle_create_worktree);
+        client.add_entity_request_handler(Self::handle_remove_worktree);
+        client.add_entity_request_handler(Self::handle_prune_worktrees);
+        client.add_entity_request_handler(Self::handle_git_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
         client.add_entity_request_handler(Self::handle_askpass);
@@ -1666,6 +1700,7 @@ impl GitStore {
                     name.zip(email),
                     CommitOptions {
                         amend: options.amend,
+                        sign: options.sign,
                     },
                     cx,
                 )
@@ -1755,6 +1790,86 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_rename_branch(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRenameBranch>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let payload = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rename_branch(payload.branch_name, payload.new_name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_branch(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteBranch>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let payload = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_branch(payload.branch_name, payload.force)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_set_upstream(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSetUpstream>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let payload = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.set_upstream(payload.branch_name, payload.upstream)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_compare_branch(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCompareBranch>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::LoadCommitDiffResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let branch_diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.compare_with_branch(envelope.payload.branch_name)
+            })?
+            .await??;
+        Ok(proto::LoadCommitDiffResponse {
+            files: branch_diff
+                .files
+                .into_iter()
+                .map(|file| proto::CommitFile {
+                    path: file.path.to_string(),
+                    old_text: file.old_text,
+                    new_text: file.new_text,
+                })
+                .collect(),
+        })
+    }
+
     async fn handle_show(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitShow>,
@@ -1768,13 +1883,7 @@ impl GitStore {
                 repository_handle.show(envelope.payload.commit)
             })?
             .await??;
-        Ok(proto::GitCommitDetails {
-            sha: commit.sha.into(),
-            message: commit.message.into(),
-            commit_timestamp: commit.commit_timestamp,
-            author_email: commit.author_email.into(),
-            author_name: commit.author_name.into(),
-        })
+        Ok(commit_details_to_proto(&commit))
     }
 
     async fn handle_load_commit_diff(
@@ -1803,905 +1912,1424 @@ impl GitStore {
         })
     }
 
-    async fn handle_reset(
+    async fn handle_load_conflict_texts(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitReset>,
+        envelope: TypedEnvelope<proto::LoadConflictTexts>,
         mut cx: AsyncApp,
-    ) -> Result<proto::Ack> {
+    ) -> Result<proto::LoadConflictTextsResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_str(&envelope.payload.path);
 
-        let mode = match envelope.payload.mode() {
-            git_reset::ResetMode::Soft => ResetMode::Soft,
-            git_reset::ResetMode::Mixed => ResetMode::Mixed,
-        };
-
-        repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.reset(envelope.payload.commit, mode, cx)
+        let texts = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_conflict_texts(path)
             })?
             .await??;
-        Ok(proto::Ack {})
+        Ok(proto::LoadConflictTextsResponse {
+            base: texts.base,
+            ours: texts.ours,
+            theirs: texts.theirs,
+        })
     }
 
-    async fn handle_checkout_files(
+    async fn handle_git_log(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitLog>,
         mut cx: AsyncApp,
-    ) -> Result<proto::Ack> {
+    ) -> Result<proto::GitLogResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let paths = envelope
-            .payload
-            .paths
-            .iter()
-            .map(|s| RepoPath::from_str(s))
-            .collect();
+        let payload = envelope.payload;
+        let options = LogOptions {
+            path: payload.path.as_deref().map(RepoPath::from_str),
+            line_range: payload
+                .line_start
+                .zip(payload.line_end)
+                .map(|(start, end)| start..end),
+            author: payload.author,
+            message: payload.message,
+            since: payload.since,
+            until: payload.until,
+            skip: payload.skip as usize,
+            limit: payload.limit as usize,
+        };
 
-        repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.log(options)
             })?
             .await??;
-        Ok(proto::Ack {})
+        Ok(proto::GitLogResponse {
+            entries: entries.iter().map(log_entry_to_proto).collect(),
+        })
     }
 
-    async fn handle_open_commit_message_buffer(
+    async fn handle_file_history(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        envelope: TypedEnvelope<proto::GitFileHistory>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenBufferResponse> {
+    ) -> Result<proto::GitFileHistoryResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let buffer = repository
-            .update(&mut cx, |repository, cx| {
-                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
-            })?
-            .await?;
-
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
-        this.update(&mut cx, |this, cx| {
-            this.buffer_store.update(cx, |buffer_store, cx| {
-                buffer_store
-                    .create_buffer_for_peer(
-                        &buffer,
-                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
-                        cx,
-                    )
-                    .detach_and_log_err(cx);
-            })
-        })?;
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_str(&envelope.payload.path);
 
-        Ok(proto::OpenBufferResponse {
-            buffer_id: buffer_id.to_proto(),
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.file_history(
+                    path,
+                    envelope.payload.skip as usize,
+                    envelope.payload.limit as usize,
+                )
+            })?
+            .await??;
+        Ok(proto::GitFileHistoryResponse {
+            entries: entries.iter().map(file_history_entry_to_proto).collect(),
         })
     }
 
-    async fn handle_askpass(
+    async fn handle_load_text_at_revision(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::AskPassRequest>,
+        envelope: TypedEnvelope<proto::GitLoadTextAtRevision>,
         mut cx: AsyncApp,
-    ) -> Result<proto::AskPassResponse> {
+    ) -> Result<proto::GitLoadTextAtRevisionResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-
-        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
-        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
-            debug_panic!("no askpass found");
-            return Err(anyhow::anyhow!("no askpass found"));
-        };
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_str(&envelope.payload.path);
 
-        let response = askpass.ask_password(envelope.payload.prompt).await?;
+        let text = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_text_at_revision(envelope.payload.commit, path)
+            })?
+            .await??;
+        Ok(proto::GitLoadTextAtRevisionResponse { text })
+    }
 
-        delegates
-            .lock()
-            .insert(envelope.payload.askpass_id, askpass);
+    async fn handle_blame_at_revision(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBlameAtRevision>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::BlameBufferResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_str(&envelope.payload.path);
 
-        Ok(proto::AskPassResponse { response })
+        let blame = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.blame_at_revision(path, envelope.payload.commit)
+            })?
+            .await??;
+        Ok(serialize_blame_buffer_response(Some(blame)))
     }
 
-    async fn handle_check_for_pushed_commits(
+    async fn handle_load_commit_signature(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        envelope: TypedEnvelope<proto::GitLoadCommitSignature>,
         mut cx: AsyncApp,
-    ) -> Result<proto::CheckForPushedCommitsResponse> {
+    ) -> Result<proto::GitLoadCommitSignatureResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        let branches = repository_handle
+        let signature = repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.check_for_pushed_commits()
+                repository_handle.commit_signature(envelope.payload.commit)
             })?
             .await??;
-        Ok(proto::CheckForPushedCommitsResponse {
-            pushed_to: branches
-                .into_iter()
-                .map(|commit| commit.to_string())
-                .collect(),
+        Ok(proto::GitLoadCommitSignatureResponse {
+            signature: signature.as_ref().map(signature_to_proto),
         })
     }
 
-    async fn handle_git_diff(
+    async fn handle_git_worktrees(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitDiff>,
+        envelope: TypedEnvelope<proto::GitWorktrees>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GitDiffResponse> {
+    ) -> Result<proto::GitWorktreesResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let diff_type = match envelope.payload.diff_type() {
-            proto::git_diff::DiffType::HeadToIndex => DiffType::HeadToIndex,
-            proto::git_diff::DiffType::HeadToWorktree => DiffType::HeadToWorktree,
-        };
 
-        let mut diff = repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.diff(diff_type, cx)
+        let worktrees = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktrees()
             })?
             .await??;
-        const ONE_MB: usize = 1_000_000;
-        if diff.len() > ONE_MB {
-            diff = diff.chars().take(ONE_MB).collect()
-        }
+        Ok(proto::GitWorktreesResponse {
+            worktrees: worktrees.iter().map(worktree_to_proto).collect(),
+        })
+    }
 
-        Ok(proto::GitDiffResponse { diff })
+    async fn handle_create_worktree(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateWorktree>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let payload = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_worktree(
+                    PathBuf::from(payload.path),
+                    payload.branch,
+                    payload.new_branch,
+                    payload.base,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_open_unstaged_diff(
+    async fn handle_remove_worktree(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::OpenUnstagedDiff>,
+        envelope: TypedEnvelope<proto::GitRemoveWorktree>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenUnstagedDiffResponse> {
-        let buffer_id = BufferId::new(request.payload.buffer_id)?;
-        let diff = this
-            .update(&mut cx, |this, cx| {
-                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
-                Some(this.open_unstaged_diff(buffer, cx))
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle
+                    .remove_worktree(PathBuf::from(envelope.payload.path), envelope.payload.force)
             })?
-            .ok_or_else(|| anyhow!("no such buffer"))?
-            .await?;
-        this.update(&mut cx, |this, _| {
-            let shared_diffs = this
-                .shared_diffs
-                .entry(request.original_sender_id.unwrap_or(request.sender_id))
-                .or_default();
-            shared_diffs.entry(buffer_id).or_default().unstaged = Some(diff.clone());
-        })?;
-        let staged_text = diff.read_with(&cx, |diff, _| diff.base_text_string())?;
-        Ok(proto::OpenUnstagedDiffResponse { staged_text })
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_open_uncommitted_diff(
+    async fn handle_prune_worktrees(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::OpenUncommittedDiff>,
+        envelope: TypedEnvelope<proto::GitPruneWorktrees>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenUncommittedDiffResponse> {
-        let buffer_id = BufferId::new(request.payload.buffer_id)?;
-        let diff = this
-            .update(&mut cx, |this, cx| {
-                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
-                Some(this.open_uncommitted_diff(buffer, cx))
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.prune_worktrees()
             })?
-            .ok_or_else(|| anyhow!("no such buffer"))?
-            .await?;
-        this.update(&mut cx, |this, _| {
-            let shared_diffs = this
-                .shared_diffs
-                .entry(request.original_sender_id.unwrap_or(request.sender_id))
-                .or_default();
-            shared_diffs.entry(buffer_id).or_default().uncommitted = Some(diff.clone());
-        })?;
-        diff.read_with(&cx, |diff, cx| {
-            use proto::open_uncommitted_diff_response::Mode;
+            .await??;
+        Ok(proto::Ack {})
+    }
 
-            let unstaged_diff = diff.secondary_diff();
-            let index_snapshot = unstaged_diff.and_then(|diff| {
-                let diff = diff.read(cx);
-                diff.base_text_exists().then(|| diff.base_text())
-            });
+    async fn handle_git_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-            let mode;
-            let staged_text;
-            let committed_text;
-            if diff.base_text_exists() {
-                let committed_snapshot = diff.base_text();
-                committed_text = Some(committed_snapshot.text());
-                if let Some(index_text) = index_snapshot {
-                    if index_text.remote_id() == committed_snapshot.remote_id() {
-                        mode = Mode::IndexMatchesHead;
-                        staged_text = None;
-                    } else {
-                        mode = Mode::IndexAndHead;
-                        staged_text = Some(index_text.text());
-                    }
-                } else {
-                    mode = Mode::IndexAndHead;
-                    staged_text = None;
-                }
-            } else {
-                mode = Mode::IndexAndHead;
-                committed_text = None;
-                staged_text = index_snapshot.as_ref().map(|buffer| buffer.text());
-            }
-
-            proto::OpenUncommittedDiffResponse {
-                committed_text,
-                staged_text,
-                mode: mode.into(),
-            }
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
         })
     }
 
-    async fn handle_update_diff_bases(
+    async fn handle_create_tag(
         this: Entity<Self>,
-        request: TypedEnvelope<proto::UpdateDiffBases>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
         mut cx: AsyncApp,
-    ) -> Result<()> {
-        let buffer_id = BufferId::new(request.payload.buffer_id)?;
-        this.update(&mut cx, |this, cx| {
-            if let Some(diff_state) = this.diffs.get_mut(&buffer_id) {
-                if let Some(buffer) = this.buffer_store.read(cx).get(buffer_id) {
-                    let buffer = buffer.read(cx).text_snapshot();
-                    diff_state.update(cx, |diff_state, cx| {
-                        diff_state.handle_base_texts_updated(buffer, request.payload, cx);
-                    })
-                }
-            }
-        })
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let payload = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(payload.name, payload.commit, payload.message)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_blame_buffer(
+    async fn handle_delete_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::BlameBuffer>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
         mut cx: AsyncApp,
-    ) -> Result<proto::BlameBufferResponse> {
-        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
-        let version = deserialize_version(&envelope.payload.version);
-        let buffer = this.read_with(&cx, |this, cx| {
-            this.buffer_store.read(cx).get_existing(buffer_id)
-        })??;
-        buffer
-            .update(&mut cx, |buffer, _| {
-                buffer.wait_for_version(version.clone())
-            })?
-            .await?;
-        let blame = this
-            .update(&mut cx, |this, cx| {
-                this.blame_buffer(&buffer, Some(version), cx)
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.name)
             })?
-            .await?;
-        Ok(serialize_blame_buffer_response(blame))
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_get_permalink_to_line(
+    async fn handle_push_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GetPermalinkToLine>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GetPermalinkToLineResponse> {
-        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
-        // let version = deserialize_version(&envelope.payload.version);
-        let selection = {
-            let proto_selection = envelope
-                .payload
-                .selection
-                .context("no selection to get permalink for defined")?;
-            proto_selection.start as u32..proto_selection.end as u32
-        };
-        let buffer = this.read_with(&cx, |this, cx| {
-            this.buffer_store.read(cx).get_existing(buffer_id)
-        })??;
-        let permalink = this
-            .update(&mut cx, |this, cx| {
-                this.get_permalink_to_line(&buffer, selection, cx)
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let name = envelope.payload.name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(name, remote_name, askpass, cx)
             })?
-            .await?;
-        Ok(proto::GetPermalinkToLineResponse {
-            permalink: permalink.to_string(),
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
         })
     }
 
-    fn repository_for_request(
-        this: &Entity<Self>,
-        id: RepositoryId,
-        cx: &mut AsyncApp,
-    ) -> Result<Entity<Repository>> {
-        this.update(cx, |this, _| {
-            this.repositories
-                .get(&id)
-                .context("missing repository handle")
-                .cloned()
-        })?
-    }
+    async fn handle_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-    pub fn repo_snapshots(&self, cx: &App) -> HashMap<RepositoryId, RepositorySnapshot> {
-        self.repositories
-            .iter()
-            .map(|(id, repo)| (*id, repo.read(cx).snapshot.clone()))
-            .collect()
+        let mode = match envelope.payload.mode() {
+            git_reset::ResetMode::Soft => ResetMode::Soft,
+            git_reset::ResetMode::Mixed => ResetMode::Mixed,
+            git_reset::ResetMode::Hard => ResetMode::Hard,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.reset(envelope.payload.commit, mode, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
-}
 
-impl BufferDiffState {
-    fn buffer_language_changed(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
-        self.language = buffer.read(cx).language().cloned();
-        self.language_changed = true;
-        let _ = self.recalculate_diffs(buffer.read(cx).text_snapshot(), cx);
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.commit, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    fn unstaged_diff(&self) -> Option<Entity<BufferDiff>> {
-        self.unstaged_diff.as_ref().and_then(|set| set.upgrade())
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commit, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    fn uncommitted_diff(&self) -> Option<Entity<BufferDiff>> {
-        self.uncommitted_diff.as_ref().and_then(|set| set.upgrade())
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_str(s))
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    fn handle_base_texts_updated(
-        &mut self,
-        buffer: text::BufferSnapshot,
-        message: proto::UpdateDiffBases,
-        cx: &mut Context<Self>,
-    ) {
-        use proto::update_diff_bases::Mode;
+    async fn handle_get_stash_entries(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashEntries>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitStashEntriesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        let Some(mode) = Mode::from_i32(message.mode) else {
-            return;
-        };
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_entries()
+            })?
+            .await??;
 
-        let diff_bases_change = match mode {
-            Mode::HeadOnly => DiffBasesChange::SetHead(message.committed_text),
-            Mode::IndexOnly => DiffBasesChange::SetIndex(message.staged_text),
-            Mode::IndexMatchesHead => DiffBasesChange::SetBoth(message.committed_text),
-            Mode::IndexAndHead => DiffBasesChange::SetEach {
-                index: message.staged_text,
-                head: message.committed_text,
-            },
+        Ok(proto::GitStashEntriesResponse {
+            entries: entries.iter().map(stash_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_stash(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStash>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let options = StashOptions {
+            include_untracked: envelope.payload.include_untracked,
+            staged: envelope.payload.staged,
         };
 
-        self.diff_bases_changed(buffer, Some(diff_bases_change), cx);
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_push(envelope.payload.message, options)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    pub fn wait_for_recalculation(&mut self) -> Option<impl Future<Output = ()> + use<>> {
-        if *self.recalculating_tx.borrow() {
-            let mut rx = self.recalculating_tx.subscribe();
-            return Some(async move {
-                loop {
-                    let is_recalculating = rx.recv().await;
-                    if is_recalculating != Some(true) {
-                        break;
-                    }
-                }
-            });
-        } else {
-            None
-        }
+    async fn handle_stash_apply(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashApply>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_apply(envelope.payload.index as usize)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    fn diff_bases_changed(
-        &mut self,
-        buffer: text::BufferSnapshot,
-        diff_bases_change: Option<DiffBasesChange>,
-        cx: &mut Context<Self>,
-    ) {
-        match diff_bases_change {
-            Some(DiffBasesChange::SetIndex(index)) => {
-                self.index_text = index.map(|mut index| {
-                    text::LineEnding::normalize(&mut index);
-                    Arc::new(index)
-                });
-                self.index_changed = true;
-            }
-            Some(DiffBasesChange::SetHead(head)) => {
-                self.head_text = head.map(|mut head| {
-                    text::LineEnding::normalize(&mut head);
-                    Arc::new(head)
-                });
-                self.head_changed = true;
-            }
-            Some(DiffBasesChange::SetBoth(text)) => {
-                let text = text.map(|mut text| {
-                    text::LineEnding::normalize(&mut text);
-                    Arc::new(text)
-                });
-                self.head_text = text.clone();
-                self.index_text = text;
-                self.head_changed = true;
-                self.index_changed = true;
-            }
-            Some(DiffBasesChange::SetEach { index, head }) => {
-                self.index_text = index.map(|mut index| {
-                    text::LineEnding::normalize(&mut index);
-                    Arc::new(index)
-                });
-                self.index_changed = true;
-                self.head_text = head.map(|mut head| {
-                    text::LineEnding::normalize(&mut head);
-                    Arc::new(head)
-                });
-                self.head_changed = true;
-            }
-            None => {}
-        }
+    async fn handle_stash_pop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashPop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        self.recalculate_diffs(buffer, cx)
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_pop(envelope.payload.index as usize)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    fn recalculate_diffs(&mut self, buffer: text::BufferSnapshot, cx: &mut Context<Self>) {
-        *self.recalculating_tx.borrow_mut() = true;
-
-        let language = self.language.clone();
-        let language_registry = self.language_registry.clone();
-        let unstaged_diff = self.unstaged_diff();
-        let uncommitted_diff = self.uncommitted_diff();
-        let head = self.head_text.clone();
-        let index = self.index_text.clone();
-        let index_changed = self.index_changed;
-        let head_changed = self.head_changed;
-        let language_changed = self.language_changed;
-        let prev_hunk_staging_operation_count = self.hunk_staging_operation_count_as_of_write;
-        let index_matches_head = match (self.index_text.as_ref(), self.head_text.as_ref()) {
-            (Some(index), Some(head)) => Arc::ptr_eq(index, head),
-            (None, None) => true,
-            _ => false,
-        };
-        self.recalculate_diff_task = Some(cx.spawn(async move |this, cx| {
-            log::debug!(
-                "start recalculating diffs for buffer {}",
-                buffer.remote_id()
-            );
+    async fn handle_stash_drop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashDrop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-            let mut new_unstaged_diff = None;
-            if let Some(unstaged_diff) = &unstaged_diff {
-                new_unstaged_diff = Some(
-                    BufferDiff::update_diff(
-                        unstaged_diff.clone(),
-                        buffer.clone(),
-                        index,
-                        index_changed,
-                        language_changed,
-                        language.clone(),
-                        language_registry.clone(),
-                        cx,
-                    )
-                    .await?,
-                );
-            }
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_drop(envelope.payload.index as usize)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
 
-            let mut new_uncommitted_diff = None;
-            if let Some(uncommitted_diff) = &uncommitted_diff {
-                new_uncommitted_diff = if index_matches_head {
-                    new_unstaged_diff.clone()
-                } else {
-                    Some(
-                        BufferDiff::update_diff(
-                            uncommitted_diff.clone(),
-                            buffer.clone(),
-                            head,
-                            head_changed,
-                            language_changed,
-                            language.clone(),
-                            language_registry.clone(),
-                            cx,
-                        )
-                        .await?,
-                    )
-                }
-            }
+    async fn handle_stash_show(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashShow>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::LoadCommitDiffResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-            let cancel = this.update(cx, |this, _| {
-                // This checks whether all pending stage/unstage operations
-                // have quiesced (i.e. both the corresponding write and the
-                // read of that write have completed). If not, then we cancel
-                // this recalculation attempt to avoid invalidating pending
-                // state too quickly; another recalculation will come along
-                // later and clear the pending state once the state of the index has settled.
-                if this.hunk_staging_operation_count > prev_hunk_staging_operation_count {
-                    *this.recalculating_tx.borrow_mut() = false;
-                    true
-                } else {
-                    false
-                }
-            })?;
-            if cancel {
-                log::debug!(
-                    concat!(
-                        "aborting recalculating diffs for buffer {}",
-                        "due to subsequent hunk operations",
-                    ),
-                    buffer.remote_id()
-                );
-                return Ok(());
-            }
+        let stash_diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_show(envelope.payload.index as usize)
+            })?
+            .await??;
+        Ok(proto::LoadCommitDiffResponse {
+            files: stash_diff
+                .files
+                .into_iter()
+                .map(|file| proto::CommitFile {
+                    path: file.path.to_string(),
+                    old_text: file.old_text,
+                    new_text: file.new_text,
+                })
+                .collect(),
+        })
+    }
 
-            let unstaged_changed_range = if let Some((unstaged_diff, new_unstaged_diff)) =
-                unstaged_diff.as_ref().zip(new_unstaged_diff.clone())
-            {
-                unstaged_diff.update(cx, |diff, cx| {
-                    if language_changed {
-                        diff.language_changed(cx);
-                    }
-                    diff.set_snapshot(new_unstaged_diff, &buffer, cx)
-                })?
-            } else {
-                None
-            };
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-            if let Some((uncommitted_diff, new_uncommitted_diff)) =
-                uncommitted_diff.as_ref().zip(new_uncommitted_diff.clone())
-            {
-                uncommitted_diff.update(cx, |diff, cx| {
-                    if language_changed {
-                        diff.language_changed(cx);
-                    }
-                    diff.set_snapshot_with_secondary(
-                        new_uncommitted_diff,
-                        &buffer,
-                        unstaged_changed_range,
-                        true,
-                        cx,
-                    );
-                })?;
-            }
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.base)
+            })?
+            .await??;
 
-            log::debug!(
-                "finished recalculating diffs for buffer {}",
-                buffer.remote_id()
-            );
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+        })
+    }
 
-            if let Some(this) = this.upgrade() {
-                this.update(cx, |this, _| {
-                    this.index_changed = false;
-                    this.head_changed = false;
-                    this.language_changed = false;
-                    *this.recalculating_tx.borrow_mut() = false;
-                })?;
-            }
+    async fn handle_rebase_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let todo = envelope
+            .payload
+            .todo
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect();
 
-            Ok(())
-        }));
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_start(envelope.payload.base, todo, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
-}
 
-impl Default for BufferDiffState {
-    fn default() -> Self {
-        Self {
-            unstaged_diff: Default::default(),
-            uncommitted_diff: Default::default(),
-            recalculate_diff_task: Default::default(),
-            language: Default::default(),
-            language_registry: Default::default(),
-            recalculating_tx: postage::watch::channel_with(false).0,
-            hunk_staging_operation_count: 0,
-            hunk_staging_operation_count_as_of_write: 0,
-            head_text: Default::default(),
-            index_text: Default::default(),
-            head_changed: Default::default(),
-            index_changed: Default::default(),
-            language_changed: Default::default(),
-        }
+    async fn handle_rebase_continue(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseContinue>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_continue(cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
-}
 
-fn make_remote_delegate(
-    this: Entity<GitStore>,
-    project_id: u64,
-    repository_id: RepositoryId,
-    askpass_id: u64,
-    cx: &mut AsyncApp,
-) -> AskPassDelegate {
-    AskPassDelegate::new(cx, move |prompt, tx, cx| {
-        this.update(cx, |this, cx| {
-            let Some((client, _)) = this.downstream_client() else {
-                return;
-            };
-            let response = client.request(proto::AskPassRequest {
-                project_id,
-                repository_id: repository_id.to_proto(),
-                askpass_id,
-                prompt,
-            });
-            cx.spawn(async move |_, _| {
-                tx.send(response.await?.response).ok();
-                anyhow::Ok(())
-            })
-            .detach_and_log_err(cx);
-        })
-        .log_err();
-    })
-}
+    async fn handle_rebase_skip(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseSkip>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-impl RepositoryId {
-    pub fn to_proto(self) -> u64 {
-        self.0
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_skip(cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    pub fn from_proto(id: u64) -> Self {
-        RepositoryId(id)
-    }
-}
+    async fn handle_rebase_abort(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseAbort>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-impl RepositorySnapshot {
-    fn empty(id: RepositoryId, work_directory_abs_path: Arc<Path>) -> Self {
-        Self {
-            id,
-            merge_message: None,
-            statuses_by_path: Default::default(),
-            work_directory_abs_path,
-            branch: None,
-            head_commit: None,
-            merge_conflicts: Default::default(),
-            merge_head_shas: Default::default(),
-            scan_id: 0,
-        }
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_abort(cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    fn initial_update(&self, project_id: u64) -> proto::UpdateRepository {
-        proto::UpdateRepository {
-            branch_summary: self.branch.as_ref().map(branch_to_proto),
-            head_commit_details: self.head_commit.as_ref().map(commit_details_to_proto),
-            updated_statuses: self
-                .statuses_by_path
-                .iter()
-                .map(|entry| entry.to_proto())
-                .collect(),
-            removed_statuses: Default::default(),
-            current_merge_conflicts: self
-                .merge_conflicts
-                .iter()
-                .map(|repo_path| repo_path.to_proto())
-                .collect(),
-            project_id,
-            id: self.id.to_proto(),
-            abs_path: self.work_directory_abs_path.to_proto(),
-            entry_ids: vec![self.id.to_proto()],
-            scan_id: self.scan_id,
-            is_last_update: true,
-        }
-    }
+    async fn handle_open_commit_message_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenBufferResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let buffer = repository
+            .update(&mut cx, |repository, cx| {
+                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+            })?
+            .await?;
 
-    fn build_update(&self, old: &Self, project_id: u64) -> proto::UpdateRepository {
-        let mut updated_statuses: Vec<proto::StatusEntry> = Vec::new();
-        let mut removed_statuses: Vec<String> = Vec::new();
+        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+        this.update(&mut cx, |this, cx| {
+            this.buffer_store.update(cx, |buffer_store, cx| {
+                buffer_store
+                    .create_buffer_for_peer(
+                        &buffer,
+                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+        })?;
 
-        let mut new_statuses = self.statuses_by_path.iter().peekable();
-        let mut old_statuses = old.statuses_by_path.iter().peekable();
+        Ok(proto::OpenBufferResponse {
+            buffer_id: buffer_id.to_proto(),
+        })
+    }
 
-        let mut current_new_entry = new_statuses.next();
-        let mut current_old_entry = old_statuses.next();
-        loop {
-            match (current_new_entry, current_old_entry) {
-                (Some(new_entry), Some(old_entry)) => {
-                    match new_entry.repo_path.cmp(&old_entry.repo_path) {
-                        Ordering::Less => {
-                            updated_statuses.push(new_entry.to_proto());
-                            current_new_entry = new_statuses.next();
-                        }
-                        Ordering::Equal => {
-                            if new_entry.status != old_entry.status {
-                                updated_statuses.push(new_entry.to_proto());
-                            }
-                            current_old_entry = old_statuses.next();
-                            current_new_entry = new_statuses.next();
-                        }
-                        Ordering::Greater => {
-                            removed_statuses.push(old_entry.repo_path.as_ref().to_proto());
-                            current_old_entry = old_statuses.next();
-                        }
-                    }
-                }
-                (None, Some(old_entry)) => {
-                    removed_statuses.push(old_entry.repo_path.as_ref().to_proto());
-                    current_old_entry = old_statuses.next();
-                }
-                (Some(new_entry), None) => {
-                    updated_statuses.push(new_entry.to_proto());
-                    current_new_entry = new_statuses.next();
-                }
-                (None, None) => break,
-            }
-        }
+    async fn handle_askpass(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::AskPassRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::AskPassResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        proto::UpdateRepository {
-            branch_summary: self.branch.as_ref().map(branch_to_proto),
-            head_commit_details: self.head_commit.as_ref().map(commit_details_to_proto),
-            updated_statuses,
-            removed_statuses,
-            current_merge_conflicts: self
-                .merge_conflicts
-                .iter()
-                .map(|path| path.as_ref().to_proto())
-                .collect(),
-            project_id,
-            id: self.id.to_proto(),
-            abs_path: self.work_directory_abs_path.to_proto(),
-            entry_ids: vec![],
-            scan_id: self.scan_id,
-            is_last_update: true,
-        }
-    }
+        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
+        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
+            debug_panic!("no askpass found");
+            return Err(anyhow::anyhow!("no askpass found"));
+        };
 
-    pub fn status(&self) -> impl Iterator<Item = StatusEntry> + '_ {
-        self.statuses_by_path.iter().cloned()
-    }
+        let response = askpass.ask_password(envelope.payload.prompt).await?;
 
-    pub fn status_summary(&self) -> GitSummary {
-        self.statuses_by_path.summary().item_summary
-    }
+        delegates
+            .lock()
+            .insert(envelope.payload.askpass_id, askpass);
 
-    pub fn status_for_path(&self, path: &RepoPath) -> Option<StatusEntry> {
-        self.statuses_by_path
-            .get(&PathKey(path.0.clone()), &())
-            .cloned()
+        Ok(proto::AskPassResponse { response })
     }
 
-    pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
-        abs_path
-            .strip_prefix(&self.work_directory_abs_path)
-            .map(RepoPath::from)
-            .ok()
-    }
+    async fn handle_check_for_pushed_commits(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::CheckForPushedCommitsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-    pub fn has_conflict(&self, repo_path: &RepoPath) -> bool {
-        self.merge_conflicts.contains(repo_path)
+        let branches = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.check_for_pushed_commits()
+            })?
+            .await??;
+        Ok(proto::CheckForPushedCommitsResponse {
+            pushed_to: branches
+                .into_iter()
+                .map(|commit| commit.to_string())
+                .collect(),
+        })
     }
 
-    /// This is the name that will be displayed in the repository selector for this repository.
-    pub fn display_name(&self) -> SharedString {
-        self.work_directory_abs_path
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string()
-            .into()
-    }
-}
+    async fn handle_git_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitDiffResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let diff_type = match envelope.payload.diff_type() {
+            proto::git_diff::DiffType::HeadToIndex => DiffType::HeadToIndex,
+            proto::git_diff::DiffType::HeadToWorktree => DiffType::HeadToWorktree,
+        };
 
-impl Repository {
-    fn local(
-        id: RepositoryId,
-        work_directory_abs_path: Arc<Path>,
-        dot_git_abs_path: Arc<Path>,
-        repository_dir_abs_path: Arc<Path>,
-        common_dir_abs_path: Arc<Path>,
-        project_environment: WeakEntity<ProjectEnvironment>,
-        fs: Arc<dyn Fs>,
-        git_store: WeakEntity<GitStore>,
-        cx: &mut Context<Self>,
-    ) -> Self {
-        let snapshot = RepositorySnapshot::empty(id, work_directory_abs_path.clone());
-        Repository {
-            this: cx.weak_entity(),
-            git_store,
-            snapshot,
-            commit_message_buffer: None,
-            askpass_delegates: Default::default(),
-            paths_needing_status_update: Default::default(),
-            latest_askpass_id: 0,
-            job_sender: Repository::spawn_local_git_worker(
-                work_directory_abs_path,
-                dot_git_abs_path,
-                repository_dir_abs_path,
-                common_dir_abs_path,
-                project_environment,
-                fs,
-                cx,
-            ),
-            job_id: 0,
-            active_jobs: Default::default(),
+        let mut diff = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.diff(diff_type, cx)
+            })?
+            .await??;
+        const ONE_MB: usize = 1_000_000;
+        if diff.len() > ONE_MB {
+            diff = diff.chars().take(ONE_MB).collect()
         }
-    }
 
-    fn remote(
-        id: RepositoryId,
-        work_directory_abs_path: Arc<Path>,
-        project_id: ProjectId,
-        client: AnyProtoClient,
-        git_store: WeakEntity<GitStore>,
-        cx: &mut Context<Self>,
-    ) -> Self {
-        let snapshot = RepositorySnapshot::empty(id, work_directory_abs_path);
-        Self {
-            this: cx.weak_entity(),
-            snapshot,
-            commit_message_buffer: None,
-            git_store,
-            paths_needing_status_update: Default::default(),
-            job_sender: Self::spawn_remote_git_worker(project_id, client, cx),
-            askpass_delegates: Default::default(),
-            latest_askpass_id: 0,
-            active_jobs: Default::default(),
-            job_id: 0,
-        }
+        Ok(proto::GitDiffResponse { diff })
     }
 
-    pub fn git_store(&self) -> Option<Entity<GitStore>> {
-        self.git_store.upgrade()
+    async fn handle_open_unstaged_diff(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::OpenUnstagedDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenUnstagedDiffResponse> {
+        let buffer_id = BufferId::new(request.payload.buffer_id)?;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
+                Some(this.open_unstaged_diff(buffer, cx))
+            })?
+            .ok_or_else(|| anyhow!("no such buffer"))?
+            .await?;
+        this.update(&mut cx, |this, _| {
+            let shared_diffs = this
+                .shared_diffs
+                .entry(request.original_sender_id.unwrap_or(request.sender_id))
+                .or_default();
+            shared_diffs.entry(buffer_id).or_default().unstaged = Some(diff.clone());
+        })?;
+        let staged_text = diff.read_with(&cx, |diff, _| diff.base_text_string())?;
+        Ok(proto::OpenUnstagedDiffResponse { staged_text })
     }
 
-    fn reload_buffer_diff_bases(&mut self, cx: &mut Context<Self>) {
-        let this = cx.weak_entity();
-        let git_store = self.git_store.clone();
-        let _ = self.send_keyed_job(
-            Some(GitJobKey::ReloadBufferDiffBases),
-            None,
-            |state, mut cx| async move {
-                let RepositoryState::Local { backend, .. } = state else {
-                    log::error!("tried to recompute diffs for a non-local repository");
-                    return Ok(());
-                };
+    async fn handle_open_uncommitted_diff(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::OpenUncommittedDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenUncommittedDiffResponse> {
+        let buffer_id = BufferId::new(request.payload.buffer_id)?;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let buffer = this.buffer_store.read(cx).get(buffer_id)?;
+                Some(this.open_uncommitted_diff(buffer, cx))
+            })?
+            .ok_or_else(|| anyhow!("no such buffer"))?
+            .await?;
+        this.update(&mut cx, |this, _| {
+            let shared_diffs = this
+                .shared_diffs
+                .entry(request.original_sender_id.unwrap_or(request.sender_id))
+                .or_default();
+            shared_diffs.entry(buffer_id).or_default().uncommitted = Some(diff.clone());
+        })?;
+        diff.read_with(&cx, |diff, cx| {
+            use proto::open_uncommitted_diff_response::Mode;
 
-                let Some(this) = this.upgrade() else {
-                    return Ok(());
-                };
+            let unstaged_diff = diff.secondary_diff();
+            let index_snapshot = unstaged_diff.and_then(|diff| {
+                let diff = diff.read(cx);
+                diff.base_text_exists().then(|| diff.base_text())
+            });
 
-                let repo_diff_state_updates = this.update(&mut cx, |this, cx| {
-                    git_store.update(cx, |git_store, cx| {
-                        git_store
-                            .diffs
-                            .iter()
-                            .filter_map(|(buffer_id, diff_state)| {
-                                let buffer_store = git_store.buffer_store.read(cx);
-                                let buffer = buffer_store.get(*buffer_id)?;
-                                let file = File::from_dyn(buffer.read(cx).file())?;
-                                let abs_path =
-                                    file.worktree.read(cx).absolutize(&file.path).ok()?;
-                                let repo_path = this.abs_path_to_repo_path(&abs_path)?;
-                                log::debug!(
-                                    "start reload diff bases for repo path {}",
-                                    repo_path.0.display()
-                                );
-                                diff_state.update(cx, |diff_state, _| {
-                                    let has_unstaged_diff = diff_state
-                                        .unstaged_diff
-                                        .as_ref()
-                                        .is_some_and(|diff| diff.is_upgradable());
-                                    let has_uncommitted_diff = diff_state
-                                        .uncommitted_diff
-                                        .as_ref()
-                                        .is_some_and(|set| set.is_upgradable());
+            let mode;
+            let staged_text;
+            let committed_text;
+            if diff.base_text_exists() {
+                let committed_snapshot = diff.base_text();
+                committed_text = Some(committed_snapshot.text());
+                if let Some(index_text) = index_snapshot {
+                    if index_text.remote_id() == committed_snapshot.remote_id() {
+                        mode = Mode::IndexMatchesHead;
+                        staged_text = None;
+                    } else {
+                        mode = Mode::IndexAndHead;
+                        staged_text = Some(index_text.text());
+                    }
+                } else {
+                    mode = Mode::IndexAndHead;
+                    staged_text = None;
+                }
+            } else {
+                mode = Mode::IndexAndHead;
+                committed_text = None;
+                staged_text = index_snapshot.as_ref().map(|buffer| buffer.text());
+            }
 
-                                    Some((
-                                        buffer,
-                                        repo_path,
-                                        has_unstaged_diff.then(|| diff_state.index_text.clone()),
-                                        has_uncommitted_diff.then(|| diff_state.head_text.clone()),
-                                    ))
-                                })
-                            })
-                            .collect::<Vec<_>>()
-                    })
-                })??;
+            proto::OpenUncommittedDiffResponse {
+                committed_text,
+                staged_text,
+                mode: mode.into(),
+            }
+        })
+    }
 
-                let buffer_diff_base_changes = cx
-                    .background_spawn(async move {
-                        let mut changes = Vec::new();
-                        for (buffer, repo_path, current_index_text, current_head_text) in
-                            &repo_diff_state_updates
-                        {
-                            let index_text = if current_index_text.is_some() {
-                                backend.load_index_text(repo_path.clone()).await
-                            } else {
-                                None
-                            };
-                            let head_text = if current_head_text.is_some() {
-                                backend.load_committed_text(repo_path.clone()).await
-                            } else {
-                                None
-                            };
+    async fn handle_update_diff_bases(
+        this: Entity<Self>,
+        request: TypedEnvelope<proto::UpdateDiffBases>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let buffer_id = BufferId::new(request.payload.buffer_id)?;
+        this.update(&mut cx, |this, cx| {
+            if let Some(diff_state) = this.diffs.get_mut(&buffer_id) {
+                if let Some(buffer) = this.buffer_store.read(cx).get(buffer_id) {
+                    let buffer = buffer.read(cx).text_snapshot();
+                    diff_state.update(cx, |diff_state, cx| {
+                        diff_state.handle_base_texts_updated(buffer, request.payload, cx);
+                    })
+                }
+            }
+        })
+    }
 
-                            let change =
-                                match (current_index_text.as_ref(), current_head_text.as_ref()) {
-                                    (Some(current_index), Some(current_head)) => {
-                                        let index_changed =
-                                            index_text.as_ref() != current_index.as_deref();
-                                        let head_changed =
-                                            head_text.as_ref() != current_head.as_deref();
-                                        if index_changed && head_changed {
-                                            if index_text == head_text {
-                                                Some(DiffBasesChange::SetBoth(head_text))
-                                            } else {
-                                                Some(DiffBasesChange::SetEach {
-                                                    index: index_text,
-                                                    head: head_text,
+    async fn handle_blame_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::BlameBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::BlameBufferResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let version = deserialize_version(&envelope.payload.version);
+        let buffer = this.read_with(&cx, |this, cx| {
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(version.clone())
+            })?
+            .await?;
+        let blame = this
+            .update(&mut cx, |this, cx| {
+                this.blame_buffer(&buffer, Some(version), cx)
+            })?
+            .await?;
+        Ok(serialize_blame_buffer_response(blame))
+    }
+
+    async fn handle_get_permalink_to_line(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetPermalinkToLine>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetPermalinkToLineResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        // let version = deserialize_version(&envelope.payload.version);
+        let selection = {
+            let proto_selection = envelope
+                .payload
+                .selection
+                .context("no selection to get permalink for defined")?;
+            proto_selection.start as u32..proto_selection.end as u32
+        };
+        let buffer = this.read_with(&cx, |this, cx| {
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        let permalink = this
+            .update(&mut cx, |this, cx| {
+                this.get_permalink_to_line(&buffer, selection, cx)
+            })?
+            .await?;
+        Ok(proto::GetPermalinkToLineResponse {
+            permalink: permalink.to_string(),
+        })
+    }
+
+    fn repository_for_request(
+        this: &Entity<Self>,
+        id: RepositoryId,
+        cx: &mut AsyncApp,
+    ) -> Result<Entity<Repository>> {
+        this.update(cx, |this, _| {
+            this.repositories
+                .get(&id)
+                .context("missing repository handle")
+                .cloned()
+        })?
+    }
+
+    pub fn repo_snapshots(&self, cx: &App) -> HashMap<RepositoryId, RepositorySnapshot> {
+        self.repositories
+            .iter()
+            .map(|(id, repo)| (*id, repo.read(cx).snapshot.clone()))
+            .collect()
+    }
+}
+
+impl BufferDiffState {
+    fn buffer_language_changed(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        self.language = buffer.read(cx).language().cloned();
+        self.language_changed = true;
+        let _ = self.recalculate_diffs(buffer.read(cx).text_snapshot(), cx);
+    }
+
+    fn unstaged_diff(&self) -> Option<Entity<BufferDiff>> {
+        self.unstaged_diff.as_ref().and_then(|set| set.upgrade())
+    }
+
+    fn uncommitted_diff(&self) -> Option<Entity<BufferDiff>> {
+        self.uncommitted_diff.as_ref().and_then(|set| set.upgrade())
+    }
+
+    fn handle_base_texts_updated(
+        &mut self,
+        buffer: text::BufferSnapshot,
+        message: proto::UpdateDiffBases,
+        cx: &mut Context<Self>,
+    ) {
+        use proto::update_diff_bases::Mode;
+
+        let Some(mode) = Mode::from_i32(message.mode) else {
+            return;
+        };
+
+        let diff_bases_change = match mode {
+            Mode::HeadOnly => DiffBasesChange::SetHead(message.committed_text),
+            Mode::IndexOnly => DiffBasesChange::SetIndex(message.staged_text),
+            Mode::IndexMatchesHead => DiffBasesChange::SetBoth(message.committed_text),
+            Mode::IndexAndHead => DiffBasesChange::SetEach {
+                index: message.staged_text,
+                head: message.committed_text,
+            },
+        };
+
+        self.diff_bases_changed(buffer, Some(diff_bases_change), cx);
+    }
+
+    pub fn wait_for_recalculation(&mut self) -> Option<impl Future<Output = ()> + use<>> {
+        if *self.recalculating_tx.borrow() {
+            let mut rx = self.recalculating_tx.subscribe();
+            return Some(async move {
+                loop {
+                    let is_recalculating = rx.recv().await;
+                    if is_recalculating != Some(true) {
+                        break;
+                    }
+                }
+            });
+        } else {
+            None
+        }
+    }
+
+    fn diff_bases_changed(
+        &mut self,
+        buffer: text::BufferSnapshot,
+        diff_bases_change: Option<DiffBasesChange>,
+        cx: &mut Context<Self>,
+    ) {
+        match diff_bases_change {
+            Some(DiffBasesChange::SetIndex(index)) => {
+                self.index_text = index.map(|mut index| {
+                    text::LineEnding::normalize(&mut index);
+                    Arc::new(index)
+                });
+                self.index_changed = true;
+            }
+            Some(DiffBasesChange::SetHead(head)) => {
+                self.head_text = head.map(|mut head| {
+                    text::LineEnding::normalize(&mut head);
+                    Arc::new(head)
+                });
+                self.head_changed = true;
+            }
+            Some(DiffBasesChange::SetBoth(text)) => {
+                let text = text.map(|mut text| {
+                    text::LineEnding::normalize(&mut text);
+                    Arc::new(text)
+                });
+                self.head_text = text.clone();
+                self.index_text = text;
+                self.head_changed = true;
+                self.index_changed = true;
+            }
+            Some(DiffBasesChange::SetEach { index, head }) => {
+                self.index_text = index.map(|mut index| {
+                    text::LineEnding::normalize(&mut index);
+                    Arc::new(index)
+                });
+                self.index_changed = true;
+                self.head_text = head.map(|mut head| {
+                    text::LineEnding::normalize(&mut head);
+                    Arc::new(head)
+                });
+                self.head_changed = true;
+            }
+            None => {}
+        }
+
+        self.recalculate_diffs(buffer, cx)
+    }
+
+    fn recalculate_diffs(&mut self, buffer: text::BufferSnapshot, cx: &mut Context<Self>) {
+        *self.recalculating_tx.borrow_mut() = true;
+
+        let language = self.language.clone();
+        let language_registry = self.language_registry.clone();
+        let unstaged_diff = self.unstaged_diff();
+        let uncommitted_diff = self.uncommitted_diff();
+        let head = self.head_text.clone();
+        let index = self.index_text.clone();
+        let index_changed = self.index_changed;
+        let head_changed = self.head_changed;
+        let language_changed = self.language_changed;
+        let prev_hunk_staging_operation_count = self.hunk_staging_operation_count_as_of_write;
+        let index_matches_head = match (self.index_text.as_ref(), self.head_text.as_ref()) {
+            (Some(index), Some(head)) => Arc::ptr_eq(index, head),
+            (None, None) => true,
+            _ => false,
+        };
+        self.recalculate_diff_task = Some(cx.spawn(async move |this, cx| {
+            log::debug!(
+                "start recalculating diffs for buffer {}",
+                buffer.remote_id()
+            );
+
+            let mut new_unstaged_diff = None;
+            if let Some(unstaged_diff) = &unstaged_diff {
+                new_unstaged_diff = Some(
+                    BufferDiff::update_diff(
+                        unstaged_diff.clone(),
+                        buffer.clone(),
+                        index,
+                        index_changed,
+                        language_changed,
+                        language.clone(),
+                        language_registry.clone(),
+                        cx,
+                    )
+                    .await?,
+                );
+            }
+
+            let mut new_uncommitted_diff = None;
+            if let Some(uncommitted_diff) = &uncommitted_diff {
+                new_uncommitted_diff = if index_matches_head {
+                    new_unstaged_diff.clone()
+                } else {
+                    Some(
+                        BufferDiff::update_diff(
+                            uncommitted_diff.clone(),
+                            buffer.clone(),
+                            head,
+                            head_changed,
+                            language_changed,
+                            language.clone(),
+                            language_registry.clone(),
+                            cx,
+                        )
+                        .await?,
+                    )
+                }
+            }
+
+            let cancel = this.update(cx, |this, _| {
+                // This checks whether all pending stage/unstage operations
+                // have quiesced (i.e. both the corresponding write and the
+                // read of that write have completed). If not, then we cancel
+                // this recalculation attempt to avoid invalidating pending
+                // state too quickly; another recalculation will come along
+                // later and clear the pending state once the state of the index has settled.
+                if this.hunk_staging_operation_count > prev_hunk_staging_operation_count {
+                    *this.recalculating_tx.borrow_mut() = false;
+                    true
+                } else {
+                    false
+                }
+            })?;
+            if cancel {
+                log::debug!(
+                    concat!(
+                        "aborting recalculating diffs for buffer {}",
+                        "due to subsequent hunk operations",
+                    ),
+                    buffer.remote_id()
+                );
+                return Ok(());
+            }
+
+            let unstaged_changed_range = if let Some((unstaged_diff, new_unstaged_diff)) =
+                unstaged_diff.as_ref().zip(new_unstaged_diff.clone())
+            {
+                unstaged_diff.update(cx, |diff, cx| {
+                    if language_changed {
+                        diff.language_changed(cx);
+                    }
+                    diff.set_snapshot(new_unstaged_diff, &buffer, cx)
+                })?
+            } else {
+                None
+            };
+
+            if let Some((uncommitted_diff, new_uncommitted_diff)) =
+                uncommitted_diff.as_ref().zip(new_uncommitted_diff.clone())
+            {
+                uncommitted_diff.update(cx, |diff, cx| {
+                    if language_changed {
+                        diff.language_changed(cx);
+                    }
+                    diff.set_snapshot_with_secondary(
+                        new_uncommitted_diff,
+                        &buffer,
+                        unstaged_changed_range,
+                        true,
+                        cx,
+                    );
+                })?;
+            }
+
+            log::debug!(
+                "finished recalculating diffs for buffer {}",
+                buffer.remote_id()
+            );
+
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, _| {
+                    this.index_changed = false;
+                    this.head_changed = false;
+                    this.language_changed = false;
+                    *this.recalculating_tx.borrow_mut() = false;
+                })?;
+            }
+
+            Ok(())
+        }));
+    }
+}
+
+impl Default for BufferDiffState {
+    fn default() -> Self {
+        Self {
+            unstaged_diff: Default::default(),
+            uncommitted_diff: Default::default(),
+            recalculate_diff_task: Default::default(),
+            language: Default::default(),
+            language_registry: Default::default(),
+            recalculating_tx: postage::watch::channel_with(false).0,
+            hunk_staging_operation_count: 0,
+            hunk_staging_operation_count_as_of_write: 0,
+            head_text: Default::default(),
+            index_text: Default::default(),
+            head_changed: Default::default(),
+            index_changed: Default::default(),
+            language_changed: Default::default(),
+        }
+    }
+}
+
+fn make_remote_delegate(
+    this: Entity<GitStore>,
+    project_id: u64,
+    repository_id: RepositoryId,
+    askpass_id: u64,
+    cx: &mut AsyncApp,
+) -> AskPassDelegate {
+    AskPassDelegate::new(cx, move |prompt, tx, cx| {
+        this.update(cx, |this, cx| {
+            let Some((client, _)) = this.downstream_client() else {
+                return;
+            };
+            let response = client.request(proto::AskPassRequest {
+                project_id,
+                repository_id: repository_id.to_proto(),
+                askpass_id,
+                prompt,
+            });
+            cx.spawn(async move |_, _| {
+                tx.send(response.await?.response).ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        })
+        .log_err();
+    })
+}
+
+impl RepositoryId {
+    pub fn to_proto(self) -> u64 {
+        self.0
+    }
+
+    pub fn from_proto(id: u64) -> Self {
+        RepositoryId(id)
+    }
+}
+
+impl RepositorySnapshot {
+    fn empty(id: RepositoryId, work_directory_abs_path: Arc<Path>) -> Self {
+        Self {
+            id,
+            merge_message: None,
+            statuses_by_path: Default::default(),
+            work_directory_abs_path,
+            branch: None,
+            head_commit: None,
+            merge_conflicts: Default::default(),
+            merge_head_shas: Default::default(),
+            rebase_state: None,
+            scan_id: 0,
+        }
+    }
+
+    fn initial_update(&self, project_id: u64) -> proto::UpdateRepository {
+        proto::UpdateRepository {
+            branch_summary: self.branch.as_ref().map(branch_to_proto),
+            head_commit_details: self.head_commit.as_ref().map(commit_details_to_proto),
+            rebase_state: self.rebase_state.as_ref().map(rebase_state_to_proto),
+            updated_statuses: self
+                .statuses_by_path
+                .iter()
+                .map(|entry| entry.to_proto())
+                .collect(),
+            removed_statuses: Default::default(),
+            current_merge_conflicts: self
+                .merge_conflicts
+                .iter()
+                .map(|repo_path| repo_path.to_proto())
+                .collect(),
+            project_id,
+            id: self.id.to_proto(),
+            abs_path: self.work_directory_abs_path.to_proto(),
+            entry_ids: vec![self.id.to_proto()],
+            scan_id: self.scan_id,
+            is_last_update: true,
+        }
+    }
+
+    fn build_update(&self, old: &Self, project_id: u64) -> proto::UpdateRepository {
+        let mut updated_statuses: Vec<proto::StatusEntry> = Vec::new();
+        let mut removed_statuses: Vec<String> = Vec::new();
+
+        let mut new_statuses = self.statuses_by_path.iter().peekable();
+        let mut old_statuses = old.statuses_by_path.iter().peekable();
+
+        let mut current_new_entry = new_statuses.next();
+        let mut current_old_entry = old_statuses.next();
+        loop {
+            match (current_new_entry, current_old_entry) {
+                (Some(new_entry), Some(old_entry)) => {
+                    match new_entry.repo_path.cmp(&old_entry.repo_path) {
+                        Ordering::Less => {
+                            updated_statuses.push(new_entry.to_proto());
+                            current_new_entry = new_statuses.next();
+                        }
+                        Ordering::Equal => {
+                            if new_entry.status != old_entry.status {
+                                updated_statuses.push(new_entry.to_proto());
+                            }
+                            current_old_entry = old_statuses.next();
+                            current_new_entry = new_statuses.next();
+                        }
+                        Ordering::Greater => {
+                            removed_statuses.push(old_entry.repo_path.as_ref().to_proto());
+                            current_old_entry = old_statuses.next();
+                        }
+                    }
+                }
+                (None, Some(old_entry)) => {
+                    removed_statuses.push(old_entry.repo_path.as_ref().to_proto());
+                    current_old_entry = old_statuses.next();
+                }
+                (Some(new_entry), None) => {
+                    updated_statuses.push(new_entry.to_proto());
+                    current_new_entry = new_statuses.next();
+                }
+                (None, None) => break,
+            }
+        }
+
+        proto::UpdateRepository {
+            branch_summary: self.branch.as_ref().map(branch_to_proto),
+            head_commit_details: self.head_commit.as_ref().map(commit_details_to_proto),
+            rebase_state: self.rebase_state.as_ref().map(rebase_state_to_proto),
+            updated_statuses,
+            removed_statuses,
+            current_merge_conflicts: self
+                .merge_conflicts
+                .iter()
+                .map(|path| path.as_ref().to_proto())
+                .collect(),
+            project_id,
+            id: self.id.to_proto(),
+            abs_path: self.work_directory_abs_path.to_proto(),
+            entry_ids: vec![],
+            scan_id: self.scan_id,
+            is_last_update: true,
+        }
+    }
+
+    pub fn status(&self) -> impl Iterator<Item = StatusEntry> + '_ {
+        self.statuses_by_path.iter().cloned()
+    }
+
+    pub fn status_summary(&self) -> GitSummary {
+        self.statuses_by_path.summary().item_summary
+    }
+
+    pub fn status_for_path(&self, path: &RepoPath) -> Option<StatusEntry> {
+        self.statuses_by_path
+            .get(&PathKey(path.0.clone()), &())
+            .cloned()
+    }
+
+    pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
+        abs_path
+            .strip_prefix(&self.work_directory_abs_path)
+            .map(RepoPath::from)
+            .ok()
+    }
+
+    pub fn has_conflict(&self, repo_path: &RepoPath) -> bool {
+        self.merge_conflicts.contains(repo_path)
+    }
+
+    /// This is the name that will be displayed in the repository selector for this repository.
+    pub fn display_name(&self) -> SharedString {
+        self.work_directory_abs_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+            .into()
+    }
+}
+
+impl Repository {
+    fn local(
+        id: RepositoryId,
+        work_directory_abs_path: Arc<Path>,
+        dot_git_abs_path: Arc<Path>,
+        repository_dir_abs_path: Arc<Path>,
+        common_dir_abs_path: Arc<Path>,
+        project_environment: WeakEntity<ProjectEnvironment>,
+        fs: Arc<dyn Fs>,
+        git_store: WeakEntity<GitStore>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let snapshot = RepositorySnapshot::empty(id, work_directory_abs_path.clone());
+        Repository {
+            this: cx.weak_entity(),
+            git_store,
+            snapshot,
+            commit_message_buffer: None,
+            askpass_delegates: Default::default(),
+            paths_needing_status_update: Default::default(),
+            latest_askpass_id: 0,
+            job_sender: Repository::spawn_local_git_worker(
+                work_directory_abs_path,
+                dot_git_abs_path,
+                repository_dir_abs_path,
+                common_dir_abs_path,
+                project_environment,
+                fs,
+                cx,
+            ),
+            job_id: 0,
+            active_jobs: Default::default(),
+        }
+    }
+
+    fn remote(
+        id: RepositoryId,
+        work_directory_abs_path: Arc<Path>,
+        project_id: ProjectId,
+        client: AnyProtoClient,
+        git_store: WeakEntity<GitStore>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let snapshot = RepositorySnapshot::empty(id, work_directory_abs_path);
+        Self {
+            this: cx.weak_entity(),
+            snapshot,
+            commit_message_buffer: None,
+            git_store,
+            paths_needing_status_update: Default::default(),
+            job_sender: Self::spawn_remote_git_worker(project_id, client, cx),
+            askpass_delegates: Default::default(),
+            latest_askpass_id: 0,
+            active_jobs: Default::default(),
+            job_id: 0,
+        }
+    }
+
+    pub fn git_store(&self) -> Option<Entity<GitStore>> {
+        self.git_store.upgrade()
+    }
+
+    fn reload_buffer_diff_bases(&mut self, cx: &mut Context<Self>) {
+        let this = cx.weak_entity();
+        let git_store = self.git_store.clone();
+        let _ = self.send_keyed_job(
+            Some(GitJobKey::ReloadBufferDiffBases),
+            None,
+            |state, mut cx| async move {
+                let RepositoryState::Local { backend, .. } = state else {
+                    log::error!("tried to recompute diffs for a non-local repository");
+                    return Ok(());
+                };
+
+                let Some(this) = this.upgrade() else {
+                    return Ok(());
+                };
+
+                let repo_diff_state_updates = this.update(&mut cx, |this, cx| {
+                    git_store.update(cx, |git_store, cx| {
+                        git_store
+                            .diffs
+                            .iter()
+                            .filter_map(|(buffer_id, diff_state)| {
+                                let buffer_store = git_store.buffer_store.read(cx);
+                                let buffer = buffer_store.get(*buffer_id)?;
+                                let file = File::from_dyn(buffer.read(cx).file())?;
+                                let abs_path =
+                                    file.worktree.read(cx).absolutize(&file.path).ok()?;
+                                let repo_path = this.abs_path_to_repo_path(&abs_path)?;
+                                log::debug!(
+                                    "start reload diff bases for repo path {}",
+                                    repo_path.0.display()
+                                );
+                                diff_state.update(cx, |diff_state, _| {
+                                    let has_unstaged_diff = diff_state
+                                        .unstaged_diff
+                                        .as_ref()
+                                        .is_some_and(|diff| diff.is_upgradable());
+                                    let has_uncommitted_diff = diff_state
+                                        .uncommitted_diff
+                                        .as_ref()
+                                        .is_some_and(|set| set.is_upgradable());
+
+                                    Some((
+                                        buffer,
+                                        repo_path,
+                                        has_unstaged_diff.then(|| diff_state.index_text.clone()),
+                                        has_uncommitted_diff.then(|| diff_state.head_text.clone()),
+                                    ))
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })??;
+
+                let buffer_diff_base_changes = cx
+                    .background_spawn(async move {
+                        let mut changes = Vec::new();
+                        for (buffer, repo_path, current_index_text, current_head_text) in
+                            &repo_diff_state_updates
+                        {
+                            let index_text = if current_index_text.is_some() {
+                                backend.load_index_text(repo_path.clone()).await
+                            } else {
+                                None
+                            };
+                            let head_text = if current_head_text.is_some() {
+                                backend.load_committed_text(repo_path.clone()).await
+                            } else {
+                                None
+                            };
+
+                            let change =
+                                match (current_index_text.as_ref(), current_head_text.as_ref()) {
+                                    (Some(current_index), Some(current_head)) => {
+                                        let index_changed =
+                                            index_text.as_ref() != current_index.as_deref();
+                                        let head_changed =
+                                            head_text.as_ref() != current_head.as_deref();
+                                        if index_changed && head_changed {
+                                            if index_text == head_text {
+                                                Some(DiffBasesChange::SetBoth(head_text))
+                                            } else {
+                                                Some(DiffBasesChange::SetEach {
+                                                    index: index_text,
+                                                    head: head_text,
                                                 })
                                             }
                                         } else if index_changed {
@@ -2718,286 +3346,1053 @@ impl Repository {
                                         index_changed
                                             .then_some(DiffBasesChange::SetIndex(index_text))
                                     }
-                                    (None, Some(current_head)) => {
-                                        let head_changed =
-                                            head_text.as_ref() != current_head.as_deref();
-                                        head_changed.then_some(DiffBasesChange::SetHead(head_text))
+                                    (None, Some(current_head)) => {
+                                        let head_changed =
+                                            head_text.as_ref() != current_head.as_deref();
+                                        head_changed.then_some(DiffBasesChange::SetHead(head_text))
+                                    }
+                                    (None, None) => None,
+                                };
+
+                            changes.push((buffer.clone(), change))
+                        }
+                        changes
+                    })
+                    .await;
+
+                git_store.update(&mut cx, |git_store, cx| {
+                    for (buffer, diff_bases_change) in buffer_diff_base_changes {
+                        let buffer_snapshot = buffer.read(cx).text_snapshot();
+                        let buffer_id = buffer_snapshot.remote_id();
+                        let Some(diff_state) = git_store.diffs.get(&buffer_id) else {
+                            continue;
+                        };
+
+                        let downstream_client = git_store.downstream_client();
+                        diff_state.update(cx, |diff_state, cx| {
+                            use proto::update_diff_bases::Mode;
+
+                            if let Some((diff_bases_change, (client, project_id))) =
+                                diff_bases_change.clone().zip(downstream_client)
+                            {
+                                let (staged_text, committed_text, mode) = match diff_bases_change {
+                                    DiffBasesChange::SetIndex(index) => {
+                                        (index, None, Mode::IndexOnly)
+                                    }
+                                    DiffBasesChange::SetHead(head) => (None, head, Mode::HeadOnly),
+                                    DiffBasesChange::SetEach { index, head } => {
+                                        (index, head, Mode::IndexAndHead)
+                                    }
+                                    DiffBasesChange::SetBoth(text) => {
+                                        (None, text, Mode::IndexMatchesHead)
                                     }
-                                    (None, None) => None,
                                 };
+                                client
+                                    .send(proto::UpdateDiffBases {
+                                        project_id: project_id.to_proto(),
+                                        buffer_id: buffer_id.to_proto(),
+                                        staged_text,
+                                        committed_text,
+                                        mode: mode as i32,
+                                    })
+                                    .log_err();
+                            }
+
+                            diff_state.diff_bases_changed(buffer_snapshot, diff_bases_change, cx);
+                        });
+                    }
+                })
+            },
+        );
+    }
+
+    pub fn send_job<F, Fut, R>(
+        &mut self,
+        status: Option<SharedString>,
+        job: F,
+    ) -> oneshot::Receiver<R>
+    where
+        F: FnOnce(RepositoryState, AsyncApp) -> Fut + 'static,
+        Fut: Future<Output = R> + 'static,
+        R: Send + 'static,
+    {
+        self.send_keyed_job(None, status, job)
+    }
+
+    fn send_keyed_job<F, Fut, R>(
+        &mut self,
+        key: Option<GitJobKey>,
+        status: Option<SharedString>,
+        job: F,
+    ) -> oneshot::Receiver<R>
+    where
+        F: FnOnce(RepositoryState, AsyncApp) -> Fut + 'static,
+        Fut: Future<Output = R> + 'static,
+        R: Send + 'static,
+    {
+        let (result_tx, result_rx) = futures::channel::oneshot::channel();
+        let job_id = post_inc(&mut self.job_id);
+        let this = self.this.clone();
+        self.job_sender
+            .unbounded_send(GitJob {
+                key,
+                job: Box::new(move |state, cx: &mut AsyncApp| {
+                    let job = job(state, cx.clone());
+                    cx.spawn(async move |cx| {
+                        if let Some(s) = status.clone() {
+                            this.update(cx, |this, cx| {
+                                this.active_jobs.insert(
+                                    job_id,
+                                    JobInfo {
+                                        start: Instant::now(),
+                                        message: s.clone(),
+                                    },
+                                );
+
+                                cx.notify();
+                            })
+                            .ok();
+                        }
+                        let result = job.await;
+
+                        this.update(cx, |this, cx| {
+                            this.active_jobs.remove(&job_id);
+                            cx.notify();
+                        })
+                        .ok();
+
+                        result_tx.send(result).ok();
+                    })
+                }),
+            })
+            .ok();
+        result_rx
+    }
+
+    pub fn set_as_active_repository(&self, cx: &mut Context<Self>) {
+        let Some(git_store) = self.git_store.upgrade() else {
+            return;
+        };
+        let entity = cx.entity();
+        git_store.update(cx, |git_store, cx| {
+            let Some((&id, _)) = git_store
+                .repositories
+                .iter()
+                .find(|(_, handle)| *handle == &entity)
+            else {
+                return;
+            };
+            git_store.active_repo_id = Some(id);
+            cx.emit(GitStoreEvent::ActiveRepositoryChanged(Some(id)));
+        });
+    }
+
+    pub fn cached_status(&self) -> impl '_ + Iterator<Item = StatusEntry> {
+        self.snapshot.status()
+    }
+
+    pub fn repo_path_to_project_path(&self, path: &RepoPath, cx: &App) -> Option<ProjectPath> {
+        let git_store = self.git_store.upgrade()?;
+        let worktree_store = git_store.read(cx).worktree_store.read(cx);
+        let abs_path = self.snapshot.work_directory_abs_path.join(&path.0);
+        let (worktree, relative_path) = worktree_store.find_worktree(abs_path, cx)?;
+        Some(ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: relative_path.into(),
+        })
+    }
+
+    pub fn project_path_to_repo_path(&self, path: &ProjectPath, cx: &App) -> Option<RepoPath> {
+        let git_store = self.git_store.upgrade()?;
+        let worktree_store = git_store.read(cx).worktree_store.read(cx);
+        let abs_path = worktree_store.absolutize(path, cx)?;
+        self.snapshot.abs_path_to_repo_path(&abs_path)
+    }
+
+    pub fn contains_sub_repo(&self, other: &Entity<Self>, cx: &App) -> bool {
+        other
+            .read(cx)
+            .snapshot
+            .work_directory_abs_path
+            .starts_with(&self.snapshot.work_directory_abs_path)
+    }
+
+    pub fn open_commit_buffer(
+        &mut self,
+        languages: Option<Arc<LanguageRegistry>>,
+        buffer_store: Entity<BufferStore>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Buffer>>> {
+        let id = self.id;
+        if let Some(buffer) = self.commit_message_buffer.clone() {
+            return Task::ready(Ok(buffer));
+        }
+        let this = cx.weak_entity();
+
+        let rx = self.send_job(None, move |state, mut cx| async move {
+            let Some(this) = this.upgrade() else {
+                bail!("git store was dropped");
+            };
+            match state {
+                RepositoryState::Local { .. } => {
+                    this.update(&mut cx, |_, cx| {
+                        Self::open_local_commit_buffer(languages, buffer_store, cx)
+                    })?
+                    .await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    let request = client.request(proto::OpenCommitMessageBuffer {
+                        project_id: project_id.0,
+                        repository_id: id.to_proto(),
+                    });
+                    let response = request.await.context("requesting to open commit buffer")?;
+                    let buffer_id = BufferId::new(response.buffer_id)?;
+                    let buffer = buffer_store
+                        .update(&mut cx, |buffer_store, cx| {
+                            buffer_store.wait_for_remote_buffer(buffer_id, cx)
+                        })?
+                        .await?;
+                    if let Some(language_registry) = languages {
+                        let git_commit_language =
+                            language_registry.language_for_name("Git Commit").await?;
+                        buffer.update(&mut cx, |buffer, cx| {
+                            buffer.set_language(Some(git_commit_language), cx);
+                        })?;
+                    }
+                    this.update(&mut cx, |this, _| {
+                        this.commit_message_buffer = Some(buffer.clone());
+                    })?;
+                    Ok(buffer)
+                }
+            }
+        });
+
+        cx.spawn(|_, _: &mut AsyncApp| async move { rx.await? })
+    }
+
+    fn open_local_commit_buffer(
+        language_registry: Option<Arc<LanguageRegistry>>,
+        buffer_store: Entity<BufferStore>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Buffer>>> {
+        cx.spawn(async move |repository, cx| {
+            let buffer = buffer_store
+                .update(cx, |buffer_store, cx| buffer_store.create_buffer(cx))?
+                .await?;
+
+            if let Some(language_registry) = language_registry {
+                let git_commit_language = language_registry.language_for_name("Git Commit").await?;
+                buffer.update(cx, |buffer, cx| {
+                    buffer.set_language(Some(git_commit_language), cx);
+                })?;
+            }
+
+            repository.update(cx, |repository, _| {
+                repository.commit_message_buffer = Some(buffer.clone());
+            })?;
+            Ok(buffer)
+        })
+    }
+
+    pub fn checkout_files(
+        &mut self,
+        commit: &str,
+        paths: Vec<RepoPath>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let commit = commit.to_string();
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git checkout {}", commit).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .checkout_files(commit, paths, environment.clone())
+                            .await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCheckoutFiles {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                                paths: paths
+                                    .into_iter()
+                                    .map(|p| p.to_string_lossy().to_string())
+                                    .collect(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
 
-                            changes.push((buffer.clone(), change))
-                        }
-                        changes
+    pub fn cherry_pick(&mut self, commit: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git cherry-pick {commit}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.cherry_pick(commit, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commit: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {commit}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.revert(commit, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn reset(
+        &mut self,
+        commit: String,
+        reset_mode: ResetMode,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let commit = commit.to_string();
+        let id = self.id;
+
+        self.send_job(None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.reset(commit, reset_mode, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitReset {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                            mode: match reset_mode {
+                                ResetMode::Soft => git_reset::ResetMode::Soft.into(),
+                                ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                                ResetMode::Hard => git_reset::ResetMode::Hard.into(),
+                            },
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.show(commit).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let resp = client
+                        .request(proto::GitShow {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+
+                    Ok(proto_to_commit_details(&resp))
+                }
+            }
+        })
+    }
+
+    /// Verifies the commit's signature. This shells out to git, so it's only done on demand
+    /// rather than as part of [`Self::show`].
+    pub fn commit_signature(
+        &mut self,
+        commit: String,
+    ) -> oneshot::Receiver<Result<Option<CommitSignature>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.commit_signature(commit).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitLoadCommitSignature {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+                    Ok(response.signature.as_ref().map(proto_to_signature))
+                }
+            }
+        })
+    }
+
+    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.load_commit(commit, cx).await,
+                RepositoryState::Remote {
+                    client, project_id, ..
+                } => {
+                    let response = client
+                        .request(proto::LoadCommitDiff {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+                    Ok(CommitDiff {
+                        files: response
+                            .files
+                            .into_iter()
+                            .map(|file| CommitFile {
+                                path: Path::new(&file.path).into(),
+                                old_text: file.old_text,
+                                new_text: file.new_text,
+                            })
+                            .collect(),
                     })
-                    .await;
+                }
+            }
+        })
+    }
+
+    pub fn log(&mut self, options: LogOptions) -> oneshot::Receiver<Result<Vec<LogEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.log(options).await,
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::GitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: options.path.as_ref().map(|path| path.to_proto()),
+                            line_start: options.line_range.as_ref().map(|range| range.start),
+                            line_end: options.line_range.as_ref().map(|range| range.end),
+                            author: options.author,
+                            message: options.message,
+                            since: options.since,
+                            until: options.until,
+                            skip: options.skip as u64,
+                            limit: options.limit as u64,
+                        })
+                        .await?;
+                    Ok(response.entries.iter().map(proto_to_log_entry).collect())
+                }
+            }
+        })
+    }
+
+    pub fn file_history(
+        &mut self,
+        path: RepoPath,
+        skip: usize,
+        limit: usize,
+    ) -> oneshot::Receiver<Result<Vec<FileHistoryEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => {
+                    backend.file_history(path, skip, limit).await
+                }
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::GitFileHistory {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                            skip: skip as u64,
+                            limit: limit as u64,
+                        })
+                        .await?;
+                    Ok(response
+                        .entries
+                        .iter()
+                        .filter_map(proto_to_file_history_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn load_text_at_revision(
+        &mut self,
+        commit: String,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<Option<String>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => {
+                    backend.load_text_at_revision(commit, path).await
+                }
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::GitLoadTextAtRevision {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(response.text)
+                }
+            }
+        })
+    }
+
+    pub fn blame_at_revision(
+        &mut self,
+        path: RepoPath,
+        commit: String,
+    ) -> oneshot::Receiver<Result<Blame>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend
+                    .blame_at_revision(path.clone(), commit)
+                    .await
+                    .with_context(|| format!("Failed to blame {:?}", path.0)),
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::GitBlameAtRevision {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                            commit,
+                        })
+                        .await?;
+                    deserialize_blame_buffer_response(response).context("missing blame in response")
+                }
+            }
+        })
+    }
+
+    pub fn worktrees(&mut self) -> oneshot::Receiver<Result<Vec<GitWorktree>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.worktrees().await,
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::GitWorktrees {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+                    Ok(response.worktrees.iter().map(proto_to_worktree).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_worktree(
+        &mut self,
+        path: PathBuf,
+        branch: String,
+        new_branch: bool,
+        base: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git worktree add {}", path.display()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .create_worktree(path, branch, new_branch, base, environment)
+                            .await
+                    }
+                    RepositoryState::Remote { client, project_id } => {
+                        client
+                            .request(proto::GitCreateWorktree {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.to_string_lossy().into_owned(),
+                                branch,
+                                new_branch,
+                                base,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
 
-                git_store.update(&mut cx, |git_store, cx| {
-                    for (buffer, diff_bases_change) in buffer_diff_base_changes {
-                        let buffer_snapshot = buffer.read(cx).text_snapshot();
-                        let buffer_id = buffer_snapshot.remote_id();
-                        let Some(diff_state) = git_store.diffs.get(&buffer_id) else {
-                            continue;
-                        };
+    pub fn remove_worktree(&mut self, path: PathBuf, force: bool) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git worktree remove {}", path.display()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.remove_worktree(path, force, environment).await,
+                    RepositoryState::Remote { client, project_id } => {
+                        client
+                            .request(proto::GitRemoveWorktree {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.to_string_lossy().into_owned(),
+                                force,
+                            })
+                            .await?;
 
-                        let downstream_client = git_store.downstream_client();
-                        diff_state.update(cx, |diff_state, cx| {
-                            use proto::update_diff_bases::Mode;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
 
-                            if let Some((diff_bases_change, (client, project_id))) =
-                                diff_bases_change.clone().zip(downstream_client)
-                            {
-                                let (staged_text, committed_text, mode) = match diff_bases_change {
-                                    DiffBasesChange::SetIndex(index) => {
-                                        (index, None, Mode::IndexOnly)
-                                    }
-                                    DiffBasesChange::SetHead(head) => (None, head, Mode::HeadOnly),
-                                    DiffBasesChange::SetEach { index, head } => {
-                                        (index, head, Mode::IndexAndHead)
-                                    }
-                                    DiffBasesChange::SetBoth(text) => {
-                                        (None, text, Mode::IndexMatchesHead)
-                                    }
-                                };
-                                client
-                                    .send(proto::UpdateDiffBases {
-                                        project_id: project_id.to_proto(),
-                                        buffer_id: buffer_id.to_proto(),
-                                        staged_text,
-                                        committed_text,
-                                        mode: mode as i32,
-                                    })
-                                    .log_err();
-                            }
+    pub fn prune_worktrees(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git worktree prune".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.prune_worktrees(environment).await,
+                    RepositoryState::Remote { client, project_id } => {
+                        client
+                            .request(proto::GitPruneWorktrees {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
 
-                            diff_state.diff_bases_changed(buffer_snapshot, diff_bases_change, cx);
-                        });
+                        Ok(())
                     }
-                })
+                }
             },
-        );
+        )
     }
 
-    pub fn send_job<F, Fut, R>(
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.tags().await,
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::GitTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_tag(
         &mut self,
-        status: Option<SharedString>,
-        job: F,
-    ) -> oneshot::Receiver<R>
-    where
-        F: FnOnce(RepositoryState, AsyncApp) -> Fut + 'static,
-        Fut: Future<Output = R> + 'static,
-        R: Send + 'static,
-    {
-        self.send_keyed_job(None, status, job)
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {name}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.create_tag(name, commit, message, environment).await,
+                    RepositoryState::Remote { client, project_id } => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                commit,
+                                message,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
     }
 
-    fn send_keyed_job<F, Fut, R>(
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag --delete {name}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.delete_tag(name, environment).await,
+                    RepositoryState::Remote { client, project_id } => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn push_tag(
         &mut self,
-        key: Option<GitJobKey>,
-        status: Option<SharedString>,
-        job: F,
-    ) -> oneshot::Receiver<R>
-    where
-        F: FnOnce(RepositoryState, AsyncApp) -> Fut + 'static,
-        Fut: Future<Output = R> + 'static,
-        R: Send + 'static,
-    {
-        let (result_tx, result_rx) = futures::channel::oneshot::channel();
-        let job_id = post_inc(&mut self.job_id);
-        let this = self.this.clone();
-        self.job_sender
-            .unbounded_send(GitJob {
-                key,
-                job: Box::new(move |state, cx: &mut AsyncApp| {
-                    let job = job(state, cx.clone());
-                    cx.spawn(async move |cx| {
-                        if let Some(s) = status.clone() {
-                            this.update(cx, |this, cx| {
-                                this.active_jobs.insert(
-                                    job_id,
-                                    JobInfo {
-                                        start: Instant::now(),
-                                        message: s.clone(),
-                                    },
-                                );
+        name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
 
-                                cx.notify();
+        self.send_job(
+            Some(format!("git push {} refs/tags/{}", remote, name).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .push_tag(
+                                name.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment,
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name: name.to_string(),
+                                remote_name: remote.to_string(),
+                                askpass_id,
                             })
-                            .ok();
-                        }
-                        let result = job.await;
+                            .await
+                            .context("sending push tag request")?;
 
-                        this.update(cx, |this, cx| {
-                            this.active_jobs.remove(&job_id);
-                            cx.notify();
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
                         })
-                        .ok();
+                    }
+                }
+            },
+        )
+    }
 
-                        result_tx.send(result).ok();
+    pub fn load_conflict_texts(
+        &mut self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<ConflictTexts>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.load_conflict_texts(path).await,
+                RepositoryState::Remote { client, project_id } => {
+                    let response = client
+                        .request(proto::LoadConflictTexts {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(ConflictTexts {
+                        base: response.base,
+                        ours: response.ours,
+                        theirs: response.theirs,
                     })
-                }),
-            })
-            .ok();
-        result_rx
+                }
+            }
+        })
     }
 
-    pub fn set_as_active_repository(&self, cx: &mut Context<Self>) {
-        let Some(git_store) = self.git_store.upgrade() else {
-            return;
-        };
-        let entity = cx.entity();
-        git_store.update(cx, |git_store, cx| {
-            let Some((&id, _)) = git_store
-                .repositories
-                .iter()
-                .find(|(_, handle)| *handle == &entity)
-            else {
-                return;
-            };
-            git_store.active_repo_id = Some(id);
-            cx.emit(GitStoreEvent::ActiveRepositoryChanged(Some(id)));
-        });
-    }
+    pub fn stash_entries(&mut self) -> oneshot::Receiver<Result<Vec<StashEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.stash_entries().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitStashEntries {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
 
-    pub fn cached_status(&self) -> impl '_ + Iterator<Item = StatusEntry> {
-        self.snapshot.status()
+                    Ok(response.entries.iter().map(proto_to_stash_entry).collect())
+                }
+            }
+        })
     }
 
-    pub fn repo_path_to_project_path(&self, path: &RepoPath, cx: &App) -> Option<ProjectPath> {
-        let git_store = self.git_store.upgrade()?;
-        let worktree_store = git_store.read(cx).worktree_store.read(cx);
-        let abs_path = self.snapshot.work_directory_abs_path.join(&path.0);
-        let (worktree, relative_path) = worktree_store.find_worktree(abs_path, cx)?;
-        Some(ProjectPath {
-            worktree_id: worktree.read(cx).id(),
-            path: relative_path.into(),
+    pub fn stash_push(
+        &mut self,
+        message: Option<String>,
+        options: StashOptions,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(Some("git stash".into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.stash_push(message, options, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitStash {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            message,
+                            include_untracked: options.include_untracked,
+                            staged: options.staged,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
         })
     }
 
-    pub fn project_path_to_repo_path(&self, path: &ProjectPath, cx: &App) -> Option<RepoPath> {
-        let git_store = self.git_store.upgrade()?;
-        let worktree_store = git_store.read(cx).worktree_store.read(cx);
-        let abs_path = worktree_store.absolutize(path, cx)?;
-        self.snapshot.abs_path_to_repo_path(&abs_path)
+    pub fn stash_apply(&mut self, index: usize) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git stash apply stash@{{{index}}}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_apply(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitStashApply {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                index: index as u64,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
     }
 
-    pub fn contains_sub_repo(&self, other: &Entity<Self>, cx: &App) -> bool {
-        other
-            .read(cx)
-            .snapshot
-            .work_directory_abs_path
-            .starts_with(&self.snapshot.work_directory_abs_path)
+    pub fn stash_pop(&mut self, index: usize) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git stash pop stash@{{{index}}}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_pop(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitStashPop {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                index: index as u64,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
     }
 
-    pub fn open_commit_buffer(
-        &mut self,
-        languages: Option<Arc<LanguageRegistry>>,
-        buffer_store: Entity<BufferStore>,
-        cx: &mut Context<Self>,
-    ) -> Task<Result<Entity<Buffer>>> {
+    pub fn stash_drop(&mut self, index: usize) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        if let Some(buffer) = self.commit_message_buffer.clone() {
-            return Task::ready(Ok(buffer));
-        }
-        let this = cx.weak_entity();
+        self.send_job(
+            Some(format!("git stash drop stash@{{{index}}}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_drop(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitStashDrop {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                index: index as u64,
+                            })
+                            .await?;
 
-        let rx = self.send_job(None, move |state, mut cx| async move {
-            let Some(this) = this.upgrade() else {
-                bail!("git store was dropped");
-            };
-            match state {
-                RepositoryState::Local { .. } => {
-                    this.update(&mut cx, |_, cx| {
-                        Self::open_local_commit_buffer(languages, buffer_store, cx)
-                    })?
-                    .await
+                        Ok(())
+                    }
                 }
+            },
+        )
+    }
+
+    pub fn stash_show(&mut self, index: usize) -> oneshot::Receiver<Result<CommitDiff>> {
+        let id = self.id;
+        self.send_job(None, move |repo, cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.stash_show(index, cx).await,
                 RepositoryState::Remote { project_id, client } => {
-                    let request = client.request(proto::OpenCommitMessageBuffer {
-                        project_id: project_id.0,
-                        repository_id: id.to_proto(),
-                    });
-                    let response = request.await.context("requesting to open commit buffer")?;
-                    let buffer_id = BufferId::new(response.buffer_id)?;
-                    let buffer = buffer_store
-                        .update(&mut cx, |buffer_store, cx| {
-                            buffer_store.wait_for_remote_buffer(buffer_id, cx)
-                        })?
+                    let response = client
+                        .request(proto::GitStashShow {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            index: index as u64,
+                        })
                         .await?;
-                    if let Some(language_registry) = languages {
-                        let git_commit_language =
-                            language_registry.language_for_name("Git Commit").await?;
-                        buffer.update(&mut cx, |buffer, cx| {
-                            buffer.set_language(Some(git_commit_language), cx);
-                        })?;
-                    }
-                    this.update(&mut cx, |this, _| {
-                        this.commit_message_buffer = Some(buffer.clone());
-                    })?;
-                    Ok(buffer)
+                    Ok(CommitDiff {
+                        files: response
+                            .files
+                            .into_iter()
+                            .map(|file| CommitFile {
+                                path: Path::new(&file.path).into(),
+                                old_text: file.old_text,
+                                new_text: file.new_text,
+                            })
+                            .collect(),
+                    })
                 }
             }
-        });
-
-        cx.spawn(|_, _: &mut AsyncApp| async move { rx.await? })
+        })
     }
 
-    fn open_local_commit_buffer(
-        language_registry: Option<Arc<LanguageRegistry>>,
-        buffer_store: Entity<BufferStore>,
-        cx: &mut Context<Self>,
-    ) -> Task<Result<Entity<Buffer>>> {
-        cx.spawn(async move |repository, cx| {
-            let buffer = buffer_store
-                .update(cx, |buffer_store, cx| buffer_store.create_buffer(cx))?
-                .await?;
+    pub fn rebase_todo(&mut self, base: String) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.rebase_todo(base).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
 
-            if let Some(language_registry) = language_registry {
-                let git_commit_language = language_registry.language_for_name("Git Commit").await?;
-                buffer.update(cx, |buffer, cx| {
-                    buffer.set_language(Some(git_commit_language), cx);
-                })?;
+                    Ok(response
+                        .entries
+                        .iter()
+                        .map(proto_to_rebase_todo_entry)
+                        .collect())
+                }
             }
-
-            repository.update(cx, |repository, _| {
-                repository.commit_message_buffer = Some(buffer.clone());
-            })?;
-            Ok(buffer)
         })
     }
 
-    pub fn checkout_files(
+    pub fn rebase_start(
         &mut self,
-        commit: &str,
-        paths: Vec<RepoPath>,
+        base: String,
+        todo: Vec<RebaseTodoEntry>,
         _cx: &mut App,
     ) -> oneshot::Receiver<Result<()>> {
-        let commit = commit.to_string();
         let id = self.id;
-
         self.send_job(
-            Some(format!("git checkout {}", commit).into()),
-            move |git_repo, _| async move {
-                match git_repo {
+            Some(format!("git rebase --interactive {base}").into()),
+            move |repo, _cx| async move {
+                match repo {
                     RepositoryState::Local {
                         backend,
                         environment,
                         ..
-                    } => {
-                        backend
-                            .checkout_files(commit, paths, environment.clone())
-                            .await
-                    }
+                    } => backend.rebase_start(base, todo, environment).await,
                     RepositoryState::Remote { project_id, client } => {
                         client
-                            .request(proto::GitCheckoutFiles {
+                            .request(proto::GitRebaseStart {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                commit,
-                                paths: paths
-                                    .into_iter()
-                                    .map(|p| p.to_string_lossy().to_string())
-                                    .collect(),
+                                base,
+                                todo: todo.iter().map(rebase_todo_entry_to_proto).collect(),
                             })
                             .await?;
 
@@ -3008,96 +4403,82 @@ impl Repository {
         )
     }
 
-    pub fn reset(
-        &mut self,
-        commit: String,
-        reset_mode: ResetMode,
-        _cx: &mut App,
-    ) -> oneshot::Receiver<Result<()>> {
-        let commit = commit.to_string();
+    pub fn rebase_continue(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
+        self.send_job(
+            Some("git rebase --continue".into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.rebase_continue(environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRebaseContinue {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
 
-        self.send_job(None, move |git_repo, _| async move {
-            match git_repo {
-                RepositoryState::Local {
-                    backend,
-                    environment,
-                    ..
-                } => backend.reset(commit, reset_mode, environment).await,
-                RepositoryState::Remote { project_id, client } => {
-                    client
-                        .request(proto::GitReset {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            commit,
-                            mode: match reset_mode {
-                                ResetMode::Soft => git_reset::ResetMode::Soft.into(),
-                                ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
-                            },
-                        })
-                        .await?;
-
-                    Ok(())
+                        Ok(())
+                    }
                 }
-            }
-        })
+            },
+        )
     }
 
-    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+    pub fn rebase_skip(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        self.send_job(None, move |git_repo, _cx| async move {
-            match git_repo {
-                RepositoryState::Local { backend, .. } => backend.show(commit).await,
-                RepositoryState::Remote { project_id, client } => {
-                    let resp = client
-                        .request(proto::GitShow {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            commit,
-                        })
-                        .await?;
+        self.send_job(
+            Some("git rebase --skip".into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.rebase_skip(environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRebaseSkip {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
 
-                    Ok(CommitDetails {
-                        sha: resp.sha.into(),
-                        message: resp.message.into(),
-                        commit_timestamp: resp.commit_timestamp,
-                        author_email: resp.author_email.into(),
-                        author_name: resp.author_name.into(),
-                    })
+                        Ok(())
+                    }
                 }
-            }
-        })
+            },
+        )
     }
 
-    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+    pub fn rebase_abort(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        self.send_job(None, move |git_repo, cx| async move {
-            match git_repo {
-                RepositoryState::Local { backend, .. } => backend.load_commit(commit, cx).await,
-                RepositoryState::Remote {
-                    client, project_id, ..
-                } => {
-                    let response = client
-                        .request(proto::LoadCommitDiff {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            commit,
-                        })
-                        .await?;
-                    Ok(CommitDiff {
-                        files: response
-                            .files
-                            .into_iter()
-                            .map(|file| CommitFile {
-                                path: Path::new(&file.path).into(),
-                                old_text: file.old_text,
-                                new_text: file.new_text,
+        self.send_job(
+            Some("git rebase --abort".into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.rebase_abort(environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRebaseAbort {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
                             })
-                            .collect(),
-                    })
+                            .await?;
+
+                        Ok(())
+                    }
                 }
-            }
-        })
+            },
+        )
     }
 
     fn buffer_store(&self, cx: &App) -> Option<Entity<BufferStore>> {
@@ -3287,6 +4668,7 @@ impl Repository {
                             email: email.map(String::from),
                             options: Some(proto::commit::CommitOptions {
                                 amend: options.amend,
+                                sign: options.sign,
                             }),
                         })
                         .await
@@ -3696,6 +5078,146 @@ impl Repository {
         )
     }
 
+    pub fn rename_branch(
+        &mut self,
+        branch_name: String,
+        new_name: String,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git branch --move {branch_name} {new_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .rename_branch(branch_name, new_name, environment)
+                            .await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRenameBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                                new_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_branch(
+        &mut self,
+        branch_name: String,
+        force: bool,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let flag = if force { "-D" } else { "-d" };
+        self.send_job(
+            Some(format!("git branch {flag} {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.delete_branch(branch_name, force, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitDeleteBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                                force,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn set_upstream(
+        &mut self,
+        branch_name: String,
+        upstream: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let description = match &upstream {
+            Some(upstream) => format!("git branch --set-upstream-to={upstream} {branch_name}"),
+            None => format!("git branch --unset-upstream {branch_name}"),
+        };
+        self.send_job(Some(description.into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => {
+                    backend
+                        .set_upstream(branch_name, upstream, environment)
+                        .await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitSetUpstream {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                            upstream,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn compare_with_branch(
+        &mut self,
+        branch_name: String,
+    ) -> oneshot::Receiver<Result<CommitDiff>> {
+        let id = self.id;
+        self.send_job(None, move |repo, cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => {
+                    backend.compare_with_branch(branch_name, cx).await
+                }
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitCompareBranch {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                        })
+                        .await?;
+                    Ok(CommitDiff {
+                        files: response
+                            .files
+                            .into_iter()
+                            .map(|file| CommitFile {
+                                path: Path::new(&file.path).into(),
+                                old_text: file.old_text,
+                                new_text: file.new_text,
+                            })
+                            .collect(),
+                    })
+                }
+            }
+        })
+    }
+
     pub fn check_for_pushed_commits(&mut self) -> oneshot::Receiver<Result<Vec<SharedString>>> {
         let id = self.id;
         self.send_job(None, move |repo, _cx| async move {
@@ -3756,6 +5278,7 @@ impl Repository {
             .head_commit_details
             .as_ref()
             .map(proto_to_commit_details);
+        self.snapshot.rebase_state = update.rebase_state.as_ref().map(proto_to_rebase_state);
 
         self.snapshot.merge_conflicts = conflicted_paths;
 
@@ -4351,6 +5874,200 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
     }
 }
 
+fn rebase_state_to_proto(rebase_state: &RebaseState) -> proto::GitRebaseState {
+    proto::GitRebaseState {
+        head_name: rebase_state.head_name.as_ref().map(ToString::to_string),
+        onto: rebase_state.onto.to_string(),
+        current_step: rebase_state.current_step as u64,
+        total_steps: rebase_state.total_steps as u64,
+        stopped_sha: rebase_state.stopped_sha.as_ref().map(ToString::to_string),
+    }
+}
+
+fn proto_to_rebase_state(proto: &proto::GitRebaseState) -> RebaseState {
+    RebaseState {
+        head_name: proto.head_name.clone().map(SharedString::from),
+        onto: proto.onto.clone().into(),
+        current_step: proto.current_step as usize,
+        total_steps: proto.total_steps as usize,
+        stopped_sha: proto.stopped_sha.clone().map(SharedString::from),
+    }
+}
+
+fn signature_to_proto(signature: &CommitSignature) -> proto::GitCommitSignature {
+    use proto::git_commit_signature::Status;
+
+    let status = match signature.status {
+        SignatureStatus::Good => Status::Good,
+        SignatureStatus::GoodUnknownValidity => Status::GoodUnknownValidity,
+        SignatureStatus::Bad => Status::Bad,
+        SignatureStatus::ExpiredSignature => Status::ExpiredSignature,
+        SignatureStatus::ExpiredKey => Status::ExpiredKey,
+        SignatureStatus::RevokedKey => Status::RevokedKey,
+        SignatureStatus::CannotCheck => Status::CannotCheck,
+    };
+    proto::GitCommitSignature {
+        status: status as i32,
+        signer: signature.signer.to_string(),
+        key: signature.key.to_string(),
+    }
+}
+
+fn proto_to_signature(proto: &proto::GitCommitSignature) -> CommitSignature {
+    use proto::git_commit_signature::Status;
+
+    let status = match Status::from_i32(proto.status) {
+        Some(Status::Good) => SignatureStatus::Good,
+        Some(Status::GoodUnknownValidity) => SignatureStatus::GoodUnknownValidity,
+        Some(Status::Bad) => SignatureStatus::Bad,
+        Some(Status::ExpiredSignature) => SignatureStatus::ExpiredSignature,
+        Some(Status::ExpiredKey) => SignatureStatus::ExpiredKey,
+        Some(Status::RevokedKey) => SignatureStatus::RevokedKey,
+        Some(Status::CannotCheck) | None => SignatureStatus::CannotCheck,
+    };
+    CommitSignature {
+        status,
+        signer: proto.signer.clone().into(),
+        key: proto.key.clone().into(),
+    }
+}
+
+fn stash_entry_to_proto(entry: &StashEntry) -> proto::StashEntry {
+    proto::StashEntry {
+        index: entry.index as u64,
+        sha: entry.sha.to_string(),
+        message: entry.message.to_string(),
+        commit_timestamp: entry.commit_timestamp,
+    }
+}
+
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::RebaseTodoEntry {
+    use proto::rebase_todo_entry::RebaseAction as ProtoRebaseAction;
+
+    let action = match entry.action {
+        RebaseAction::Pick => ProtoRebaseAction::Pick,
+        RebaseAction::Reword => ProtoRebaseAction::Reword,
+        RebaseAction::Squash => ProtoRebaseAction::Squash,
+        RebaseAction::Fixup => ProtoRebaseAction::Fixup,
+        RebaseAction::Drop => ProtoRebaseAction::Drop,
+    };
+    proto::RebaseTodoEntry {
+        action: action as i32,
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.clone(),
+    }
+}
+
+fn proto_to_rebase_todo_entry(proto: &proto::RebaseTodoEntry) -> RebaseTodoEntry {
+    use proto::rebase_todo_entry::RebaseAction as ProtoRebaseAction;
+
+    let action = match ProtoRebaseAction::from_i32(proto.action) {
+        Some(ProtoRebaseAction::Pick) | None => RebaseAction::Pick,
+        Some(ProtoRebaseAction::Reword) => RebaseAction::Reword,
+        Some(ProtoRebaseAction::Squash) => RebaseAction::Squash,
+        Some(ProtoRebaseAction::Fixup) => RebaseAction::Fixup,
+        Some(ProtoRebaseAction::Drop) => RebaseAction::Drop,
+    };
+    RebaseTodoEntry {
+        action,
+        sha: proto.sha.clone().into(),
+        subject: proto.subject.clone().into(),
+        message: proto.message.clone(),
+    }
+}
+
+fn log_entry_to_proto(entry: &LogEntry) -> proto::GitLogEntry {
+    proto::GitLogEntry {
+        sha: entry.sha.to_string(),
+        parents: entry.parents.iter().map(ToString::to_string).collect(),
+        author_name: entry.author_name.to_string(),
+        author_email: entry.author_email.to_string(),
+        commit_timestamp: entry.commit_timestamp,
+        subject: entry.subject.to_string(),
+        refs: entry.refs.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn proto_to_log_entry(proto: &proto::GitLogEntry) -> LogEntry {
+    LogEntry {
+        sha: proto.sha.clone().into(),
+        parents: proto.parents.iter().cloned().map(Into::into).collect(),
+        author_name: proto.author_name.clone().into(),
+        author_email: proto.author_email.clone().into(),
+        commit_timestamp: proto.commit_timestamp,
+        subject: proto.subject.clone().into(),
+        refs: proto.refs.iter().cloned().map(Into::into).collect(),
+    }
+}
+
+fn file_history_entry_to_proto(entry: &FileHistoryEntry) -> proto::GitFileHistoryEntry {
+    proto::GitFileHistoryEntry {
+        commit: Some(log_entry_to_proto(&entry.commit)),
+        path: entry.path.to_proto(),
+        old_path: entry.old_path.as_ref().map(|path| path.to_proto()),
+    }
+}
+
+fn proto_to_file_history_entry(proto: &proto::GitFileHistoryEntry) -> Option<FileHistoryEntry> {
+    Some(FileHistoryEntry {
+        commit: proto_to_log_entry(proto.commit.as_ref()?),
+        path: RepoPath::from_str(&proto.path),
+        old_path: proto.old_path.as_deref().map(RepoPath::from_str),
+    })
+}
+
+fn worktree_to_proto(worktree: &GitWorktree) -> proto::GitWorktreeEntry {
+    proto::GitWorktreeEntry {
+        path: worktree.path.to_string_lossy().into_owned(),
+        head_sha: worktree.head_sha.as_ref().map(ToString::to_string),
+        branch: worktree.branch.as_ref().map(ToString::to_string),
+        is_main: worktree.is_main,
+        is_locked: worktree.is_locked,
+        is_prunable: worktree.is_prunable,
+    }
+}
+
+fn proto_to_worktree(proto: &proto::GitWorktreeEntry) -> GitWorktree {
+    GitWorktree {
+        path: PathBuf::from(&proto.path),
+        head_sha: proto.head_sha.clone().map(Into::into),
+        branch: proto.branch.clone().map(Into::into),
+        is_main: proto.is_main,
+        is_locked: proto.is_locked,
+        is_prunable: proto.is_prunable,
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::GitTagEntry {
+    proto::GitTagEntry {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        is_annotated: tag.is_annotated,
+        subject: tag.subject.to_string(),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTagEntry) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        is_annotated: proto.is_annotated,
+        subject: proto.subject.clone().into(),
+        timestamp: proto.timestamp,
+    }
+}
+
+fn proto_to_stash_entry(proto: &proto::StashEntry) -> StashEntry {
+    StashEntry {
+        index: proto.index as usize,
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().into(),
+        commit_timestamp: proto.commit_timestamp,
+    }
+}
+
 async fn compute_snapshot(
     id: RepositoryId,
     work_directory_abs_path: Arc<Path>,
@@ -4370,6 +6087,7 @@ async fn compute_snapshot(
         .into_iter()
         .map(SharedString::from)
         .collect();
+    let rebase_state = backend.rebase_state().await;
 
     let statuses_by_path = SumTree::from_iter(
         statuses
@@ -4383,8 +6101,11 @@ async fn compute_snapshot(
     );
 
     let merge_head_shas_changed = merge_head_shas != prev_snapshot.merge_head_shas;
+    // Each rebase step can stop on its own set of conflicts.
+    let rebase_state_changed = rebase_state != prev_snapshot.rebase_state;
 
     if merge_head_shas_changed
+        || rebase_state_changed
         || branch != prev_snapshot.branch
         || statuses_by_path != prev_snapshot.statuses_by_path
     {
@@ -4399,9 +6120,9 @@ async fn compute_snapshot(
     }
 
     // Cache merge conflict paths so they don't change from staging/unstaging,
-    // until the merge heads change (at commit time, etc.).
+    // until the merge heads or rebase step change (at commit time, etc.).
     let mut merge_conflicts = prev_snapshot.merge_conflicts.clone();
-    if merge_head_shas_changed {
+    if merge_head_shas_changed || rebase_state_changed {
         merge_conflicts = current_merge_conflicts;
         events.push(RepositoryEvent::MergeHeadsChanged);
     }
@@ -4422,6 +6143,7 @@ async fn compute_snapshot(
         head_commit,
         merge_conflicts,
         merge_head_shas,
+        rebase_state,
     };
 
     Ok((snapshot, events))
@@ -158,11 +158,19 @@ pub struct LocalLspStore {
         >,
     >,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
+    /// Diagnostics produced by the problem matchers of tasks, keyed by the task's full label.
+    task_diagnostics: HashMap<String, TaskDiagnostics>,
     _subscription: gpui::Subscription,
     lsp_tree: Entity<LanguageServerTree>,
     registered_buffers: HashMap<BufferId, usize>,
 }
 
+/// Task diagnostics are stored like the ones from a language server, under an id that no server uses.
+struct TaskDiagnostics {
+    server_id: LanguageServerId,
+    paths: HashSet<PathBuf>,
+}
+
 impl LocalLspStore {
     /// Returns the running language server for the given ID. Note if the language server is starting, it will not be returned.
     pub fn running_language_server_for_id(
@@ -3553,6 +3561,7 @@ impl LspStore {
                 yarn,
                 next_diagnostic_group_id: Default::default(),
                 diagnostics: Default::default(),
+                task_diagnostics: Default::default(),
                 _subscription: cx.on_app_quit(|this, cx| {
                     this.as_local_mut().unwrap().shutdown_language_servers(cx)
                 }),
@@ -6379,6 +6388,85 @@ impl LspStore {
         self.merge_diagnostic_entries(server_id, abs_path, version, diagnostics, |_| false, cx)
     }
 
+    /// Replaces the diagnostics found in the output of a task by its problem matchers,
+    /// clearing the ones left from the task's previous run.
+    /// Problems are expected to have absolute paths.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_full_label: &str,
+        task_label: &str,
+        problems: Vec<task::Problem>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let local = self
+            .as_local_mut()
+            .context("task diagnostics are only tracked in local projects")?;
+        if problems.is_empty() && !local.task_diagnostics.contains_key(task_full_label) {
+            return Ok(());
+        }
+
+        let mut diagnostics_by_path =
+            HashMap::<PathBuf, Vec<DiagnosticEntry<Unclipped<PointUtf16>>>>::default();
+        for problem in problems {
+            let start = PointUtf16::new(
+                problem.line.saturating_sub(1),
+                problem.column.map_or(0, |column| column.saturating_sub(1)),
+            );
+            let end = match (problem.end_line, problem.end_column) {
+                (end_line, Some(end_column)) => PointUtf16::new(
+                    end_line.unwrap_or(problem.line).saturating_sub(1),
+                    end_column.saturating_sub(1),
+                ),
+                (Some(end_line), None) => PointUtf16::new(end_line.saturating_sub(1), u32::MAX),
+                (None, None) => PointUtf16::new(start.row, u32::MAX),
+            };
+            diagnostics_by_path
+                .entry(problem.path)
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(start)..Unclipped(end),
+                    diagnostic: Diagnostic {
+                        source: Some(task_label.to_string()),
+                        code: problem.code.map(lsp::NumberOrString::String),
+                        severity: match problem.severity {
+                            task::ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                            task::ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                            task::ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                            task::ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+                        },
+                        message: problem.message,
+                        group_id: post_inc(&mut local.next_diagnostic_group_id),
+                        is_primary: true,
+                        is_disk_based: true,
+                        is_unnecessary: false,
+                        data: None,
+                    },
+                });
+        }
+
+        let languages = local.languages.clone();
+        let task_diagnostics = local
+            .task_diagnostics
+            .entry(task_full_label.to_string())
+            .or_insert_with(|| TaskDiagnostics {
+                server_id: languages.next_language_server_id(),
+                paths: HashSet::default(),
+            });
+        let server_id = task_diagnostics.server_id;
+        let previous_paths = mem::replace(
+            &mut task_diagnostics.paths,
+            diagnostics_by_path.keys().cloned().collect(),
+        );
+        for path in previous_paths {
+            diagnostics_by_path.entry(path).or_default();
+        }
+
+        for (abs_path, diagnostics) in diagnostics_by_path {
+            self.update_diagnostic_entries(server_id, abs_path, None, diagnostics, cx)?;
+        }
+        Ok(())
+    }
+
     pub fn merge_diagnostic_entries<F: Fn(&Diagnostic) -> bool + Clone>(
         &mut self,
         server_id: LanguageServerId,
@@ -126,7 +126,8 @@ pub use language::Location;
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
-    BasicContextProvider, ContextProviderWithTasks, Inventory, TaskContexts, TaskSourceKind,
+    BasicContextProvider, ContextProviderWithTasks, Inventory, TaskContexts, TaskDependencyTree,
+    TaskSourceKind, TestStatus,
 };
 
 pub use buffer_store::ProjectTransaction;
@@ -306,6 +307,8 @@ pub enum Event {
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
+    /// A background task in one of the project's terminals became busy or idle.
+    TaskActivityChanged,
 }
 
 pub enum DebugAdapterClientState {
@@ -2652,6 +2655,11 @@ impl Project {
                 self.register_buffer(buffer, cx).log_err();
             }
             BufferStoreEvent::BufferDropped(buffer_id) => {
+                if let Some(inventory) = self.task_store.read(cx).task_inventory().cloned() {
+                    inventory.update(cx, |inventory, _| {
+                        inventory.remove_test_statuses(*buffer_id);
+                    });
+                }
                 if let Some(ref ssh_client) = self.ssh_client {
                     ssh_client
                         .read(cx)
@@ -7,7 +7,8 @@ use gpui::{App, AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Task}
 use lsp::LanguageServerName;
 use paths::{
     EDITORCONFIG_NAME, local_debug_file_relative_path, local_settings_file_relative_path,
-    local_tasks_file_relative_path, local_vscode_tasks_file_relative_path,
+    local_tasks_file_relative_path, local_vscode_launch_file_relative_path,
+    local_vscode_tasks_file_relative_path,
 };
 use rpc::{
     AnyProtoClient, TypedEnvelope,
@@ -24,7 +25,7 @@ use std::{
     sync::Arc,
     time::Duration,
 };
-use task::{TaskTemplates, VsCodeTaskFile};
+use task::{TaskTemplates, VsCodeDebugTaskFile, VsCodeTaskFile};
 use util::{ResultExt, serde::default_true};
 use worktree::{PathChange, UpdatedEntriesSet, Worktree, WorktreeId};
 
@@ -573,6 +574,18 @@ impl SettingsObserver {
                         .unwrap(),
                 );
                 (settings_dir, LocalSettingsKind::Tasks(TaskKind::Debug))
+            } else if path.ends_with(local_vscode_launch_file_relative_path()) {
+                let settings_dir = Arc::<Path>::from(
+                    path.ancestors()
+                        .nth(
+                            local_vscode_launch_file_relative_path()
+                                .components()
+                                .count()
+                                .saturating_sub(1),
+                        )
+                        .unwrap(),
+                );
+                (settings_dir, LocalSettingsKind::Tasks(TaskKind::Debug))
             } else if path.ends_with(EDITORCONFIG_NAME) {
                 let Some(settings_dir) = path.parent().map(Arc::from) else {
                     continue;
@@ -618,6 +631,15 @@ impl SettingsObserver {
                                             "serializing Zed tasks into JSON, file {abs_path:?}"
                                         )
                                     })
+                                } else if abs_path
+                                    .ends_with(local_vscode_launch_file_relative_path())
+                                {
+                                    vscode_launch_configurations_to_zed(
+                                        fs.as_ref(),
+                                        &abs_path,
+                                        &content,
+                                    )
+                                    .await
                                 } else {
                                     Ok(content)
                                 }
@@ -797,6 +819,41 @@ impl SettingsObserver {
     }
 }
 
+/// Converts the VS Code launch configurations of a `.vscode/launch.json` file into the contents of a Zed debug.json file.
+/// The environment files the configurations refer to get loaded into their environment.
+async fn vscode_launch_configurations_to_zed(
+    fs: &dyn Fs,
+    abs_path: &Path,
+    content: &str,
+) -> anyhow::Result<String> {
+    let vscode_configurations = parse_json_with_comments::<VsCodeDebugTaskFile>(content)
+        .with_context(|| format!("parsing VSCode launch configurations, file {abs_path:?}"))?;
+    let workspace_folder = abs_path
+        .ancestors()
+        .nth(
+            local_vscode_launch_file_relative_path()
+                .components()
+                .count(),
+        )
+        .with_context(|| format!("no workspace folder for {abs_path:?}"))?;
+
+    let mut env_files = HashMap::default();
+    for env_file in vscode_configurations.env_files(workspace_folder) {
+        if let Some(contents) = fs
+            .load(&env_file)
+            .await
+            .with_context(|| format!("loading environment file {env_file:?}"))
+            .log_err()
+        {
+            env_files.insert(env_file, contents);
+        }
+    }
+
+    let debug_tasks = vscode_configurations.into_zed_format(workspace_folder, &env_files);
+    serde_json::to_string(&debug_tasks)
+        .with_context(|| format!("serializing Zed debug tasks into JSON, file {abs_path:?}"))
+}
+
 pub fn local_settings_kind_from_proto(kind: proto::LocalSettingsKind) -> LocalSettingsKind {
     match kind {
         proto::LocalSettingsKind::Settings => LocalSettingsKind::Settings,
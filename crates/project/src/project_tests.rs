@@ -2403,6 +2403,96 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics_replace_previous_run(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({ "a.rs": "fn main() {\n    let x = y;\n}\n", "b.rs": "" }),
+    )
+    .await;
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store.clone());
+    let problem = |path: &str, line, severity| task::Problem {
+        path: PathBuf::from(path),
+        line,
+        column: Some(5),
+        end_line: None,
+        end_column: None,
+        severity,
+        code: Some("E0425".to_string()),
+        message: "cannot find value `y` in this scope".to_string(),
+    };
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics(
+                "cargo build",
+                "cargo build",
+                vec![
+                    problem("/dir/a.rs", 2, task::ProblemSeverity::Error),
+                    problem("/dir/b.rs", 1, task::ProblemSeverity::Warning),
+                ],
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 1,
+            }
+        );
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| project.open_local_buffer("/dir/a.rs", cx))
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        let diagnostics = buffer
+            .snapshot()
+            .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Point::new(1, 4)..Point::new(1, 14));
+        assert_eq!(
+            diagnostics[0].diagnostic.source.as_deref(),
+            Some("cargo build")
+        );
+    });
+
+    // A rerun replaces the previous problems, clearing the files that no longer have any.
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics(
+                "cargo build",
+                "cargo build",
+                vec![problem("/dir/a.rs", 1, task::ProblemSeverity::Warning)],
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+
+        lsp_store
+            .update_task_diagnostics("cargo build", "cargo build", Vec::new(), cx)
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary::default()
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
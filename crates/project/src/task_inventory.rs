@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result, bail};
 use collections::{HashMap, HashSet, VecDeque};
 use gpui::{App, AppContext as _, Entity, SharedString, Task};
 use itertools::Itertools;
@@ -19,8 +19,8 @@ use language::{
 use lsp::{LanguageServerId, LanguageServerName};
 use settings::{InvalidSettingsError, TaskKind, parse_json_with_comments};
 use task::{
-    DebugTaskTemplate, ResolvedTask, TaskContext, TaskId, TaskTemplate, TaskTemplates,
-    TaskVariables, VariableName,
+    DebugScenario, DependsOrder, ResolvedTask, RunnableTag, TaskContext, TaskId, TaskInput,
+    TaskTemplate, TaskTemplates, TaskVariables, VariableName,
 };
 use text::{BufferId, Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, paths::PathExt as _, post_inc};
@@ -33,6 +33,15 @@ use crate::{task_store::TaskSettingsLocation, worktree_store::WorktreeStore};
 pub struct Inventory {
     last_scheduled_tasks: VecDeque<(TaskSourceKind, ResolvedTask)>,
     templates_from_settings: ParsedTemplates,
+    test_statuses: HashMap<BufferId, Vec<(text::Anchor, TestStatus)>>,
+}
+
+/// The outcome of the latest run of a test, shown next to it in the test explorer and the editor gutter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStatus {
+    Running,
+    Passed,
+    Failed,
 }
 
 #[derive(Debug, Default)]
@@ -63,6 +72,37 @@ pub enum TaskSourceKind {
     Lsp(LanguageServerId),
 }
 
+/// A task together with the tasks it depends on, all resolved in the context of the task that
+/// was scheduled.
+#[derive(Debug, Clone)]
+pub struct TaskDependencyTree {
+    pub source_kind: TaskSourceKind,
+    pub task: ResolvedTask,
+    /// How [`Self::dependencies`] are run before [`Self::task`].
+    pub order: DependsOrder,
+    pub dependencies: Vec<TaskDependencyTree>,
+}
+
+impl TaskDependencyTree {
+    /// The inputs of the task and of all of its dependencies that have no value yet, each listed once.
+    pub fn pending_inputs(&self) -> Vec<TaskInput> {
+        let mut inputs = Vec::new();
+        self.collect_pending_inputs(&mut inputs);
+        inputs
+    }
+
+    fn collect_pending_inputs(&self, inputs: &mut Vec<TaskInput>) {
+        for input in self.task.pending_inputs() {
+            if !inputs.iter().any(|known| known.id == input.id) {
+                inputs.push(input.clone());
+            }
+        }
+        for dependency in &self.dependencies {
+            dependency.collect_pending_inputs(inputs);
+        }
+    }
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -134,20 +174,30 @@ impl Inventory {
         cx.new(|_| Self::default())
     }
 
-    pub fn list_debug_tasks(&self) -> Vec<&TaskTemplate> {
+    /// Lists the debug tasks and compound debug tasks of all worktrees, with the sources they come from.
+    pub fn list_debug_tasks(&self) -> Vec<(TaskSourceKind, &TaskTemplate)> {
         self.templates_from_settings
             .worktree
-            .values()
-            .flat_map(|tasks| {
-                tasks.iter().filter_map(|(kind, tasks)| {
-                    if matches!(kind.1, TaskKind::Debug) {
-                        Some(tasks)
-                    } else {
-                        None
-                    }
-                })
+            .iter()
+            .flat_map(|(worktree, tasks)| {
+                tasks
+                    .iter()
+                    .filter(|((_, kind), _)| matches!(kind, TaskKind::Debug))
+                    .flat_map(move |((directory, _), tasks)| {
+                        tasks.iter().map(move |task| {
+                            (
+                                TaskSourceKind::Worktree {
+                                    id: *worktree,
+                                    directory_in_worktree: directory.to_path_buf(),
+                                    id_base: Cow::Owned(format!(
+                                        "local worktree tasks from directory {directory:?}"
+                                    )),
+                                },
+                                task,
+                            )
+                        })
+                    })
             })
-            .flatten()
             .collect()
     }
     /// Pulls its task sources relevant to the worktree and the language given,
@@ -181,6 +231,42 @@ impl Inventory {
             .collect()
     }
 
+    /// Lists the task templates tagged with any of the given runnable tags.
+    /// Only the strongest source is kept: worktree tasks win over global ones, and global tasks win over language ones.
+    pub fn list_tagged_tasks(
+        &self,
+        file: Option<Arc<dyn File>>,
+        language: Arc<Language>,
+        worktree: Option<WorktreeId>,
+        tags: impl IntoIterator<Item = RunnableTag>,
+        cx: &App,
+    ) -> Vec<(TaskSourceKind, TaskTemplate)> {
+        let mut templates_with_tags = tags
+            .into_iter()
+            .flat_map(|RunnableTag(tag)| {
+                self.list_tasks(file.clone(), Some(language.clone()), worktree, cx)
+                    .into_iter()
+                    .filter(move |(_, template)| {
+                        template.tags.iter().any(|source_tag| source_tag == &tag)
+                    })
+            })
+            .sorted_by_key(|(kind, _)| kind.to_owned())
+            .collect::<Vec<_>>();
+        if let Some((leading_tag_source, _)) = templates_with_tags.first() {
+            // Strongest source wins; if we have worktree tag binding, prefer that to
+            // global and language bindings;
+            // if we have a global binding, prefer that to language binding.
+            let first_mismatch = templates_with_tags
+                .iter()
+                .position(|(tag_source, _)| tag_source != leading_tag_source);
+            if let Some(index) = first_mismatch {
+                templates_with_tags.truncate(index);
+            }
+        }
+
+        templates_with_tags
+    }
+
     /// Pulls its task sources relevant to the worktree and the language given and resolves them with the [`TaskContexts`] given.
     /// Joins the new resolutions with the resolved tasks that were used (spawned) before,
     /// orders them so that the most recently used come first, all equally used ones are ordered so that the most specific tasks come first.
@@ -349,12 +435,102 @@ impl Inventory {
         }
     }
 
+    /// Records the status of the test whose runnable starts at the given position, replacing the previous status on that row.
+    /// `None` forgets the status, e.g. when the test did not report its result.
+    pub fn set_test_status(
+        &mut self,
+        buffer: &text::BufferSnapshot,
+        position: text::Anchor,
+        status: Option<TestStatus>,
+    ) {
+        let row = position.to_point(buffer).row;
+        let statuses = self.test_statuses.entry(buffer.remote_id()).or_default();
+        statuses.retain(|(anchor, _)| anchor.to_point(buffer).row != row);
+        if let Some(status) = status {
+            statuses.push((position, status));
+        }
+    }
+
+    /// The status of the test whose runnable starts on the given row, if it was run.
+    pub fn test_status(&self, buffer: &text::BufferSnapshot, row: u32) -> Option<TestStatus> {
+        self.test_statuses
+            .get(&buffer.remote_id())?
+            .iter()
+            .rev()
+            .find(|(anchor, _)| anchor.to_point(buffer).row == row)
+            .map(|(_, status)| *status)
+    }
+
+    /// Forgets the test statuses of a buffer that was released, as its anchors can't be resolved anymore.
+    pub fn remove_test_statuses(&mut self, buffer_id: BufferId) {
+        self.test_statuses.remove(&buffer_id);
+    }
+
     /// Deletes a resolved task from history, using its id.
     /// A similar may still resurface in `used_and_current_resolved_tasks` when its [`TaskTemplate`] is resolved again.
     pub fn delete_previously_used(&mut self, id: &TaskId) {
         self.last_scheduled_tasks.retain(|(_, task)| &task.id != id);
     }
 
+    /// Resolves the tasks the given task depends on, recursively.
+    /// Dependencies are looked up by label among the worktree and global tasks, and are resolved
+    /// with the same context as the task that requires them.
+    ///
+    /// Fails if a dependency cannot be found or resolved, or if the dependencies form a cycle.
+    pub fn dependency_tree(
+        &self,
+        worktree: Option<WorktreeId>,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+    ) -> Result<TaskDependencyTree> {
+        self.resolve_dependencies(worktree, task_source_kind, resolved_task, &mut Vec::new())
+    }
+
+    fn resolve_dependencies(
+        &self,
+        worktree: Option<WorktreeId>,
+        source_kind: TaskSourceKind,
+        task: ResolvedTask,
+        ancestors: &mut Vec<String>,
+    ) -> Result<TaskDependencyTree> {
+        let label = task.original_task().label.clone();
+        if let Some(cycle_start) = ancestors.iter().position(|ancestor| ancestor == &label) {
+            let cycle = ancestors[cycle_start..]
+                .iter()
+                .chain(Some(&label))
+                .join(" → ");
+            bail!("Task dependencies form a cycle: {cycle}");
+        }
+
+        ancestors.push(label.clone());
+        let dependencies = task
+            .original_task()
+            .depends_on
+            .iter()
+            .map(|dependency_label| {
+                let (dependency_source_kind, template) = self
+                    .worktree_templates_from_settings(worktree)
+                    .chain(self.global_templates_from_settings())
+                    .find(|(_, template)| &template.label == dependency_label)
+                    .with_context(|| {
+                        format!("Task {label:?} depends on unknown task {dependency_label:?}")
+                    })?;
+                let dependency = template
+                    .resolve_task(&dependency_source_kind.to_id_base(), task.task_context())
+                    .with_context(|| format!("Failed to resolve task {dependency_label:?}"))?;
+                self.resolve_dependencies(worktree, dependency_source_kind, dependency, ancestors)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ancestors.pop();
+
+        Ok(TaskDependencyTree {
+            source_kind,
+            order: task.original_task().depends_order,
+            task,
+            dependencies,
+        })
+    }
+
     fn global_templates_from_settings(
         &self,
     ) -> impl '_ + Iterator<Item = (TaskSourceKind, TaskTemplate)> {
@@ -367,7 +543,9 @@ impl Inventory {
                         TaskSourceKind::AbsPath {
                             id_base: match template.task_type {
                                 task::TaskType::Script => Cow::Borrowed("global tasks.json"),
-                                task::TaskType::Debug(_) => Cow::Borrowed("global debug.json"),
+                                task::TaskType::Debug(_) | task::TaskType::DebugCompound => {
+                                    Cow::Borrowed("global debug.json")
+                                }
                             },
                             abs_path: file_path.clone(),
                         },
@@ -435,7 +613,7 @@ impl Inventory {
             .into_iter()
             .filter_map(|raw_template| match &task_kind {
                 TaskKind::Script => serde_json::from_value::<TaskTemplate>(raw_template).log_err(),
-                TaskKind::Debug => serde_json::from_value::<DebugTaskTemplate>(raw_template)
+                TaskKind::Debug => serde_json::from_value::<DebugScenario>(raw_template)
                     .log_err()
                     .map(|content| content.to_zed_format()),
             });
@@ -658,6 +836,36 @@ impl ContextProvider for BasicContextProvider {
             }
         }
 
+        let manifest_file_names = buffer
+            .language()
+            .and_then(|language| language.context_provider())
+            .map(|context_provider| context_provider.manifest_file_names())
+            .unwrap_or_default();
+        let manifest_dir = buffer
+            .file()
+            .filter(|_| !manifest_file_names.is_empty())
+            .and_then(|file| {
+                let worktree = self
+                    .worktree_store
+                    .read(cx)
+                    .worktree_for_id(file.worktree_id(cx), cx)?;
+                let worktree = worktree.read(cx);
+                let manifest_dir = file.path().parent()?.ancestors().find(|dir| {
+                    manifest_file_names.iter().any(|manifest_file_name| {
+                        worktree
+                            .entry_for_path(dir.join(manifest_file_name))
+                            .is_some_and(|entry| entry.is_file())
+                    })
+                })?;
+                worktree.absolutize(manifest_dir).log_err()
+            });
+        if let Some(manifest_dir) = manifest_dir {
+            task_variables.insert(
+                VariableName::ManifestDir,
+                manifest_dir.to_sanitized_string(),
+            );
+        }
+
         if let Some(path_as_string) = current_file {
             let path = Path::new(&path_as_string);
             if let Some(filename) = path.file_name().and_then(|f| f.to_str()) {
@@ -704,7 +912,7 @@ impl ContextProvider for ContextProviderWithTasks {
 
 #[cfg(test)]
 mod tests {
-    use gpui::TestAppContext;
+    use gpui::{AppContext as _, TestAppContext};
     use paths::tasks_file;
     use pretty_assertions::assert_eq;
     use serde_json::json;
@@ -1001,6 +1209,137 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_task_dependency_tree(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(Inventory::new);
+        let worktree_id = WorktreeId::from_usize(0);
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(tasks_file()),
+                    Some(
+                        &json!([
+                            { "label": "lint", "command": "cargo clippy" },
+                        ])
+                        .to_string(),
+                    ),
+                    settings::TaskKind::Script,
+                )
+                .unwrap();
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Worktree(SettingsLocation {
+                        worktree_id,
+                        path: Path::new(".zed"),
+                    }),
+                    Some(
+                        &json!([
+                            { "label": "build", "command": "cargo build", "depends_on": ["lint"] },
+                            { "label": "test", "command": "cargo test" },
+                            {
+                                "label": "check",
+                                "depends_on": ["build", "test"],
+                                "depends_order": "sequence",
+                            },
+                            { "label": "docs", "command": "cargo doc", "depends_on": ["lint"] },
+                            { "label": "ci", "depends_on": ["build", "docs"] },
+                            {
+                                "label": "deploy",
+                                "command": "deploy $ZED_INPUT_env",
+                                "inputs": [{ "id": "env", "type": "prompt_string" }],
+                            },
+                            {
+                                "label": "release",
+                                "command": "release $ZED_INPUT_env",
+                                "depends_on": ["deploy"],
+                                "inputs": [{ "id": "env", "type": "prompt_string" }],
+                            },
+                            { "label": "cycle a", "command": "a", "depends_on": ["cycle b"] },
+                            { "label": "cycle b", "command": "b", "depends_on": ["cycle a"] },
+                            { "label": "broken", "command": "c", "depends_on": ["missing"] },
+                        ])
+                        .to_string(),
+                    ),
+                    settings::TaskKind::Script,
+                )
+                .unwrap();
+        });
+
+        let task_context = TaskContext {
+            cwd: Some(PathBuf::from("/project")),
+            ..TaskContext::default()
+        };
+        let tree_for = |label: &str, cx: &mut TestAppContext| {
+            let label = label.to_string();
+            let task_context = task_context.clone();
+            inventory.update(cx, |inventory, _| {
+                let (source_kind, template) = inventory
+                    .worktree_templates_from_settings(Some(worktree_id))
+                    .find(|(_, template)| template.label == label)
+                    .unwrap();
+                let resolved_task = template
+                    .resolve_task(&source_kind.to_id_base(), &task_context)
+                    .unwrap();
+                inventory.dependency_tree(Some(worktree_id), source_kind, resolved_task)
+            })
+        };
+
+        let tree = tree_for("check", cx).unwrap();
+        assert!(tree.task.original_task().is_compound());
+        assert_eq!(tree.order, DependsOrder::Sequence);
+        assert_eq!(
+            tree.dependencies
+                .iter()
+                .map(|dependency| dependency.task.resolved_label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["build", "test"]
+        );
+        let build = &tree.dependencies[0];
+        assert_eq!(build.order, DependsOrder::Parallel);
+        assert_eq!(build.dependencies.len(), 1);
+        let lint = &build.dependencies[0];
+        assert_eq!(lint.task.resolved_label, "lint");
+        assert!(
+            matches!(lint.source_kind, TaskSourceKind::AbsPath { .. }),
+            "Dependencies should be looked up in global tasks too"
+        );
+        assert_eq!(lint.task.task_context(), &task_context);
+
+        let tree = tree_for("ci", cx).unwrap();
+        let lint_ids = tree
+            .dependencies
+            .iter()
+            .flat_map(|dependency| &dependency.dependencies)
+            .map(|dependency| dependency.task.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(lint_ids.len(), 2);
+        assert_eq!(
+            lint_ids[0], lint_ids[1],
+            "A task several others depend on should resolve to the same id, so that it runs once"
+        );
+        assert_eq!(tree.pending_inputs(), Vec::new());
+
+        let tree = tree_for("release", cx).unwrap();
+        assert_eq!(
+            tree.pending_inputs()
+                .into_iter()
+                .map(|input| input.id)
+                .collect::<Vec<_>>(),
+            vec!["env"],
+            "Inputs shared by a task and its dependencies should be asked for once"
+        );
+
+        assert_eq!(
+            tree_for("cycle a", cx).unwrap_err().to_string(),
+            "Task dependencies form a cycle: cycle a → cycle b → cycle a"
+        );
+        assert_eq!(
+            tree_for("broken", cx).unwrap_err().to_string(),
+            "Task \"broken\" depends on unknown task \"missing\""
+        );
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         if std::env::var("RUST_LOG").is_ok() {
             env_logger::try_init().ok();
@@ -1025,6 +1364,54 @@ mod tests {
         })
     }
 
+    #[gpui::test]
+    async fn test_test_statuses_follow_edits(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(Inventory::new);
+        let buffer = cx.new(|cx| {
+            language::Buffer::local("#[test]\nfn first() {}\n#[test]\nfn second() {}\n", cx)
+        });
+
+        buffer.update(cx, |buffer, cx| {
+            let first = buffer.anchor_before(Point::new(1, 0));
+            let second = buffer.anchor_before(Point::new(3, 0));
+            inventory.update(cx, |inventory, _| {
+                inventory.set_test_status(buffer, first, Some(TestStatus::Running));
+                inventory.set_test_status(buffer, first, Some(TestStatus::Passed));
+                inventory.set_test_status(buffer, second, Some(TestStatus::Failed));
+            });
+        });
+        buffer.read_with(cx, |buffer, cx| {
+            let inventory = inventory.read(cx);
+            assert_eq!(inventory.test_status(buffer, 0), None);
+            assert_eq!(inventory.test_status(buffer, 1), Some(TestStatus::Passed));
+            assert_eq!(inventory.test_status(buffer, 3), Some(TestStatus::Failed));
+        });
+
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "use super::*;\n\n")], None, cx)
+        });
+        buffer.update(cx, |buffer, cx| {
+            assert_eq!(inventory.read(cx).test_status(buffer, 1), None);
+            assert_eq!(
+                inventory.read(cx).test_status(buffer, 3),
+                Some(TestStatus::Passed)
+            );
+            let second = buffer.anchor_before(Point::new(5, 0));
+            inventory.update(cx, |inventory, _| {
+                inventory.set_test_status(buffer, second, None);
+            });
+            assert_eq!(inventory.read(cx).test_status(buffer, 5), None);
+        });
+
+        buffer.update(cx, |buffer, cx| {
+            inventory.update(cx, |inventory, _| {
+                inventory.remove_test_statuses(buffer.remote_id());
+            });
+            assert_eq!(inventory.read(cx).test_status(buffer, 3), None);
+        });
+    }
+
     fn mock_tasks_from_names<'a>(task_names: impl Iterator<Item = &'a str> + 'a) -> String {
         serde_json::to_string(&serde_json::Value::Array(
             task_names
@@ -1,20 +1,21 @@
-use crate::{Project, ProjectPath};
+use crate::{Event, Project, ProjectPath};
 use anyhow::{Context as _, Result};
 use collections::HashMap;
 use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
 use itertools::Itertools;
 use language::LanguageName;
 use settings::{Settings, SettingsLocation};
-use smol::channel::bounded;
 use std::{
     borrow::Cow,
     env::{self},
     path::{Path, PathBuf},
     sync::Arc,
 };
-use task::{DEFAULT_REMOTE_SHELL, Shell, ShellBuilder, SpawnInTerminal};
+use task::{
+    DEFAULT_REMOTE_SHELL, OutputMatcher, ProblemMatcher, Shell, ShellBuilder, SpawnInTerminal,
+};
 use terminal::{
-    TaskState, TaskStatus, Terminal, TerminalBuilder,
+    BackgroundTaskState, TaskActivity, TaskState, TaskStatus, Terminal, TerminalBuilder,
     terminal_settings::{self, TerminalSettings, VenvSettings},
 };
 use util::ResultExt;
@@ -224,8 +225,6 @@ impl Project {
         }
         let settings = TerminalSettings::get(settings_location, cx).clone();
 
-        let (completion_tx, completion_rx) = bounded(1);
-
         // Start with the environment that we might have inherited from the Zed CLI.
         let mut env = this
             .environment
@@ -244,6 +243,7 @@ impl Project {
 
         let mut python_venv_activate_command = None;
         let debug_terminal = matches!(kind, TerminalKind::Debug { .. });
+        let mut task_problem_matchers = None;
 
         let (spawn_task, shell) = match kind {
             TerminalKind::Shell(_) => {
@@ -279,6 +279,11 @@ impl Project {
                 }
             }
             TerminalKind::Task(spawn_task) => {
+                task_problem_matchers = Some((
+                    spawn_task.full_label.clone(),
+                    spawn_task.label.clone(),
+                    spawn_task.problem_matchers,
+                ));
                 let task_state = Some(TaskState {
                     id: spawn_task.id,
                     full_label: spawn_task.full_label,
@@ -289,7 +294,10 @@ impl Project {
                     show_summary: spawn_task.show_summary,
                     show_command: spawn_task.show_command,
                     show_rerun: spawn_task.show_rerun,
-                    completion_rx,
+                    background: spawn_task
+                        .background
+                        .as_ref()
+                        .and_then(|patterns| BackgroundTaskState::new(patterns).log_err()),
                 });
 
                 env.extend(spawn_task.env);
@@ -372,7 +380,6 @@ impl Project {
             settings.max_scroll_history_lines,
             ssh_details.is_some(),
             window,
-            completion_tx,
             debug_terminal,
             cx,
         )
@@ -397,13 +404,148 @@ impl Project {
             })
             .detach();
 
+            if terminal_handle
+                .read(cx)
+                .task()
+                .is_some_and(|task| task.background.is_some())
+            {
+                cx.subscribe(&terminal_handle, |_, _, event, cx| {
+                    if let terminal::Event::TaskActivityChanged(_) = event {
+                        cx.emit(Event::TaskActivityChanged);
+                    }
+                })
+                .detach();
+                cx.observe_release(&terminal_handle, |_, _, cx| {
+                    cx.emit(Event::TaskActivityChanged);
+                })
+                .detach();
+            }
             if let Some(activate_command) = python_venv_activate_command {
                 this.activate_python_virtual_environment(activate_command, &terminal_handle, cx);
             }
+            if let Some((full_label, label, problem_matchers)) = task_problem_matchers {
+                this.match_task_problems(
+                    &terminal_handle,
+                    full_label,
+                    label,
+                    problem_matchers,
+                    path,
+                    cx,
+                );
+            }
             terminal_handle
         })
     }
 
+    /// Clears the diagnostics left from the previous run of a task and, once the task finishes,
+    /// publishes the problems its problem matchers find in the output.
+    /// Background tasks publish the problems of every cycle as it ends instead.
+    fn match_task_problems(
+        &mut self,
+        terminal: &Entity<Terminal>,
+        task_full_label: String,
+        task_label: String,
+        problem_matchers: Vec<ProblemMatcher>,
+        cwd: Option<Arc<Path>>,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_local() {
+            return;
+        }
+        let lsp_store = self.lsp_store.clone();
+        lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.update_task_diagnostics(&task_full_label, &task_label, Vec::new(), cx)
+            })
+            .log_err();
+        if problem_matchers.is_empty() {
+            return;
+        }
+
+        if terminal
+            .read(cx)
+            .task()
+            .is_some_and(|task| task.background.is_some())
+        {
+            cx.subscribe(terminal, move |project, terminal, event, cx| {
+                if let terminal::Event::TaskActivityChanged(TaskActivity::Idle) = event {
+                    let lines = terminal.read(cx).last_task_cycle_lines();
+                    project.publish_task_problems(
+                        lines,
+                        task_full_label.clone(),
+                        task_label.clone(),
+                        problem_matchers.clone(),
+                        cwd.clone(),
+                        cx,
+                    );
+                }
+            })
+            .detach();
+            return;
+        }
+
+        let completion = terminal.update(cx, |terminal, cx| terminal.wait_for_completed_task(cx));
+        let terminal = terminal.clone();
+        cx.spawn(async move |project, cx| {
+            completion.await;
+            let lines = terminal.read_with(cx, |terminal, _| terminal.all_lines())?;
+            project.update(cx, |project, cx| {
+                project.publish_task_problems(
+                    lines,
+                    task_full_label,
+                    task_label,
+                    problem_matchers,
+                    cwd,
+                    cx,
+                )
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn publish_task_problems(
+        &self,
+        lines: Vec<String>,
+        task_full_label: String,
+        task_label: String,
+        problem_matchers: Vec<ProblemMatcher>,
+        cwd: Option<Arc<Path>>,
+        cx: &mut Context<Self>,
+    ) {
+        let lsp_store = self.lsp_store.clone();
+        cx.spawn(async move |_, cx| {
+            let problems = cx
+                .background_spawn(async move {
+                    let mut problems = Vec::new();
+                    for problem_matcher in &problem_matchers {
+                        let Some(mut output_matcher) =
+                            OutputMatcher::new(problem_matcher).log_err()
+                        else {
+                            continue;
+                        };
+                        problems.extend(
+                            lines
+                                .iter()
+                                .filter_map(|line| output_matcher.process_line(line)),
+                        );
+                    }
+                    if let Some(cwd) = cwd {
+                        for problem in &mut problems {
+                            if problem.path.is_relative() {
+                                problem.path = cwd.join(&problem.path);
+                            }
+                        }
+                    }
+                    problems
+                })
+                .await;
+            lsp_store.update(cx, |lsp_store, cx| {
+                lsp_store.update_task_diagnostics(&task_full_label, &task_label, problems, cx)
+            })?
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn python_venv_directory(
         &self,
         abs_path: Arc<Path>,
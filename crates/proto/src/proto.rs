@@ -284,6 +284,8 @@ messages!(
     (UsersResponse, Foreground),
     (GitReset, Background),
     (GitCheckoutFiles, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
     (GitShow, Background),
     (GitCommitDetails, Background),
     (SetIndexText, Background),
@@ -302,6 +304,44 @@ messages!(
     (GitDiff, Background),
     (GitDiffResponse, Background),
     (GitInit, Background),
+    (GitStashEntries, Background),
+    (GitStashEntriesResponse, Background),
+    (GitStash, Background),
+    (GitStashApply, Background),
+    (GitStashPop, Background),
+    (GitStashDrop, Background),
+    (GitStashShow, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebaseStart, Background),
+    (GitRebaseContinue, Background),
+    (GitRebaseSkip, Background),
+    (GitRebaseAbort, Background),
+    (LoadConflictTexts, Background),
+    (LoadConflictTextsResponse, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (GitWorktrees, Background),
+    (GitWorktreesResponse, Background),
+    (GitCreateWorktree, Background),
+    (GitRemoveWorktree, Background),
+    (GitPruneWorktrees, Background),
+    (GitTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitRenameBranch, Background),
+    (GitDeleteBranch, Background),
+    (GitSetUpstream, Background),
+    (GitCompareBranch, Background),
+    (GitFileHistory, Background),
+    (GitFileHistoryResponse, Background),
+    (GitLoadTextAtRevision, Background),
+    (GitLoadTextAtRevisionResponse, Background),
+    (GitBlameAtRevision, Background),
+    (GitLoadCommitSignature, Background),
+    (GitLoadCommitSignatureResponse, Background),
     (GetDebugAdapterBinary, Background),
     (DebugAdapterBinary, Background),
     (RunDebugLocator, Background),
@@ -452,6 +492,8 @@ request_messages!(
     (GitShow, GitCommitDetails),
     (GitReset, Ack),
     (GitCheckoutFiles, Ack),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
     (SetIndexText, Ack),
     (Push, RemoteMessageResponse),
     (Fetch, RemoteMessageResponse),
@@ -463,6 +505,35 @@ request_messages!(
     (CheckForPushedCommits, CheckForPushedCommitsResponse),
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
+    (GitStashEntries, GitStashEntriesResponse),
+    (GitStash, Ack),
+    (GitStashApply, Ack),
+    (GitStashPop, Ack),
+    (GitStashDrop, Ack),
+    (GitStashShow, LoadCommitDiffResponse),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebaseStart, Ack),
+    (GitRebaseContinue, Ack),
+    (GitRebaseSkip, Ack),
+    (GitRebaseAbort, Ack),
+    (LoadConflictTexts, LoadConflictTextsResponse),
+    (GitLog, GitLogResponse),
+    (GitWorktrees, GitWorktreesResponse),
+    (GitCreateWorktree, Ack),
+    (GitRemoveWorktree, Ack),
+    (GitPruneWorktrees, Ack),
+    (GitTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitRenameBranch, Ack),
+    (GitDeleteBranch, Ack),
+    (GitSetUpstream, Ack),
+    (GitCompareBranch, LoadCommitDiffResponse),
+    (GitFileHistory, GitFileHistoryResponse),
+    (GitLoadTextAtRevision, GitLoadTextAtRevisionResponse),
+    (GitBlameAtRevision, BlameBufferResponse),
+    (GitLoadCommitSignature, GitLoadCommitSignatureResponse),
     (ToggleBreakpoint, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocator, DebugTaskDefinition),
@@ -571,6 +642,8 @@ entity_messages!(
     GitShow,
     GitReset,
     GitCheckoutFiles,
+    GitCherryPick,
+    GitRevert,
     SetIndexText,
 
     Push,
@@ -583,6 +656,35 @@ entity_messages!(
     CheckForPushedCommits,
     GitDiff,
     GitInit,
+    GitStashEntries,
+    GitStash,
+    GitStashApply,
+    GitStashPop,
+    GitStashDrop,
+    GitStashShow,
+    GitRebaseTodo,
+    GitRebaseStart,
+    GitRebaseContinue,
+    GitRebaseSkip,
+    GitRebaseAbort,
+    LoadConflictTexts,
+    GitLog,
+    GitWorktrees,
+    GitCreateWorktree,
+    GitRemoveWorktree,
+    GitPruneWorktrees,
+    GitTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitRenameBranch,
+    GitDeleteBranch,
+    GitSetUpstream,
+    GitCompareBranch,
+    GitFileHistory,
+    GitLoadTextAtRevision,
+    GitBlameAtRevision,
+    GitLoadCommitSignature,
     BreakpointsForFile,
     ToggleBreakpoint,
     RunDebugLocator,
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::{net::Ipv4Addr, path::Path};
 
-use crate::{TaskTemplate, TaskType, task_template::DebugArgs};
+use crate::{DependsOrder, TaskTemplate, TaskType, task_template::DebugArgs};
 
 /// Represents the host information of the debug adapter
 #[derive(Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
@@ -106,6 +106,7 @@ impl TryFrom<TaskTemplate> for DebugTaskTemplate {
 
         Ok(DebugTaskTemplate {
             locator: debug_args.locator,
+            depends_on: value.depends_on,
             definition: DebugTaskDefinition {
                 adapter: debug_args.adapter,
                 request,
@@ -153,6 +154,7 @@ impl DebugTaskTemplate {
             args: vec![],
             task_type,
             cwd,
+            depends_on: self.depends_on,
             ..Default::default()
         }
     }
@@ -162,10 +164,57 @@ impl DebugTaskTemplate {
 #[serde(rename_all = "snake_case")]
 pub struct DebugTaskTemplate {
     pub locator: Option<String>,
+    /// Labels of the tasks to run before the debug session starts, e.g. one that builds the program.
+    /// The session is not started if any of them fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
     #[serde(flatten)]
     pub definition: DebugTaskDefinition,
 }
 
+/// Several debug tasks started together, e.g. a server and a client that connects to it.
+#[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct DebugCompoundTemplate {
+    /// Name of the compound debug task
+    pub label: String,
+    /// Labels of the debug tasks to start, in order
+    pub compound: Vec<String>,
+    /// Labels of the tasks to run before any of the debug sessions starts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+impl DebugCompoundTemplate {
+    /// Translate into a compound task, that runs the tasks it depends on and then starts each of its debug tasks.
+    pub fn to_zed_format(self) -> TaskTemplate {
+        TaskTemplate {
+            label: self.label,
+            depends_on: self.depends_on.into_iter().chain(self.compound).collect(),
+            depends_order: DependsOrder::Sequence,
+            task_type: TaskType::DebugCompound,
+            ..Default::default()
+        }
+    }
+}
+
+/// An entry of a debug.json file: either a single debug task or a compound one.
+#[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
+#[serde(untagged)]
+pub enum DebugScenario {
+    Compound(DebugCompoundTemplate),
+    Task(DebugTaskTemplate),
+}
+
+impl DebugScenario {
+    pub fn to_zed_format(self) -> TaskTemplate {
+        match self {
+            Self::Compound(compound) => compound.to_zed_format(),
+            Self::Task(task) => task.to_zed_format(),
+        }
+    }
+}
+
 /// This struct represent a user created debug task
 #[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -260,7 +309,7 @@ impl DebugTaskDefinition {
 /// A group of Debug Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
-pub struct DebugTaskFile(pub Vec<DebugTaskTemplate>);
+pub struct DebugTaskFile(pub Vec<DebugScenario>);
 
 impl DebugTaskFile {
     /// Generates JSON schema of Tasks JSON template format.
@@ -276,7 +325,7 @@ impl DebugTaskFile {
 
 #[cfg(test)]
 mod tests {
-    use crate::{DebugRequest, LaunchRequest};
+    use crate::{DebugRequest, DebugScenario, DependsOrder, LaunchRequest, TaskType};
 
     #[test]
     fn test_can_deserialize_non_attach_task() {
@@ -290,4 +339,35 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_can_deserialize_compound_scenario() {
+        let scenarios: Vec<DebugScenario> = serde_json::from_str(
+            r#"[
+                {"label": "Server", "adapter": "JavaScript", "program": "server.js", "depends_on": ["build"]},
+                {"label": "Server + Client", "compound": ["Server", "Client"], "depends_on": ["install"]}
+            ]"#,
+        )
+        .unwrap();
+        let [server, compound] = <[DebugScenario; 2]>::try_from(scenarios).unwrap();
+
+        let server = server.to_zed_format();
+        assert!(matches!(server.task_type, TaskType::Debug(_)));
+        assert_eq!(server.command, "server.js");
+        assert_eq!(server.depends_on, vec!["build".to_owned()]);
+
+        let compound = compound.to_zed_format();
+        assert!(compound.is_compound());
+        assert_eq!(compound.task_type, TaskType::DebugCompound);
+        assert_eq!(compound.label, "Server + Client");
+        assert_eq!(
+            compound.depends_on,
+            vec![
+                "install".to_owned(),
+                "Server".to_owned(),
+                "Client".to_owned()
+            ]
+        );
+        assert_eq!(compound.depends_order, DependsOrder::Sequence);
+    }
 }
@@ -1,9 +1,11 @@
 //! Baseline interface of Tasks in Zed: all tasks in Zed are intended to use those for implementing their own logic.
 
 mod debug_format;
+mod problem_matcher;
 mod serde_helpers;
 pub mod static_source;
 mod task_template;
+mod vscode_debug_format;
 mod vscode_format;
 
 use collections::{HashMap, HashSet, hash_map};
@@ -15,13 +17,18 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 pub use debug_format::{
-    AttachRequest, DebugRequest, DebugTaskDefinition, DebugTaskFile, DebugTaskTemplate,
-    LaunchRequest, TcpArgumentsTemplate,
+    AttachRequest, DebugCompoundTemplate, DebugRequest, DebugScenario, DebugTaskDefinition,
+    DebugTaskFile, DebugTaskTemplate, LaunchRequest, TcpArgumentsTemplate,
+};
+pub use problem_matcher::{
+    OutputMatcher, Problem, ProblemMatcher, ProblemMatcherTemplate, ProblemPattern,
+    ProblemSeverity, builtin_background_patterns, builtin_problem_matcher,
 };
 pub use task_template::{
-    DebugArgs, DebugArgsRequest, HideStrategy, RevealStrategy, TaskModal, TaskTemplate,
-    TaskTemplates, TaskType,
+    BackgroundTaskPatterns, DebugArgs, DebugArgsRequest, DependsOrder, HideStrategy,
+    RevealStrategy, TaskInput, TaskInputKind, TaskModal, TaskTemplate, TaskTemplates, TaskType,
 };
+pub use vscode_debug_format::VsCodeDebugTaskFile;
 pub use vscode_format::VsCodeTaskFile;
 pub use zed_actions::RevealTarget;
 
@@ -68,6 +75,10 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// Problem matchers to turn the task's output into diagnostics with.
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// Cycle markers of a long-running task, if it is one.
+    pub background: Option<BackgroundTaskPatterns>,
 }
 
 /// A final form of the [`TaskTemplate`], that got resolved with a particular [`TaskContext`] and now is ready to spawn the actual task.
@@ -85,6 +96,8 @@ pub struct ResolvedTask {
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
     substituted_variables: HashSet<VariableName>,
+    /// The context the task was resolved with, used to resolve its dependencies the same way.
+    task_context: TaskContext,
     /// Further actions that need to take place after the resolved task is spawned,
     /// with all task variables resolved.
     pub resolved: Option<SpawnInTerminal>,
@@ -114,20 +127,16 @@ impl ResolvedTask {
                 let args = resolved
                     .args
                     .iter()
-                    .cloned()
-                    .map(|arg| {
-                        if arg.starts_with("$") {
-                            arg.strip_prefix("$")
-                                .and_then(|arg| resolved.env.get(arg).map(ToOwned::to_owned))
-                                .unwrap_or_else(|| arg)
-                        } else {
-                            arg
-                        }
-                    })
+                    .map(|arg| substitute_env_variables(arg, &resolved.env))
                     .collect();
+                let initialize_args = debug_args.initialize_args.map(|mut initialize_args| {
+                    substitute_env_variables_in_json(&mut initialize_args, &resolved.env);
+                    initialize_args
+                });
 
                 Some(DebugTaskTemplate {
                     locator: debug_args.locator.clone(),
+                    depends_on: self.original_task.depends_on.clone(),
                     definition: DebugTaskDefinition {
                         label: resolved.label.clone(),
                         adapter: debug_args.adapter.clone(),
@@ -143,7 +152,7 @@ impl ResolvedTask {
                                 DebugRequest::Attach(attach_config)
                             }
                         },
-                        initialize_args: debug_args.initialize_args,
+                        initialize_args,
                         tcp_connection: debug_args.tcp_connection,
                         stop_on_entry: debug_args.stop_on_entry,
                     },
@@ -158,6 +167,22 @@ impl ResolvedTask {
         &self.substituted_variables
     }
 
+    /// The context this task was resolved with.
+    pub fn task_context(&self) -> &TaskContext {
+        &self.task_context
+    }
+
+    /// Inputs of the task that the user has not been asked for yet.
+    /// The task has to be resolved again with their values before it can run.
+    pub fn pending_inputs(&self) -> impl Iterator<Item = &TaskInput> {
+        self.original_task.inputs.iter().filter(|input| {
+            self.task_context
+                .task_variables
+                .get(&input.variable_name())
+                .is_none()
+        })
+    }
+
     /// A human-readable label to display in the UI.
     pub fn display_label(&self) -> &str {
         self.resolved
@@ -167,6 +192,31 @@ impl ResolvedTask {
     }
 }
 
+/// Replaces the variables set in a resolved task's environment, task variables included, in a string.
+/// Other variables are left as they are.
+fn substitute_env_variables(value: &str, env: &HashMap<String, String>) -> String {
+    shellexpand::env_with_context_no_errors(value, |variable| env.get(variable)).into_owned()
+}
+
+/// Replaces the variables set in a resolved task's environment in all strings of a JSON value,
+/// e.g. in the debug adapter arguments imported from a VS Code launch configuration.
+fn substitute_env_variables_in_json(value: &mut serde_json::Value, env: &HashMap<String, String>) {
+    match value {
+        serde_json::Value::String(string) => *string = substitute_env_variables(string, env),
+        serde_json::Value::Array(values) => {
+            for value in values {
+                substitute_env_variables_in_json(value, env);
+            }
+        }
+        serde_json::Value::Object(values) => {
+            for value in values.values_mut() {
+                substitute_env_variables_in_json(value, env);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Variables, available for use in [`TaskContext`] when a Zed's [`TaskTemplate`] gets resolved into a [`ResolvedTask`].
 /// Name of the variable must be a valid shell variable identifier, which generally means that it is
 /// a word  consisting only  of alphanumeric characters and underscores,
@@ -195,9 +245,24 @@ pub enum VariableName {
     SelectedText,
     /// The symbol selected by the symbol tagging system, specifically the @run capture in a runnables.scm
     RunnableSymbol,
+    /// Fully qualified path of the test at the latest cursor/selection position, in the notation of the language's test runner.
+    /// Provided by the language's context provider.
+    TestName,
+    /// Name of the package (crate, module, project) declared in the manifest in [`VariableName::ManifestDir`].
+    /// Provided by the language's context provider.
+    Package,
+    /// Path of the module that contains the currently opened file, in the notation of the language.
+    /// Provided by the language's context provider.
+    ModulePath,
+    /// An absolute path of the closest directory containing the language's manifest file (e.g. `Cargo.toml` or `package.json`).
+    /// Found among the worktree entries, for the manifest files named by the language's context provider.
+    ManifestDir,
     /// Custom variable, provided by the plugin or other external source.
     /// Will be printed with `CUSTOM_` prefix to avoid potential conflicts with other variables.
     Custom(Cow<'static, str>),
+    /// A value of one of the task's [`TaskInput`]s, asked from the user before the task runs.
+    /// Will be printed with `INPUT_` prefix, followed by the input's id.
+    Input(Cow<'static, str>),
 }
 
 impl VariableName {
@@ -228,11 +293,19 @@ impl FromStr for VariableName {
             "SELECTED_TEXT" => Self::SelectedText,
             "ROW" => Self::Row,
             "COLUMN" => Self::Column,
+            "TEST_NAME" => Self::TestName,
+            "PACKAGE" => Self::Package,
+            "MODULE_PATH" => Self::ModulePath,
+            "MANIFEST_DIR" => Self::ManifestDir,
             _ => {
                 if let Some(custom_name) =
                     without_prefix.strip_prefix(ZED_CUSTOM_VARIABLE_NAME_PREFIX)
                 {
                     Self::Custom(Cow::Owned(custom_name.to_owned()))
+                } else if let Some(input_id) =
+                    without_prefix.strip_prefix(ZED_INPUT_VARIABLE_NAME_PREFIX)
+                {
+                    Self::Input(Cow::Owned(input_id.to_owned()))
                 } else {
                     return Err(());
                 }
@@ -245,6 +318,7 @@ impl FromStr for VariableName {
 /// A prefix that all [`VariableName`] variants are prefixed with when used in environment variables and similar template contexts.
 pub const ZED_VARIABLE_NAME_PREFIX: &str = "ZED_";
 const ZED_CUSTOM_VARIABLE_NAME_PREFIX: &str = "CUSTOM_";
+const ZED_INPUT_VARIABLE_NAME_PREFIX: &str = "INPUT_";
 
 impl std::fmt::Display for VariableName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -260,10 +334,18 @@ impl std::fmt::Display for VariableName {
             Self::Column => write!(f, "{ZED_VARIABLE_NAME_PREFIX}COLUMN"),
             Self::SelectedText => write!(f, "{ZED_VARIABLE_NAME_PREFIX}SELECTED_TEXT"),
             Self::RunnableSymbol => write!(f, "{ZED_VARIABLE_NAME_PREFIX}RUNNABLE_SYMBOL"),
+            Self::TestName => write!(f, "{ZED_VARIABLE_NAME_PREFIX}TEST_NAME"),
+            Self::Package => write!(f, "{ZED_VARIABLE_NAME_PREFIX}PACKAGE"),
+            Self::ModulePath => write!(f, "{ZED_VARIABLE_NAME_PREFIX}MODULE_PATH"),
+            Self::ManifestDir => write!(f, "{ZED_VARIABLE_NAME_PREFIX}MANIFEST_DIR"),
             Self::Custom(s) => write!(
                 f,
                 "{ZED_VARIABLE_NAME_PREFIX}{ZED_CUSTOM_VARIABLE_NAME_PREFIX}{s}"
             ),
+            Self::Input(id) => write!(
+                f,
+                "{ZED_VARIABLE_NAME_PREFIX}{ZED_INPUT_VARIABLE_NAME_PREFIX}{id}"
+            ),
         }
     }
 }
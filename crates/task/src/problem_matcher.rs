@@ -0,0 +1,502 @@
+//! Problem matchers find errors and warnings in the output of a task, so they can be shown as diagnostics.
+
+use std::path::PathBuf;
+
+use anyhow::{Context as _, bail};
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::BackgroundTaskPatterns;
+
+/// A problem matcher, as written in a task template.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcherTemplate {
+    /// A built-in matcher, referenced by name: `$rustc`, `$tsc`, `$gcc` or `$eslint`.
+    Named(String),
+    /// A matcher defined in place.
+    Custom(ProblemMatcher),
+}
+
+impl ProblemMatcherTemplate {
+    /// Looks up the built-in matcher this template refers to, if it is not a custom one.
+    pub fn resolve(&self) -> anyhow::Result<ProblemMatcher> {
+        match self {
+            Self::Named(name) => builtin_problem_matcher(name)
+                .with_context(|| format!("Unknown problem matcher {name:?}")),
+            Self::Custom(matcher) => Ok(matcher.clone()),
+        }
+    }
+}
+
+/// Describes how to find problems in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// Severity of the problems whose pattern does not capture one.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+    /// Patterns that match consecutive lines of the output, each capturing a part of a problem.
+    /// A problem is reported once the last pattern matches.
+    pub pattern: Vec<ProblemPattern>,
+}
+
+/// A regular expression matching a single line of the output, with the indices of the capture
+/// groups that hold each part of a problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    pub regexp: String,
+    /// The path of the file, absolute or relative to the task's working directory.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// 1-based line of the problem.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// 1-based column of the problem.
+    #[serde(default)]
+    pub column: Option<usize>,
+    #[serde(default, alias = "endLine")]
+    pub end_line: Option<usize>,
+    #[serde(default, alias = "endColumn")]
+    pub end_column: Option<usize>,
+    /// Severity of the problem, e.g. `error` or `warning`.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    #[serde(default)]
+    pub code: Option<usize>,
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Whether the last pattern of a multi-line matcher may match several lines in a row,
+    /// reporting a problem for each of them.
+    #[serde(default, rename = "loop")]
+    pub loops: bool,
+}
+
+/// How severe a matched problem is.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    fn from_output(severity: &str) -> Option<Self> {
+        match severity.trim().to_lowercase().as_str() {
+            "error" | "fatal" | "fatal error" => Some(Self::Error),
+            "warning" | "warn" => Some(Self::Warning),
+            "info" | "information" | "note" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The path as it was printed, relative paths are not resolved.
+    pub path: PathBuf,
+    /// 1-based line, the first line of the file if the output had none.
+    pub line: u32,
+    /// 1-based column.
+    pub column: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Returns the built-in problem matcher with the given name, with or without the leading `$`.
+pub fn builtin_problem_matcher(name: &str) -> Option<ProblemMatcher> {
+    let pattern = |regexp: &str| ProblemPattern {
+        regexp: regexp.to_string(),
+        ..ProblemPattern::default()
+    };
+    let pattern = match name.strip_prefix('$').unwrap_or(name) {
+        "rustc" => vec![
+            ProblemPattern {
+                severity: Some(1),
+                code: Some(2),
+                message: Some(3),
+                ..pattern(r"^(warning|error)(?:\[(\w+)\])?: (.*)$")
+            },
+            ProblemPattern {
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                ..pattern(r"^\s*--> (.*?):(\d+):(\d+)$")
+            },
+        ],
+        "gcc" => vec![ProblemPattern {
+            file: Some(1),
+            line: Some(2),
+            column: Some(3),
+            severity: Some(4),
+            message: Some(5),
+            ..pattern(r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error):\s+(.*)$")
+        }],
+        "tsc" | "tsc-watch" => vec![ProblemPattern {
+            file: Some(1),
+            line: Some(2),
+            column: Some(3),
+            severity: Some(4),
+            code: Some(5),
+            message: Some(6),
+            ..pattern(
+                r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+TS(\d+)\s*:\s*(.*)$",
+            )
+        }],
+        "eslint" | "eslint-stylish" => vec![
+            ProblemPattern {
+                file: Some(1),
+                ..pattern(r"^([^\s].*)$")
+            },
+            ProblemPattern {
+                line: Some(1),
+                column: Some(2),
+                severity: Some(3),
+                message: Some(4),
+                code: Some(5),
+                loops: true,
+                ..pattern(r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.*?)(?:\s\s+(.*))?$")
+            },
+        ],
+        _ => return None,
+    };
+    Some(ProblemMatcher {
+        severity: ProblemSeverity::Error,
+        pattern,
+    })
+}
+
+/// Returns the cycle markers of the built-in problem matchers meant for watching tasks, such as `$tsc-watch`.
+pub fn builtin_background_patterns(name: &str) -> Option<BackgroundTaskPatterns> {
+    match name.strip_prefix('$').unwrap_or(name) {
+        "tsc-watch" => Some(BackgroundTaskPatterns {
+            begins_pattern: r"(?:Starting compilation in watch mode|File change detected\. Starting incremental compilation)\.\.\."
+                .to_string(),
+            ends_pattern: r"(?:Compilation complete\.|Found \d+ errors?\.) Watching for file changes\."
+                .to_string(),
+        }),
+        _ => None,
+    }
+}
+
+/// Feeds task output through a [`ProblemMatcher`], line by line.
+pub struct OutputMatcher {
+    severity: ProblemSeverity,
+    patterns: Vec<(Regex, ProblemPattern)>,
+    /// How many of the patterns matched the lines right before the current one.
+    matched: usize,
+    /// Whether the last, looping pattern matched the previous line.
+    looping: bool,
+    captured: CapturedProblem,
+}
+
+#[derive(Clone, Default)]
+struct CapturedProblem {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl OutputMatcher {
+    pub fn new(matcher: &ProblemMatcher) -> anyhow::Result<Self> {
+        if matcher.pattern.is_empty() {
+            bail!("Problem matcher has no patterns");
+        }
+        let patterns = matcher
+            .pattern
+            .iter()
+            .map(|pattern| {
+                let regex = Regex::new(&pattern.regexp)
+                    .with_context(|| format!("Invalid problem pattern {:?}", pattern.regexp))?;
+                anyhow::Ok((regex, pattern.clone()))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            severity: matcher.severity,
+            patterns,
+            matched: 0,
+            looping: false,
+            captured: CapturedProblem::default(),
+        })
+    }
+
+    /// Matches the next line of output, returning a problem if the line completes one.
+    pub fn process_line(&mut self, line: &str) -> Option<Problem> {
+        let last = self.patterns.len() - 1;
+        if self.looping {
+            let (regex, pattern) = &self.patterns[last];
+            if let Some(captures) = regex.captures(line) {
+                let mut captured = self.captured.clone();
+                captured.apply(pattern, &captures);
+                return captured.into_problem(self.severity);
+            }
+            self.reset();
+        }
+
+        let (regex, pattern) = &self.patterns[self.matched];
+        let Some(captures) = regex.captures(line) else {
+            if self.matched > 0 {
+                // The line may still start a new problem.
+                self.reset();
+                return self.process_line(line);
+            }
+            return None;
+        };
+
+        if self.matched < last {
+            self.captured.apply(pattern, &captures);
+            self.matched += 1;
+            return None;
+        }
+
+        let mut captured = self.captured.clone();
+        captured.apply(pattern, &captures);
+        if pattern.loops && last > 0 {
+            self.looping = true;
+        } else {
+            self.reset();
+        }
+        captured.into_problem(self.severity)
+    }
+
+    fn reset(&mut self) {
+        self.matched = 0;
+        self.looping = false;
+        self.captured = CapturedProblem::default();
+    }
+}
+
+impl CapturedProblem {
+    fn apply(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let text = |group: Option<usize>| {
+            group
+                .and_then(|group| captures.get(group))
+                .map(|capture| capture.as_str().trim())
+                .filter(|text| !text.is_empty())
+        };
+        let number = |group: Option<usize>| text(group).and_then(|text| text.parse().ok());
+
+        if let Some(file) = text(pattern.file) {
+            self.file = Some(file.to_string());
+        }
+        if let Some(line) = number(pattern.line) {
+            self.line = Some(line);
+        }
+        if let Some(column) = number(pattern.column) {
+            self.column = Some(column);
+        }
+        if let Some(end_line) = number(pattern.end_line) {
+            self.end_line = Some(end_line);
+        }
+        if let Some(end_column) = number(pattern.end_column) {
+            self.end_column = Some(end_column);
+        }
+        if let Some(severity) = text(pattern.severity).and_then(ProblemSeverity::from_output) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = text(pattern.code) {
+            self.code = Some(code.to_string());
+        }
+        if let Some(message) = text(pattern.message) {
+            self.message = Some(message.to_string());
+        }
+    }
+
+    fn into_problem(self, default_severity: ProblemSeverity) -> Option<Problem> {
+        Some(Problem {
+            path: PathBuf::from(self.file?),
+            line: self.line.unwrap_or(1),
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self.severity.unwrap_or(default_severity),
+            code: self.code,
+            message: self.message?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(matcher: &str, output: &str) -> Vec<Problem> {
+        let matcher = ProblemMatcherTemplate::Named(matcher.to_string())
+            .resolve()
+            .unwrap();
+        let mut output_matcher = OutputMatcher::new(&matcher).unwrap();
+        output
+            .lines()
+            .filter_map(|line| output_matcher.process_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_rustc_problems() {
+        let output = r#"   Compiling app v0.1.0 (/project)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+error[E0425]: cannot find value `y` in this scope
+  --> src/lib.rs:10:5
+   |
+error: could not compile `app` (bin "app") due to 1 previous error; 1 warning emitted
+"#;
+        assert_eq!(
+            problems("$rustc", output),
+            vec![
+                Problem {
+                    path: PathBuf::from("src/main.rs"),
+                    line: 2,
+                    column: Some(9),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Warning,
+                    code: None,
+                    message: "unused variable: `x`".to_string(),
+                },
+                Problem {
+                    path: PathBuf::from("src/lib.rs"),
+                    line: 10,
+                    column: Some(5),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Error,
+                    code: Some("E0425".to_string()),
+                    message: "cannot find value `y` in this scope".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_problems() {
+        let gcc = problems(
+            "$gcc",
+            "main.c:3:12: error: expected ';' before '}' token\nmain.c:7:: warning: unused label",
+        );
+        assert_eq!(gcc.len(), 2);
+        assert_eq!(gcc[0].path, PathBuf::from("main.c"));
+        assert_eq!((gcc[0].line, gcc[0].column), (3, Some(12)));
+        assert_eq!(gcc[0].message, "expected ';' before '}' token");
+        assert_eq!(gcc[1].column, None);
+        assert_eq!(gcc[1].severity, ProblemSeverity::Warning);
+
+        let tsc = problems(
+            "tsc",
+            "src/index.ts(4,7): error TS2322: Type 'string' is not assignable to type 'number'.",
+        );
+        assert_eq!(tsc.len(), 1);
+        assert_eq!(tsc[0].path, PathBuf::from("src/index.ts"));
+        assert_eq!((tsc[0].line, tsc[0].column), (4, Some(7)));
+        assert_eq!(tsc[0].code.as_deref(), Some("2322"));
+    }
+
+    #[test]
+    fn test_tsc_watch_background_patterns() {
+        let patterns = builtin_background_patterns("$tsc-watch").unwrap();
+        let begins = Regex::new(&patterns.begins_pattern).unwrap();
+        let ends = Regex::new(&patterns.ends_pattern).unwrap();
+        assert!(begins.is_match("[9:41:07 AM] Starting compilation in watch mode..."));
+        assert!(
+            begins
+                .is_match("[9:42:13 AM] File change detected. Starting incremental compilation...")
+        );
+        assert!(ends.is_match("[9:41:09 AM] Found 0 errors. Watching for file changes."));
+        assert!(ends.is_match("[9:42:14 AM] Found 1 error. Watching for file changes."));
+        assert!(!ends.is_match("[9:41:07 AM] Starting compilation in watch mode..."));
+        assert_eq!(builtin_background_patterns("$tsc"), None);
+    }
+
+    #[test]
+    fn test_looping_pattern() {
+        let output = r#"
+/project/src/a.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  3:1   warning  Unexpected console statement     no-console
+
+/project/src/b.js
+  2:5  error  Missing semicolon  semi
+
+✖ 3 problems (2 errors, 1 warning)
+"#;
+        let problems = problems("$eslint", output);
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.to_string_lossy().to_string(),
+                    problem.line,
+                    problem.severity,
+                    problem.code.clone().unwrap_or_default(),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/project/src/a.js".to_string(),
+                    1,
+                    ProblemSeverity::Error,
+                    "no-unused-vars".to_string()
+                ),
+                (
+                    "/project/src/a.js".to_string(),
+                    3,
+                    ProblemSeverity::Warning,
+                    "no-console".to_string()
+                ),
+                (
+                    "/project/src/b.js".to_string(),
+                    2,
+                    ProblemSeverity::Error,
+                    "semi".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_matcher() {
+        let template: ProblemMatcherTemplate = serde_json::from_value(serde_json::json!({
+            "severity": "warning",
+            "pattern": [{
+                "regexp": "^(.+)@(\\d+): (.+)$",
+                "file": 1,
+                "line": 2,
+                "message": 3,
+            }],
+        }))
+        .unwrap();
+        let matcher = template.resolve().unwrap();
+        let mut output_matcher = OutputMatcher::new(&matcher).unwrap();
+        let problem = output_matcher
+            .process_line("docs/README.md@12: broken link")
+            .unwrap();
+        assert_eq!(problem.path, PathBuf::from("docs/README.md"));
+        assert_eq!(problem.line, 12);
+        assert_eq!(problem.severity, ProblemSeverity::Warning);
+        assert_eq!(problem.message, "broken link");
+
+        assert!(
+            ProblemMatcherTemplate::Named("$unknown".to_string())
+                .resolve()
+                .is_err()
+        );
+    }
+}
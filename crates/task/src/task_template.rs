@@ -3,14 +3,14 @@ use collections::{HashMap, HashSet};
 use schemars::{JsonSchema, r#gen::SchemaSettings};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::{borrow::Cow, path::PathBuf};
 use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::debug_format::TcpArgumentsTemplate;
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX,
+    AttachRequest, ProblemMatcherTemplate, ResolvedTask, RevealTarget, Shell, SpawnInTerminal,
+    TaskContext, TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX,
     serde_helpers::{non_empty_string_vec, non_empty_string_vec_json_schema},
 };
 
@@ -76,6 +76,26 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks that must finish successfully before this task is started.
+    /// A task with dependencies and an empty command only runs its dependencies.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// How the tasks in `depends_on` are run:
+    /// * `parallel` — start all of them at once (default)
+    /// * `sequence` — start them one after another, in the order they are listed
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Problem matchers that turn the task's output into diagnostics, either the names of
+    /// built-in ones (`$rustc`, `$tsc`, `$gcc`, `$eslint`) or custom definitions.
+    #[serde(default)]
+    pub problem_matchers: Vec<ProblemMatcherTemplate>,
+    /// Marks the task as a long-running one (a dev server or a watcher) that works in cycles.
+    /// Its dependents start once it finishes the first cycle rather than when it exits.
+    #[serde(default)]
+    pub background: Option<BackgroundTaskPatterns>,
+    /// Values the user is asked for right before the task runs, available in the task as `$ZED_INPUT_<id>`.
+    #[serde(default)]
+    pub inputs: Vec<TaskInput>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -113,6 +133,8 @@ pub enum TaskType {
     Script,
     /// This task starts the debugger for a language
     Debug(DebugArgs),
+    /// This task starts the debug tasks it depends on, e.g. a compound scenario of a debug.json
+    DebugCompound,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -150,6 +172,67 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How the dependencies of a task are run.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Start all dependencies at once.
+    #[default]
+    Parallel,
+    /// Start each dependency after the previous one has succeeded.
+    Sequence,
+}
+
+/// Output patterns that mark the work cycles of a background task.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BackgroundTaskPatterns {
+    /// A regex matching the output line printed when the task starts working, e.g. on a file change.
+    pub begins_pattern: String,
+    /// A regex matching the output line printed when the task is done working and is idle.
+    pub ends_pattern: String,
+}
+
+/// A value the user is asked for before the task runs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TaskInput {
+    /// Identifier of the input, the task refers to its value as `$ZED_INPUT_<id>`.
+    pub id: String,
+    /// Text shown in the prompt.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Value the prompt starts with.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// How the value is chosen.
+    #[serde(flatten)]
+    pub kind: TaskInputKind,
+}
+
+impl TaskInput {
+    /// The task variable that holds the value of this input.
+    pub fn variable_name(&self) -> VariableName {
+        VariableName::Input(Cow::Owned(self.id.clone()))
+    }
+}
+
+/// How the value of a [`TaskInput`] is chosen.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskInputKind {
+    /// Any text, typed by the user.
+    PromptString,
+    /// One of the listed options.
+    PickString { options: Vec<String> },
+    /// One of the lines printed by a command, which runs in the task's working directory.
+    PickCommandOutput {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -167,6 +250,11 @@ impl TaskTemplates {
 }
 
 impl TaskTemplate {
+    /// Whether this task only groups its dependencies together and has no command of its own.
+    pub fn is_compound(&self) -> bool {
+        self.command.trim().is_empty() && !self.depends_on.is_empty()
+    }
+
     /// Replaces all `VariableName` task variables in the task template string fields.
     /// If any replacement fails or the new string substitutions still have [`ZED_VARIABLE_NAME_PREFIX`],
     /// `None` is returned.
@@ -175,7 +263,9 @@ impl TaskTemplate {
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
         if self.label.trim().is_empty()
-            || (self.command.trim().is_empty() && matches!(self.task_type, TaskType::Script))
+            || (self.command.trim().is_empty()
+                && self.depends_on.is_empty()
+                && matches!(self.task_type, TaskType::Script))
         {
             return None;
         }
@@ -284,6 +374,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            task_context: cx.clone(),
             resolved_label: full_label.clone(),
             resolved: Some(SpawnInTerminal {
                 id,
@@ -310,6 +401,12 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers: self
+                    .problem_matchers
+                    .iter()
+                    .filter_map(|matcher| matcher.resolve().log_err())
+                    .collect(),
+                background: self.background.clone(),
             }),
         })
     }
@@ -357,7 +454,10 @@ fn substitute_all_template_variables_in_str<A: AsRef<str>>(
                 name.push_str(default);
             }
             return Ok(Some(name));
-        } else if variable_name.starts_with(ZED_VARIABLE_NAME_PREFIX) {
+        } else if variable_name.starts_with(ZED_VARIABLE_NAME_PREFIX)
+            // Inputs only get their values once the user is prompted for them, right before the task runs.
+            && !matches!(variable_name.parse(), Ok(VariableName::Input(_)))
+        {
             bail!("Unknown variable name: {variable_name}");
         }
         // This is an unknown variable.
@@ -460,6 +560,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_compound_task_without_command() {
+        let compound_task = TaskTemplate {
+            label: "build all".to_string(),
+            depends_on: vec!["build server".to_string(), "build client".to_string()],
+            depends_order: DependsOrder::Sequence,
+            ..TaskTemplate::default()
+        };
+        assert!(compound_task.is_compound());
+
+        let task_context = TaskContext {
+            cwd: Some(PathBuf::from("/dir")),
+            ..TaskContext::default()
+        };
+        let resolved_task = compound_task
+            .resolve_task(TEST_ID_BASE, &task_context)
+            .expect("should resolve a task that only has dependencies");
+        assert_eq!(resolved_task.task_context(), &task_context);
+        assert_eq!(resolved_task.original_task().depends_on.len(), 2);
+
+        let task_with_command = TaskTemplate {
+            command: "cargo build".to_string(),
+            ..compound_task
+        };
+        assert!(!task_with_command.is_compound());
+    }
+
+    #[test]
+    fn test_resolving_task_with_inputs() {
+        let task = TaskTemplate {
+            label: "deploy to $ZED_INPUT_target".to_string(),
+            command: "./deploy".to_string(),
+            args: vec!["--target=${ZED_INPUT_target}".to_string()],
+            inputs: vec![TaskInput {
+                id: "target".to_string(),
+                description: Some("Where to deploy".to_string()),
+                default: None,
+                kind: TaskInputKind::PickString {
+                    options: vec!["staging".to_string(), "production".to_string()],
+                },
+            }],
+            ..TaskTemplate::default()
+        };
+
+        let unprompted = task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("inputs without values should not prevent the resolution");
+        assert_eq!(
+            unprompted
+                .pending_inputs()
+                .map(|input| input.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["target"]
+        );
+
+        let task_context = TaskContext {
+            task_variables: TaskVariables::from_iter([(
+                VariableName::Input(Cow::Borrowed("target")),
+                "staging".to_string(),
+            )]),
+            ..TaskContext::default()
+        };
+        let prompted = task.resolve_task(TEST_ID_BASE, &task_context).unwrap();
+        assert_eq!(prompted.pending_inputs().count(), 0);
+        assert_ne!(prompted.id, unprompted.id);
+        let spawn_in_terminal = prompted.resolved.unwrap();
+        assert_eq!(spawn_in_terminal.label, "deploy to staging");
+        assert_eq!(spawn_in_terminal.args, vec!["--target=staging".to_string()]);
+        assert_eq!(
+            spawn_in_terminal
+                .env
+                .get("ZED_INPUT_target")
+                .map(String::as_str),
+            Some("staging")
+        );
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -0,0 +1,361 @@
+use std::path::{Path, PathBuf};
+
+use collections::HashMap;
+use serde::Deserialize;
+
+use crate::{
+    AttachRequest, DebugCompoundTemplate, DebugRequest, DebugScenario, DebugTaskDefinition,
+    DebugTaskFile, DebugTaskTemplate, LaunchRequest, TcpArgumentsTemplate,
+    vscode_format::EnvVariableReplacer,
+};
+
+/// Attributes that only affect how VS Code presents a debug session, they are not passed to the debug adapter.
+const VSCODE_ONLY_ATTRIBUTES: &[&str] = &[
+    "presentation",
+    "internalConsoleOptions",
+    "postDebugTask",
+    "serverReadyAction",
+];
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum VsCodeDebugRequest {
+    Launch,
+    Attach,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeDebugConfiguration {
+    name: String,
+    #[serde(rename = "type")]
+    debug_type: String,
+    request: VsCodeDebugRequest,
+    program: Option<String>,
+    cwd: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    process_id: Option<serde_json::Value>,
+    stop_on_entry: Option<bool>,
+    pre_launch_task: Option<String>,
+    debug_server: Option<u16>,
+    /// Variables set to `null` are removed from the environment.
+    #[serde(default)]
+    env: HashMap<String, Option<String>>,
+    env_file: Option<String>,
+    /// Adapter-specific attributes, passed to the debug adapter as they are.
+    #[serde(flatten)]
+    other_attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeCompound {
+    name: String,
+    configurations: Vec<VsCodeCompoundConfiguration>,
+    pre_launch_task: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeCompoundConfiguration {
+    Name(String),
+    /// A configuration of another folder of a multi-root workspace.
+    InFolder {
+        name: String,
+    },
+}
+
+impl VsCodeCompoundConfiguration {
+    fn into_name(self) -> String {
+        match self {
+            Self::Name(name) | Self::InFolder { name } => name,
+        }
+    }
+}
+
+/// The Zed debug adapter for a VS Code debug type. Other types are expected to name a Zed adapter already.
+fn zed_adapter_name(debug_type: &str) -> &str {
+    match debug_type {
+        "node" | "pwa-node" | "node-terminal" | "chrome" | "pwa-chrome" | "msedge"
+        | "pwa-msedge" => "JavaScript",
+        "python" | "debugpy" => "Debugpy",
+        "lldb" | "codelldb" => "CodeLLDB",
+        "cppdbg" | "gdb" => "GDB",
+        "lldb-dap" | "lldb-vscode" => "lldb-dap",
+        "go" => "Delve",
+        "php" => "PHP",
+        other => other,
+    }
+}
+
+fn env_file_path(env_file: &str, workspace_folder: &Path) -> PathBuf {
+    let env_file = env_file.replace("${workspaceFolder}", &workspace_folder.to_string_lossy());
+    workspace_folder.join(env_file)
+}
+
+/// Parses the `KEY=value` lines of a dotenv file, as referred to by `envFile`.
+fn parse_env_file(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = ['"', '\'']
+                .into_iter()
+                .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+                .unwrap_or(value);
+            Some((key.trim().to_owned(), value.to_owned()))
+        })
+        .collect()
+}
+
+fn replace_in_json(replacer: &EnvVariableReplacer, value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(string) => *string = replacer.replace(string),
+        serde_json::Value::Array(values) => {
+            for value in values {
+                replace_in_json(replacer, value);
+            }
+        }
+        serde_json::Value::Object(values) => {
+            for value in values.values_mut() {
+                replace_in_json(replacer, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl VsCodeDebugConfiguration {
+    fn into_zed_format(
+        self,
+        replacer: &EnvVariableReplacer,
+        env_file: Option<HashMap<String, String>>,
+    ) -> DebugTaskTemplate {
+        let adapter = zed_adapter_name(&self.debug_type).to_owned();
+        let request = match self.request {
+            VsCodeDebugRequest::Launch => DebugRequest::Launch(LaunchRequest {
+                program: self
+                    .program
+                    .map(|program| replacer.replace(&program))
+                    .unwrap_or_default(),
+                cwd: self.cwd.map(|cwd| PathBuf::from(replacer.replace(&cwd))),
+                args: self.args.iter().map(|arg| replacer.replace(arg)).collect(),
+            }),
+            // Anything but a process ID, e.g. `${command:pickProcess}`, lets the user pick the process.
+            VsCodeDebugRequest::Attach => DebugRequest::Attach(AttachRequest {
+                process_id: match self.process_id {
+                    Some(serde_json::Value::Number(process_id)) => process_id
+                        .as_u64()
+                        .and_then(|process_id| u32::try_from(process_id).ok()),
+                    Some(serde_json::Value::String(process_id)) => process_id.parse().ok(),
+                    _ => None,
+                },
+            }),
+        };
+
+        let mut env = env_file.unwrap_or_default();
+        for (name, value) in self.env {
+            match value {
+                Some(value) => env.insert(name, value),
+                None => env.remove(&name),
+            };
+        }
+
+        let mut initialize_args = self.other_attributes;
+        for attribute in VSCODE_ONLY_ATTRIBUTES {
+            initialize_args.remove(*attribute);
+        }
+        if !env.is_empty() {
+            initialize_args.insert("env".to_owned(), serde_json::json!(env));
+        }
+        // The JavaScript adapter debugs browsers too, depending on the type, which it only knows by their current names.
+        if adapter == "JavaScript" {
+            let debug_type = match self.debug_type.as_str() {
+                "node" | "chrome" | "msedge" => format!("pwa-{}", self.debug_type),
+                _ => self.debug_type,
+            };
+            initialize_args.insert("type".to_owned(), debug_type.into());
+        }
+        let mut initialize_args = serde_json::Value::Object(initialize_args);
+        replace_in_json(replacer, &mut initialize_args);
+
+        DebugTaskTemplate {
+            locator: None,
+            depends_on: self.pre_launch_task.into_iter().collect(),
+            definition: DebugTaskDefinition {
+                adapter,
+                request,
+                label: self.name,
+                initialize_args: initialize_args
+                    .as_object()
+                    .is_some_and(|args| !args.is_empty())
+                    .then_some(initialize_args),
+                tcp_connection: self.debug_server.map(|port| TcpArgumentsTemplate {
+                    port: Some(port),
+                    host: None,
+                    timeout: None,
+                }),
+                stop_on_entry: self.stop_on_entry,
+            },
+        }
+    }
+}
+
+impl VsCodeCompound {
+    fn into_zed_format(self) -> DebugCompoundTemplate {
+        DebugCompoundTemplate {
+            label: self.name,
+            compound: self
+                .configurations
+                .into_iter()
+                .map(VsCodeCompoundConfiguration::into_name)
+                .collect(),
+            depends_on: self.pre_launch_task.into_iter().collect(),
+        }
+    }
+}
+
+/// [`VsCodeDebugTaskFile`] is a superset of Code's launch configuration (launch.json) format.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct VsCodeDebugTaskFile {
+    #[serde(default)]
+    configurations: Vec<VsCodeDebugConfiguration>,
+    #[serde(default)]
+    compounds: Vec<VsCodeCompound>,
+}
+
+impl VsCodeDebugTaskFile {
+    /// Paths of the `envFile`s the configurations load environment variables from.
+    pub fn env_files(&self, workspace_folder: &Path) -> Vec<PathBuf> {
+        self.configurations
+            .iter()
+            .filter_map(|configuration| configuration.env_file.as_deref())
+            .map(|env_file| env_file_path(env_file, workspace_folder))
+            .collect()
+    }
+
+    /// Converts the configurations and compounds into Zed debug scenarios.
+    /// `env_files` holds the contents of the [`Self::env_files`] that could be read, the others are skipped.
+    pub fn into_zed_format(
+        self,
+        workspace_folder: &Path,
+        env_files: &HashMap<PathBuf, String>,
+    ) -> DebugTaskFile {
+        let replacer = EnvVariableReplacer::vscode();
+        let configurations = self.configurations.into_iter().map(|configuration| {
+            let env_file = configuration
+                .env_file
+                .as_deref()
+                .and_then(|env_file| env_files.get(&env_file_path(env_file, workspace_folder)))
+                .map(|contents| parse_env_file(contents));
+            DebugScenario::Task(configuration.into_zed_format(&replacer, env_file))
+        });
+        let compounds = self
+            .compounds
+            .into_iter()
+            .map(|compound| DebugScenario::Compound(compound.into_zed_format()));
+        DebugTaskFile(configurations.chain(compounds).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use collections::HashMap;
+    use serde_json::json;
+
+    use crate::{
+        AttachRequest, DebugCompoundTemplate, DebugRequest, DebugScenario, DebugTaskDefinition,
+        DebugTaskFile, DebugTaskTemplate, LaunchRequest, VsCodeDebugTaskFile,
+    };
+
+    #[test]
+    fn test_converts_launch_configurations() {
+        const LAUNCH_CONFIGURATIONS: &str = include_str!("../test_data/launch.json");
+        let vscode_configurations: VsCodeDebugTaskFile =
+            serde_json_lenient::from_str(LAUNCH_CONFIGURATIONS).unwrap();
+        let workspace_folder = Path::new("/project");
+        assert_eq!(
+            vscode_configurations.env_files(workspace_folder),
+            vec![PathBuf::from("/project/.env")]
+        );
+
+        let env_files = HashMap::from_iter([(
+            PathBuf::from("/project/.env"),
+            "# Secrets\nexport API_KEY=\"secret\"\nPORT=8080\nDEBUG=true\n".to_owned(),
+        )]);
+        let zed_configurations =
+            vscode_configurations.into_zed_format(workspace_folder, &env_files);
+        assert_eq!(
+            zed_configurations,
+            DebugTaskFile(vec![
+                DebugScenario::Task(DebugTaskTemplate {
+                    locator: None,
+                    depends_on: vec!["npm: build".to_owned()],
+                    definition: DebugTaskDefinition {
+                        adapter: "JavaScript".to_owned(),
+                        request: DebugRequest::Launch(LaunchRequest {
+                            program: "${ZED_WORKTREE_ROOT}/server/index.js".to_owned(),
+                            cwd: Some(PathBuf::from("${ZED_WORKTREE_ROOT}/server")),
+                            args: vec!["--port".to_owned(), "${PORT}".to_owned()],
+                        }),
+                        label: "Server".to_owned(),
+                        initialize_args: Some(json!({
+                            "type": "pwa-node",
+                            "console": "integratedTerminal",
+                            "outFiles": ["${ZED_WORKTREE_ROOT}/dist/**/*.js"],
+                            "env": {
+                                "API_KEY": "secret",
+                                "PORT": "3000",
+                                "LOG_DIR": "${ZED_WORKTREE_ROOT}/logs",
+                            },
+                        })),
+                        tcp_connection: None,
+                        stop_on_entry: None,
+                    },
+                }),
+                DebugScenario::Task(DebugTaskTemplate {
+                    locator: None,
+                    depends_on: Vec::new(),
+                    definition: DebugTaskDefinition {
+                        adapter: "Debugpy".to_owned(),
+                        request: DebugRequest::Launch(LaunchRequest {
+                            program: "${ZED_FILE}".to_owned(),
+                            cwd: None,
+                            args: Vec::new(),
+                        }),
+                        label: "Client".to_owned(),
+                        initialize_args: None,
+                        tcp_connection: None,
+                        stop_on_entry: Some(true),
+                    },
+                }),
+                DebugScenario::Task(DebugTaskTemplate {
+                    locator: None,
+                    depends_on: Vec::new(),
+                    definition: DebugTaskDefinition {
+                        adapter: "CodeLLDB".to_owned(),
+                        request: DebugRequest::Attach(AttachRequest { process_id: None }),
+                        label: "Attach".to_owned(),
+                        initialize_args: None,
+                        tcp_connection: None,
+                        stop_on_entry: None,
+                    },
+                }),
+                DebugScenario::Compound(DebugCompoundTemplate {
+                    label: "Server + Client".to_owned(),
+                    compound: vec!["Server".to_owned(), "Client".to_owned()],
+                    depends_on: vec!["npm: install".to_owned()],
+                }),
+            ])
+        );
+    }
+}
@@ -1,9 +1,15 @@
-use anyhow::bail;
+use std::borrow::Cow;
+
+use anyhow::{Context as _, bail};
 use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    BackgroundTaskPatterns, DependsOrder, ProblemMatcher, ProblemMatcherTemplate, ProblemPattern,
+    ProblemSeverity, TaskInput, TaskInputKind, TaskTemplate, TaskTemplates, VariableName,
+    builtin_background_patterns,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +28,16 @@ struct VsCodeTaskDefinition {
     #[serde(flatten)]
     other_attributes: HashMap<String, serde_json_lenient::Value>,
     options: Option<TaskOptions>,
+    depends_on: Option<DependsOn>,
+    #[serde(default)]
+    depends_order: DependsOrder,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum DependsOn {
+    Single(String),
+    Multiple(Vec<String>),
 }
 
 #[derive(Clone, Deserialize, PartialEq, Debug)]
@@ -41,10 +57,169 @@ enum Command {
     },
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::One(item) => vec![item],
+            Self::Many(items) => items,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VsCodeProblemMatcher {
+    Named(String),
+    Custom {
+        #[serde(default)]
+        base: Option<String>,
+        #[serde(default)]
+        severity: Option<ProblemSeverity>,
+        #[serde(default)]
+        pattern: Option<OneOrMany<ProblemPattern>>,
+        #[serde(default)]
+        background: Option<VsCodeBackground>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeBackground {
+    begins_pattern: VsCodeBackgroundPattern,
+    ends_pattern: VsCodeBackgroundPattern,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VsCodeBackgroundPattern {
+    Regexp(String),
+    Pattern { regexp: String },
+}
+
+impl VsCodeBackgroundPattern {
+    fn regexp(&self) -> String {
+        match self {
+            Self::Regexp(regexp) | Self::Pattern { regexp } => regexp.clone(),
+        }
+    }
+}
+
+impl VsCodeProblemMatcher {
+    fn background_patterns(&self) -> Option<BackgroundTaskPatterns> {
+        match self {
+            Self::Named(name) => builtin_background_patterns(name),
+            Self::Custom {
+                background: Some(background),
+                ..
+            } => Some(BackgroundTaskPatterns {
+                begins_pattern: background.begins_pattern.regexp(),
+                ends_pattern: background.ends_pattern.regexp(),
+            }),
+            Self::Custom { base, .. } => base.as_deref().and_then(builtin_background_patterns),
+        }
+    }
+
+    fn into_zed_format(self) -> Option<ProblemMatcherTemplate> {
+        match self {
+            Self::Named(name) => Some(ProblemMatcherTemplate::Named(name)),
+            Self::Custom {
+                pattern: Some(pattern),
+                severity,
+                ..
+            } => Some(ProblemMatcherTemplate::Custom(ProblemMatcher {
+                severity: severity.unwrap_or_default(),
+                pattern: pattern.into_vec(),
+            })),
+            // A matcher that only tweaks a built-in one, e.g. its file location, is imported as the built-in matcher.
+            Self::Custom {
+                base,
+                pattern: None,
+                ..
+            } => base.map(ProblemMatcherTemplate::Named),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeInput {
+    id: String,
+    description: Option<String>,
+    default: Option<String>,
+    #[serde(flatten)]
+    kind: VsCodeInputKind,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum VsCodeInputKind {
+    PromptString,
+    PickString {
+        options: Vec<VsCodePickOption>,
+    },
+    Command {
+        command: String,
+        #[serde(default)]
+        args: serde_json_lenient::Value,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodePickOption {
+    Value(String),
+    Labeled { value: String },
+}
+
+impl VsCodeInput {
+    fn into_zed_format(self) -> anyhow::Result<TaskInput> {
+        let kind = match self.kind {
+            VsCodeInputKind::PromptString => TaskInputKind::PromptString,
+            VsCodeInputKind::PickString { options } => TaskInputKind::PickString {
+                options: options
+                    .into_iter()
+                    .map(|option| match option {
+                        VsCodePickOption::Value(value) | VsCodePickOption::Labeled { value } => {
+                            value
+                        }
+                    })
+                    .collect(),
+            },
+            // Other commands belong to VS Code extensions, only the shell command one has an equivalent.
+            VsCodeInputKind::Command { command, args } if command == "shellCommand.execute" => {
+                let command = args
+                    .get("command")
+                    .and_then(|command| command.as_str())
+                    .with_context(|| format!("Input {:?} has no shell command", self.id))?;
+                TaskInputKind::PickCommandOutput {
+                    command: command.to_owned(),
+                    args: Vec::new(),
+                }
+            }
+            VsCodeInputKind::Command { command, .. } => {
+                bail!("Input {:?} uses unsupported command {command:?}", self.id)
+            }
+        };
+        Ok(TaskInput {
+            id: self.id,
+            description: self.description,
+            default: self.default,
+            kind,
+        })
+    }
+}
+
 type VsCodeEnvVariable = String;
 type ZedEnvVariable = String;
 
-struct EnvVariableReplacer {
+pub(crate) struct EnvVariableReplacer {
     variables: HashMap<VsCodeEnvVariable, ZedEnvVariable>,
 }
 
@@ -52,12 +227,55 @@ impl EnvVariableReplacer {
     fn new(variables: HashMap<VsCodeEnvVariable, ZedEnvVariable>) -> Self {
         Self { variables }
     }
+
+    /// A replacer for the variables VS Code tasks and launch configurations have Zed task variables for.
+    pub(crate) fn vscode() -> Self {
+        Self::new(HashMap::from_iter([
+            (
+                "workspaceFolder".to_owned(),
+                VariableName::WorktreeRoot.to_string(),
+            ),
+            ("file".to_owned(), VariableName::File.to_string()),
+            (
+                "relativeFile".to_owned(),
+                VariableName::RelativeFile.to_string(),
+            ),
+            (
+                "fileBasename".to_owned(),
+                VariableName::Filename.to_string(),
+            ),
+            (
+                "fileBasenameNoExtension".to_owned(),
+                VariableName::Stem.to_string(),
+            ),
+            ("fileDirname".to_owned(), VariableName::Dirname.to_string()),
+            ("lineNumber".to_owned(), VariableName::Row.to_string()),
+            (
+                "selectedText".to_owned(),
+                VariableName::SelectedText.to_string(),
+            ),
+        ]))
+    }
+
     // Replaces occurrences of VsCode-specific environment variables with Zed equivalents.
-    fn replace(&self, input: &str) -> String {
+    pub(crate) fn replace(&self, input: &str) -> String {
         shellexpand::env_with_context_no_errors(&input, |var: &str| {
             // Colons denote a default value in case the variable is not set. We want to preserve that default, as otherwise shellexpand will substitute it for us.
             let colon_position = var.find(':').unwrap_or(var.len());
             let (variable_name, default) = var.split_at(colon_position);
+            // `${input:id}` refers to the value of one of the task inputs.
+            if variable_name == "input" {
+                if let Some(input_id) = default.strip_prefix(':') {
+                    let input = VariableName::Input(Cow::Owned(input_id.to_owned()));
+                    return Some(format!("${{{input}}}"));
+                }
+            }
+            // `${env:NAME}` refers to an environment variable.
+            if variable_name == "env" {
+                if let Some(env_variable) = default.strip_prefix(':') {
+                    return Some(format!("${{{env_variable}}}"));
+                }
+            }
             let append_previous_default = |ret: &mut String| {
                 if !default.is_empty() {
                     ret.push_str(default);
@@ -84,23 +302,54 @@ impl EnvVariableReplacer {
 }
 
 impl VsCodeTaskDefinition {
-    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> anyhow::Result<TaskTemplate> {
-        if self.other_attributes.contains_key("dependsOn") {
-            bail!("Encountered unsupported `dependsOn` key during deserialization");
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+    fn into_zed_format(
+        self,
+        replacer: &EnvVariableReplacer,
+        inputs: &[TaskInput],
+    ) -> anyhow::Result<TaskTemplate> {
+        let depends_on = match self.depends_on {
+            Some(DependsOn::Single(label)) => vec![label],
+            Some(DependsOn::Multiple(labels)) => labels,
+            None => Vec::new(),
         };
-
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+        // `type` is not set in tasks that only group their `dependsOn` tasks together,
+        // those become compound tasks without a command of their own.
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
+        let problem_matchers = self
+            .other_attributes
+            .get("problemMatcher")
+            .and_then(|problem_matcher| {
+                serde_json_lenient::from_value::<OneOrMany<VsCodeProblemMatcher>>(
+                    problem_matcher.clone(),
+                )
+                .context("Unsupported `problemMatcher` definition")
+                .log_err()
+            })
+            .map(OneOrMany::into_vec)
+            .unwrap_or_default();
+        let is_background = self
+            .other_attributes
+            .get("isBackground")
+            .and_then(|is_background| is_background.as_bool())
+            .unwrap_or(false);
+        // Background tasks without cycle markers cannot report readiness, so they are imported as regular ones.
+        let background = if is_background {
+            problem_matchers
+                .iter()
+                .find_map(VsCodeProblemMatcher::background_patterns)
+        } else {
+            None
+        };
+        let problem_matchers = problem_matchers
+            .into_iter()
+            .filter_map(VsCodeProblemMatcher::into_zed_format)
+            .collect();
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
         let args = args.into_iter().map(|arg| replacer.replace(&arg)).collect();
@@ -108,12 +357,28 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order: self.depends_order,
+            problem_matchers,
+            background,
             ..Default::default()
         };
         if let Some(options) = self.options {
             ret.cwd = options.cwd.map(|cwd| replacer.replace(&cwd));
             ret.env = options.env;
         }
+        // Inputs are defined for the whole file, each task gets the ones it refers to.
+        ret.inputs = inputs
+            .iter()
+            .filter(|input| {
+                let reference = format!("${{{}}}", input.variable_name());
+                std::iter::once(&ret.command)
+                    .chain(&ret.args)
+                    .chain(&ret.cwd)
+                    .any(|value| value.contains(&reference))
+            })
+            .cloned()
+            .collect();
         Ok(ret)
     }
 }
@@ -122,28 +387,33 @@ impl VsCodeTaskDefinition {
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct VsCodeTaskFile {
     tasks: Vec<VsCodeTaskDefinition>,
+    #[serde(default)]
+    inputs: Vec<serde_json_lenient::Value>,
 }
 
 impl TryFrom<VsCodeTaskFile> for TaskTemplates {
     type Error = anyhow::Error;
 
     fn try_from(value: VsCodeTaskFile) -> Result<Self, Self::Error> {
-        let replacer = EnvVariableReplacer::new(HashMap::from_iter([
-            (
-                "workspaceFolder".to_owned(),
-                VariableName::WorktreeRoot.to_string(),
-            ),
-            ("file".to_owned(), VariableName::File.to_string()),
-            ("lineNumber".to_owned(), VariableName::Row.to_string()),
-            (
-                "selectedText".to_owned(),
-                VariableName::SelectedText.to_string(),
-            ),
-        ]));
+        let replacer = EnvVariableReplacer::vscode();
+        let inputs = value
+            .inputs
+            .into_iter()
+            .filter_map(|input| {
+                serde_json_lenient::from_value::<VsCodeInput>(input)
+                    .context("Unsupported input definition")
+                    .and_then(VsCodeInput::into_zed_format)
+                    .log_err()
+            })
+            .collect::<Vec<_>>();
         let templates = value
             .tasks
             .into_iter()
-            .filter_map(|vscode_definition| vscode_definition.into_zed_format(&replacer).log_err())
+            .filter_map(|vscode_definition| {
+                vscode_definition
+                    .into_zed_format(&replacer, &inputs)
+                    .log_err()
+            })
             .collect();
         Ok(Self(templates))
     }
@@ -154,8 +424,9 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
-        vscode_format::{Command, VsCodeTaskDefinition},
+        DependsOrder, ProblemMatcherTemplate, TaskInput, TaskInputKind, TaskTemplate,
+        TaskTemplates, VsCodeTaskFile, builtin_background_patterns,
+        vscode_format::{Command, DependsOn, VsCodeTaskDefinition},
     };
 
     use super::EnvVariableReplacer;
@@ -212,6 +483,8 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
             },
             VsCodeTaskDefinition {
                 label: "tsc: watch ./src".to_string(),
@@ -226,6 +499,8 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
             },
             VsCodeTaskDefinition {
                 label: "npm: build:compiler".to_string(),
@@ -234,6 +509,8 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
             },
             VsCodeTaskDefinition {
                 label: "npm: build:tests".to_string(),
@@ -242,6 +519,8 @@ mod tests {
                 }),
                 other_attributes: Default::default(),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
             },
         ];
 
@@ -257,6 +536,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -268,18 +548,22 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc-watch".to_string())],
+                background: builtin_background_patterns("$tsc-watch"),
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -300,6 +584,8 @@ mod tests {
                     script: "watch".to_string(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -308,6 +594,8 @@ mod tests {
                     script: "build".to_string(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -317,6 +605,8 @@ mod tests {
                     args: Default::default(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -326,6 +616,8 @@ mod tests {
                     args: Default::default(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -334,18 +626,30 @@ mod tests {
                     script: "pretest".to_string(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
                 label: "Build Server and Extension".to_string(),
                 command: None,
                 options: None,
+                depends_on: Some(DependsOn::Multiple(vec![
+                    "Build Server".to_string(),
+                    "Build Extension".to_string(),
+                ])),
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
                 label: "Build Server (Release) and Extension".to_string(),
                 command: None,
                 options: None,
+                depends_on: Some(DependsOn::Multiple(vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ])),
+                depends_order: DependsOrder::default(),
                 other_attributes: Default::default(),
             },
         ];
@@ -360,28 +664,105 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc-watch".to_string())],
+                background: builtin_background_patterns("$tsc-watch"),
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$tsc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$rustc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![ProblemMatcherTemplate::Named("$rustc".to_string())],
+                ..Default::default()
+            },
+        ];
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(tasks.0, expected);
+    }
+
+    #[test]
+    fn can_deserialize_inputs() {
+        const INPUT_TASKS: &str = include_str!("../test_data/inputs.json");
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(INPUT_TASKS).unwrap();
+        let environment = TaskInput {
+            id: "environment".to_string(),
+            description: Some("Where to deploy".to_string()),
+            default: Some("staging".to_string()),
+            kind: TaskInputKind::PickString {
+                options: vec!["staging".to_string(), "production".to_string()],
+            },
+        };
+        let tag = TaskInput {
+            id: "tag".to_string(),
+            description: Some("Release tag".to_string()),
+            default: None,
+            kind: TaskInputKind::PromptString,
+        };
+        let example = TaskInput {
+            id: "example".to_string(),
+            description: None,
+            default: None,
+            kind: TaskInputKind::PickCommandOutput {
+                command: "ls examples".to_string(),
+                args: Vec::new(),
+            },
+        };
+        let expected = vec![
+            TaskTemplate {
+                label: "deploy".to_string(),
+                command: "./scripts/deploy.sh".to_string(),
+                args: vec![
+                    "--env".to_string(),
+                    "${ZED_INPUT_environment}".to_string(),
+                    "--tag".to_string(),
+                    "${ZED_INPUT_tag}".to_string(),
+                ],
+                inputs: vec![environment, tag],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "run example".to_string(),
+                command: "cargo run --example ${ZED_INPUT_example}".to_string(),
+                inputs: vec![example],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "lint".to_string(),
+                command: "cargo clippy".to_string(),
                 ..Default::default()
             },
         ];
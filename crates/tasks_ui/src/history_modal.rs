@@ -0,0 +1,424 @@
+use std::{sync::Arc, time::Duration};
+
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    Action, AnyElement, App, AppContext as _, Context, DismissEvent, Entity, EventEmitter,
+    Focusable, InteractiveElement, ParentElement, Render, SharedString, Styled, Subscription, Task,
+    WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
+use terminal_view::task_history::{TASK_HISTORY, TaskRun};
+use time::OffsetDateTime;
+use time_format::TimestampFormat;
+use ui::{
+    ActiveTheme, Button, Clickable, Color, FluentBuilder as _, Icon, IconName, IconSize,
+    IntoElement, KeyBinding, Label, LabelCommon as _, LabelSize, ListItem, ListItemSpacing,
+    Toggleable, Tooltip, div, h_flex, v_flex,
+};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+use zed_actions::ShowHistory;
+
+pub(crate) fn toggle_task_history(
+    workspace: &mut Workspace,
+    _: &ShowHistory,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let workspace_id = workspace.database_id();
+    let runs = cx.background_spawn(async move { TASK_HISTORY.recent_task_runs(workspace_id) });
+    cx.spawn_in(window, async move |workspace, cx| {
+        let runs = runs.await.log_err().unwrap_or_default();
+        workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_handle = cx.entity().downgrade();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                TaskHistoryModal::new(runs, workspace_handle, window, cx)
+            });
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// A modal listing the past task runs, to rerun them as they were or with an edited command.
+pub(crate) struct TaskHistoryModal {
+    picker: Entity<Picker<TaskHistoryModalDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TaskHistoryModal {
+    fn new(
+        runs: Vec<TaskRun>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(TaskHistoryModalDelegate::new(runs, workspace), window, cx)
+        });
+        let _subscription = cx.subscribe(&picker, |_, _, _: &DismissEvent, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl Render for TaskHistoryModal {
+    fn render(&mut self, _window: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TaskHistoryModal")
+            .w(rems(40.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for TaskHistoryModal {}
+
+impl Focusable for TaskHistoryModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for TaskHistoryModal {}
+
+pub(crate) struct TaskHistoryModalDelegate {
+    runs: Vec<TaskRun>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    /// The run whose command was put into the query for editing, to rerun it with the same environment.
+    edited_run: Option<usize>,
+    workspace: WeakEntity<Workspace>,
+}
+
+impl TaskHistoryModalDelegate {
+    fn new(runs: Vec<TaskRun>, workspace: WeakEntity<Workspace>) -> Self {
+        Self {
+            runs,
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            edited_run: None,
+            workspace,
+        }
+    }
+
+    fn selected_run(&self) -> Option<&TaskRun> {
+        let run_index = self.matches.get(self.selected_index)?.candidate_id;
+        self.runs.get(run_index)
+    }
+
+    fn edited_run(&self) -> Option<&TaskRun> {
+        self.runs
+            .get(self.edited_run?)
+            .filter(|_| !self.query.trim().is_empty())
+    }
+
+    fn render_exit_status(run: &TaskRun) -> AnyElement {
+        match (run.exit_code, run.duration) {
+            (Some(0), _) => Icon::new(IconName::Check)
+                .color(Color::Success)
+                .size(IconSize::Small)
+                .into_any_element(),
+            (Some(exit_code), _) => Label::new(format!("exit {exit_code}"))
+                .size(LabelSize::Small)
+                .color(Color::Error)
+                .into_any_element(),
+            (None, Some(_)) => Label::new("cancelled")
+                .size(LabelSize::Small)
+                .color(Color::Warning)
+                .into_any_element(),
+            (None, None) => Label::new("unfinished")
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element(),
+        }
+    }
+}
+
+impl PickerDelegate for TaskHistoryModalDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _: &mut App) -> Arc<str> {
+        Arc::from("Find a past task run…")
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        if self.runs.is_empty() {
+            Some("No task runs recorded yet".into())
+        } else {
+            Some("No matching task runs".into())
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .runs
+            .iter()
+            .enumerate()
+            .map(|(id, run)| StringMatchCandidate::new(id, &run.label))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string: candidate.string,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    1000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    if delegate.query != query {
+                        delegate.selected_index = 0;
+                    }
+                    delegate.matches = matches;
+                    delegate.query = query;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(run) = self.selected_run() else {
+            return;
+        };
+        let spawn_in_terminal = run.to_spawn_in_terminal();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .spawn_in_terminal(spawn_in_terminal, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm_completion(
+        &mut self,
+        _: String,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<String> {
+        let run_index = self.matches.get(self.selected_index)?.candidate_id;
+        let run = self.runs.get(run_index)?;
+        self.edited_run = Some(run_index);
+        Some(run.command_label.clone())
+    }
+
+    fn confirm_input(
+        &mut self,
+        _secondary: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(run) = self.edited_run().or_else(|| self.selected_run()) else {
+            return;
+        };
+        let command = self.query.trim().to_string();
+        if command.is_empty() {
+            return;
+        }
+        let spawn_in_terminal = run.to_spawn_in_terminal_with_command(command);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .spawn_in_terminal(spawn_in_terminal, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let run = self.runs.get(hit.candidate_id)?;
+        let highlighted_label = HighlightedMatch {
+            text: hit.string.clone(),
+            highlight_positions: hit.positions.clone(),
+            char_count: hit.string.chars().count(),
+            color: Color::Default,
+        };
+
+        let mut tooltip_text = run.command_label.clone();
+        if let Some(cwd) = &run.cwd {
+            tooltip_text.push('\n');
+            tooltip_text.push_str(&cwd.to_string_lossy());
+        }
+        let started_at = time_format::format_local_timestamp(
+            run.started_at,
+            OffsetDateTime::now_utc(),
+            TimestampFormat::Relative,
+        );
+
+        Some(
+            ListItem::new(SharedString::from(format!("task-history-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .tooltip(Tooltip::text(tooltip_text))
+                .end_slot::<AnyElement>(
+                    h_flex()
+                        .gap_2()
+                        .flex_none()
+                        .child(Self::render_exit_status(run))
+                        .when_some(run.duration, |this, duration| {
+                            this.child(
+                                Label::new(format_duration(duration))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        })
+                        .child(
+                            Label::new(started_at)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .into_any_element(),
+                )
+                .child(highlighted_label.render(window, cx)),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let edit_action = picker::ConfirmCompletion.boxed_clone();
+        let run_edited_action = picker::ConfirmInput { secondary: false }.boxed_clone();
+        Some(
+            h_flex()
+                .w_full()
+                .h_8()
+                .p_2()
+                .justify_between()
+                .rounded_b_sm()
+                .bg(cx.theme().colors().ghost_element_selected)
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    KeyBinding::for_action(&*edit_action, window, cx)
+                        .map(|keybind| {
+                            Button::new("edit-task-run", "Edit Command")
+                                .label_size(LabelSize::Small)
+                                .key_binding(keybind)
+                                .on_click(move |_, window, cx| {
+                                    window.dispatch_action(edit_action.boxed_clone(), cx);
+                                })
+                                .into_any_element()
+                        })
+                        .unwrap_or_else(|| div().into_any_element()),
+                )
+                .map(|this| {
+                    if self.edited_run().is_some() {
+                        this.children(KeyBinding::for_action(&*run_edited_action, window, cx).map(
+                            |keybind| {
+                                Button::new("run-edited-task", "Run Edited Command")
+                                    .label_size(LabelSize::Small)
+                                    .key_binding(keybind)
+                                    .on_click(move |_, window, cx| {
+                                        window.dispatch_action(run_edited_action.boxed_clone(), cx);
+                                    })
+                            },
+                        ))
+                    } else {
+                        this.children(KeyBinding::for_action(&menu::Confirm, window, cx).map(
+                            |keybind| {
+                                Button::new("rerun-task", "Rerun")
+                                    .label_size(LabelSize::Small)
+                                    .key_binding(keybind)
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(menu::Confirm.boxed_clone(), cx);
+                                    })
+                            },
+                        ))
+                    }
+                })
+                .into_any_element(),
+        )
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds == 0 {
+        format!("{}ms", duration.as_millis())
+    } else if seconds < 60 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else if seconds < 60 * 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h {}m", seconds / 3600, seconds % 3600 / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_duration;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(
+            format_duration(Duration::from_secs(3 * 3600 + 120)),
+            "3h 2m"
+        );
+    }
+}
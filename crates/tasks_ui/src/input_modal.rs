@@ -0,0 +1,341 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result, anyhow, ensure};
+use futures::channel::oneshot;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    App, AppContext as _, Context, DismissEvent, Entity, EventEmitter, Focusable,
+    InteractiveElement, ParentElement, Render, SharedString, Styled, Subscription, Task, Window,
+    rems,
+};
+use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
+use task::{Shell, ShellBuilder, TaskInput, TaskInputKind};
+use ui::{Color, IntoElement, ListItem, ListItemSpacing, Toggleable, v_flex};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+/// Shows a modal asking for the value of a task input.
+/// The receiver gets nothing if the modal is dismissed.
+pub(crate) fn prompt_for_task_input(
+    workspace: &mut Workspace,
+    input: TaskInput,
+    cwd: Option<PathBuf>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> oneshot::Receiver<String> {
+    let (value_tx, value_rx) = oneshot::channel();
+    let options = match &input.kind {
+        TaskInputKind::PromptString => None,
+        TaskInputKind::PickString { options } => Some(Task::ready(Ok(options.clone()))),
+        TaskInputKind::PickCommandOutput { command, args } => Some(command_output_lines(
+            workspace,
+            command.clone(),
+            args.clone(),
+            cwd,
+            cx,
+        )),
+    };
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TaskInputModal::new(input, options, value_tx, window, cx)
+    });
+    value_rx
+}
+
+/// Runs the command in the user's shell and returns the non-empty lines of its output.
+fn command_output_lines(
+    workspace: &Workspace,
+    command: String,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    cx: &App,
+) -> Task<Result<Vec<String>>> {
+    let project = workspace.project().read(cx);
+    if !project.is_local() {
+        return Task::ready(Err(anyhow!(
+            "Inputs picked from a command output are only supported in local projects"
+        )));
+    }
+    let cwd = cwd.or_else(|| {
+        project
+            .visible_worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+    });
+    cx.background_spawn(async move {
+        let (program, program_args) =
+            ShellBuilder::new(true, &Shell::System).build(command.clone(), &args);
+        let mut shell_command = util::command::new_smol_command(program);
+        shell_command.args(program_args);
+        if let Some(cwd) = cwd {
+            shell_command.current_dir(cwd);
+        }
+        let output = shell_command
+            .output()
+            .await
+            .with_context(|| format!("running `{command}`"))?;
+        ensure!(
+            output.status.success(),
+            "`{command}` failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(ToOwned::to_owned)
+            .collect())
+    })
+}
+
+pub(crate) struct TaskInputModal {
+    picker: Entity<Picker<TaskInputModalDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TaskInputModal {
+    fn new(
+        input: TaskInput,
+        options: Option<Task<Result<Vec<String>>>>,
+        value_tx: oneshot::Sender<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let default = input.default.clone();
+        let picks_option = options.is_some();
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(
+                TaskInputModalDelegate::new(input, picks_option, value_tx),
+                window,
+                cx,
+            )
+        });
+        match options {
+            Some(options) => {
+                let picker = picker.downgrade();
+                cx.spawn_in(window, async move |_, cx| {
+                    let options = options.await;
+                    picker.update_in(cx, |picker, window, cx| {
+                        picker.delegate.set_options(options);
+                        picker.refresh(window, cx);
+                    })
+                })
+                .detach_and_log_err(cx);
+            }
+            None => {
+                if let Some(default) = default {
+                    picker.update(cx, |picker, cx| picker.set_query(default, window, cx));
+                }
+            }
+        }
+        let _subscription = cx.subscribe(&picker, |_, _, _: &DismissEvent, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl Render for TaskInputModal {
+    fn render(&mut self, _window: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TaskInputModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for TaskInputModal {}
+
+impl Focusable for TaskInputModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for TaskInputModal {}
+
+pub(crate) struct TaskInputModalDelegate {
+    input: TaskInput,
+    /// Values to pick from, `None` for inputs that take any text or while they are being loaded.
+    options: Option<Vec<String>>,
+    /// Whether the input is picked from a list of options, rather than typed in.
+    picks_option: bool,
+    error: Option<SharedString>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    placeholder_text: Arc<str>,
+    value_tx: Option<oneshot::Sender<String>>,
+}
+
+impl TaskInputModalDelegate {
+    fn new(input: TaskInput, picks_option: bool, value_tx: oneshot::Sender<String>) -> Self {
+        let placeholder_text = match &input.description {
+            Some(description) => Arc::from(description.as_str()),
+            None if picks_option => Arc::from(format!("Pick a value for {}", input.id)),
+            None => Arc::from(format!("Enter a value for {}", input.id)),
+        };
+        Self {
+            input,
+            options: None,
+            picks_option,
+            error: None,
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            placeholder_text,
+            value_tx: Some(value_tx),
+        }
+    }
+
+    fn set_options(&mut self, options: Result<Vec<String>>) {
+        match options {
+            Ok(options) => {
+                self.selected_index = self
+                    .input
+                    .default
+                    .as_ref()
+                    .and_then(|default| options.iter().position(|option| option == default))
+                    .unwrap_or(0);
+                self.options = Some(options);
+            }
+            Err(e) => {
+                self.error = Some(format!("{e:#}").into());
+                self.options = Some(Vec::new());
+            }
+        }
+    }
+
+    fn submit(&mut self, value: String, cx: &mut Context<Picker<Self>>) {
+        if let Some(value_tx) = self.value_tx.take() {
+            value_tx.send(value).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for TaskInputModalDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _: &mut App) -> Arc<str> {
+        self.placeholder_text.clone()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        if let Some(error) = &self.error {
+            Some(error.clone())
+        } else if !self.picks_option {
+            None
+        } else if self.options.is_none() {
+            Some("Loading options…".into())
+        } else {
+            Some("No matches".into())
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let options = self.options.clone().unwrap_or_default();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                options
+                    .into_iter()
+                    .enumerate()
+                    .map(|(candidate_id, string)| StringMatch {
+                        candidate_id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string,
+                    })
+                    .collect()
+            } else {
+                let candidates = options
+                    .iter()
+                    .enumerate()
+                    .map(|(id, option)| StringMatchCandidate::new(id, option))
+                    .collect::<Vec<_>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    1000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    if delegate.query != query {
+                        delegate.selected_index = 0;
+                    }
+                    delegate.matches = matches;
+                    delegate.query = query;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if !self.picks_option {
+            self.submit(self.query.clone(), cx);
+        } else if let Some(selected) = self.matches.get(self.selected_index) {
+            self.submit(selected.string.clone(), cx);
+        }
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let highlighted_option = HighlightedMatch {
+            text: hit.string.clone(),
+            highlight_positions: hit.positions.clone(),
+            char_count: hit.string.chars().count(),
+            color: Color::Default,
+        };
+        Some(
+            ListItem::new(SharedString::from(format!("task-input-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(highlighted_option.render(window, cx)),
+        )
+    }
+}
@@ -376,12 +376,19 @@ impl PickerDelegate for TasksModalDelegate {
                     _ => {
                         self.workspace
                             .update(cx, |workspace, cx| {
-                                workspace.schedule_debug_task(task, window, cx);
+                                workspace.schedule_debug_task(task_source_kind, task, window, cx);
                             })
                             .ok();
                     }
                 }
             }
+            TaskType::DebugCompound => {
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.schedule_debug_task(task_source_kind, task, window, cx);
+                    })
+                    .ok();
+            }
             TaskType::Script => {
                 self.workspace
                     .update(cx, |workspace, cx| {
@@ -586,8 +593,8 @@ impl PickerDelegate for TasksModalDelegate {
                     ),
                     // todo(debugger): Should create a schedule_resolved_debug_task function
                     // This would allow users to access to debug history and other issues
-                    TaskType::Debug(_) => {
-                        workspace.schedule_debug_task(task, window, cx);
+                    TaskType::Debug(_) | TaskType::DebugCompound => {
+                        workspace.schedule_debug_task(task_source_kind, task, window, cx);
                     }
                 };
             })
@@ -718,7 +725,9 @@ fn string_match_candidates<'a>(
         .enumerate()
         .filter(|(_, (_, candidate))| match candidate.task_type() {
             TaskType::Script => task_modal_type == TaskModal::ScriptModal,
-            TaskType::Debug(_) => task_modal_type == TaskModal::DebugModal,
+            TaskType::Debug(_) | TaskType::DebugCompound => {
+                task_modal_type == TaskModal::DebugModal
+            }
         })
         .map(|(index, (_, candidate))| StringMatchCandidate::new(index, candidate.display_label()))
         .collect()
@@ -10,6 +10,8 @@ use task::{
 };
 use workspace::Workspace;
 
+mod history_modal;
+mod input_modal;
 mod modal;
 
 pub use modal::{Rerun, ShowAttachModal, Spawn, TasksModal};
@@ -17,8 +19,10 @@ pub use modal::{Rerun, ShowAttachModal, Spawn, TasksModal};
 pub fn init(cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>| {
+            workspace.set_prompt_for_task_input(Box::new(input_modal::prompt_for_task_input));
             workspace
                 .register_action(spawn_task_or_modal)
+                .register_action(history_modal::toggle_task_history)
                 .register_action(move |workspace, action: &modal::Rerun, window, cx| {
                     if let Some((task_source_kind, mut last_scheduled_task)) = workspace
                         .project()
@@ -28,7 +28,10 @@ use anyhow::{Result, bail};
 
 use futures::{
     FutureExt,
-    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+    channel::{
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+        oneshot,
+    },
 };
 
 use mappings::mouse::{
@@ -42,11 +45,10 @@ use pty_info::PtyProcessInfo;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
-use smol::channel::{Receiver, Sender};
-use task::{HideStrategy, Shell, TaskId};
+use task::{BackgroundTaskPatterns, HideStrategy, Shell, TaskId};
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
-use util::{ResultExt, paths::home_dir, truncate_and_trailoff};
+use util::{paths::home_dir, truncate_and_trailoff};
 
 use std::{
     cmp::{self, min},
@@ -110,6 +112,8 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// A background task switched between being busy and idle.
+    TaskActivityChanged(TaskActivity),
 }
 
 #[derive(Clone, Debug)]
@@ -351,7 +355,6 @@ impl TerminalBuilder {
         max_scroll_history_lines: Option<usize>,
         is_ssh_terminal: bool,
         window: AnyWindowHandle,
-        completion_tx: Sender<Option<ExitStatus>>,
         debug_terminal: bool,
         cx: &App,
     ) -> Result<TerminalBuilder> {
@@ -483,7 +486,8 @@ impl TerminalBuilder {
         let terminal = Terminal {
             task,
             pty_tx: Notifier(pty_tx),
-            completion_tx,
+            completion_waiters: Vec::new(),
+            ready_waiters: Vec::new(),
             term,
             term_config: config,
             title_override: terminal_title_override,
@@ -639,7 +643,8 @@ pub enum SelectionPhase {
 
 pub struct Terminal {
     pty_tx: Notifier,
-    completion_tx: Sender<Option<ExitStatus>>,
+    completion_waiters: Vec<oneshot::Sender<Option<ExitStatus>>>,
+    ready_waiters: Vec<oneshot::Sender<Option<ExitStatus>>>,
     term: Arc<FairMutex<Term<ZedListener>>>,
     term_config: Config,
     events: VecDeque<InternalEvent>,
@@ -670,11 +675,60 @@ pub struct TaskState {
     pub label: String,
     pub command_label: String,
     pub status: TaskStatus,
-    pub completion_rx: Receiver<Option<ExitStatus>>,
     pub hide: HideStrategy,
     pub show_summary: bool,
     pub show_command: bool,
     pub show_rerun: bool,
+    pub background: Option<BackgroundTaskState>,
+}
+
+/// How many of the last non-empty output lines are scanned for background task cycle markers,
+/// when it is unknown which lines were scanned before.
+const BACKGROUND_TASK_SCAN_LINES: usize = 200;
+
+/// Tracks the work cycles of a long-running task, such as a watcher that rebuilds on every change.
+pub struct BackgroundTaskState {
+    begins_pattern: Regex,
+    ends_pattern: Regex,
+    /// Whether the task is currently in the middle of a cycle.
+    pub activity: TaskActivity,
+    /// Whether the task has finished at least one cycle since it was started.
+    pub ready: bool,
+    /// The row of the cursor when the output was last scanned, counted from the top of the scrollback.
+    /// Output is only scanned from that row on, as the lines above it were already scanned.
+    scanned_row: Option<usize>,
+}
+
+impl BackgroundTaskState {
+    pub fn new(patterns: &BackgroundTaskPatterns) -> Result<Self> {
+        Ok(Self {
+            begins_pattern: Regex::new(&patterns.begins_pattern)?,
+            ends_pattern: Regex::new(&patterns.ends_pattern)?,
+            activity: TaskActivity::Busy,
+            ready: false,
+            scanned_row: None,
+        })
+    }
+
+    /// Finds the activity indicated by the most recent cycle marker among the lines given.
+    fn activity_in(&self, lines: &[String]) -> Option<TaskActivity> {
+        lines.iter().rev().find_map(|line| {
+            if self.ends_pattern.is_match(line) {
+                Some(TaskActivity::Idle)
+            } else if self.begins_pattern.is_match(line) {
+                Some(TaskActivity::Busy)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Whether a background task is currently working or waiting for changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskActivity {
+    Busy,
+    Idle,
 }
 
 /// A status of the current terminal tab's task.
@@ -686,7 +740,7 @@ pub enum TaskStatus {
     /// The task is started and running currently.
     Running,
     /// After the start, the task stopped running and reported its error code back.
-    Completed { success: bool },
+    Completed { success: bool, exit_code: i32 },
 }
 
 impl TaskStatus {
@@ -699,6 +753,7 @@ impl TaskStatus {
     fn register_task_exit(&mut self, error_code: i32) {
         *self = TaskStatus::Completed {
             success: error_code == 0,
+            exit_code: error_code,
         };
     }
 }
@@ -744,6 +799,7 @@ impl Terminal {
             }
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
+                self.update_background_task_activity(cx);
 
                 if self.pty_info.has_changed() {
                     cx.emit(Event::TitleChanged);
@@ -1448,6 +1504,72 @@ impl Terminal {
         lines
     }
 
+    /// The non-empty lines from `row` to the bottom of the terminal, and the row of the cursor, with rows
+    /// counted from the top of the scrollback.
+    ///
+    /// Rows stay the same as the output scrolls, unless the scrollback got cleared, or is full and drops its
+    /// oldest lines. In these cases, and without a `row`, the last [`BACKGROUND_TASK_SCAN_LINES`] are returned.
+    fn non_empty_lines_since_row(&self, row: Option<usize>) -> (Vec<String>, usize) {
+        let term = self.term.clone();
+        let terminal = term.lock_unfair();
+        let grid = terminal.grid();
+        let history_size = grid.history_size();
+        let cursor_row = history_size + grid.cursor.point.line.0.max(0) as usize;
+        let Some(row) =
+            row.filter(|row| *row <= cursor_row && history_size < MAX_SCROLL_HISTORY_LINES)
+        else {
+            drop(terminal);
+            return (
+                self.last_n_non_empty_lines(BACKGROUND_TASK_SCAN_LINES),
+                cursor_row,
+            );
+        };
+
+        let topmost_line = grid.topmost_line().0;
+        let bottommost_line = grid.bottommost_line().0;
+        let last_column = Column(grid.columns() - 1);
+        let mut lines = Vec::new();
+        let mut line_start =
+            self.find_logical_line_start(grid, row as i32 - history_size as i32, topmost_line);
+        while line_start <= bottommost_line {
+            let mut line_end = line_start;
+            while line_end < bottommost_line
+                && grid[Line(line_end)][last_column]
+                    .flags
+                    .contains(Flags::WRAPLINE)
+            {
+                line_end += 1;
+            }
+            let logical_line = self.construct_logical_line(grid, line_start, line_end);
+            if let Some(line) = self.process_line(logical_line) {
+                lines.push(line);
+            }
+            line_start = line_end + 1;
+        }
+        (lines, cursor_row)
+    }
+
+    /// All lines of the terminal's scrollback and screen, with wrapped lines joined back together.
+    pub fn all_lines(&self) -> Vec<String> {
+        let term = self.term.clone();
+        let terminal = term.lock_unfair();
+        let grid = terminal.grid();
+        let mut lines = Vec::new();
+
+        let mut current_line = grid.bottommost_line().0;
+        let topmost_line = grid.topmost_line().0;
+
+        while current_line >= topmost_line {
+            let logical_line_start = self.find_logical_line_start(grid, current_line, topmost_line);
+            let logical_line = self.construct_logical_line(grid, logical_line_start, current_line);
+            lines.push(logical_line.trim_end().to_string());
+            current_line = logical_line_start - 1;
+        }
+
+        lines.reverse();
+        lines
+    }
+
     fn find_logical_line_start(&self, grid: &Grid<Cell>, current: i32, topmost: i32) -> i32 {
         let mut line_start = current;
         while line_start > topmost {
@@ -1859,17 +1981,81 @@ impl Terminal {
         self.debug_terminal
     }
 
-    pub fn wait_for_completed_task(&self, cx: &App) -> Task<Option<ExitStatus>> {
+    pub fn wait_for_completed_task(&mut self, cx: &App) -> Task<Option<ExitStatus>> {
         if let Some(task) = self.task() {
             if task.status == TaskStatus::Running {
-                let completion_receiver = task.completion_rx.clone();
-                return cx
-                    .spawn(async move |_| completion_receiver.recv().await.log_err().flatten());
+                let (completion_tx, completion_rx) = oneshot::channel();
+                self.completion_waiters.push(completion_tx);
+                return cx.spawn(async move |_| completion_rx.await.ok().flatten());
             }
         }
         Task::ready(None)
     }
 
+    /// Waits until the task is ready for its dependents to proceed.
+    ///
+    /// Regular tasks are ready when they exit, background tasks when they finish their first cycle,
+    /// in which case a successful exit status is reported.
+    pub fn wait_for_task_ready(&mut self, cx: &App) -> Task<Option<ExitStatus>> {
+        let Some(task) = self.task() else {
+            return Task::ready(None);
+        };
+        let is_running = task.status == TaskStatus::Running;
+        match task.background.as_ref().map(|background| background.ready) {
+            Some(true) => Task::ready(Some(ExitStatus::default())),
+            Some(false) if is_running => {
+                let (ready_tx, ready_rx) = oneshot::channel();
+                self.ready_waiters.push(ready_tx);
+                cx.spawn(async move |_| ready_rx.await.ok().flatten())
+            }
+            _ => self.wait_for_completed_task(cx),
+        }
+    }
+
+    /// The output of the background task's latest cycle, or all output if no cycle start is found.
+    pub fn last_task_cycle_lines(&self) -> Vec<String> {
+        let mut lines = self.all_lines();
+        if let Some(background) = self.task().and_then(|task| task.background.as_ref()) {
+            if let Some(cycle_start) = lines
+                .iter()
+                .rposition(|line| background.begins_pattern.is_match(line))
+            {
+                lines.drain(..=cycle_start);
+            }
+        }
+        lines
+    }
+
+    fn update_background_task_activity(&mut self, cx: &mut Context<Self>) {
+        let Some(background) = self
+            .task
+            .as_ref()
+            .filter(|task| task.status == TaskStatus::Running)
+            .and_then(|task| task.background.as_ref())
+        else {
+            return;
+        };
+        let (lines, cursor_row) = self.non_empty_lines_since_row(background.scanned_row);
+        let activity = background.activity_in(&lines);
+
+        let Some(background) = self.task.as_mut().and_then(|task| task.background.as_mut()) else {
+            return;
+        };
+        background.scanned_row = Some(cursor_row);
+        let Some(activity) = activity.filter(|activity| *activity != background.activity) else {
+            return;
+        };
+        background.activity = activity;
+        if activity == TaskActivity::Idle && !background.ready {
+            background.ready = true;
+            for ready_tx in self.ready_waiters.drain(..) {
+                ready_tx.send(Some(ExitStatus::default())).ok();
+            }
+        }
+        cx.emit(Event::TaskActivityChanged(activity));
+        cx.notify();
+    }
+
     fn register_task_finished(&mut self, error_code: Option<i32>, cx: &mut Context<Terminal>) {
         let e: Option<ExitStatus> = error_code.map(|code| {
             #[cfg(unix)]
@@ -1882,7 +2068,12 @@ impl Terminal {
             }
         });
 
-        self.completion_tx.try_send(e).ok();
+        for completion_tx in self.completion_waiters.drain(..) {
+            completion_tx.send(e).ok();
+        }
+        for ready_tx in self.ready_waiters.drain(..) {
+            ready_tx.send(e).ok();
+        }
         let task = match &mut self.task {
             Some(task) => task,
             None => {
@@ -1903,6 +2094,11 @@ impl Terminal {
                 task.status.register_terminal_exit();
             }
         };
+        // A stopped background task is not working anymore, whatever its last cycle marker was.
+        if let Some(background) = &mut task.background {
+            background.activity = TaskActivity::Idle;
+            cx.emit(Event::TaskActivityChanged(TaskActivity::Idle));
+        }
 
         let (finished_successfully, task_line, command_line) = task_summary(task, error_code);
         let mut lines_to_show = Vec::new();
@@ -2166,8 +2362,8 @@ mod tests {
     use rand::{Rng, distributions::Alphanumeric, rngs::ThreadRng, thread_rng};
 
     use crate::{
-        IndexedCell, TerminalBounds, TerminalContent, content_index_for_mouse,
-        python_extract_path_and_line, rgb_for_index,
+        BackgroundTaskState, IndexedCell, TaskActivity, TerminalBounds, TerminalContent,
+        content_index_for_mouse, python_extract_path_and_line, rgb_for_index,
     };
 
     #[test]
@@ -2179,6 +2375,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_background_task_activity() {
+        let background = BackgroundTaskState::new(&task::BackgroundTaskPatterns {
+            begins_pattern: "^Rebuilding".to_string(),
+            ends_pattern: r"^Finished in \d+ms".to_string(),
+        })
+        .unwrap();
+        let lines = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(background.activity_in(&lines(&["Starting watcher"])), None);
+        assert_eq!(
+            background.activity_in(&lines(&["Rebuilding app", "src/main.rs changed"])),
+            Some(TaskActivity::Busy)
+        );
+        assert_eq!(
+            background.activity_in(&lines(&[
+                "Rebuilding app",
+                "Finished in 12ms",
+                "Watching for changes"
+            ])),
+            Some(TaskActivity::Idle)
+        );
+        assert_eq!(
+            background.activity_in(&lines(&[
+                "Finished in 12ms",
+                "Rebuilding app",
+                "src/lib.rs changed"
+            ])),
+            Some(TaskActivity::Busy)
+        );
+    }
+
     #[test]
     fn test_mouse_to_cell_test() {
         let mut rng = thread_rng();
@@ -0,0 +1,414 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use db::{
+    define_connection, query,
+    sqlez::{bindable::Column, statement::Statement},
+    sqlez_macros::sql,
+};
+use gpui::{AsyncWindowContext, Task, WeakEntity};
+use serde::{Deserialize, Serialize};
+use task::{
+    BackgroundTaskPatterns, HideStrategy, ProblemMatcher, RevealStrategy, RevealTarget, Shell,
+    SpawnInTerminal, TaskId,
+};
+use terminal::{TaskStatus, Terminal};
+use time::OffsetDateTime;
+use util::ResultExt as _;
+use workspace::WorkspaceId;
+
+/// How many task runs are kept in the history.
+const MAX_TASK_RUNS: usize = 1000;
+
+/// A single invocation of a task, as stored in the task history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskRun {
+    pub id: i64,
+    pub task_id: TaskId,
+    pub label: String,
+    pub full_label: String,
+    pub command_label: String,
+    /// The resolved command, before it got wrapped into the shell invocation.
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+    pub shell: Shell,
+    /// `None` if the task has not finished yet, was cancelled or got killed by a signal.
+    pub exit_code: Option<i32>,
+    pub started_at: OffsetDateTime,
+    /// `None` while the task is running, or if Zed was closed before it finished.
+    pub duration: Option<Duration>,
+    pub options: TaskRunOptions,
+}
+
+/// How the task was shown in the terminal and how its output was processed, to rerun it the same way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskRunOptions {
+    pub use_new_terminal: bool,
+    pub allow_concurrent_runs: bool,
+    pub reveal: RevealStrategy,
+    pub reveal_target: RevealTarget,
+    pub hide: HideStrategy,
+    pub show_summary: bool,
+    pub show_command: bool,
+    pub problem_matchers: Vec<ProblemMatcher>,
+    pub background: Option<BackgroundTaskPatterns>,
+}
+
+/// The options of the runs recorded before the options were stored.
+impl Default for TaskRunOptions {
+    fn default() -> Self {
+        Self {
+            use_new_terminal: false,
+            allow_concurrent_runs: false,
+            reveal: RevealStrategy::default(),
+            reveal_target: RevealTarget::default(),
+            hide: HideStrategy::default(),
+            show_summary: true,
+            show_command: true,
+            problem_matchers: Vec::new(),
+            background: None,
+        }
+    }
+}
+
+impl TaskRunOptions {
+    fn from_spawn_in_terminal(task: &SpawnInTerminal) -> Self {
+        Self {
+            use_new_terminal: task.use_new_terminal,
+            allow_concurrent_runs: task.allow_concurrent_runs,
+            reveal: task.reveal,
+            reveal_target: task.reveal_target,
+            hide: task.hide,
+            show_summary: task.show_summary,
+            show_command: task.show_command,
+            problem_matchers: task.problem_matchers.clone(),
+            background: task.background.clone(),
+        }
+    }
+}
+
+impl TaskRun {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// Recreates the task as it was spawned in the terminal.
+    pub fn to_spawn_in_terminal(&self) -> SpawnInTerminal {
+        let options = self.options.clone();
+        SpawnInTerminal {
+            id: self.task_id.clone(),
+            full_label: self.full_label.clone(),
+            label: self.label.clone(),
+            command: self.command.clone(),
+            args: self.args.clone(),
+            command_label: self.command_label.clone(),
+            cwd: self.cwd.clone(),
+            env: self.env.clone(),
+            use_new_terminal: options.use_new_terminal,
+            allow_concurrent_runs: options.allow_concurrent_runs,
+            reveal: options.reveal,
+            reveal_target: options.reveal_target,
+            hide: options.hide,
+            shell: self.shell.clone(),
+            show_summary: options.show_summary,
+            show_command: options.show_command,
+            show_rerun: true,
+            problem_matchers: options.problem_matchers,
+            background: options.background,
+        }
+    }
+
+    /// Same as [`Self::to_spawn_in_terminal`], but runs a different command line in the same environment.
+    pub fn to_spawn_in_terminal_with_command(&self, command: String) -> SpawnInTerminal {
+        SpawnInTerminal {
+            id: TaskId(format!("{}_edited", self.task_id.0)),
+            full_label: command.clone(),
+            label: command.clone(),
+            command_label: command.clone(),
+            command,
+            args: Vec::new(),
+            ..self.to_spawn_in_terminal()
+        }
+    }
+}
+
+impl Column for TaskRun {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (id, next_index): (i64, i32) = Column::column(statement, start_index)?;
+        let (task_id, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (label, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (full_label, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (command_label, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (command, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (args, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (cwd, next_index): (Option<String>, i32) = Column::column(statement, next_index)?;
+        let (env, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (shell, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (exit_code, next_index): (Option<i32>, i32) = Column::column(statement, next_index)?;
+        let (started_at, next_index): (i64, i32) = Column::column(statement, next_index)?;
+        let (duration_ms, next_index): (Option<i64>, i32) = Column::column(statement, next_index)?;
+        let (options, next_index): (Option<String>, i32) = Column::column(statement, next_index)?;
+
+        let run = Self {
+            id,
+            task_id: TaskId(task_id),
+            label,
+            full_label,
+            command_label,
+            command,
+            args: serde_json::from_str(&args).context("deserializing task run args")?,
+            cwd: cwd.map(PathBuf::from),
+            env: serde_json::from_str(&env).context("deserializing task run env")?,
+            shell: serde_json::from_str(&shell).context("deserializing task run shell")?,
+            exit_code,
+            started_at: OffsetDateTime::from_unix_timestamp(started_at)?,
+            duration: duration_ms.map(|duration_ms| Duration::from_millis(duration_ms as u64)),
+            options: options
+                .map(|options| serde_json::from_str(&options))
+                .transpose()
+                .context("deserializing task run options")?
+                .unwrap_or_default(),
+        };
+        Ok((run, next_index))
+    }
+}
+
+define_connection!(pub static ref TASK_HISTORY: TaskHistoryDb<()> =
+    &[sql!(
+        CREATE TABLE IF NOT EXISTS task_runs(
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            label TEXT NOT NULL,
+            full_label TEXT NOT NULL,
+            command_label TEXT NOT NULL,
+            command TEXT NOT NULL,
+            args TEXT NOT NULL,
+            cwd TEXT,
+            env TEXT NOT NULL,
+            shell TEXT NOT NULL,
+            exit_code INTEGER,
+            started_at INTEGER DEFAULT (unixepoch()) NOT NULL,
+            duration_ms INTEGER
+        ) STRICT;
+    ),
+    // Runs are listed per workspace, `NULL` for the ones without a database id.
+    sql!(
+        ALTER TABLE task_runs ADD COLUMN workspace_id INTEGER;
+    ),
+    // `NULL` for the runs recorded before the options were stored.
+    sql!(
+        ALTER TABLE task_runs ADD COLUMN options TEXT;
+    )];
+);
+
+impl TaskHistoryDb {
+    /// Records the start of a task in the given workspace, returning the id of its history entry.
+    pub async fn record_task_start(
+        &self,
+        task: &SpawnInTerminal,
+        workspace_id: Option<WorkspaceId>,
+    ) -> Result<i64> {
+        self.insert_task_run(
+            workspace_id,
+            task.id.0.clone(),
+            task.label.clone(),
+            task.full_label.clone(),
+            task.command_label.clone(),
+            task.command.clone(),
+            serde_json::to_string(&task.args)?,
+            task.cwd
+                .as_ref()
+                .map(|cwd| cwd.to_string_lossy().into_owned()),
+            serde_json::to_string(&task.env)?,
+            serde_json::to_string(&task.shell)?,
+            serde_json::to_string(&TaskRunOptions::from_spawn_in_terminal(task))?,
+        )
+        .await?
+        .context("inserting task run")
+    }
+
+    pub async fn record_task_finish(
+        &self,
+        run_id: i64,
+        exit_code: Option<i32>,
+        duration: Duration,
+    ) -> Result<()> {
+        self.update_task_run(
+            exit_code,
+            duration.as_millis() as i64,
+            run_id,
+            MAX_TASK_RUNS as i64,
+        )
+        .await
+    }
+
+    query! {
+        async fn insert_task_run(
+            workspace_id: Option<WorkspaceId>,
+            task_id: String,
+            label: String,
+            full_label: String,
+            command_label: String,
+            command: String,
+            args: String,
+            cwd: Option<String>,
+            env: String,
+            shell: String,
+            options: String
+        ) -> Result<Option<i64>> {
+            INSERT INTO task_runs
+                (workspace_id, task_id, label, full_label, command_label, command, args, cwd, env, shell, options)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            RETURNING id
+        }
+    }
+
+    query! {
+        async fn update_task_run(exit_code: Option<i32>, duration_ms: i64, run_id: i64, max_runs: i64) -> Result<()> {
+            UPDATE task_runs SET exit_code = ?1, duration_ms = ?2 WHERE id = ?3;
+            DELETE FROM task_runs WHERE id NOT IN (SELECT id FROM task_runs ORDER BY id DESC LIMIT ?4);
+        }
+    }
+
+    query! {
+        pub fn recent_task_runs(workspace_id: Option<WorkspaceId>) -> Result<Vec<TaskRun>> {
+            SELECT
+                id, task_id, label, full_label, command_label, command, args, cwd, env, shell,
+                exit_code, started_at, duration_ms, options
+            FROM task_runs
+            WHERE workspace_id IS ?1
+            ORDER BY id DESC
+        }
+    }
+}
+
+/// Records the task run in the history of the workspace, and updates the entry once the task's process exits.
+pub(crate) fn record_task_run(
+    task: SpawnInTerminal,
+    workspace_id: Option<WorkspaceId>,
+    terminal: WeakEntity<Terminal>,
+    task_completed: Task<Option<std::process::ExitStatus>>,
+    cx: &AsyncWindowContext,
+) {
+    let started_at = Instant::now();
+    cx.spawn(async move |cx| {
+        let Some(run_id) = TASK_HISTORY
+            .record_task_start(&task, workspace_id)
+            .await
+            .log_err()
+        else {
+            return;
+        };
+        task_completed.await;
+        let exit_code = terminal
+            .read_with(cx, |terminal, _| match terminal.task()?.status {
+                TaskStatus::Completed { exit_code, .. } => Some(exit_code),
+                TaskStatus::Running | TaskStatus::Unknown => None,
+            })
+            .ok()
+            .flatten();
+        TASK_HISTORY
+            .record_task_finish(run_id, exit_code, started_at.elapsed())
+            .await
+            .log_err();
+    })
+    .detach();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use collections::HashMap;
+    use task::{
+        BackgroundTaskPatterns, HideStrategy, RevealStrategy, RevealTarget, Shell, SpawnInTerminal,
+        TaskId,
+    };
+    use workspace::WorkspaceId;
+
+    use super::TaskHistoryDb;
+
+    fn spawn_in_terminal(label: &str) -> SpawnInTerminal {
+        SpawnInTerminal {
+            id: TaskId(format!("test_{label}")),
+            full_label: label.to_string(),
+            label: label.to_string(),
+            command: "cargo".to_string(),
+            args: vec!["test".to_string(), "--workspace".to_string()],
+            command_label: "cargo test --workspace".to_string(),
+            cwd: Some("/project".into()),
+            env: HashMap::from_iter([("RUST_LOG".to_string(), "info".to_string())]),
+            use_new_terminal: false,
+            allow_concurrent_runs: true,
+            reveal: RevealStrategy::Never,
+            reveal_target: RevealTarget::Center,
+            hide: HideStrategy::OnSuccess,
+            shell: Shell::System,
+            show_summary: true,
+            show_command: true,
+            show_rerun: true,
+            problem_matchers: Vec::new(),
+            background: Some(BackgroundTaskPatterns {
+                begins_pattern: "Compiling".to_string(),
+                ends_pattern: "Finished".to_string(),
+            }),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_records_task_runs() {
+        let db = TaskHistoryDb(db::open_test_db("test_records_task_runs").await);
+        let workspace_id = Some(WorkspaceId::default());
+        assert!(db.recent_task_runs(workspace_id).unwrap().is_empty());
+
+        let first_task = spawn_in_terminal("first");
+        let first_run = db
+            .record_task_start(&first_task, workspace_id)
+            .await
+            .unwrap();
+        db.record_task_finish(first_run, Some(101), Duration::from_millis(1500))
+            .await
+            .unwrap();
+        let second_task = spawn_in_terminal("second");
+        db.record_task_start(&second_task, workspace_id)
+            .await
+            .unwrap();
+        db.record_task_start(&spawn_in_terminal("elsewhere"), None)
+            .await
+            .unwrap();
+
+        let runs = db.recent_task_runs(workspace_id).unwrap();
+        assert_eq!(runs.len(), 2);
+
+        assert_eq!(runs[0].label, "second");
+        assert_eq!(runs[0].exit_code, None);
+        assert_eq!(runs[0].duration, None);
+
+        assert_eq!(runs[1].id, first_run);
+        assert_eq!(runs[1].exit_code, Some(101));
+        assert!(!runs[1].succeeded());
+        assert_eq!(runs[1].duration, Some(Duration::from_millis(1500)));
+        assert_eq!(runs[1].to_spawn_in_terminal(), first_task);
+    }
+
+    #[gpui::test]
+    async fn test_edited_task_run_keeps_environment() {
+        let db = TaskHistoryDb(db::open_test_db("test_edited_task_run_keeps_environment").await);
+        let task = spawn_in_terminal("tests");
+        db.record_task_start(&task, None).await.unwrap();
+
+        let run = db.recent_task_runs(None).unwrap().remove(0);
+        let edited = run.to_spawn_in_terminal_with_command("cargo test -p task".to_string());
+        assert_eq!(edited.command, "cargo test -p task");
+        assert!(edited.args.is_empty());
+        assert_eq!(edited.cwd, task.cwd);
+        assert_eq!(edited.env, task.env);
+        assert_ne!(edited.id, task.id);
+    }
+}
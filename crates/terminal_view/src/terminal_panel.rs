@@ -5,6 +5,7 @@ use crate::{
     persistence::{
         SerializedItems, SerializedTerminalPanel, deserialize_terminal_panel, serialize_pane_group,
     },
+    task_history,
 };
 use breadcrumbs::Breadcrumbs;
 use collections::HashMap;
@@ -1466,8 +1467,24 @@ impl workspace::TerminalProvider for TerminalProvider {
                     terminal_panel.spawn_task(&task, window, cx)
                 })?
                 .await?;
+            let task_completed =
+                terminal.update(cx, |terminal, cx| terminal.wait_for_completed_task(cx))?;
+            let workspace_id = this.read_with(cx, |terminal_panel, cx| {
+                terminal_panel
+                    .workspace
+                    .read_with(cx, |workspace, _| workspace.database_id())
+                    .ok()
+                    .flatten()
+            })?;
+            task_history::record_task_run(
+                task,
+                workspace_id,
+                terminal.downgrade(),
+                task_completed,
+                cx,
+            );
             let Some(exit_code) = terminal
-                .read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))?
+                .update(cx, |terminal, cx| terminal.wait_for_task_ready(cx))?
                 .await
             else {
                 return Err(anyhow!("Task cancelled"));
@@ -1,4 +1,5 @@
 mod persistence;
+pub mod task_history;
 pub mod terminal_element;
 pub mod terminal_panel;
 pub mod terminal_scrollbar;
@@ -16,8 +17,8 @@ use project::{Entry, Metadata, Project, search::SearchQuery, terminals::Terminal
 use schemars::JsonSchema;
 use terminal::{
     Clear, Copy, Event, MaybeNavigationTarget, Paste, ScrollLineDown, ScrollLineUp, ScrollPageDown,
-    ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskState, TaskStatus,
-    Terminal, TerminalBounds, ToggleViMode,
+    ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskActivity, TaskState,
+    TaskStatus, Terminal, TerminalBounds, ToggleViMode,
     alacritty_terminal::{
         index::Point,
         term::{TermMode, search::RegexSearch},
@@ -866,7 +867,7 @@ fn subscribe_for_terminal_events(
                 }
             }
 
-            Event::TitleChanged => {
+            Event::TitleChanged | Event::TaskActivityChanged(_) => {
                 cx.emit(ItemEvent::UpdateTab);
             }
 
@@ -1336,17 +1337,33 @@ impl Item for TerminalView {
 
         let (icon, icon_color, rerun_button) = match terminal.task() {
             Some(terminal_task) => match &terminal_task.status {
-                TaskStatus::Running => (
-                    IconName::Play,
-                    Color::Disabled,
-                    TerminalView::rerun_button(&terminal_task),
-                ),
+                TaskStatus::Running => match terminal_task
+                    .background
+                    .as_ref()
+                    .map(|background| background.activity)
+                {
+                    Some(TaskActivity::Busy) => (
+                        IconName::ArrowCircle,
+                        Color::Accent,
+                        TerminalView::rerun_button(&terminal_task),
+                    ),
+                    Some(TaskActivity::Idle) => (
+                        IconName::Circle,
+                        Color::Success,
+                        TerminalView::rerun_button(&terminal_task),
+                    ),
+                    None => (
+                        IconName::Play,
+                        Color::Disabled,
+                        TerminalView::rerun_button(&terminal_task),
+                    ),
+                },
                 TaskStatus::Unknown => (
                     IconName::Warning,
                     Color::Warning,
                     TerminalView::rerun_button(&terminal_task),
                 ),
-                TaskStatus::Completed { success } => {
+                TaskStatus::Completed { success, .. } => {
                     let rerun_button = TerminalView::rerun_button(&terminal_task);
 
                     if *success {
@@ -1361,6 +1378,16 @@ impl Item for TerminalView {
             }
             None => (IconName::Terminal, Color::Muted, None),
         };
+        let failed_exit_code =
+            terminal
+                .task()
+                .and_then(|terminal_task| match terminal_task.status {
+                    TaskStatus::Completed {
+                        success: false,
+                        exit_code,
+                    } => Some(exit_code),
+                    _ => None,
+                });
 
         h_flex()
             .gap_1()
@@ -1385,6 +1412,19 @@ impl Item for TerminalView {
                     }),
             )
             .child(Label::new(title).color(params.text_color()))
+            .when_some(failed_exit_code, |this, exit_code| {
+                this.child(
+                    div()
+                        .px_1()
+                        .rounded_sm()
+                        .bg(cx.theme().status().error_background)
+                        .child(
+                            Label::new(exit_code.to_string())
+                                .size(LabelSize::XSmall)
+                                .color(Color::Error),
+                        ),
+                )
+            })
             .into_any()
     }
 
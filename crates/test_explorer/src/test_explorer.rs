@@ -0,0 +1,1441 @@
+mod test_explorer_settings;
+mod test_output;
+mod test_tree;
+
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use collections::{HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, scroll::Autoscroll};
+use feature_flags::{Debugger, FeatureFlagAppExt as _};
+use gpui::{
+    Action, AnyElement, App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyContext, ParentElement as _,
+    Pixels, Render, SharedString, Styled as _, Subscription, Task, UniformListScrollHandle,
+    WeakEntity, Window, actions, div, px, uniform_list,
+};
+use language::{
+    Bias, Buffer, BufferEvent, BufferId, BufferSnapshot, File, Language, Location, ParseStatus,
+    Point,
+};
+use menu::{Confirm, SelectNext, SelectPrevious};
+use project::{
+    Fs, Project, ProjectPath, TaskSourceKind, TestStatus, buffer_store::BufferStoreEvent,
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use task::{RunnableTag, TaskTemplate, TaskType, TaskVariables, VariableName};
+use test_explorer_settings::{TestExplorerDockPosition, TestExplorerSettings};
+use test_output::{TestResult, find_test_status, parse_test_output};
+use test_tree::TestNode;
+use ui::{
+    ActiveTheme as _, ButtonCommon as _, Clickable as _, Color, Disableable as _, Icon, IconButton,
+    IconName, IconSize, Label, LabelCommon as _, LabelSize, ListItem, Tab, Toggleable as _,
+    Tooltip, h_flex, prelude::FluentBuilder as _, v_flex,
+};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    test_explorer,
+    [
+        /// Toggles focus on the test explorer.
+        ToggleFocus,
+        /// Discovers the tests of the project again.
+        Refresh,
+        /// Runs every test in the project.
+        RunAll,
+        /// Debugs every test in the project.
+        DebugAll,
+        /// Runs the selected test, or all tests of the selected file or module.
+        RunSelected,
+        /// Debugs the selected test, or all tests of the selected file or module.
+        DebugSelected,
+    ]
+);
+
+const TEST_EXPLORER_KEY: &str = "TestExplorer";
+
+/// How many files whose languages have runnables are opened at most, so that large projects do not get fully opened.
+const MAX_SCANNED_FILES: usize = 5_000;
+
+/// How long to wait after files or buffers change before their tests are discovered again.
+const REFRESH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub fn init(cx: &mut App) {
+    TestExplorerSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<TestExplorer>(window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SerializedTestExplorer {
+    width: Option<Pixels>,
+}
+
+/// A test found by the runnables query of its language.
+#[derive(Debug, Clone)]
+struct DiscoveredTest {
+    name: SharedString,
+    /// Where the runnable starts, i.e. where its gutter indicator is shown.
+    /// Kept as a point rather than an anchor, so that the buffer can be released after discovery.
+    position: Point,
+    /// The range the tests nested in this one are within, e.g. the body of a test module.
+    scope_range: Range<usize>,
+    language: Arc<Language>,
+    tags: Vec<RunnableTag>,
+    extra_captures: HashMap<String, String>,
+}
+
+struct TestFile {
+    project_path: ProjectPath,
+    file: Arc<dyn File>,
+    tests: Vec<TestNode<DiscoveredTest>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ListEntry {
+    File {
+        file_ix: usize,
+    },
+    Test {
+        file_ix: usize,
+        /// Indices of the test and its ancestors in their parents' children, outermost first.
+        node_path: Vec<usize>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunMode {
+    Run,
+    Debug,
+}
+
+/// A test, with the tests nested in it, that gets run by a single task.
+struct RunTarget {
+    project_path: ProjectPath,
+    test: DiscoveredTest,
+    source_kind: TaskSourceKind,
+    template: TaskTemplate,
+    tests: Vec<TargetTest>,
+}
+
+struct TargetTest {
+    position: Point,
+    /// Names of the test and the tests it is nested in, outermost first.
+    name_parts: Vec<String>,
+    scope_range: Range<usize>,
+    is_leaf: bool,
+}
+
+/// A panel that lists the tests discovered across the project, grouped by file and module,
+/// and runs or debugs them, showing the result of their latest run.
+pub struct TestExplorer {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    width: Option<Pixels>,
+    files: Vec<TestFile>,
+    /// The statuses of the tests run from the panel, by the keys of their entries.
+    statuses: HashMap<(ProjectPath, Vec<SharedString>), TestStatus>,
+    /// Whether the last discovery skipped files because of [`MAX_SCANNED_FILES`].
+    truncated: bool,
+    entries: Vec<ListEntry>,
+    collapsed: HashSet<(ProjectPath, Vec<SharedString>)>,
+    selected_entry: Option<usize>,
+    discovered: bool,
+    discovery_task: Option<Task<()>>,
+    /// Files that changed since the last discovery, and whose tests are discovered again after a delay.
+    pending_refresh_paths: HashSet<ProjectPath>,
+    path_refresh_task: Option<Task<()>>,
+    buffer_subscriptions: HashMap<BufferId, Subscription>,
+    run_task: Option<Task<()>>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TestExplorer {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(TEST_EXPLORER_KEY) })
+            .await
+            .context("loading test explorer")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedTestExplorer>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| Self::new(workspace, window, cx));
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let project = workspace.project().clone();
+        let mut subscriptions = Vec::new();
+        subscriptions.push(
+            cx.subscribe_in(&project, window, |this, _, event, window, cx| match event {
+                project::Event::WorktreeAdded(_) | project::Event::WorktreeRemoved(_) => {
+                    if this.discovered {
+                        this.refresh(&Refresh, window, cx);
+                    }
+                }
+                project::Event::WorktreeUpdatedEntries(worktree_id, changes) => {
+                    let paths = changes.iter().map(|(path, _, _)| ProjectPath {
+                        worktree_id: *worktree_id,
+                        path: path.clone(),
+                    });
+                    this.schedule_path_refresh(paths, window, cx);
+                }
+                _ => {}
+            }),
+        );
+        let buffer_store = project.read(cx).buffer_store().clone();
+        subscriptions.push(
+            cx.subscribe_in(
+                &buffer_store,
+                window,
+                |this, _, event, window, cx| match event {
+                    BufferStoreEvent::BufferAdded(buffer) => this.watch_buffer(buffer, window, cx),
+                    BufferStoreEvent::BufferDropped(buffer_id) => {
+                        this.buffer_subscriptions.remove(buffer_id);
+                    }
+                    _ => {}
+                },
+            ),
+        );
+
+        let mut this = Self {
+            workspace: workspace.weak_handle(),
+            fs: project.read(cx).fs().clone(),
+            project,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            width: None,
+            files: Vec::new(),
+            statuses: HashMap::default(),
+            truncated: false,
+            entries: Vec::new(),
+            collapsed: HashSet::default(),
+            selected_entry: None,
+            discovered: false,
+            discovery_task: None,
+            pending_refresh_paths: HashSet::default(),
+            path_refresh_task: None,
+            buffer_subscriptions: HashMap::default(),
+            run_task: None,
+            pending_serialization: Task::ready(None),
+            _subscriptions: subscriptions,
+        };
+        for buffer in this.project.read(cx).opened_buffers(cx) {
+            this.watch_buffer(&buffer, window, cx);
+        }
+        this
+    }
+
+    /// Discovers the tests of a buffer again when it is edited, without keeping the buffer alive.
+    fn watch_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let subscription = cx.subscribe_in(buffer, window, |this, buffer, event, window, cx| {
+            if let BufferEvent::Edited = event {
+                if let Some(file) = buffer.read(cx).file() {
+                    let project_path = ProjectPath {
+                        worktree_id: file.worktree_id(cx),
+                        path: file.path().clone(),
+                    };
+                    this.schedule_path_refresh([project_path], window, cx);
+                }
+            }
+        });
+        self.buffer_subscriptions
+            .insert(buffer.read(cx).remote_id(), subscription);
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        TEST_EXPLORER_KEY.into(),
+                        serde_json::to_string(&SerializedTestExplorer { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn refresh(&mut self, _: &Refresh, window: &mut Window, cx: &mut Context<Self>) {
+        self.discovered = true;
+        self.pending_refresh_paths.clear();
+        self.path_refresh_task = None;
+        let project = self.project.clone();
+        let project_paths = project
+            .read(cx)
+            .visible_worktrees(cx)
+            .flat_map(|worktree| {
+                let worktree = worktree.read(cx);
+                let worktree_id = worktree.id();
+                worktree
+                    .files(false, 0)
+                    .map(|entry| ProjectPath {
+                        worktree_id,
+                        path: entry.path.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        self.discovery_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let (files, truncated) = discover_tests(project, project_paths, cx)
+                .await
+                .log_err()
+                .unwrap_or_default();
+            this.update(cx, |this, cx| {
+                this.files = files;
+                this.truncated = truncated;
+                this.discovery_task = None;
+                this.update_entries(cx);
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// Discovers the tests of the given files again, once they stop changing for a while.
+    fn schedule_path_refresh(
+        &mut self,
+        project_paths: impl IntoIterator<Item = ProjectPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.discovered {
+            return;
+        }
+        self.pending_refresh_paths.extend(project_paths);
+        let project = self.project.clone();
+        self.path_refresh_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(REFRESH_DEBOUNCE).await;
+            // The paths stay pending until they are refreshed, as a newer change cancels this task.
+            let Some((changed_paths, existing_paths)) = this
+                .read_with(cx, |this, cx| {
+                    let changed_paths = this.pending_refresh_paths.clone();
+                    let project = this.project.read(cx);
+                    let existing_paths = changed_paths
+                        .iter()
+                        .filter(|project_path| {
+                            project
+                                .entry_for_path(project_path, cx)
+                                .is_some_and(|entry| entry.is_file() && !entry.is_ignored)
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (changed_paths, existing_paths)
+                })
+                .ok()
+            else {
+                return;
+            };
+            let (files, truncated) = discover_tests(project, existing_paths, cx)
+                .await
+                .log_err()
+                .unwrap_or_default();
+            this.update(cx, |this, cx| {
+                this.files
+                    .retain(|file| !changed_paths.contains(&file.project_path));
+                this.files.extend(files);
+                this.files
+                    .sort_by(|a, b| a.project_path.cmp(&b.project_path));
+                this.truncated |= truncated;
+                this.pending_refresh_paths
+                    .retain(|project_path| !changed_paths.contains(project_path));
+                this.path_refresh_task = None;
+                this.update_entries(cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        let selected_entry = self
+            .selected_entry
+            .and_then(|ix| self.entries.get(ix).cloned());
+        self.entries.clear();
+        for (file_ix, file) in self.files.iter().enumerate() {
+            self.entries.push(ListEntry::File { file_ix });
+            if !self
+                .collapsed
+                .contains(&(file.project_path.clone(), Vec::new()))
+            {
+                push_test_entries(
+                    &mut self.entries,
+                    &self.collapsed,
+                    file,
+                    file_ix,
+                    &file.tests,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                );
+            }
+        }
+        self.selected_entry = selected_entry
+            .and_then(|selected_entry| self.entries.iter().position(|e| e == &selected_entry));
+        cx.notify();
+    }
+
+    /// Identifies an entry across discoveries, by its file and the names of its test and the tests it is nested in.
+    fn entry_key(&self, entry: &ListEntry) -> Option<(ProjectPath, Vec<SharedString>)> {
+        match entry {
+            ListEntry::File { file_ix } => {
+                Some((self.files.get(*file_ix)?.project_path.clone(), Vec::new()))
+            }
+            ListEntry::Test { file_ix, node_path } => {
+                let file = self.files.get(*file_ix)?;
+                let mut names = Vec::new();
+                let mut nodes = &file.tests;
+                for ix in node_path {
+                    let node = nodes.get(*ix)?;
+                    names.push(node.test.name.clone());
+                    nodes = &node.children;
+                }
+                Some((file.project_path.clone(), names))
+            }
+        }
+    }
+
+    fn toggle_expanded(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(key) = self.entries.get(ix).and_then(|entry| self.entry_key(entry)) else {
+            return;
+        };
+        if !self.collapsed.remove(&key) {
+            self.collapsed.insert(key);
+        }
+        self.update_entries(cx);
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_entry
+            .map_or(0, |ix| (ix + 1).min(self.entries.len() - 1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self.selected_entry.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, gpui::ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_entry(ix, window, cx);
+        }
+    }
+
+    fn open_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let (file_ix, point) = match entry {
+            ListEntry::File { file_ix } => (*file_ix, None),
+            ListEntry::Test { file_ix, node_path } => (
+                *file_ix,
+                self.node(*file_ix, node_path)
+                    .map(|node| node.test.position),
+            ),
+        };
+        let Some(file) = self.files.get(file_ix) else {
+            return;
+        };
+        let project_path = file.project_path.clone();
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let open_task = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(project_path, None, true, window, cx)
+        });
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open_task.await?;
+            if let (Some(point), Some(editor)) = (point, item.downcast::<Editor>()) {
+                editor.update_in(cx, |editor, window, cx| {
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([point..point])
+                    });
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn node(&self, file_ix: usize, node_path: &[usize]) -> Option<&TestNode<DiscoveredTest>> {
+        let mut nodes = &self.files.get(file_ix)?.tests;
+        let mut node = None;
+        for ix in node_path {
+            let current = nodes.get(*ix)?;
+            nodes = &current.children;
+            node = Some(current);
+        }
+        node
+    }
+
+    fn run_all(&mut self, _: &RunAll, window: &mut Window, cx: &mut Context<Self>) {
+        let targets = (0..self.files.len())
+            .flat_map(|file_ix| self.entry_targets(&ListEntry::File { file_ix }, RunMode::Run, cx))
+            .collect();
+        self.run_targets(targets, window, cx);
+    }
+
+    fn debug_all(&mut self, _: &DebugAll, window: &mut Window, cx: &mut Context<Self>) {
+        let targets = (0..self.files.len())
+            .flat_map(|file_ix| {
+                self.entry_targets(&ListEntry::File { file_ix }, RunMode::Debug, cx)
+            })
+            .collect();
+        self.debug_targets(targets, window, cx);
+    }
+
+    fn run_selected(&mut self, _: &RunSelected, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.run_entry(ix, RunMode::Run, window, cx);
+        }
+    }
+
+    fn debug_selected(&mut self, _: &DebugSelected, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.run_entry(ix, RunMode::Debug, window, cx);
+        }
+    }
+
+    fn run_entry(&mut self, ix: usize, mode: RunMode, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let targets = self.entry_targets(entry, mode, cx);
+        match mode {
+            RunMode::Run => self.run_targets(targets, window, cx),
+            RunMode::Debug => self.debug_targets(targets, window, cx),
+        }
+    }
+
+    /// The tasks to run for an entry: the entry's own task if its language has one for the mode,
+    /// otherwise the tasks of the tests nested in it.
+    fn entry_targets(&self, entry: &ListEntry, mode: RunMode, cx: &App) -> Vec<RunTarget> {
+        let mut targets = Vec::new();
+        match entry {
+            ListEntry::File { file_ix } => {
+                if let Some(file) = self.files.get(*file_ix) {
+                    for node in &file.tests {
+                        self.collect_targets(file, node, &[], mode, &mut targets, cx);
+                    }
+                }
+            }
+            ListEntry::Test { file_ix, node_path } => {
+                let Some(file) = self.files.get(*file_ix) else {
+                    return targets;
+                };
+                let mut ancestors = Vec::new();
+                let mut nodes = &file.tests;
+                for (depth, ix) in node_path.iter().enumerate() {
+                    let Some(node) = nodes.get(*ix) else {
+                        return targets;
+                    };
+                    if depth + 1 == node_path.len() {
+                        self.collect_targets(file, node, &ancestors, mode, &mut targets, cx);
+                    } else {
+                        ancestors.push(node.test.name.to_string());
+                        nodes = &node.children;
+                    }
+                }
+            }
+        }
+        targets
+    }
+
+    fn collect_targets(
+        &self,
+        file: &TestFile,
+        node: &TestNode<DiscoveredTest>,
+        ancestors: &[String],
+        mode: RunMode,
+        targets: &mut Vec<RunTarget>,
+        cx: &App,
+    ) {
+        if let Some((source_kind, template)) = self.test_template(file, &node.test, mode, cx) {
+            let tests = node
+                .with_descendants()
+                .into_iter()
+                .map(|(chain, descendant)| TargetTest {
+                    position: descendant.test.position,
+                    name_parts: ancestors
+                        .iter()
+                        .cloned()
+                        .chain(chain.iter().map(|test| test.name.to_string()))
+                        .collect(),
+                    scope_range: descendant.test.scope_range.clone(),
+                    is_leaf: descendant.children.is_empty(),
+                })
+                .collect();
+            targets.push(RunTarget {
+                project_path: file.project_path.clone(),
+                test: node.test.clone(),
+                source_kind,
+                template,
+                tests,
+            });
+        } else {
+            let mut ancestors = ancestors.to_vec();
+            ancestors.push(node.test.name.to_string());
+            for child in &node.children {
+                self.collect_targets(file, child, &ancestors, mode, targets, cx);
+            }
+        }
+    }
+
+    /// The first task template tagged for the test, among the ones of the given mode.
+    fn test_template(
+        &self,
+        file: &TestFile,
+        test: &DiscoveredTest,
+        mode: RunMode,
+        cx: &App,
+    ) -> Option<(TaskSourceKind, TaskTemplate)> {
+        let inventory = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()?;
+        inventory
+            .read(cx)
+            .list_tagged_tasks(
+                Some(file.file.clone()),
+                test.language.clone(),
+                Some(file.project_path.worktree_id),
+                test.tags.iter().cloned(),
+                cx,
+            )
+            .into_iter()
+            .find(|(_, template)| match mode {
+                RunMode::Run => matches!(template.task_type, TaskType::Script),
+                RunMode::Debug => matches!(template.task_type, TaskType::Debug(_)),
+            })
+    }
+
+    /// Runs the targets one after another, updating the statuses of their tests as they finish.
+    fn run_targets(
+        &mut self,
+        targets: Vec<RunTarget>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if targets.is_empty() {
+            return;
+        }
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        self.run_task = Some(cx.spawn_in(window, async move |this, cx| {
+            for target in targets {
+                run_target(&this, &project, &workspace, target, cx)
+                    .await
+                    .log_err();
+            }
+            this.update(cx, |this, cx| {
+                this.run_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn debug_targets(
+        &mut self,
+        targets: Vec<RunTarget>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            for target in targets {
+                let buffer = open_target_buffer(&project, &target, cx).await?;
+                let task_context = target_task_context(&project, &target, buffer, cx).await?;
+                let resolved_task = target
+                    .template
+                    .resolve_task(&target.source_kind.to_id_base(), &task_context)
+                    .with_context(|| format!("resolving task \"{}\"", target.template.label))?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    workspace.schedule_debug_task(target.source_kind, resolved_task, window, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn entry_status(&self, entry: &ListEntry) -> Option<TestStatus> {
+        match entry {
+            ListEntry::File { file_ix } => {
+                let file = self.files.get(*file_ix)?;
+                combined_status(file.tests.iter().map(|node| {
+                    let key = (file.project_path.clone(), vec![node.test.name.clone()]);
+                    self.statuses.get(&key).copied()
+                }))
+            }
+            ListEntry::Test { .. } => self.statuses.get(&self.entry_key(entry)?).copied(),
+        }
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let entry = &self.entries[ix];
+        let (label, depth, has_children) = match entry {
+            ListEntry::File { file_ix } => {
+                let file = &self.files[*file_ix];
+                (
+                    SharedString::from(file.project_path.path.to_string_lossy().into_owned()),
+                    0,
+                    !file.tests.is_empty(),
+                )
+            }
+            ListEntry::Test { file_ix, node_path } => match self.node(*file_ix, node_path) {
+                Some(node) => (
+                    node.test.name.clone(),
+                    node_path.len(),
+                    !node.children.is_empty(),
+                ),
+                None => return div().into_any_element(),
+            },
+        };
+        let expanded = self
+            .entry_key(entry)
+            .is_some_and(|key| !self.collapsed.contains(&key));
+        let status_icon = match self.entry_status(entry) {
+            None => Icon::new(IconName::Circle).color(Color::Muted),
+            Some(TestStatus::Running) => Icon::new(IconName::ArrowCircle).color(Color::Accent),
+            Some(TestStatus::Passed) => Icon::new(IconName::Check).color(Color::Success),
+            Some(TestStatus::Failed) => Icon::new(IconName::XCircle).color(Color::Error),
+        };
+        let debugger_enabled = cx.has_flag::<Debugger>();
+
+        ListItem::new(ix)
+            .indent_level(depth)
+            .indent_step_size(px(12.))
+            .toggle(has_children.then_some(expanded))
+            .on_toggle(cx.listener(move |this, _, _, cx| this.toggle_expanded(ix, cx)))
+            .toggle_state(self.selected_entry == Some(ix))
+            .start_slot(status_icon.size(IconSize::Small))
+            .child(
+                Label::new(label)
+                    .single_line()
+                    .when(depth == 0, |label| label.color(Color::Muted)),
+            )
+            .end_hover_slot(
+                h_flex()
+                    .child(
+                        IconButton::new(("run-test", ix), IconName::Play)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Run"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.run_entry(ix, RunMode::Run, window, cx)
+                            })),
+                    )
+                    .when(debugger_enabled, |buttons| {
+                        buttons.child(
+                            IconButton::new(("debug-test", ix), IconName::Debug)
+                                .icon_size(IconSize::XSmall)
+                                .tooltip(Tooltip::text("Debug"))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.run_entry(ix, RunMode::Debug, window, cx)
+                                })),
+                        )
+                    }),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_entry(ix, cx);
+                this.open_entry(ix, window, cx);
+            }))
+            .into_any_element()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let test_count = self
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry, ListEntry::Test { .. }))
+            .count();
+        let is_running = self.run_task.is_some();
+        let focus_handle = self.focus_handle.clone();
+
+        h_flex()
+            .justify_between()
+            .px_2()
+            .h(Tab::container_height(cx))
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(Label::new("Tests"))
+                    .when(test_count > 0, |this| {
+                        this.child(
+                            Label::new(test_count.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new("refresh-tests", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.discovery_task.is_some())
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Refresh Tests",
+                                        &Refresh,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.refresh(&Refresh, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("run-all-tests", IconName::Play)
+                            .icon_size(IconSize::Small)
+                            .disabled(is_running || self.files.is_empty())
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Run All Tests",
+                                        &RunAll,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            })
+                            .on_click(
+                                cx.listener(|this, _, window, cx| {
+                                    this.run_all(&RunAll, window, cx)
+                                }),
+                            ),
+                    )
+                    .when(cx.has_flag::<Debugger>(), |this| {
+                        this.child(
+                            IconButton::new("debug-all-tests", IconName::Debug)
+                                .icon_size(IconSize::Small)
+                                .disabled(self.files.is_empty())
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        "Debug All Tests",
+                                        &DebugAll,
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                })
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.debug_all(&DebugAll, window, cx)
+                                })),
+                        )
+                    }),
+            )
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("TestExplorer");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+}
+
+fn push_test_entries(
+    entries: &mut Vec<ListEntry>,
+    collapsed: &HashSet<(ProjectPath, Vec<SharedString>)>,
+    file: &TestFile,
+    file_ix: usize,
+    nodes: &[TestNode<DiscoveredTest>],
+    node_path: &mut Vec<usize>,
+    names: &mut Vec<SharedString>,
+) {
+    for (ix, node) in nodes.iter().enumerate() {
+        node_path.push(ix);
+        names.push(node.test.name.clone());
+        entries.push(ListEntry::Test {
+            file_ix,
+            node_path: node_path.clone(),
+        });
+        if !collapsed.contains(&(file.project_path.clone(), names.clone())) {
+            push_test_entries(
+                entries,
+                collapsed,
+                file,
+                file_ix,
+                &node.children,
+                node_path,
+                names,
+            );
+        }
+        names.pop();
+        node_path.pop();
+    }
+}
+
+/// Opens the given files whose languages have runnables, and collects the tests their runnables mark.
+/// Also returns whether some of these files were skipped because of [`MAX_SCANNED_FILES`].
+async fn discover_tests(
+    project: Entity<Project>,
+    project_paths: Vec<ProjectPath>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<(Vec<TestFile>, bool)> {
+    let languages = project.read_with(cx, |project, _| project.languages().clone())?;
+
+    let mut candidate_paths = Vec::new();
+    let mut truncated = false;
+    for project_path in project_paths {
+        let Ok(language) = languages.language_for_file_path(&project_path.path).await else {
+            continue;
+        };
+        if !language.has_runnables() {
+            continue;
+        }
+        if candidate_paths.len() == MAX_SCANNED_FILES {
+            truncated = true;
+            break;
+        }
+        candidate_paths.push(project_path);
+    }
+
+    let mut files = Vec::new();
+    for project_path in candidate_paths {
+        let Some(buffer) = project
+            .update(cx, |project, cx| {
+                project.open_buffer(project_path.clone(), cx)
+            })?
+            .await
+            .log_err()
+        else {
+            continue;
+        };
+
+        // Wait until the buffer has been fully parsed, so that its runnables are known.
+        let mut parse_status = buffer.read_with(cx, |buffer, _| buffer.parse_status())?;
+        while *parse_status.borrow() != ParseStatus::Idle {
+            parse_status.changed().await?;
+        }
+        let (snapshot, file) =
+            buffer.read_with(cx, |buffer, _| (buffer.snapshot(), buffer.file().cloned()))?;
+        let Some(file) = file else {
+            continue;
+        };
+        let tests = cx
+            .background_spawn(async move { file_tests(&snapshot) })
+            .await;
+        if !tests.is_empty() {
+            files.push(TestFile {
+                project_path,
+                file,
+                tests,
+            });
+        }
+    }
+    Ok((files, truncated))
+}
+
+fn file_tests(snapshot: &BufferSnapshot) -> Vec<TestNode<DiscoveredTest>> {
+    let tests = snapshot
+        .runnable_ranges(0..snapshot.len())
+        .filter(|runnable| runnable.runnable.tags.iter().any(is_test_tag))
+        .map(|runnable| {
+            let full_range = runnable.full_range.clone();
+            let scope_range = match snapshot.syntax_ancestor(runnable.run_range.clone()) {
+                Some(node) => {
+                    let node_range = node.byte_range();
+                    full_range.start.min(node_range.start)..full_range.end.max(node_range.end)
+                }
+                None => full_range,
+            };
+            let name = match runnable.extra_captures.get("_subtest_name") {
+                // Subtests, such as Go's `t.Run("name", ...)`, are run by a call, so the name is in a separate capture.
+                Some(subtest_name) => subtest_name.trim_matches('"').to_string(),
+                None => snapshot
+                    .text_for_range(runnable.run_range.clone())
+                    .collect::<String>(),
+            };
+            DiscoveredTest {
+                name: name.into(),
+                position: snapshot.offset_to_point(runnable.run_range.start),
+                scope_range,
+                language: runnable.runnable.language,
+                tags: runnable.runnable.tags.into_vec(),
+                extra_captures: runnable.extra_captures,
+            }
+        })
+        .collect();
+    test_tree::nest_by_range(tests, |test| test.scope_range.clone())
+}
+
+/// Doc tests are skipped, as their runnables are code blocks in comments rather than named tests.
+fn is_test_tag(tag: &RunnableTag) -> bool {
+    tag.0.contains("test") && !tag.0.contains("doc-test")
+}
+
+async fn open_target_buffer(
+    project: &Entity<Project>,
+    target: &RunTarget,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<Entity<Buffer>> {
+    project
+        .update(cx, |project, cx| {
+            project.open_buffer(target.project_path.clone(), cx)
+        })?
+        .await
+}
+
+async fn target_task_context(
+    project: &Entity<Project>,
+    target: &RunTarget,
+    buffer: Entity<Buffer>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<task::TaskContext> {
+    let captured_variables = TaskVariables::from_iter(target.test.extra_captures.iter().map(
+        |(capture_name, value)| {
+            (
+                VariableName::Custom(capture_name.clone().into()),
+                value.clone(),
+            )
+        },
+    ));
+    let position = buffer.read_with(cx, |buffer, _| {
+        buffer.anchor_before(buffer.clip_point(target.test.position, Bias::Left))
+    })?;
+    let location = Location {
+        buffer,
+        range: position..position,
+    };
+    project
+        .update(cx, |project, cx| {
+            project.task_store().update(cx, |task_store, cx| {
+                task_store.task_context_for_location(captured_variables, location, cx)
+            })
+        })?
+        .await
+        .with_context(|| {
+            format!(
+                "building the task context for test \"{}\"",
+                target.test.name
+            )
+        })
+}
+
+/// Runs the task of the target in a terminal, then parses the terminal output for the results of the target's tests.
+async fn run_target(
+    this: &WeakEntity<TestExplorer>,
+    project: &Entity<Project>,
+    workspace: &WeakEntity<Workspace>,
+    target: RunTarget,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<()> {
+    let Some(task_inventory) = project.read_with(cx, |project, cx| {
+        project.task_store().read(cx).task_inventory().cloned()
+    })?
+    else {
+        return Ok(());
+    };
+    let buffer = open_target_buffer(project, &target, cx).await?;
+    let set_statuses = |statuses: Vec<Option<TestStatus>>, cx: &mut AsyncWindowContext| {
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.text_snapshot())?;
+        task_inventory.update(cx, |inventory, cx| {
+            for (test, status) in target.tests.iter().zip(&statuses) {
+                let position =
+                    snapshot.anchor_before(snapshot.clip_point(test.position, Bias::Left));
+                inventory.set_test_status(&snapshot, position, *status);
+            }
+            cx.notify();
+        })?;
+        this.update(cx, |this, cx| {
+            for (test, status) in target.tests.iter().zip(statuses) {
+                let key = (
+                    target.project_path.clone(),
+                    test.name_parts
+                        .iter()
+                        .cloned()
+                        .map(SharedString::from)
+                        .collect(),
+                );
+                match status {
+                    Some(status) => {
+                        this.statuses.insert(key, status);
+                    }
+                    None => {
+                        this.statuses.remove(&key);
+                    }
+                }
+            }
+            cx.notify();
+        })
+    };
+    set_statuses(vec![Some(TestStatus::Running); target.tests.len()], cx)?;
+
+    let exit_status = async {
+        let task_context = target_task_context(project, &target, buffer.clone(), cx).await?;
+        let resolved_task = target
+            .template
+            .resolve_task(&target.source_kind.to_id_base(), &task_context)
+            .with_context(|| format!("resolving task \"{}\"", target.template.label))?;
+        let spawn_in_terminal = resolved_task
+            .resolved
+            .clone()
+            .with_context(|| format!("task \"{}\" has no command", target.template.label))?;
+        task_inventory.update(cx, |inventory, _| {
+            inventory.task_scheduled(target.source_kind.clone(), resolved_task);
+        })?;
+        let exit_status = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.spawn_in_terminal(spawn_in_terminal.clone(), window, cx)
+            })?
+            .await?;
+        let output = project.read_with(cx, |project, cx| {
+            project
+                .local_terminal_handles()
+                .iter()
+                .filter_map(|terminal| terminal.upgrade())
+                .find(|terminal| {
+                    terminal
+                        .read(cx)
+                        .task()
+                        .is_some_and(|task| task.id == spawn_in_terminal.id)
+                })
+                .map(|terminal| terminal.read(cx).all_lines())
+                .unwrap_or_default()
+        })?;
+        anyhow::Ok((exit_status.success(), output))
+    }
+    .await;
+
+    let (succeeded, output) = match &exit_status {
+        Ok((succeeded, output)) => (*succeeded, output.as_slice()),
+        Err(_) => (false, [].as_slice()),
+    };
+    let results = parse_test_output(output);
+    set_statuses(target_statuses(&target.tests, &results, succeeded), cx)?;
+    exit_status.map(|_| ())
+}
+
+/// The statuses of the tests of a target, in the same order. Tests missing from the output
+/// take the status of the whole run when it is the test itself that was run, or when every test passed.
+/// Tests that contain other tests get the combined status of the tests within them.
+fn target_statuses(
+    tests: &[TargetTest],
+    results: &[TestResult],
+    succeeded: bool,
+) -> Vec<Option<TestStatus>> {
+    let run_status = if succeeded {
+        TestStatus::Passed
+    } else {
+        TestStatus::Failed
+    };
+    let leaf_statuses = tests
+        .iter()
+        .map(|test| {
+            if !test.is_leaf {
+                return None;
+            }
+            find_test_status(results, &test.name_parts)
+                .or_else(|| (tests.len() == 1 || succeeded).then_some(run_status))
+        })
+        .collect::<Vec<_>>();
+
+    tests
+        .iter()
+        .zip(&leaf_statuses)
+        .map(|(test, leaf_status)| {
+            if test.is_leaf {
+                return *leaf_status;
+            }
+            combined_status(tests.iter().zip(&leaf_statuses).filter_map(
+                |(other_test, leaf_status)| {
+                    let within_test = other_test.is_leaf
+                        && test.scope_range.start <= other_test.scope_range.start
+                        && other_test.scope_range.end <= test.scope_range.end;
+                    within_test.then_some(*leaf_status)
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Any failure fails the group, any running test makes it running, and it only passes if all of its tests passed.
+fn combined_status(statuses: impl IntoIterator<Item = Option<TestStatus>>) -> Option<TestStatus> {
+    let mut any_status = false;
+    let mut any_running = false;
+    let mut all_passed = true;
+    for status in statuses {
+        any_status = true;
+        match status {
+            Some(TestStatus::Failed) => return Some(TestStatus::Failed),
+            Some(TestStatus::Running) => any_running = true,
+            Some(TestStatus::Passed) => {}
+            None => all_passed = false,
+        }
+    }
+    if any_running {
+        Some(TestStatus::Running)
+    } else if any_status && all_passed {
+        Some(TestStatus::Passed)
+    } else {
+        None
+    }
+}
+
+impl Panel for TestExplorer {
+    fn persistent_name() -> &'static str {
+        "Test Explorer"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match TestExplorerSettings::get_global(cx).dock {
+            TestExplorerDockPosition::Left => DockPosition::Left,
+            TestExplorerDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<TestExplorerSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => TestExplorerDockPosition::Left,
+                    DockPosition::Right => TestExplorerDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| TestExplorerSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        TestExplorerSettings::get_global(cx)
+            .button
+            .then_some(IconName::Microscope)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Test Explorer")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn set_active(&mut self, active: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if active && !self.discovered {
+            self.refresh(&Refresh, window, cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        9
+    }
+}
+
+impl Focusable for TestExplorer {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TestExplorer {}
+
+impl Render for TestExplorer {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let placeholder = if self.discovery_task.is_some() && self.files.is_empty() {
+            Some("Discovering tests…")
+        } else if self.entries.is_empty() {
+            Some("No tests found.")
+        } else {
+            None
+        };
+
+        v_flex()
+            .id("test-explorer")
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::refresh))
+            .on_action(cx.listener(Self::run_all))
+            .on_action(cx.listener(Self::debug_all))
+            .on_action(cx.listener(Self::run_selected))
+            .on_action(cx.listener(Self::debug_selected))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .child(self.render_header(cx))
+            .when(self.truncated, |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .px_2()
+                        .py_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(
+                            Label::new(format!(
+                                "Only the first {MAX_SCANNED_FILES} files that can contain tests were scanned."
+                            ))
+                            .size(LabelSize::Small)
+                            .color(Color::Warning),
+                        ),
+                )
+            })
+            .map(|this| match placeholder {
+                Some(placeholder) => this.child(
+                    v_flex().p_4().child(
+                        Label::new(placeholder)
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    ),
+                ),
+                None => this.child(
+                    uniform_list(
+                        cx.entity(),
+                        "test-explorer-entries",
+                        self.entries.len(),
+                        |this, range, _, cx| range.map(|ix| this.render_entry(ix, cx)).collect(),
+                    )
+                    .size_full()
+                    .track_scroll(self.scroll_handle.clone()),
+                ),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target_test(name_parts: &[&str], scope_range: Range<usize>, is_leaf: bool) -> TargetTest {
+        TargetTest {
+            position: Point::zero(),
+            name_parts: name_parts.iter().map(ToString::to_string).collect(),
+            scope_range,
+            is_leaf,
+        }
+    }
+
+    #[test]
+    fn test_target_statuses() {
+        let tests = [
+            target_test(&["tests"], 0..100, false),
+            target_test(&["tests", "passes"], 10..20, true),
+            target_test(&["tests", "fails"], 30..40, true),
+            target_test(&["tests", "filtered_out"], 50..60, true),
+            target_test(&["tests", "nested"], 70..90, false),
+            target_test(&["tests", "nested", "passes"], 75..85, true),
+        ];
+        let results = [
+            TestResult {
+                name: "tests::passes".to_string(),
+                status: TestStatus::Passed,
+            },
+            TestResult {
+                name: "tests::fails".to_string(),
+                status: TestStatus::Failed,
+            },
+            TestResult {
+                name: "tests::nested::passes".to_string(),
+                status: TestStatus::Passed,
+            },
+        ];
+        assert_eq!(
+            target_statuses(&tests, &results, false),
+            vec![
+                Some(TestStatus::Failed),
+                Some(TestStatus::Passed),
+                Some(TestStatus::Failed),
+                None,
+                Some(TestStatus::Passed),
+                Some(TestStatus::Passed),
+            ]
+        );
+
+        let single_test = [target_test(&["it_works"], 0..10, true)];
+        assert_eq!(
+            target_statuses(&single_test, &[], false),
+            vec![Some(TestStatus::Failed)]
+        );
+        assert_eq!(
+            target_statuses(&single_test, &[], true),
+            vec![Some(TestStatus::Passed)]
+        );
+    }
+}
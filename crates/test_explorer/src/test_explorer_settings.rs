@@ -0,0 +1,47 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TestExplorerDockPosition {
+    Left,
+    Right,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TestExplorerSettings {
+    pub button: bool,
+    pub default_width: Pixels,
+    pub dock: TestExplorerDockPosition,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct TestExplorerSettingsContent {
+    /// Whether to show the test explorer button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customize default width (in pixels) taken by the test explorer
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+    /// The position of the test explorer
+    ///
+    /// Default: right
+    pub dock: Option<TestExplorerDockPosition>,
+}
+
+impl Settings for TestExplorerSettings {
+    const KEY: Option<&'static str> = Some("test_explorer");
+
+    type FileContent = TestExplorerSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
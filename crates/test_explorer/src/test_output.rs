@@ -0,0 +1,239 @@
+use std::sync::LazyLock;
+
+use project::TestStatus;
+use regex::Regex;
+
+/// The outcome of a single test, as reported in the output of a test runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TestResult {
+    pub name: String,
+    pub status: TestStatus,
+}
+
+/// `test tests::it_works ... ok`, as printed by `cargo test`.
+static CARGO_TEST_RESULT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^test (\S+) \.\.\. (ok|FAILED)").unwrap());
+/// `--- FAIL: TestParse/empty_input (0.00s)`, as printed by `go test -v`.
+static GO_TEST_RESULT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*--- (PASS|FAIL): (\S+)").unwrap());
+/// `tests/test_app.py::TestApp::test_start PASSED [ 50%]`, as printed by `pytest -v`,
+/// and `FAILED tests/test_app.py::test_stop - AssertionError`, as printed in its summary.
+static PYTEST_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(\S+::\S+) (PASSED|FAILED|ERROR)|(FAILED|ERROR) (\S+::\S+))").unwrap()
+});
+/// `✓ renders the title (5 ms)` or `✕ renders the title`, as printed by Jest, Vitest and the Node.js test runner.
+static JS_TEST_RESULT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*([✓✔√✕✖×]) (.+?)(?: \(\d+(?:\.\d+)? ?m?s\))?$").unwrap());
+
+/// Extracts the results of individual tests from the output of the common test runners.
+pub(crate) fn parse_test_output(lines: &[String]) -> Vec<TestResult> {
+    lines
+        .iter()
+        .filter_map(|line| parse_test_result(line))
+        .collect()
+}
+
+fn parse_test_result(line: &str) -> Option<TestResult> {
+    let status = |passed: bool| {
+        if passed {
+            TestStatus::Passed
+        } else {
+            TestStatus::Failed
+        }
+    };
+
+    if let Some(captures) = CARGO_TEST_RESULT.captures(line) {
+        return Some(TestResult {
+            name: captures[1].to_string(),
+            status: status(&captures[2] == "ok"),
+        });
+    }
+    if let Some(captures) = GO_TEST_RESULT.captures(line) {
+        return Some(TestResult {
+            name: captures[2].to_string(),
+            status: status(&captures[1] == "PASS"),
+        });
+    }
+    if let Some(captures) = PYTEST_RESULT.captures(line) {
+        let (name, outcome) = match (captures.get(1), captures.get(2)) {
+            (Some(name), Some(outcome)) => (name.as_str(), outcome.as_str()),
+            _ => (&captures[4], &captures[3]),
+        };
+        return Some(TestResult {
+            name: name.to_string(),
+            status: status(outcome == "PASSED"),
+        });
+    }
+    if let Some(captures) = JS_TEST_RESULT.captures(line) {
+        return Some(TestResult {
+            name: captures[2].to_string(),
+            status: status(matches!(&captures[1], "✓" | "✔" | "√")),
+        });
+    }
+    None
+}
+
+/// Finds the status of the test with the given name, outermost part (e.g. the test module) first,
+/// among the parsed results. Test runners report names with different prefixes (crate modules, file paths),
+/// so the reported name only has to end with the parts of the test name it has.
+/// A test reported more than once, e.g. with different parameters, fails if any of its runs failed.
+pub(crate) fn find_test_status(
+    results: &[TestResult],
+    name_parts: &[String],
+) -> Option<TestStatus> {
+    let mut matching_statuses = results
+        .iter()
+        .filter(|result| result_matches(&result.name, name_parts))
+        .map(|result| result.status)
+        .peekable();
+    matching_statuses.peek()?;
+    if matching_statuses.any(|status| status == TestStatus::Failed) {
+        Some(TestStatus::Failed)
+    } else {
+        Some(TestStatus::Passed)
+    }
+}
+
+fn result_matches(result_name: &str, name_parts: &[String]) -> bool {
+    let result_name = result_name.replace("::", "/");
+    let result_parts = result_name.split('/').collect::<Vec<_>>();
+    !name_parts.is_empty()
+        && result_parts
+            .iter()
+            .rev()
+            .zip(name_parts.iter().rev())
+            .all(|(result_part, name_part)| part_matches(result_part, name_part))
+}
+
+fn part_matches(result_part: &str, name_part: &str) -> bool {
+    // Strip parameters of parametrized tests, e.g. `test_add[1-2]`.
+    let result_part = match result_part.find('[') {
+        Some(parameters_start) if result_part.ends_with(']') => &result_part[..parameters_start],
+        _ => result_part,
+    };
+    // Go replaces spaces in subtest names with underscores.
+    result_part == name_part || result_part == name_part.replace(' ', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(output: &str) -> Vec<String> {
+        output.lines().map(ToString::to_string).collect()
+    }
+
+    fn names(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_cargo_output() {
+        let results = parse_test_output(&lines(
+            "running 3 tests\n\
+             test tests::test_add ... ok\n\
+             test tests::nested::test_sub ... FAILED\n\
+             test tests::test_slow ... ignored\n\
+             \n\
+             test result: FAILED. 1 passed; 1 failed; 1 ignored",
+        ));
+        assert_eq!(
+            results,
+            vec![
+                TestResult {
+                    name: "tests::test_add".to_string(),
+                    status: TestStatus::Passed,
+                },
+                TestResult {
+                    name: "tests::nested::test_sub".to_string(),
+                    status: TestStatus::Failed,
+                },
+            ]
+        );
+        assert_eq!(
+            find_test_status(&results, &names(&["tests", "test_add"])),
+            Some(TestStatus::Passed)
+        );
+        assert_eq!(
+            find_test_status(&results, &names(&["nested", "test_sub"])),
+            Some(TestStatus::Failed)
+        );
+        assert_eq!(
+            find_test_status(&results, &names(&["other", "test_add"])),
+            None
+        );
+        assert_eq!(find_test_status(&results, &names(&["test_slow"])), None);
+    }
+
+    #[test]
+    fn test_parse_go_output() {
+        let results = parse_test_output(&lines(
+            "=== RUN   TestParse\n\
+             === RUN   TestParse/empty_input\n\
+             --- FAIL: TestParse (0.00s)\n\
+             \x20   --- FAIL: TestParse/empty_input (0.00s)\n\
+             --- PASS: TestFormat (0.01s)",
+        ));
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            find_test_status(&results, &names(&["TestParse", "empty input"])),
+            Some(TestStatus::Failed)
+        );
+        assert_eq!(
+            find_test_status(&results, &names(&["TestFormat"])),
+            Some(TestStatus::Passed)
+        );
+    }
+
+    #[test]
+    fn test_parse_pytest_output() {
+        let results = parse_test_output(&lines(
+            "tests/test_app.py::TestApp::test_start PASSED                  [ 33%]\n\
+             tests/test_app.py::test_add[1-2] PASSED                        [ 66%]\n\
+             tests/test_app.py::test_add[2-2] FAILED                        [100%]\n\
+             =========================== short test summary info ============================\n\
+             FAILED tests/test_app.py::test_add[2-2] - assert 4 == 5",
+        ));
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            find_test_status(&results, &names(&["TestApp", "test_start"])),
+            Some(TestStatus::Passed)
+        );
+        assert_eq!(
+            find_test_status(&results, &names(&["test_add"])),
+            Some(TestStatus::Failed)
+        );
+    }
+
+    #[test]
+    fn test_parse_js_output() {
+        let results = parse_test_output(&lines(
+            " PASS  src/app.test.ts\n\
+             \x20 App\n\
+             \x20   ✓ renders the title (5 ms)\n\
+             \x20   ✕ handles clicks\n\
+             \x20   ✔ works with node (1.25ms)",
+        ));
+        assert_eq!(
+            results,
+            vec![
+                TestResult {
+                    name: "renders the title".to_string(),
+                    status: TestStatus::Passed,
+                },
+                TestResult {
+                    name: "handles clicks".to_string(),
+                    status: TestStatus::Failed,
+                },
+                TestResult {
+                    name: "works with node".to_string(),
+                    status: TestStatus::Passed,
+                },
+            ]
+        );
+        assert_eq!(
+            find_test_status(&results, &names(&["App", "handles clicks"])),
+            Some(TestStatus::Failed)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use std::ops::Range;
+
+/// A test together with the tests nested in it, e.g. a test module and its test functions.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TestNode<T> {
+    pub test: T,
+    pub children: Vec<TestNode<T>>,
+}
+
+impl<T> TestNode<T> {
+    /// The node itself and all of its descendants, each with the chain of tests leading to it, outermost first.
+    pub fn with_descendants(&self) -> Vec<(Vec<&T>, &Self)> {
+        let mut result = Vec::new();
+        self.collect_descendants(&mut Vec::new(), &mut result);
+        result
+    }
+
+    fn collect_descendants<'a>(
+        &'a self,
+        ancestors: &mut Vec<&'a T>,
+        result: &mut Vec<(Vec<&'a T>, &'a Self)>,
+    ) {
+        ancestors.push(&self.test);
+        result.push((ancestors.clone(), self));
+        for child in &self.children {
+            child.collect_descendants(ancestors, result);
+        }
+        ancestors.pop();
+    }
+}
+
+/// Nests the tests of a file by their ranges: a test is a child of the innermost test whose range contains it.
+pub(crate) fn nest_by_range<T>(
+    mut tests: Vec<T>,
+    range: impl Fn(&T) -> Range<usize>,
+) -> Vec<TestNode<T>> {
+    tests.sort_by_key(|test| {
+        let range = range(test);
+        (range.start, usize::MAX - range.end)
+    });
+
+    let mut roots = Vec::new();
+    let mut stack: Vec<(Range<usize>, TestNode<T>)> = Vec::new();
+    for test in tests {
+        let test_range = range(&test);
+        while let Some((parent_range, _)) = stack.last() {
+            if parent_range.start <= test_range.start && test_range.end <= parent_range.end {
+                break;
+            }
+            let (_, node) = stack.pop().unwrap();
+            push_node(&mut stack, &mut roots, node);
+        }
+        stack.push((
+            test_range,
+            TestNode {
+                test,
+                children: Vec::new(),
+            },
+        ));
+    }
+    while let Some((_, node)) = stack.pop() {
+        push_node(&mut stack, &mut roots, node);
+    }
+    roots
+}
+
+fn push_node<T>(
+    stack: &mut [(Range<usize>, TestNode<T>)],
+    roots: &mut Vec<TestNode<T>>,
+    node: TestNode<T>,
+) {
+    match stack.last_mut() {
+        Some((_, parent)) => parent.children.push(node),
+        None => roots.push(node),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &'static str, children: Vec<TestNode<&'static str>>) -> TestNode<&'static str> {
+        TestNode {
+            test: name,
+            children,
+        }
+    }
+
+    fn strip_ranges(node: TestNode<(&'static str, Range<usize>)>) -> TestNode<&'static str> {
+        TestNode {
+            test: node.test.0,
+            children: node.children.into_iter().map(strip_ranges).collect(),
+        }
+    }
+
+    #[test]
+    fn test_nest_by_range() {
+        let tests = vec![
+            ("second_test", 60..80),
+            ("tests", 10..100),
+            ("first_test", 20..40),
+            ("nested", 45..58),
+            ("nested_test", 50..55),
+            ("other_tests", 120..200),
+            ("other_test", 130..150),
+        ];
+        let nested = nest_by_range(tests, |(_, range)| range.clone())
+            .into_iter()
+            .map(strip_ranges)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nested,
+            vec![
+                node(
+                    "tests",
+                    vec![
+                        node("first_test", Vec::new()),
+                        node("nested", vec![node("nested_test", Vec::new())]),
+                        node("second_test", Vec::new()),
+                    ]
+                ),
+                node("other_tests", vec![node("other_test", Vec::new())]),
+            ]
+        );
+
+        let paths = nested[0]
+            .with_descendants()
+            .into_iter()
+            .map(|(path, _)| path.into_iter().copied().collect::<Vec<_>>().join("::"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "tests",
+                "tests::first_test",
+                "tests::nested",
+                "tests::nested::nested_test",
+                "tests::second_test",
+            ]
+        );
+    }
+}
@@ -1465,6 +1465,8 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matchers: Vec::new(),
+                    background: None,
                 };
                 workspace
                     .spawn_in_terminal(spawn_in_terminal, window, cx)
@@ -13,7 +13,9 @@ use client::DevServerProjectId;
 use db::{define_connection, query, sqlez::connection::Connection, sqlez_macros::sql};
 use gpui::{Axis, Bounds, WindowBounds, WindowId, point, size};
 use itertools::Itertools;
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::debugger::breakpoint_store::{
+    BreakpointState, DataBreakpoint, DataBreakpointAccessType, FunctionBreakpoint, SourceBreakpoint,
+};
 
 use language::{LanguageName, Toolchain};
 use project::WorktreeId;
@@ -541,6 +543,30 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        CREATE TABLE function_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER DEFAULT(0) NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+        CREATE TABLE data_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            data_id TEXT NOT NULL,
+            description TEXT NOT NULL,
+            access_type INTEGER NOT NULL,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER DEFAULT(0) NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
     ];
 }
 
@@ -629,6 +655,8 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             window_id,
         })
     }
@@ -683,6 +711,8 @@ impl WorkspaceDb {
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             display,
             docks,
             session_id: None,
@@ -736,6 +766,83 @@ impl WorkspaceDb {
         }
     }
 
+    fn function_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<FunctionBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper<'static>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT name, condition, hit_condition, state
+                FROM function_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(name, condition, hit_condition, state)| FunctionBreakpoint {
+                        name: name.into(),
+                        condition: condition.map(Arc::from),
+                        hit_condition: hit_condition.map(Arc::from),
+                        state: state.0.into_owned(),
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Function breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
+    fn data_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<DataBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                String,
+                i32,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper<'static>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT data_id, description, access_type, condition, hit_condition, state
+                FROM data_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .filter_map(
+                    |(data_id, description, access_type, condition, hit_condition, state)| {
+                        Some(DataBreakpoint {
+                            data_id: data_id.into(),
+                            description: description.into(),
+                            access_type: DataBreakpointAccessType::from_int(access_type)?,
+                            condition: condition.map(Arc::from),
+                            hit_condition: hit_condition.map(Arc::from),
+                            state: state.0.into_owned(),
+                            session_id: None,
+                        })
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Data breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -777,6 +884,40 @@ impl WorkspaceDb {
 
                 }
 
+                conn.exec_bound(sql!(
+                    DELETE FROM function_breakpoints WHERE workspace_id = ?1;
+                    DELETE FROM data_breakpoints WHERE workspace_id = ?1;))?(workspace.id)
+                    .context("Clearing old function and data breakpoints")?;
+
+                for bp in workspace.function_breakpoints {
+                    conn.exec_bound(sql!(
+                        INSERT INTO function_breakpoints (workspace_id, name, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5);))?
+                    ((
+                        workspace.id,
+                        bp.name,
+                        bp.condition,
+                        bp.hit_condition,
+                        BreakpointStateWrapper::from(bp.state),
+                    ))
+                    .log_err();
+                }
+
+                for bp in workspace.data_breakpoints {
+                    conn.exec_bound(sql!(
+                        INSERT INTO data_breakpoints (workspace_id, data_id, description, access_type, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+                    ((
+                        workspace.id,
+                        bp.data_id,
+                        bp.description,
+                        bp.access_type.to_int(),
+                        bp.condition,
+                        bp.hit_condition,
+                        BreakpointStateWrapper::from(bp.state),
+                    ))
+                    .log_err();
+                }
 
                 match workspace.location {
                     SerializedWorkspaceLocation::Local(local_paths, local_paths_order) => {
@@ -1481,6 +1622,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -1609,6 +1752,71 @@ mod tests {
         assert_eq!(loaded_breakpoints[4].path, Arc::from(path));
     }
 
+    #[gpui::test]
+    async fn test_function_and_data_breakpoints() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_function_and_data_breakpoints").await);
+        let id = db.next_id().await.unwrap();
+
+        let function_breakpoints = vec![
+            FunctionBreakpoint {
+                name: "main".into(),
+                condition: None,
+                hit_condition: None,
+                state: BreakpointState::Enabled,
+            },
+            FunctionBreakpoint {
+                name: "std::panicking::begin_panic".into(),
+                condition: Some("x > 5".into()),
+                hit_condition: Some(">= 3".into()),
+                state: BreakpointState::Disabled,
+            },
+        ];
+        let data_breakpoints = vec![DataBreakpoint {
+            data_id: "0x7ffee4b8c9a0".into(),
+            description: "counter".into(),
+            access_type: DataBreakpointAccessType::Write,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+            session_id: None,
+        }];
+
+        let workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            function_breakpoints: function_breakpoints.clone(),
+            data_breakpoints: data_breakpoints.clone(),
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.function_breakpoints, function_breakpoints);
+        assert_eq!(loaded.data_breakpoints, data_breakpoints);
+
+        // Removing all of them is persisted too.
+        db.save_workspace(SerializedWorkspace {
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
+            ..workspace
+        })
+        .await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.function_breakpoints.is_empty());
+        assert!(loaded.data_breakpoints.is_empty());
+    }
+
     #[gpui::test]
     async fn test_remove_last_breakpoint() {
         env_logger::try_init().ok();
@@ -1634,6 +1842,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -1680,6 +1890,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
@@ -1774,6 +1986,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -1787,6 +2001,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -1892,6 +2108,8 @@ mod tests {
             ),
             center_group,
             window_bounds: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -1927,6 +2145,8 @@ mod tests {
             ),
             center_group: Default::default(),
             window_bounds: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -1943,6 +2163,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
@@ -1983,6 +2205,8 @@ mod tests {
             ),
             center_group: Default::default(),
             window_bounds: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -2023,6 +2247,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
@@ -2036,6 +2262,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
@@ -2049,6 +2277,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
@@ -2062,6 +2292,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -2080,6 +2312,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
@@ -2093,6 +2327,8 @@ mod tests {
             ),
             center_group: Default::default(),
             window_bounds: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -2151,6 +2387,8 @@ mod tests {
             window_bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
@@ -2199,6 +2437,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id),
         })
@@ -2291,6 +2531,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id),
         })
@@ -1,13 +1,21 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc, sync::Arc};
 
-use anyhow::{Result, anyhow};
-use gpui::{Context, Task};
-use project::TaskSourceKind;
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{AsyncWindowContext, Context, Task, WeakEntity};
+use project::{TaskDependencyTree, TaskSourceKind};
 use remote::ConnectionState;
-use task::{ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
+use task::{
+    DependsOrder, ResolvedTask, SpawnInTerminal, TaskContext, TaskId, TaskInput, TaskTemplate,
+    TaskType,
+};
 use ui::Window;
 
-use crate::Workspace;
+use crate::{Workspace, notifications::NotifyTaskExt as _};
 
 impl Workspace {
     pub fn schedule_task(
@@ -53,6 +61,30 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
+        let inputs = resolved_task.pending_inputs().cloned().collect::<Vec<_>>();
+        if !inputs.is_empty() {
+            self.schedule_task_with_inputs(
+                task_source_kind,
+                resolved_task,
+                inputs,
+                omit_history,
+                window,
+                cx,
+            );
+            return;
+        }
+
+        if !resolved_task.original_task().depends_on.is_empty() {
+            self.schedule_task_with_dependencies(
+                task_source_kind,
+                resolved_task,
+                omit_history,
+                window,
+                cx,
+            );
+            return;
+        }
+
         if let Some(spawn_in_terminal) = resolved_task.resolved.take() {
             if !omit_history {
                 resolved_task.resolved = Some(spawn_in_terminal.clone());
@@ -75,44 +107,132 @@ impl Workspace {
         }
     }
 
-    pub fn schedule_debug_task(
+    /// Asks the user for the given inputs one after another, then resolves the task with their values and schedules it.
+    /// The inputs may come from the task's dependencies too, which are resolved with the task's context.
+    /// Dismissing any of the prompts cancels the task.
+    fn schedule_task_with_inputs(
         &mut self,
-        task: ResolvedTask,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        inputs: Vec<TaskInput>,
+        omit_history: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let cwd = resolved_task
+            .resolved
+            .as_ref()
+            .and_then(|spawn_in_terminal| spawn_in_terminal.cwd.clone());
+        cx.spawn_in(window, async move |workspace, cx| {
+            let mut task_context = resolved_task.task_context().clone();
+            for input in inputs {
+                let value = workspace.update_in(cx, |workspace, window, cx| {
+                    workspace.prompt_for_task_input(input.clone(), cwd.clone(), window, cx)
+                })?;
+                let Ok(value) = value.await else {
+                    return Ok(());
+                };
+                task_context
+                    .task_variables
+                    .insert(input.variable_name(), value);
+            }
+
+            let resolved_task = resolved_task
+                .original_task()
+                .resolve_task(&task_source_kind.to_id_base(), &task_context)
+                .with_context(|| {
+                    format!(
+                        "Failed to resolve task \"{}\" with its inputs",
+                        resolved_task.resolved_label
+                    )
+                })?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.schedule_resolved_task(
+                    task_source_kind,
+                    resolved_task,
+                    omit_history,
+                    window,
+                    cx,
+                );
+            })
+        })
+        .detach_and_notify_err(window, cx);
+    }
+
+    /// Runs the dependencies of a task first, in the order it asks for, and starts the task itself
+    /// only if all of them succeed. Compound tasks without a command of their own only run their dependencies.
+    fn schedule_task_with_dependencies(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        omit_history: bool,
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
-        let Some(debug_config) = task.resolved_debug_adapter_config() else {
-            log::error!("Debug task has no debug adapter config");
+        let project = self.project.read(cx);
+        let Some(task_inventory) = project.task_store().read(cx).task_inventory().cloned() else {
             return;
         };
+        let worktree = match &task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => project
+                .active_entry()
+                .and_then(|entry_id| project.worktree_for_entry(entry_id, cx))
+                .map(|worktree| worktree.read(cx).id()),
+        };
+
+        let tree = match task_inventory.read(cx).dependency_tree(
+            worktree,
+            task_source_kind.clone(),
+            resolved_task.clone(),
+        ) {
+            Ok(tree) => tree,
+            Err(e) => {
+                self.show_error(&e, cx);
+                return;
+            }
+        };
+        // Dependencies are resolved with the context of the task that requires them, so the values of their inputs
+        // are asked for upfront and added to that context.
+        let inputs = tree.pending_inputs();
+        if !inputs.is_empty() {
+            self.schedule_task_with_inputs(
+                task_source_kind,
+                resolved_task,
+                inputs,
+                omit_history,
+                window,
+                cx,
+            );
+            return;
+        }
+        if !omit_history {
+            task_inventory.update(cx, |inventory, _| {
+                inventory.task_scheduled(task_source_kind, resolved_task);
+            });
+        }
 
-        let project = self.project().clone();
         cx.spawn_in(window, async move |workspace, cx| {
-            let config = if debug_config.locator.is_some() {
-                let task = workspace.update_in(cx, |workspace, window, cx| {
-                    workspace.spawn_in_terminal(task.resolved.unwrap(), window, cx)
-                })?;
+            run_task_tree(workspace, tree, None, Rc::default(), cx.clone()).await
+        })
+        .detach_and_notify_err(window, cx);
+    }
 
-                let exit_code = task.await?;
-                if !exit_code.success() {
-                    return anyhow::Ok(());
-                }
-                let ret = project
-                    .update(cx, |project, cx| {
-                        project.dap_store().update(cx, |dap_store, cx| {
-                            dap_store.run_debug_locator(debug_config, cx)
-                        })
-                    })?
-                    .await?;
-                ret
-            } else {
-                debug_config.definition
-            };
-
-            project
-                .update(cx, |project, cx| project.start_debug_session(config, cx))?
-                .await?;
-            anyhow::Ok(())
+    /// Starts a debug session for the resolved debug task, once the tasks it depends on, e.g. its `preLaunchTask`, have succeeded.
+    pub fn schedule_debug_task(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if !task.original_task().depends_on.is_empty() {
+            self.schedule_task_with_dependencies(task_source_kind, task, true, window, cx);
+            return;
+        }
+
+        cx.spawn_in(window, async move |workspace, cx| {
+            start_debug_task(workspace, task, cx).await
         })
         .detach_and_log_err(cx);
     }
@@ -130,3 +250,128 @@ impl Workspace {
         }
     }
 }
+
+/// Runs of the tasks of a dependency tree, by task id, so that a task several others depend on runs only once.
+type TaskRuns =
+    Rc<RefCell<HashMap<TaskId, Shared<LocalBoxFuture<'static, Result<(), Arc<anyhow::Error>>>>>>>;
+
+/// Runs the dependencies of a task, then the task itself, resolving once the task has exited.
+/// Dependencies get the chain of tasks that required them prepended to their terminal tab labels.
+/// A task required by several others, e.g. in a diamond, runs once and all of them wait for the same run.
+fn run_task_tree(
+    workspace: WeakEntity<Workspace>,
+    tree: TaskDependencyTree,
+    parent_label: Option<String>,
+    task_runs: TaskRuns,
+    cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    let run = task_runs
+        .borrow_mut()
+        .entry(tree.task.id.clone())
+        .or_insert_with(|| {
+            run_task_node(workspace, tree, parent_label, task_runs.clone(), cx)
+                .map(|result| result.map_err(Arc::new))
+                .boxed_local()
+                .shared()
+        })
+        .clone();
+    async move { run.await.map_err(|e| anyhow!("{e:#}")) }.boxed_local()
+}
+
+fn run_task_node(
+    workspace: WeakEntity<Workspace>,
+    tree: TaskDependencyTree,
+    parent_label: Option<String>,
+    task_runs: TaskRuns,
+    mut cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    async move {
+        let label = match &parent_label {
+            Some(parent_label) => format!("{parent_label} › {}", tree.task.display_label()),
+            None => tree.task.display_label().to_string(),
+        };
+
+        let dependencies = tree.dependencies.into_iter().map(|dependency| {
+            run_task_tree(
+                workspace.clone(),
+                dependency,
+                Some(label.clone()),
+                task_runs.clone(),
+                cx.clone(),
+            )
+        });
+        match tree.order {
+            DependsOrder::Sequence => {
+                for dependency in dependencies {
+                    dependency.await?;
+                }
+            }
+            DependsOrder::Parallel => {
+                futures::future::try_join_all(dependencies).await?;
+            }
+        }
+
+        if tree.task.original_task().is_compound() {
+            return Ok(());
+        }
+        // Debug tasks, e.g. the ones of a compound debug task, are done once their session has started.
+        if let TaskType::Debug(_) = tree.task.task_type() {
+            return start_debug_task(workspace, tree.task, &mut cx).await;
+        }
+        let Some(mut spawn_in_terminal) = tree.task.resolved else {
+            return Ok(());
+        };
+        if parent_label.is_some() {
+            spawn_in_terminal.label = label.clone();
+        }
+        let exit_status = workspace
+            .update_in(&mut cx, |workspace, window, cx| {
+                workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+            })?
+            .await?;
+        anyhow::ensure!(
+            exit_status.success(),
+            "Task \"{label}\" failed with {exit_status}"
+        );
+        Ok(())
+    }
+    .boxed_local()
+}
+
+/// Starts a debug session for a resolved debug task. Tasks with a locator run their command first, e.g. a build,
+/// and the locator then finds the program to debug.
+async fn start_debug_task(
+    workspace: WeakEntity<Workspace>,
+    task: ResolvedTask,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let debug_config = task
+        .resolved_debug_adapter_config()
+        .context("Debug task has no debug adapter config")?;
+    let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+
+    let config = if debug_config.locator.is_some() {
+        let task = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(task.resolved.unwrap(), window, cx)
+        })?;
+
+        let exit_code = task.await?;
+        if !exit_code.success() {
+            return Ok(());
+        }
+        project
+            .update(cx, |project, cx| {
+                project.dap_store().update(cx, |dap_store, cx| {
+                    dap_store.run_debug_locator(debug_config, cx)
+                })
+            })?
+            .await?
+    } else {
+        debug_config.definition
+    };
+
+    project
+        .update(cx, |project, cx| project.start_debug_session(config, cx))?
+        .await?;
+    Ok(())
+}
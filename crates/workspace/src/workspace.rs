@@ -96,7 +96,7 @@ use std::{
     sync::{Arc, LazyLock, Weak, atomic::AtomicUsize},
     time::Duration,
 };
-use task::SpawnInTerminal;
+use task::{SpawnInTerminal, TaskInput};
 use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
@@ -812,6 +812,16 @@ type PromptForOpenPath = Box<
     ) -> oneshot::Receiver<Option<Vec<PathBuf>>>,
 >;
 
+type PromptForTaskInput = Box<
+    dyn Fn(
+        &mut Workspace,
+        TaskInput,
+        Option<PathBuf>,
+        &mut Window,
+        &mut Context<Workspace>,
+    ) -> oneshot::Receiver<String>,
+>;
+
 /// Collects everything project-related for a certain window opened.
 /// In some way, is a counterpart of a window, as the [`WindowHandle`] could be downcast into `Workspace`.
 ///
@@ -859,6 +869,7 @@ pub struct Workspace {
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
+    on_prompt_for_task_input: Option<PromptForTaskInput>,
     terminal_provider: Option<Box<dyn TerminalProvider>>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
     serialized_ssh_project: Option<SerializedSshProject>,
@@ -982,7 +993,9 @@ impl Workspace {
             window,
             |workspace, _, event, window, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(_, _)
-                | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                | BreakpointStoreEvent::BreakpointsCleared(_)
+                | BreakpointStoreEvent::FunctionBreakpointsUpdated
+                | BreakpointStoreEvent::DataBreakpointsUpdated => {
                     workspace.serialize_workspace(window, cx);
                 }
                 BreakpointStoreEvent::ActiveDebugLineChanged => {}
@@ -1185,6 +1198,7 @@ impl Workspace {
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
+            on_prompt_for_task_input: None,
             terminal_provider: None,
             serializable_items_tx,
             _items_serializer,
@@ -1701,6 +1715,10 @@ impl Workspace {
         self.on_prompt_for_open_path = Some(prompt)
     }
 
+    pub fn set_prompt_for_task_input(&mut self, prompt: PromptForTaskInput) {
+        self.on_prompt_for_task_input = Some(prompt)
+    }
+
     pub fn set_terminal_provider(&mut self, provider: impl TerminalProvider + 'static) {
         self.terminal_provider = Some(Box::new(provider));
     }
@@ -1713,6 +1731,23 @@ impl Workspace {
         self.serialized_ssh_project = Some(serialized_ssh_project);
     }
 
+    /// Asks the user for the value of a task input, the receiver is cancelled if they dismiss the prompt.
+    pub fn prompt_for_task_input(
+        &mut self,
+        input: TaskInput,
+        cwd: Option<PathBuf>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<String> {
+        let Some(prompt) = self.on_prompt_for_task_input.take() else {
+            let (_, rx) = oneshot::channel();
+            return rx;
+        };
+        let rx = prompt(self, input, cwd, window, cx);
+        self.on_prompt_for_task_input = Some(prompt);
+        rx
+    }
+
     pub fn prompt_for_open_path(
         &mut self,
         path_prompt_options: PathPromptOptions,
@@ -4732,9 +4767,15 @@ impl Workspace {
         }
 
         if let Some(location) = self.serialize_workspace_location(cx) {
-            let breakpoints = self.project.update(cx, |project, cx| {
-                project.breakpoint_store().read(cx).all_breakpoints(cx)
-            });
+            let (breakpoints, function_breakpoints, data_breakpoints) =
+                self.project.update(cx, |project, cx| {
+                    let breakpoint_store = project.breakpoint_store().read(cx);
+                    (
+                        breakpoint_store.all_breakpoints(cx),
+                        breakpoint_store.function_breakpoints().to_vec(),
+                        breakpoint_store.persistent_data_breakpoints(),
+                    )
+                });
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
             let docks = build_serialized_docks(self, window, cx);
@@ -4749,6 +4790,8 @@ impl Workspace {
                 centered_layout: self.centered_layout,
                 session_id: self.session_id.clone(),
                 breakpoints,
+                function_breakpoints,
+                data_breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
             };
 
@@ -4920,6 +4963,11 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_function_and_data_breakpoints(
+                                serialized_workspace.function_breakpoints,
+                                serialized_workspace.data_breakpoints,
+                                cx,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })
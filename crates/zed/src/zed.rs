@@ -58,6 +58,7 @@ use std::sync::atomic::{self, AtomicBool};
 use std::time::Duration;
 use std::{borrow::Cow, path::Path, sync::Arc};
 use terminal_view::terminal_panel::{self, TerminalPanel};
+use test_explorer::TestExplorer;
 use theme::{ActiveTheme, ThemeSettings};
 use ui::{PopoverMenuHandle, prelude::*};
 use util::markdown::MarkdownString;
@@ -407,6 +408,7 @@ fn initialize_panels(
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
+        let test_explorer = TestExplorer::load(workspace_handle.clone(), cx.clone());
         let channels_panel =
             collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
         let chat_panel =
@@ -420,6 +422,7 @@ fn initialize_panels(
             project_panel,
             outline_panel,
             terminal_panel,
+            test_explorer,
             channels_panel,
             chat_panel,
             notification_panel,
@@ -427,6 +430,7 @@ fn initialize_panels(
             project_panel,
             outline_panel,
             terminal_panel,
+            test_explorer,
             channels_panel,
             chat_panel,
             notification_panel,
@@ -436,6 +440,7 @@ fn initialize_panels(
             workspace.add_panel(project_panel, window, cx);
             workspace.add_panel(outline_panel, window, cx);
             workspace.add_panel(terminal_panel, window, cx);
+            workspace.add_panel(test_explorer, window, cx);
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(chat_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
@@ -4244,6 +4249,7 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            test_explorer::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
@@ -137,7 +137,7 @@ pub mod workspace {
 pub mod git {
     use gpui::{action_with_deprecated_aliases, actions};
 
-    actions!(git, [CheckoutBranch, Switch, SelectRepo]);
+    actions!(git, [CheckoutBranch, Switch, SelectRepo, Worktrees, Tags]);
     action_with_deprecated_aliases!(git, Branch, ["branches::OpenRecent"]);
 }
 
@@ -297,6 +297,7 @@ pub struct Rerun {
 }
 
 impl_actions!(task, [Spawn, Rerun]);
+actions!(task, [ShowHistory]);
 
 pub mod outline {
     use std::sync::OnceLock;